@@ -0,0 +1,217 @@
+/*
+ * Magba is licensed under The 3-Clause BSD, see LICENSE.
+ * Copyright 2025 Sira Pornsiriprasert <code@psira.me>
+ */
+
+#ifndef MAGBA_H
+#define MAGBA_H
+
+/* Generated by cbindgen from src/capi.rs. Do not edit. */
+
+#include <stddef.h>
+
+// Result of a call, where anything but `Ok` is a failure.
+typedef enum {
+  MAGBA_STATUS_OK = 0,
+  // A required pointer is `NULL`.
+  MAGBA_STATUS_NULL_POINTER = 1,
+  // An argument is out of range or of the wrong kind.
+  MAGBA_STATUS_INVALID_ARGUMENT = 2,
+  // The computation panicked.
+  MAGBA_STATUS_PANIC = 3,
+} MagbaStatus;
+
+// A sensor.
+typedef struct MagbaSensor MagbaSensor;
+
+// An assembly of sensors.
+typedef struct MagbaSensorAssembly MagbaSensorAssembly;
+
+// A magnet, a current, or an assembly of sources.
+typedef struct MagbaSource MagbaSource;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Returns the message of the last failed call on this thread, or an empty string.
+//
+// The string stays valid until the next failed call on this thread.
+const char *magba_last_error(void);
+
+// Creates a [CylinderMagnet] with the diameter and height in m.
+MagbaStatus magba_cylinder_magnet_new(const double *position,
+                                      const double *orientation,
+                                      const double *polarization,
+                                      double diameter,
+                                      double height,
+                                      MagbaSource **out);
+
+// Creates a [CuboidMagnet] with the side lengths `dimensions` (3) in m.
+MagbaStatus magba_cuboid_magnet_new(const double *position,
+                                    const double *orientation,
+                                    const double *polarization,
+                                    const double *dimensions,
+                                    MagbaSource **out);
+
+// Creates a [SphereMagnet] with the diameter in m.
+MagbaStatus magba_sphere_magnet_new(const double *position,
+                                    const double *orientation,
+                                    const double *polarization,
+                                    double diameter,
+                                    MagbaSource **out);
+
+// Creates a [TetrahedronMagnet] from 4 `vertices` in m, relative to the position.
+MagbaStatus magba_tetrahedron_magnet_new(const double *position,
+                                         const double *orientation,
+                                         const double *polarization,
+                                         const double *vertices,
+                                         MagbaSource **out);
+
+// Creates a [PrismMagnet] extruding a polygon of `n` vertices (2 doubles each) in m by
+// the height along z.
+MagbaStatus magba_prism_magnet_new(const double *position,
+                                   const double *orientation,
+                                   const double *polarization,
+                                   double height,
+                                   const double *polygon,
+                                   size_t n,
+                                   MagbaSource **out);
+
+// Creates a [MeshMagnet] from `n_vertices` vertices in m and `n_faces` triangles of 3
+// vertex indices each, wound counterclockwise seen from outside.
+MagbaStatus magba_mesh_magnet_new(const double *position,
+                                  const double *orientation,
+                                  const double *polarization,
+                                  const double *vertices,
+                                  size_t n_vertices,
+                                  const size_t *faces,
+                                  size_t n_faces,
+                                  MagbaSource **out);
+
+// Creates a magnetic [Dipole] with the moment in A·m².
+MagbaStatus magba_dipole_new(const double *position,
+                             const double *orientation,
+                             const double *moment,
+                             MagbaSource **out);
+
+// Creates a [CircularCurrent] loop with the diameter in m and the current in A.
+MagbaStatus magba_circular_current_new(const double *position,
+                                       const double *orientation,
+                                       double diameter,
+                                       double current,
+                                       MagbaSource **out);
+
+// Creates a [PathCurrent] through `n` vertices in m with the current in A.
+MagbaStatus magba_path_current_new(const double *position,
+                                   const double *orientation,
+                                   double current,
+                                   const double *vertices,
+                                   size_t n,
+                                   MagbaSource **out);
+
+// Creates an empty [SourceAssembly].
+MagbaStatus magba_source_assembly_new(const double *position,
+                                      const double *orientation,
+                                      MagbaSource **out);
+
+// Moves `component` into `assembly`, keeping its global pose.
+//
+// On success, `component` belongs to the assembly and must not be used or freed.
+MagbaStatus magba_source_assembly_add(MagbaSource *assembly, MagbaSource *component);
+
+// Frees a source. `NULL` is ignored.
+void magba_source_free(MagbaSource *source);
+
+// Sets the pose of a source. An assembly moves its components along.
+MagbaStatus magba_source_set_pose(MagbaSource *source,
+                                  const double *position,
+                                  const double *orientation);
+
+// Writes the position (3) and orientation (4) of a source.
+MagbaStatus magba_source_get_pose(const MagbaSource *source, double *position, double *orientation);
+
+// Computes the B-field (T) of a source at `n` points (m) into `b` (`n` × 3).
+MagbaStatus magba_source_compute_B(const MagbaSource *source,
+                                   const double *points,
+                                   size_t n,
+                                   double *b);
+
+// Creates a [LinearHallSensor] with the sensitivity in V/T and the supply voltage in V.
+MagbaStatus magba_linear_hall_sensor_new(const double *position,
+                                         const double *orientation,
+                                         const double *sensitive_axis,
+                                         double sensitivity,
+                                         double supply_voltage,
+                                         MagbaSensor **out);
+
+// Creates a [HallSwitch] with the operate point `b_op` in T.
+MagbaStatus magba_hall_switch_new(const double *position,
+                                  const double *orientation,
+                                  const double *sensitive_axis,
+                                  double b_op,
+                                  MagbaSensor **out);
+
+// Creates a [HallLatch] with the operate and release points `b_op` and `b_rp` in T.
+MagbaStatus magba_hall_latch_new(const double *position,
+                                 const double *orientation,
+                                 const double *sensitive_axis,
+                                 double b_op,
+                                 double b_rp,
+                                 MagbaSensor **out);
+
+// Frees a sensor. `NULL` is ignored.
+void magba_sensor_free(MagbaSensor *sensor);
+
+// Sets the pose of a sensor.
+MagbaStatus magba_sensor_set_pose(MagbaSensor *sensor,
+                                  const double *position,
+                                  const double *orientation);
+
+// Writes the position (3) and orientation (4) of a sensor.
+MagbaStatus magba_sensor_get_pose(const MagbaSensor *sensor, double *position, double *orientation);
+
+// Reads a sensor in the field of a source into `value`: the voltage (V) of a linear Hall
+// sensor, or 1 or 0 for the state of a switch or latch.
+//
+// A latch remembers its state between reads.
+MagbaStatus magba_sensor_read(const MagbaSensor *sensor, const MagbaSource *source, double *value);
+
+// Creates an empty [ObserverAssembly].
+MagbaStatus magba_sensor_assembly_new(const double *position,
+                                      const double *orientation,
+                                      MagbaSensorAssembly **out);
+
+// Moves `sensor` into `assembly`, keeping its global pose.
+//
+// On success, `sensor` belongs to the assembly and must not be used or freed.
+MagbaStatus magba_sensor_assembly_add(MagbaSensorAssembly *assembly, MagbaSensor *sensor);
+
+// Writes the number of sensors in an assembly to `len`.
+MagbaStatus magba_sensor_assembly_len(const MagbaSensorAssembly *assembly, size_t *len);
+
+// Frees a sensor assembly and its sensors. `NULL` is ignored.
+void magba_sensor_assembly_free(MagbaSensorAssembly *assembly);
+
+// Sets the pose of a sensor assembly, moving its sensors along.
+MagbaStatus magba_sensor_assembly_set_pose(MagbaSensorAssembly *assembly,
+                                           const double *position,
+                                           const double *orientation);
+
+// Writes the position (3) and orientation (4) of a sensor assembly.
+MagbaStatus magba_sensor_assembly_get_pose(const MagbaSensorAssembly *assembly,
+                                           double *position,
+                                           double *orientation);
+
+// Reads all `n` sensors of an assembly in the field of a source into `values`, as
+// [magba_sensor_read]. `n` must match the number of sensors.
+MagbaStatus magba_sensor_assembly_read(const MagbaSensorAssembly *assembly,
+                                       const MagbaSource *source,
+                                       double *values,
+                                       size_t n);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* MAGBA_H */
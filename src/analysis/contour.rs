@@ -0,0 +1,299 @@
+/*
+ * Magba is licensed under The 3-Clause BSD, see LICENSE.
+ * Copyright 2025 Sira Pornsiriprasert <code@psira.me>
+ */
+
+//! Isosurfaces and contour lines of sampled fields.
+
+use alloc::{collections::BTreeMap, vec, vec::Vec};
+
+use nalgebra::Point3;
+use num_traits::Float as NumFloat;
+
+#[cfg(feature = "mesh")]
+use crate::base::mesh::{TriMesh, march_tetrahedra};
+use crate::{analysis::GridField, base::Float};
+
+impl<T: Float> GridField<T> {
+    /// Offsets `scalar` to `level - scalar`, so that values above `level` are negative.
+    ///
+    /// Zero and NaN samples, e.g., on a wire, are moved just below `level`.
+    fn level_offsets(&self, scalar: &[T], level: T) -> Vec<T> {
+        if scalar.len() != self.points().len() {
+            panic!("Scalar field length does not match the grid.");
+        }
+
+        let values: Vec<T> = scalar.iter().map(|&s| level - s).collect();
+        let scale = values
+            .iter()
+            .filter(|v| !v.is_nan())
+            .fold(T::zero(), |acc, &v| NumFloat::max(acc, NumFloat::abs(v)));
+        let eps = NumFloat::max(scale * T::from_f64(1e-12).unwrap(), T::min_positive_value());
+        values
+            .into_iter()
+            .map(|v| if v == T::zero() || v.is_nan() { eps } else { v })
+            .collect()
+    }
+
+    /// Triangulates the isosurface where `scalar` equals `level`.
+    ///
+    /// `scalar` holds one value per grid point in flat order, e.g., from
+    /// [GridField::magnitude], [GridField::component], or [GridField::projection]. The surface
+    /// is extracted with marching tetrahedra rather than marching cubes: each grid cell is
+    /// split into six tetrahedra as in [mesh_sdf](crate::base::mesh::mesh_sdf), and the
+    /// crossings are interpolated linearly between grid points. This has no ambiguous cell
+    /// configurations, so the surface has no cracks between cells, but it has more faces
+    /// than marching cubes. The faces are oriented towards decreasing values,
+    /// and the surface is open where it leaves the grid.
+    ///
+    /// Cylindrical and spherical grids are meshed in grid coordinates, so a full turn of
+    /// azimuth leaves a seam and the axis gives degenerate faces.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `scalar` does not match the grid.
+    #[cfg(feature = "mesh")]
+    pub fn isosurface(&self, scalar: &[T], level: T) -> TriMesh<T> {
+        let values = self.level_offsets(scalar, level);
+        march_tetrahedra(self.shape(), &values, |id| self.points()[id].coords)
+    }
+
+    /// Triangulates the boundary of the region where `scalar` is at least `level`.
+    ///
+    /// Same as [GridField::isosurface], but the surface is closed by the grid bounds, which
+    /// are only accurate to within one cell. On a Cartesian grid the result is a closed,
+    /// outward oriented mesh, so it supports [TriMesh::contains] and [TriMesh::volume].
+    ///
+    /// # Examples
+    ///
+    /// The operate point envelope of a Hall switch around a magnet, where it turns on.
+    ///
+    /// ```
+    /// # use magba::{analysis::{FieldGrid, GridAxis}, magnets::CylinderMagnet};
+    /// # use nalgebra::{point, UnitQuaternion, Vector3};
+    /// let magnet = CylinderMagnet::new(
+    ///     [0.0, 0.0, 0.0],
+    ///     UnitQuaternion::identity(),
+    ///     [0.0, 0.0, 1.0],
+    ///     0.005,
+    ///     0.005,
+    /// );
+    /// let axis = GridAxis::new(-0.0101, 0.0099, 21);
+    /// let field = FieldGrid::cartesian(axis, axis, GridAxis::new(0.003, 0.015, 13))
+    ///     .compute_B(&magnet);
+    ///
+    /// // Switch facing the magnet, operating at 20 mT along its z-axis
+    /// let envelope = field.level_region(&field.projection(Vector3::z()), 0.02);
+    /// assert!(envelope.contains(point![0.0, 0.0, 0.004]));
+    /// assert!(!envelope.contains(point![0.0, 0.0, 0.014]));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `scalar` does not match the grid.
+    #[cfg(feature = "mesh")]
+    pub fn level_region(&self, scalar: &[T], level: T) -> TriMesh<T> {
+        let mut values = self.level_offsets(scalar, level);
+        let [nx, ny, nz] = self.shape();
+        let eps = T::min_positive_value();
+        for k in 0..nz {
+            for j in 0..ny {
+                for i in 0..nx {
+                    if i == 0 || j == 0 || k == 0 || i == nx - 1 || j == ny - 1 || k == nz - 1 {
+                        let id = self.grid().flat_index([i, j, k]);
+                        values[id] = NumFloat::max(values[id], eps);
+                    }
+                }
+            }
+        }
+        march_tetrahedra(self.shape(), &values, |id| self.points()[id].coords)
+    }
+
+    /// Traces the contour lines where `scalar` equals `level` on a plane grid.
+    ///
+    /// The grid must have exactly one fixed axis, such as [FieldGrid::plane](
+    /// crate::analysis::FieldGrid::plane) or a [GridField::slice]. `scalar` holds one value
+    /// per grid point in flat order. Each cell is split into two triangles, and the
+    /// crossings are interpolated linearly between grid points.
+    ///
+    /// Returns the global polylines. Closed contours repeat their first point at the end,
+    /// and open ones end on the grid bounds.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the grid does not have exactly one fixed axis, or if the length of
+    /// `scalar` does not match the grid.
+    pub fn contours(&self, scalar: &[T], level: T) -> Vec<Vec<Point3<T>>> {
+        let shape = self.shape();
+        let axes: Vec<usize> = (0..3).filter(|&axis| shape[axis] > 1).collect();
+        let &[u, v] = axes.as_slice() else {
+            panic!("Contours need a grid with exactly one fixed axis.");
+        };
+        let values = self.level_offsets(scalar, level);
+        let id = |a: usize, b: usize| {
+            let mut index = [0; 3];
+            index[u] = a;
+            index[v] = b;
+            self.grid().flat_index(index)
+        };
+
+        // Segments between crossings, with the crossings shared by neighboring triangles
+        let mut keys = BTreeMap::new();
+        let mut vertices = Vec::new();
+        let mut neighbors: Vec<Vec<usize>> = Vec::new();
+        let mut crossing = |a: usize, b: usize| {
+            let key = if a < b { (a, b) } else { (b, a) };
+            *keys.entry(key).or_insert_with(|| {
+                let t = values[a] / (values[a] - values[b]);
+                let (pa, pb) = (self.points()[a], self.points()[b]);
+                vertices.push(pa + (pb - pa) * t);
+                neighbors.push(Vec::new());
+                vertices.len() - 1
+            })
+        };
+        let mut segments = Vec::new();
+        for b in 0..shape[v] - 1 {
+            for a in 0..shape[u] - 1 {
+                let corners = [id(a, b), id(a + 1, b), id(a + 1, b + 1), id(a, b + 1)];
+                for triangle in [[0, 1, 2], [0, 2, 3]] {
+                    let [p, q, r] = triangle.map(|c| corners[c]);
+                    let above = [p, q, r].map(|id| values[id] < T::zero());
+                    // The corner on its own side of the level, and the other two
+                    let (lone, others) = match above {
+                        [true, true, true] | [false, false, false] => continue,
+                        [x, y, _] if x == y => (r, [p, q]),
+                        [x, _, z] if x == z => (q, [p, r]),
+                        _ => (p, [q, r]),
+                    };
+                    segments.push([crossing(lone, others[0]), crossing(lone, others[1])]);
+                }
+            }
+        }
+        for [a, b] in segments {
+            neighbors[a].push(b);
+            neighbors[b].push(a);
+        }
+
+        // Chain the segments, starting with the open contours from their ends
+        let mut visited = vec![false; vertices.len()];
+        let mut lines = Vec::new();
+        let starts = (0..vertices.len())
+            .filter(|&i| neighbors[i].len() == 1)
+            .chain(0..vertices.len());
+        for start in starts {
+            if visited[start] {
+                continue;
+            }
+            visited[start] = true;
+            let mut line = vec![vertices[start]];
+            let mut current = start;
+            while let Some(&next) = neighbors[current].iter().find(|&&n| !visited[n]) {
+                visited[next] = true;
+                line.push(vertices[next]);
+                current = next;
+            }
+            if neighbors[start].len() == 2 && neighbors[current].contains(&start) {
+                line.push(vertices[start]);
+            }
+            lines.push(line);
+        }
+        lines
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::{
+        analysis::{FieldGrid, GridAxis},
+        magnets::Dipole,
+    };
+    #[cfg(feature = "mesh")]
+    use crate::{base::Source, magnets::CuboidMagnet};
+    use nalgebra::UnitQuaternion;
+    #[cfg(feature = "mesh")]
+    use nalgebra::{Vector3, point};
+    #[cfg(feature = "mesh")]
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_dipole_contours() {
+        // In the equatorial plane |B| = μ0 m / (4π r³), so the contours are circles
+        let dipole = Dipole::new([0.0; 3], UnitQuaternion::identity(), [0.0, 0.0, 1.0]);
+        let axis = GridAxis::new(-0.205, 0.195, 41);
+        let field =
+            FieldGrid::cartesian(axis, axis, GridAxis::new(-0.01, 0.01, 3)).compute_B(&dipole);
+        let equator = field.slice(2, 1);
+        let level = 1e-7 / 0.1f64.powi(3);
+        let lines = equator.contours(&equator.magnitude(), level);
+
+        assert_eq!(lines.len(), 1);
+        let line = &lines[0];
+        assert_eq!(line.first(), line.last());
+        assert!(line.len() > 20);
+        for p in line {
+            assert!(p.z.abs() < 1e-15);
+            approx::assert_relative_eq!(p.coords.norm(), 0.1, max_relative = 0.02);
+        }
+
+        // Above the equator Bx vanishes on x = 0, an open contour across the grid
+        let above = field.slice(2, 2);
+        let lines = above.contours(&above.component(0), 0.0);
+        assert_eq!(lines.len(), 1);
+        // One crossing on each of the 41 grid rows and the 40 cell diagonals between them
+        assert_eq!(lines[0].len(), 81);
+        // Exact on the rows by symmetry, and within a cell on the diagonals
+        for (i, p) in lines[0].iter().enumerate() {
+            assert!(p.x.abs() < if i % 2 == 0 { 1e-9 } else { 0.005 });
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "mesh")]
+    fn test_dipole_isosurface() {
+        let dipole = Dipole::new([0.0; 3], UnitQuaternion::identity(), [0.0, 0.0, 1.0]);
+        let axis = GridAxis::new(-0.205, 0.195, 41);
+        let field = FieldGrid::cartesian(axis, axis, axis).compute_B(&dipole);
+        let level = 1e-7 / 0.1f64.powi(3);
+        let magnitude = field.magnitude();
+
+        // The surface is closed around the dipole, so it matches the closed region
+        let surface = field.isosurface(&magnitude, level);
+        let region = field.level_region(&magnitude, level);
+        assert_eq!(surface.faces().len(), region.faces().len());
+        for v in surface.vertices() {
+            approx::assert_relative_eq!(
+                dipole.compute_B((*v).into()).norm(),
+                level,
+                max_relative = 0.1
+            );
+        }
+
+        // |B| = level at r = 0.1 (1 + 3 cos²θ)^(1/6)
+        let volume: f64 = (0..1000)
+            .map(|i| {
+                let theta = PI * (i as f64 + 0.5) / 1000.0;
+                let r = 0.1 * (1.0 + 3.0 * theta.cos().powi(2)).powf(1.0 / 6.0);
+                2.0 * PI / 3.0 * r.powi(3) * theta.sin() * PI / 1000.0
+            })
+            .sum();
+        approx::assert_relative_eq!(region.volume(), volume, max_relative = 0.02);
+        assert!(region.contains(point![0.0, 0.0, 0.1]));
+        assert!(!region.contains(point![0.11, 0.0, 0.0]));
+    }
+
+    #[test]
+    #[cfg(feature = "mesh")]
+    fn test_level_region_clipped() {
+        let magnet = CuboidMagnet::default();
+        let axis = GridAxis::new(-1.0, 1.0, 11);
+        let field = FieldGrid::cartesian(axis, axis, GridAxis::new(0.6, 2.0, 8)).compute_B(&magnet);
+        let region = field.level_region(&field.projection(Vector3::z()), 0.05);
+
+        // Closed by the bottom face of the grid
+        let bottom = region.vertices().iter().filter(|v| v.z == 0.6).count();
+        assert!(bottom > 0);
+        assert!(region.contains(point![0.0, 0.0, 0.7]));
+        assert!(!region.contains(point![0.0, 0.0, 1.9]));
+        assert!(region.volume() > 0.0);
+    }
+}
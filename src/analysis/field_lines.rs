@@ -0,0 +1,565 @@
+/*
+ * Magba is licensed under The 3-Clause BSD, see LICENSE.
+ * Copyright 2025 Sira Pornsiriprasert <code@psira.me>
+ */
+
+//! Field-line tracing.
+
+use alloc::{boxed::Box, vec::Vec};
+
+use nalgebra::{Point3, Vector3};
+use num_traits::Float as NumFloat;
+
+use crate::base::{Float, Source};
+
+/// Maximum number of halvings when locating a crossing, past which the step no longer
+/// shrinks in floating point.
+const MAX_BISECTIONS: usize = 64;
+
+/// Direction to trace from the seed point, relative to the B-field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceDirection {
+    /// Along the B-field.
+    Forward,
+    /// Against the B-field.
+    Backward,
+    /// Both ways. The line runs backward to the seed, then forward.
+    Both,
+}
+
+/// Why a field line ends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopReason {
+    /// The line starts at the seed point, when tracing only forward.
+    Seed,
+    /// The line reached a stop region, such as a magnet body.
+    Region,
+    /// The field magnitude fell below the threshold or is not finite.
+    WeakField,
+    /// The line left the bounding box.
+    OutOfBounds,
+    /// The line returned to its seed point.
+    Closed,
+    /// The line reached the maximum length.
+    MaxLength,
+    /// The line reached the maximum number of steps.
+    MaxSteps,
+}
+
+/// Polyline traced along the B-field, returned by [FieldLineTracer::trace].
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldLine<T: Float = f64> {
+    points: Vec<Point3<T>>,
+    start: StopReason,
+    end: StopReason,
+}
+
+impl<T: Float> FieldLine<T> {
+    /// Points along the line, oriented along the B-field.
+    #[inline]
+    pub fn points(&self) -> &[Point3<T>] {
+        &self.points
+    }
+
+    #[inline]
+    pub fn into_points(self) -> Vec<Point3<T>> {
+        self.points
+    }
+
+    /// Why the line ends at its first point.
+    #[inline]
+    pub fn start(&self) -> StopReason {
+        self.start
+    }
+
+    /// Why the line ends at its last point.
+    #[inline]
+    pub fn end(&self) -> StopReason {
+        self.end
+    }
+
+    /// Length of the polyline (m).
+    pub fn length(&self) -> T {
+        self.points
+            .windows(2)
+            .fold(T::zero(), |acc, w| acc + (w[1] - w[0]).norm())
+    }
+}
+
+type StopRegion<'a, T> = Box<dyn Fn(Point3<T>) -> bool + Send + Sync + 'a>;
+
+/// Traces field lines of any [Source] with adaptive Runge-Kutta integration.
+///
+/// The tracer integrates `dx/ds = B/|B|` over the arc length `s` with the Dormand-Prince
+/// RK45 method, adapting the step to keep the local position error below the tolerance.
+/// A line stops when it
+///
+/// - enters a stop region, e.g., a magnet body, see [FieldLineTracer::with_stop_region],
+/// - reaches a point where `|B|` is below [FieldLineTracer::with_min_field],
+/// - leaves the bounding box, see [FieldLineTracer::with_bounds],
+/// - returns to its seed point within [FieldLineTracer::with_closure_tolerance], or
+/// - reaches the maximum length or number of steps.
+///
+/// Crossings of stop regions and the bounding box are located on the last step, so lines
+/// end on the surface.
+///
+/// # Examples
+///
+/// ```
+/// # use magba::analysis::{FieldLineTracer, StopReason, TraceDirection};
+/// # use magba::base::mesh::Tessellate;
+/// # use magba::prelude::*;
+/// # use nalgebra::{point, Point3, UnitQuaternion};
+/// let magnet = CuboidMagnet::new([0.0; 3], UnitQuaternion::identity(), [0.0, 0.0, 1.0], [0.01; 3]);
+///
+/// // Stop at the magnet surface, here exactly as a box
+/// let tracer = FieldLineTracer::new(&magnet)
+///     .with_bounds(point![-0.05, -0.05, -0.05], point![0.05, 0.05, 0.05])
+///     .with_stop_region(|p: Point3<f64>| p.coords.amax() < 0.005);
+///
+/// let line = tracer.trace(point![0.004, 0.0, 0.006]);
+/// assert_eq!(line.start(), StopReason::Region); // left the north face
+/// assert_eq!(line.end(), StopReason::Region); // entered the south face
+///
+/// // Curved magnets can stop at their tessellated surface
+/// let cylinder = CylinderMagnet::default();
+/// let mesh = cylinder.tessellate(0.01);
+/// let tracer = FieldLineTracer::new(&cylinder)
+///     .with_stop_region(move |p| mesh.contains(p))
+///     .with_direction(TraceDirection::Forward);
+/// ```
+pub struct FieldLineTracer<'a, T: Float = f64> {
+    source: &'a dyn Source<T>,
+    direction: TraceDirection,
+    initial_step: T,
+    min_step: T,
+    max_step: T,
+    tolerance: T,
+    max_length: T,
+    max_steps: usize,
+    min_field: T,
+    closure_tolerance: T,
+    bounds: Option<(Point3<T>, Point3<T>)>,
+    stop_regions: Vec<StopRegion<'a, T>>,
+}
+
+impl<'a, T: Float> FieldLineTracer<'a, T> {
+    /// Construct a [FieldLineTracer] of `source` with default settings.
+    ///
+    /// | Setting | Default |
+    /// |---------|---------|
+    /// | Direction | [TraceDirection::Both] |
+    /// | Initial, min, and max step | 0.1 mm, 0.1 µm, 1 mm |
+    /// | Tolerance | 0.1 µm per step |
+    /// | Max length | 1 m |
+    /// | Max steps | 100000 per direction |
+    /// | Min field | 1 nT |
+    /// | Closure tolerance | 10 µm |
+    pub fn new(source: &'a dyn Source<T>) -> Self {
+        Self {
+            source,
+            direction: TraceDirection::Both,
+            initial_step: T::from_f64(1e-4).unwrap(),
+            min_step: T::from_f64(1e-7).unwrap(),
+            max_step: T::from_f64(1e-3).unwrap(),
+            tolerance: T::from_f64(1e-7).unwrap(),
+            max_length: T::one(),
+            max_steps: 100_000,
+            min_field: T::from_f64(1e-9).unwrap(),
+            closure_tolerance: T::from_f64(1e-5).unwrap(),
+            bounds: None,
+            stop_regions: Vec::new(),
+        }
+    }
+
+    pub fn with_direction(mut self, direction: TraceDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Sets the initial, minimum, and maximum step length (m).
+    pub fn with_steps(mut self, initial: T, min: T, max: T) -> Self {
+        self.initial_step = initial;
+        self.min_step = min;
+        self.max_step = max;
+        self
+    }
+
+    /// Sets the allowed local position error per step (m).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tolerance` is not positive.
+    pub fn with_tolerance(mut self, tolerance: T) -> Self {
+        #[allow(clippy::neg_cmp_op_on_partial_ord)]
+        if !(tolerance > T::zero()) {
+            panic!("Field line tolerance must be positive.");
+        }
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Sets the maximum length (m) and number of steps of each direction of a line.
+    pub fn with_max_length(mut self, max_length: T, max_steps: usize) -> Self {
+        self.max_length = max_length;
+        self.max_steps = max_steps;
+        self
+    }
+
+    /// Sets the field magnitude (T) below which lines stop.
+    pub fn with_min_field(mut self, min_field: T) -> Self {
+        self.min_field = min_field;
+        self
+    }
+
+    /// Sets the distance (m) to the seed point at which a line counts as closed.
+    pub fn with_closure_tolerance(mut self, closure_tolerance: T) -> Self {
+        self.closure_tolerance = closure_tolerance;
+        self
+    }
+
+    /// Sets the axis-aligned bounding box that lines stop at.
+    pub fn with_bounds(mut self, min: Point3<T>, max: Point3<T>) -> Self {
+        self.bounds = Some((min, max));
+        self
+    }
+
+    /// Adds a region that lines stop at, given as a point containment test.
+    ///
+    /// A seed inside a region gives a line of that single point.
+    pub fn with_stop_region<F>(mut self, contains: F) -> Self
+    where
+        F: Fn(Point3<T>) -> bool + Send + Sync + 'a,
+    {
+        self.stop_regions.push(Box::new(contains));
+        self
+    }
+
+    fn in_region(&self, point: Point3<T>) -> bool {
+        self.stop_regions.iter().any(|contains| contains(point))
+    }
+
+    fn in_bounds(&self, point: Point3<T>) -> bool {
+        self.bounds
+            .is_none_or(|(min, max)| (0..3).all(|i| point[i] >= min[i] && point[i] <= max[i]))
+    }
+
+    /// Unit field direction at `point`, or [None] if the field is weak.
+    fn direction_at(&self, point: Point3<T>, sign: T) -> Option<Vector3<T>> {
+        let b = self.source.compute_B(point);
+        let norm = b.norm();
+        if !NumFloat::is_finite(norm) || norm < self.min_field || norm == T::zero() {
+            return None;
+        }
+        Some(b * (sign / norm))
+    }
+
+    /// One Dormand-Prince step from `x` with the derivative `k1` at `x`.
+    ///
+    /// Returns the 5th-order solution, its derivative, and the error estimate.
+    #[allow(clippy::type_complexity)]
+    fn step(
+        &self,
+        x: Point3<T>,
+        k1: Vector3<T>,
+        h: T,
+        sign: T,
+    ) -> Option<(Point3<T>, Vector3<T>, T)> {
+        let c = |v: f64| T::from_f64(v).unwrap();
+        let f = |p: Point3<T>| self.direction_at(p, sign);
+
+        let k2 = f(x + k1 * (h * c(1.0 / 5.0)))?;
+        let k3 = f(x + (k1 * c(3.0 / 40.0) + k2 * c(9.0 / 40.0)) * h)?;
+        let k4 = f(x + (k1 * c(44.0 / 45.0) - k2 * c(56.0 / 15.0) + k3 * c(32.0 / 9.0)) * h)?;
+        let k5 = f(x
+            + (k1 * c(19372.0 / 6561.0) - k2 * c(25360.0 / 2187.0) + k3 * c(64448.0 / 6561.0)
+                - k4 * c(212.0 / 729.0))
+                * h)?;
+        let k6 = f(x
+            + (k1 * c(9017.0 / 3168.0) - k2 * c(355.0 / 33.0)
+                + k3 * c(46732.0 / 5247.0)
+                + k4 * c(49.0 / 176.0)
+                - k5 * c(5103.0 / 18656.0))
+                * h)?;
+        let x5 = x
+            + (k1 * c(35.0 / 384.0) + k3 * c(500.0 / 1113.0) + k4 * c(125.0 / 192.0)
+                - k5 * c(2187.0 / 6784.0)
+                + k6 * c(11.0 / 84.0))
+                * h;
+        let k7 = f(x5)?;
+        let error = (k1 * c(71.0 / 57600.0) - k3 * c(71.0 / 16695.0) + k4 * c(71.0 / 1920.0)
+            - k5 * c(17253.0 / 339200.0)
+            + k6 * c(22.0 / 525.0)
+            - k7 * c(1.0 / 40.0))
+            * h;
+        Some((x5, k7, error.norm()))
+    }
+
+    /// Shortens the step of length `h` from `x` to `end` until it ends within the
+    /// tolerance of where it first satisfies `crossed`, and returns that end point.
+    fn locate<F>(
+        &self,
+        x: Point3<T>,
+        k: Vector3<T>,
+        h: T,
+        sign: T,
+        mut end: Point3<T>,
+        crossed: F,
+    ) -> Point3<T>
+    where
+        F: Fn(Point3<T>) -> bool,
+    {
+        let (mut lo, mut hi) = (T::zero(), h);
+        for _ in 0..MAX_BISECTIONS {
+            if hi - lo <= self.tolerance {
+                break;
+            }
+            let mid = (lo + hi) * T::from_f64(0.5).unwrap();
+            match self.step(x, k, mid, sign) {
+                Some((p, _, _)) if !crossed(p) => lo = mid,
+                Some((p, _, _)) => {
+                    hi = mid;
+                    end = p;
+                }
+                None => hi = mid,
+            }
+        }
+        end
+    }
+
+    /// Traces from `seed` along `sign * B`, excluding the seed from the returned points.
+    fn trace_one(&self, seed: Point3<T>, sign: T) -> (Vec<Point3<T>>, StopReason) {
+        let mut points = Vec::new();
+        if self.in_region(seed) {
+            return (points, StopReason::Region);
+        }
+        if !self.in_bounds(seed) {
+            return (points, StopReason::OutOfBounds);
+        }
+        let Some(mut k) = self.direction_at(seed, sign) else {
+            return (points, StopReason::WeakField);
+        };
+
+        let mut x = seed;
+        let mut h = self.initial_step;
+        let mut length = T::zero();
+        let mut left_seed = false;
+
+        for _ in 0..self.max_steps {
+            h = NumFloat::min(h, self.max_length - length);
+            let Some((x_new, k_new, error)) = self.step(x, k, h, sign) else {
+                // The field is weak somewhere in the step, so shorten it or stop
+                if h > self.min_step {
+                    h = NumFloat::max(h * T::from_f64(0.25).unwrap(), self.min_step);
+                    continue;
+                }
+                return (points, StopReason::WeakField);
+            };
+
+            let factor = if error > T::zero() {
+                T::from_f64(0.9).unwrap()
+                    * NumFloat::powf(self.tolerance / error, T::from_f64(0.2).unwrap())
+            } else {
+                T::from_f64(5.0).unwrap()
+            };
+            let factor = NumFloat::min(
+                NumFloat::max(factor, T::from_f64(0.2).unwrap()),
+                T::from_f64(5.0).unwrap(),
+            );
+            if error > self.tolerance && h > self.min_step {
+                h = NumFloat::max(h * factor, self.min_step);
+                continue;
+            }
+
+            if self.in_region(x_new) {
+                points.push(self.locate(x, k, h, sign, x_new, |p| self.in_region(p)));
+                return (points, StopReason::Region);
+            }
+            if !self.in_bounds(x_new) {
+                let end = self.locate(x, k, h, sign, x_new, |p| !self.in_bounds(p));
+                // Snap onto the box face, which is within the tolerance
+                let (min, max) = self.bounds.unwrap();
+                points.push(end.coords.sup(&min.coords).inf(&max.coords).into());
+                return (points, StopReason::OutOfBounds);
+            }
+
+            // Closest approach of the step to the seed
+            let segment = x_new - x;
+            let t = NumFloat::min(
+                NumFloat::max((seed - x).dot(&segment) / segment.norm_squared(), T::zero()),
+                T::one(),
+            );
+            let distance = (x + segment * t - seed).norm();
+            if left_seed && distance <= self.closure_tolerance {
+                points.push(seed);
+                return (points, StopReason::Closed);
+            }
+            left_seed = left_seed
+                || (x_new - seed).norm() > T::from_f64(2.0).unwrap() * self.closure_tolerance;
+
+            length += segment.norm();
+            points.push(x_new);
+            x = x_new;
+            k = k_new;
+            if length >= self.max_length {
+                return (points, StopReason::MaxLength);
+            }
+            h = NumFloat::min(NumFloat::max(h * factor, self.min_step), self.max_step);
+        }
+        (points, StopReason::MaxSteps)
+    }
+
+    /// Traces the field line through `seed`.
+    pub fn trace(&self, seed: impl Into<Point3<T>>) -> FieldLine<T> {
+        let seed = seed.into();
+        if self.in_region(seed) {
+            return FieldLine {
+                points: alloc::vec![seed],
+                start: StopReason::Region,
+                end: StopReason::Region,
+            };
+        }
+
+        let (backward, start) = match self.direction {
+            TraceDirection::Forward => (Vec::new(), StopReason::Seed),
+            _ => self.trace_one(seed, -T::one()),
+        };
+        let forward = match self.direction {
+            TraceDirection::Backward => None,
+            // A closed loop already contains the whole line
+            _ if start == StopReason::Closed => None,
+            _ => Some(self.trace_one(seed, T::one())),
+        };
+
+        let mut points: Vec<Point3<T>> = backward.into_iter().rev().collect();
+        let end = match forward {
+            Some((forward, end)) => {
+                points.push(seed);
+                points.extend(forward);
+                end
+            }
+            None if start == StopReason::Closed => {
+                // Backward closure ends at the seed, which now leads the line
+                points.push(seed);
+                StopReason::Closed
+            }
+            None => {
+                points.push(seed);
+                StopReason::Seed
+            }
+        };
+
+        FieldLine { points, start, end }
+    }
+
+    /// Traces the field lines through `seeds`, in parallel with the `rayon` feature.
+    pub fn trace_all(&self, seeds: &[Point3<T>]) -> Vec<FieldLine<T>> {
+        #[cfg(feature = "rayon")]
+        {
+            use rayon::prelude::*;
+            seeds.par_iter().map(|&seed| self.trace(seed)).collect()
+        }
+
+        #[cfg(not(feature = "rayon"))]
+        seeds.iter().map(|&seed| self.trace(seed)).collect()
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::{
+        collections::SourceAssembly,
+        currents::CircularCurrent,
+        magnets::{CuboidMagnet, Dipole},
+    };
+    use nalgebra::{UnitQuaternion, point};
+
+    #[test]
+    fn test_dipole_field_line() {
+        // Dipole field lines follow r = L sin²θ
+        let dipole = Dipole::new([0.0; 3], UnitQuaternion::identity(), [0.0, 0.0, 1.0]);
+        let tracer = FieldLineTracer::new(&dipole)
+            .with_steps(1e-3, 1e-7, 1e-2)
+            .with_tolerance(1e-9)
+            .with_bounds(point![-1.0, -1.0, -0.03], point![1.0, 1.0, 0.03]);
+        let line = tracer.trace(point![0.1, 0.0, 0.0]);
+
+        assert_eq!(line.start(), StopReason::OutOfBounds);
+        assert_eq!(line.end(), StopReason::OutOfBounds);
+        for p in line.points() {
+            let r: f64 = p.coords.norm();
+            let sin_theta = p.coords.xy().norm() / r;
+            approx::assert_relative_eq!(r / sin_theta.powi(2), 0.1, max_relative = 1e-6);
+        }
+        // Clipped by the box, running down along the field outside the dipole
+        approx::assert_relative_eq!(line.points()[0].z, 0.03, epsilon = 1e-12);
+        approx::assert_relative_eq!(line.points().last().unwrap().z, -0.03, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_closed_loop() {
+        let current = CircularCurrent::new([0.0; 3], UnitQuaternion::identity(), 1.0, 1.0);
+        let sources = SourceAssembly::default().with(current);
+        let tracer = FieldLineTracer::new(&sources).with_closure_tolerance(1e-4);
+        let line = tracer.trace(point![0.45, 0.0, 0.0]);
+
+        assert_eq!(line.end(), StopReason::Closed);
+        assert_eq!(line.points()[0], point![0.45, 0.0, 0.0]);
+        assert_eq!(*line.points().last().unwrap(), point![0.45, 0.0, 0.0]);
+        // The loop encloses the wire at r = 0.5
+        assert!(line.points().iter().any(|p| p.x > 0.5));
+        assert!(line.points().iter().all(|p| p.y.abs() < 1e-6));
+    }
+
+    #[test]
+    fn test_stop_region_and_weak_field() {
+        let magnet = CuboidMagnet::default();
+        let tracer = FieldLineTracer::new(&magnet)
+            .with_direction(TraceDirection::Backward)
+            .with_stop_region(|p: Point3<f64>| p.coords.amax() <= 0.5);
+        let line = tracer.trace(point![0.1, 0.0, 0.8]);
+        assert_eq!(line.start(), StopReason::Region);
+        assert_eq!(line.end(), StopReason::Seed);
+        approx::assert_relative_eq!(line.points()[0].z, 0.5, epsilon = 1e-6);
+
+        assert_eq!(tracer.trace(point![0.0, 0.0, 0.0]).points().len(), 1);
+
+        let line = FieldLineTracer::new(&magnet)
+            .with_min_field(10.0)
+            .trace(point![0.0, 0.0, 1.0]);
+        assert_eq!(line.start(), StopReason::WeakField);
+        assert_eq!(line.points().len(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Field line tolerance must be positive.")]
+    fn test_zero_tolerance() {
+        let magnet = CuboidMagnet::default();
+        let _ = FieldLineTracer::new(&magnet).with_tolerance(0.0);
+    }
+
+    #[test]
+    fn test_tiny_tolerance() {
+        let magnet = CuboidMagnet::default();
+        let line = FieldLineTracer::new(&magnet)
+            .with_direction(TraceDirection::Forward)
+            .with_tolerance(1e-300)
+            .with_steps(1e-3, 1e-3, 1e-3)
+            .with_bounds(point![-0.1, -0.1, -0.1], point![0.1, 0.1, 0.1])
+            .trace(point![0.0, 0.0, 0.0995]);
+        assert_eq!(line.end(), StopReason::OutOfBounds);
+        assert!(line.points().last().unwrap().z <= 0.1);
+    }
+
+    #[test]
+    fn test_max_length() {
+        let magnet = CuboidMagnet::default();
+        let line = FieldLineTracer::new(&magnet)
+            .with_direction(TraceDirection::Forward)
+            .with_max_length(0.1, 1000)
+            .trace(point![0.0, 0.0, 1.0]);
+        assert_eq!(line.end(), StopReason::MaxLength);
+        approx::assert_relative_eq!(line.length(), 0.1, max_relative = 1e-9);
+    }
+}
@@ -0,0 +1,514 @@
+/*
+ * Magba is licensed under The 3-Clause BSD, see LICENSE.
+ * Copyright 2025 Sira Pornsiriprasert <code@psira.me>
+ */
+
+//! Structured sampling grids.
+
+use alloc::vec::Vec;
+use core::ops::Index;
+
+use nalgebra::{Point3, UnitQuaternion, Vector3};
+use num_traits::Float as NumFloat;
+
+use crate::base::{Float, Pose, Source, pose::impl_pose_methods, transform::impl_transform};
+
+/// Coordinate system of the [FieldGrid] axes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GridLayout {
+    /// Axes are `(x, y, z)` (m).
+    Cartesian,
+    /// Axes are radius `r` (m), azimuth `φ` (rad) from the x-axis, and `z` (m).
+    Cylindrical,
+    /// Axes are radius `r` (m), polar angle `θ` (rad) from the z-axis, and azimuth `φ`
+    /// (rad) from the x-axis.
+    Spherical,
+}
+
+/// Evenly spaced coordinates from `start` to `stop`, both included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GridAxis<T: Float> {
+    start: T,
+    stop: T,
+    count: usize,
+}
+
+impl<T: Float> GridAxis<T> {
+    /// Construct a [GridAxis] with `count` coordinates from `start` to `stop`.
+    ///
+    /// A single coordinate is at `start`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `count` is zero.
+    pub fn new(start: T, stop: T, count: usize) -> Self {
+        if count == 0 {
+            panic!("Grid axis must have at least one coordinate.");
+        }
+        Self { start, stop, count }
+    }
+
+    /// Construct a [GridAxis] with the single coordinate `value`.
+    pub fn fixed(value: T) -> Self {
+        Self::new(value, value, 1)
+    }
+
+    #[inline]
+    pub fn start(&self) -> T {
+        self.start
+    }
+
+    #[inline]
+    pub fn stop(&self) -> T {
+        self.stop
+    }
+
+    /// Number of coordinates.
+    #[inline]
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// The `i`-th coordinate.
+    #[inline]
+    pub fn coordinate(&self, i: usize) -> T {
+        if self.count == 1 {
+            return self.start;
+        }
+        self.start
+            + (self.stop - self.start) * T::from_usize(i).unwrap()
+                / T::from_usize(self.count - 1).unwrap()
+    }
+}
+
+/// Structured grid of observer points for field sampling.
+///
+/// The grid has three axes in the coordinate system of its [GridLayout]. Axes with a
+/// single coordinate reduce the grid to a plane or a line. The points are generated in
+/// the local frame and placed by the grid pose, so planes and lines can have any
+/// orientation.
+///
+/// Grid points are stored with the first axis varying fastest, i.e., the point `[i, j, k]`
+/// has the flat index `i + n0 * (j + n1 * k)`, where `[n0, n1, n2]` is the grid shape.
+/// This is the point order of VTK structured data.
+///
+/// # Examples
+///
+/// ```
+/// # use magba::analysis::{FieldGrid, GridAxis};
+/// # use magba::prelude::*;
+/// # use nalgebra::{UnitQuaternion, Vector3};
+/// let magnet = CylinderMagnet::default();
+///
+/// // Tilted plane of 21 x 11 points, 2 mm above the magnet
+/// let grid = FieldGrid::plane(GridAxis::new(-0.01, 0.01, 21), GridAxis::new(-0.005, 0.005, 11))
+///     .with_position([0.0, 0.0, 0.002])
+///     .with_orientation(UnitQuaternion::from_axis_angle(&Vector3::x_axis(), 0.3));
+/// let field = grid.compute_B(&magnet);
+///
+/// assert_eq!(field.shape(), [21, 11, 1]);
+/// let b = field[[3, 4, 0]];
+/// let bz = field.component(2);
+/// let row = field.slice(1, 4);
+/// assert_eq!(row.shape(), [21, 1, 1]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldGrid<T: Float = f64> {
+    pose: Pose<T>,
+    layout: GridLayout,
+    axes: [GridAxis<T>; 3],
+}
+
+impl_transform!(FieldGrid<T> where T: Float);
+
+impl<T: Float> FieldGrid<T> {
+    /// Construct a [FieldGrid] at the origin from its layout and axes.
+    pub fn new(layout: GridLayout, axes: [GridAxis<T>; 3]) -> Self {
+        Self {
+            pose: Pose::default(),
+            layout,
+            axes,
+        }
+    }
+
+    /// Construct a Cartesian [FieldGrid], e.g., a box.
+    pub fn cartesian(x: GridAxis<T>, y: GridAxis<T>, z: GridAxis<T>) -> Self {
+        Self::new(GridLayout::Cartesian, [x, y, z])
+    }
+
+    /// Construct a cylindrical [FieldGrid] around the local z-axis.
+    pub fn cylindrical(r: GridAxis<T>, phi: GridAxis<T>, z: GridAxis<T>) -> Self {
+        Self::new(GridLayout::Cylindrical, [r, phi, z])
+    }
+
+    /// Construct a spherical [FieldGrid] around the local origin.
+    pub fn spherical(r: GridAxis<T>, theta: GridAxis<T>, phi: GridAxis<T>) -> Self {
+        Self::new(GridLayout::Spherical, [r, theta, phi])
+    }
+
+    /// Construct a Cartesian plane in the local XY plane.
+    ///
+    /// Orient the plane with [FieldGrid::with_orientation].
+    pub fn plane(u: GridAxis<T>, v: GridAxis<T>) -> Self {
+        Self::cartesian(u, v, GridAxis::fixed(T::zero()))
+    }
+
+    /// Construct a line of `count` points from `start` to `end`, both included.
+    ///
+    /// The line is the local x-axis of the grid.
+    pub fn line(start: impl Into<Point3<T>>, end: impl Into<Point3<T>>, count: usize) -> Self {
+        let (start, end) = (start.into(), end.into());
+        let direction = end - start;
+        let orientation = UnitQuaternion::rotation_between(&Vector3::x(), &direction)
+            .unwrap_or_else(|| {
+                if direction.x < T::zero() {
+                    UnitQuaternion::from_axis_angle(&Vector3::z_axis(), T::pi())
+                } else {
+                    UnitQuaternion::identity()
+                }
+            });
+        Self::cartesian(
+            GridAxis::new(T::zero(), direction.norm(), count),
+            GridAxis::fixed(T::zero()),
+            GridAxis::fixed(T::zero()),
+        )
+        .with_position(start)
+        .with_orientation(orientation)
+    }
+
+    impl_pose_methods!();
+
+    #[inline]
+    pub fn layout(&self) -> GridLayout {
+        self.layout
+    }
+
+    #[inline]
+    pub fn axes(&self) -> &[GridAxis<T>; 3] {
+        &self.axes
+    }
+
+    /// Number of coordinates along each axis.
+    #[inline]
+    pub fn shape(&self) -> [usize; 3] {
+        self.axes.map(|axis| axis.count)
+    }
+
+    /// Number of grid points.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.shape().iter().product()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Flat index of the point `[i, j, k]`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    #[inline]
+    pub fn flat_index(&self, [i, j, k]: [usize; 3]) -> usize {
+        let [n0, n1, n2] = self.shape();
+        if i >= n0 || j >= n1 || k >= n2 {
+            panic!("Grid index out of bounds.");
+        }
+        i + n0 * (j + n1 * k)
+    }
+
+    /// Layout coordinates of the point `[i, j, k]`.
+    #[inline]
+    pub fn coordinates(&self, [i, j, k]: [usize; 3]) -> [T; 3] {
+        [
+            self.axes[0].coordinate(i),
+            self.axes[1].coordinate(j),
+            self.axes[2].coordinate(k),
+        ]
+    }
+
+    /// Position of the point `[i, j, k]` in the local frame of the grid.
+    pub fn local_point(&self, index: [usize; 3]) -> Point3<T> {
+        let [a, b, c] = self.coordinates(index);
+        match self.layout {
+            GridLayout::Cartesian => Point3::new(a, b, c),
+            GridLayout::Cylindrical => Point3::new(a * NumFloat::cos(b), a * NumFloat::sin(b), c),
+            GridLayout::Spherical => Point3::new(
+                a * NumFloat::sin(b) * NumFloat::cos(c),
+                a * NumFloat::sin(b) * NumFloat::sin(c),
+                a * NumFloat::cos(b),
+            ),
+        }
+    }
+
+    /// Global position of the point `[i, j, k]`.
+    #[inline]
+    pub fn point(&self, index: [usize; 3]) -> Point3<T> {
+        self.pose.as_isometry() * self.local_point(index)
+    }
+
+    /// Global positions of all grid points in flat order.
+    pub fn points(&self) -> Vec<Point3<T>> {
+        let [n0, n1, n2] = self.shape();
+        let mut points = Vec::with_capacity(self.len());
+        for k in 0..n2 {
+            for j in 0..n1 {
+                for i in 0..n0 {
+                    points.push(self.point([i, j, k]));
+                }
+            }
+        }
+        points
+    }
+
+    /// Computes the B-field of `source` at the grid points with
+    /// [Source::compute_B_batch].
+    #[allow(non_snake_case)]
+    pub fn compute_B<S: Source<T> + ?Sized>(&self, source: &S) -> GridField<T> {
+        let points = self.points();
+        let values = source.compute_B_batch(&points);
+        GridField {
+            grid: self.clone(),
+            points,
+            values,
+        }
+    }
+}
+
+/// Field vectors sampled on a [FieldGrid], returned by [FieldGrid::compute_B].
+///
+/// Index with `field[[i, j, k]]` to get the vector at a grid point.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GridField<T: Float = f64> {
+    grid: FieldGrid<T>,
+    points: Vec<Point3<T>>,
+    values: Vec<Vector3<T>>,
+}
+
+impl<T: Float> GridField<T> {
+    /// Construct a [GridField] from precomputed values at [FieldGrid::points].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of values does not match the number of grid points.
+    pub fn new(grid: FieldGrid<T>, values: Vec<Vector3<T>>) -> Self {
+        if values.len() != grid.len() {
+            panic!("Number of values must match the number of grid points.");
+        }
+        Self {
+            points: grid.points(),
+            grid,
+            values,
+        }
+    }
+
+    #[inline]
+    pub fn grid(&self) -> &FieldGrid<T> {
+        &self.grid
+    }
+
+    /// See [FieldGrid::shape].
+    #[inline]
+    pub fn shape(&self) -> [usize; 3] {
+        self.grid.shape()
+    }
+
+    /// Global grid points in flat order.
+    #[inline]
+    pub fn points(&self) -> &[Point3<T>] {
+        &self.points
+    }
+
+    /// Field vectors in flat order.
+    #[inline]
+    pub fn values(&self) -> &[Vector3<T>] {
+        &self.values
+    }
+
+    #[inline]
+    pub fn into_values(self) -> Vec<Vector3<T>> {
+        self.values
+    }
+
+    /// Global position of the point `[i, j, k]`.
+    #[inline]
+    pub fn point(&self, index: [usize; 3]) -> Point3<T> {
+        self.points[self.grid.flat_index(index)]
+    }
+
+    /// Global x, y, or z component (`axis` 0, 1, or 2) of the field in flat order.
+    pub fn component(&self, axis: usize) -> Vec<T> {
+        self.values.iter().map(|v| v[axis]).collect()
+    }
+
+    /// Field magnitude in flat order.
+    pub fn magnitude(&self) -> Vec<T> {
+        self.values.iter().map(|v| v.norm()).collect()
+    }
+
+    /// Field component along the global `direction` in flat order.
+    ///
+    /// `direction` is normalized, e.g., the sensitive axis of a sensor.
+    pub fn projection(&self, direction: Vector3<T>) -> Vec<T> {
+        let direction = direction.normalize();
+        self.values.iter().map(|v| v.dot(&direction)).collect()
+    }
+
+    /// Takes the slice at `index` along `axis`, which keeps that axis with one coordinate.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `axis` or `index` is out of bounds.
+    pub fn slice(&self, axis: usize, index: usize) -> Self {
+        let shape = self.shape();
+        if axis >= 3 || index >= shape[axis] {
+            panic!("Grid slice out of bounds.");
+        }
+
+        let mut grid = self.grid.clone();
+        grid.axes[axis] = GridAxis::fixed(self.grid.axes[axis].coordinate(index));
+
+        let mut points = Vec::with_capacity(grid.len());
+        let mut values = Vec::with_capacity(grid.len());
+        let mut range = [0..shape[0], 0..shape[1], 0..shape[2]];
+        range[axis] = index..index + 1;
+        let [r0, r1, r2] = range;
+        for k in r2 {
+            for j in r1.clone() {
+                for i in r0.clone() {
+                    let flat = self.grid.flat_index([i, j, k]);
+                    points.push(self.points[flat]);
+                    values.push(self.values[flat]);
+                }
+            }
+        }
+
+        Self {
+            grid,
+            points,
+            values,
+        }
+    }
+}
+
+impl<T: Float> Index<[usize; 3]> for GridField<T> {
+    type Output = Vector3<T>;
+
+    fn index(&self, index: [usize; 3]) -> &Self::Output {
+        &self.values[self.grid.flat_index(index)]
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::magnets::CuboidMagnet;
+    use approx::assert_relative_eq;
+    use core::f64::consts::{FRAC_PI_2, PI};
+    use nalgebra::{point, vector};
+
+    #[test]
+    fn test_layouts() {
+        let grid = FieldGrid::cylindrical(
+            GridAxis::new(1.0, 2.0, 2),
+            GridAxis::new(0.0, FRAC_PI_2, 3),
+            GridAxis::fixed(0.5),
+        );
+        assert_eq!(grid.shape(), [2, 3, 1]);
+        assert_relative_eq!(
+            grid.point([1, 2, 0]),
+            point![0.0, 2.0, 0.5],
+            epsilon = 1e-15
+        );
+        assert_eq!(
+            grid.points()[grid.flat_index([1, 2, 0])],
+            grid.point([1, 2, 0])
+        );
+
+        let grid = FieldGrid::spherical(
+            GridAxis::fixed(2.0),
+            GridAxis::new(0.0, PI, 3),
+            GridAxis::new(0.0, PI, 3),
+        )
+        .with_position([1.0, 0.0, 0.0]);
+        assert_relative_eq!(
+            grid.point([0, 1, 1]),
+            point![1.0, 2.0, 0.0],
+            epsilon = 1e-15
+        );
+        assert_relative_eq!(
+            grid.point([0, 2, 0]),
+            point![1.0, 0.0, -2.0],
+            epsilon = 1e-15
+        );
+
+        let grid = FieldGrid::line([1.0, 1.0, 0.0], [1.0, 1.0, -2.0], 5);
+        assert_relative_eq!(
+            grid.point([1, 0, 0]),
+            point![1.0, 1.0, -0.5],
+            epsilon = 1e-15
+        );
+        assert_relative_eq!(
+            grid.point([4, 0, 0]),
+            point![1.0, 1.0, -2.0],
+            epsilon = 1e-15
+        );
+        let grid = FieldGrid::line([1.0, 0.0, 0.0], [-1.0, 0.0, 0.0], 3);
+        assert_relative_eq!(
+            grid.point([2, 0, 0]),
+            point![-1.0, 0.0, 0.0],
+            epsilon = 1e-15
+        );
+    }
+
+    #[test]
+    fn test_compute_and_slice() {
+        let magnet = CuboidMagnet::default();
+        let grid = FieldGrid::plane(GridAxis::new(-1.0, 1.0, 4), GridAxis::new(-2.0, 2.0, 3))
+            .with_position([0.0, 0.0, 1.0])
+            .with_orientation(UnitQuaternion::from_axis_angle(&Vector3::y_axis(), 0.4));
+        let field = grid.compute_B(&magnet);
+
+        #[cfg(not(feature = "simd"))]
+        {
+            for (point, b) in field.points().iter().zip(field.values()) {
+                assert_eq!(*b, magnet.compute_B(*point));
+            }
+            assert_eq!(field[[2, 1, 0]], magnet.compute_B(field.point([2, 1, 0])));
+        }
+        // The vectorized kernel rounds differently from the scalar field function
+        #[cfg(feature = "simd")]
+        {
+            assert_eq!(field.values(), magnet.compute_B_batch(field.points()));
+            assert_relative_eq!(
+                field[[2, 1, 0]],
+                magnet.compute_B(field.point([2, 1, 0])),
+                epsilon = 1e-15,
+                max_relative = 1e-12
+            );
+        }
+        assert_eq!(field.component(1)[5], field.values()[5].y);
+        assert_eq!(field.magnitude()[5], field.values()[5].norm());
+        assert_relative_eq!(
+            field.projection(vector![0.0, 0.0, 2.0])[5],
+            field.values()[5].z
+        );
+
+        let column = field.slice(0, 2);
+        assert_eq!(column.shape(), [1, 3, 1]);
+        for j in 0..3 {
+            assert_eq!(column[[0, j, 0]], field[[2, j, 0]]);
+            assert_eq!(column.point([0, j, 0]), column.grid().point([0, j, 0]));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_out_of_bounds() {
+        let field =
+            FieldGrid::line([0.0; 3], [1.0, 0.0, 0.0], 3).compute_B(&CuboidMagnet::default());
+        let _ = field[[0, 1, 0]];
+    }
+}
@@ -0,0 +1,13 @@
+/*
+ * Magba is licensed under The 3-Clause BSD, see LICENSE.
+ * Copyright 2025 Sira Pornsiriprasert <code@psira.me>
+ */
+
+//! Field sampling and analysis.
+
+mod contour;
+mod field_lines;
+mod grid;
+
+pub use field_lines::{FieldLine, FieldLineTracer, StopReason, TraceDirection};
+pub use grid::{FieldGrid, GridAxis, GridField, GridLayout};
@@ -0,0 +1,521 @@
+/*
+ * Magba is licensed under The 3-Clause BSD, see LICENSE.
+ * Copyright 2025 Sira Pornsiriprasert <code@psira.me>
+ */
+
+//! Bounding volume hierarchy for triangular meshes.
+//!
+//! Triangles far from the observer are grouped into clusters and approximated by the
+//! monopole, dipole, and quadrupole terms of a multipole expansion about the cluster center
+//! (Barnes–Hut). A cluster is approximated if `radius < theta * distance`, where `radius`
+//! bounds the cluster and `distance` is measured from the observer to the cluster center.
+//! The error of each cluster relative to its monopole field decreases as `theta³`. Since
+//! the fields of clusters on a closed surface largely cancel, the error relative to the
+//! net field is larger near field minima.
+//!
+//! # References
+//!
+//! - Barnes, Josh, and Piet Hut. “A Hierarchical O(N log N) Force-Calculation Algorithm.” Nature 324, no. 6096 (December 1986): 446–49. <https://doi.org/10.1038/324446a0>.
+//! - Barill, Gavin, Neil G. Dickson, Ryan Schmidt, David I. W. Levin, and Alec Jacobson. “Fast Winding Numbers for Soups and Clouds.” ACM Transactions on Graphics 37, no. 4 (August 2018): 1–12. <https://doi.org/10.1145/3197517.3201337>.
+
+use alloc::vec::Vec;
+use nalgebra::{Matrix3, Vector3};
+
+use crate::base::{Float, mesh::Triangle};
+
+/// Maximum number of triangles in a leaf node.
+const LEAF_SIZE: usize = 8;
+
+/// Capacity of the traversal stack in [Bvh::fold]. Nodes are split at the median, so the
+/// depth is at most `log2(n)` and the stack holds at most one node per level plus one.
+const STACK_SIZE: usize = usize::BITS as usize + 1;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct BvhNode<T: Float> {
+    /// Area-weighted center of the cluster.
+    pub center: Vector3<T>,
+    /// Radius of the sphere around `center` containing all triangles of the cluster.
+    pub radius: T,
+    /// Sum of triangle area vectors `Σ Aᵢ`.
+    pub area_vector: Vector3<T>,
+    /// First moment of the area vectors `Σ (cᵢ - c) Aᵢᵀ`.
+    pub area_moment: Matrix3<T>,
+    /// Second moment of the area vectors `Σ ((cᵢ - c)(cᵢ - c)ᵀ + Σᵢ) Aᵢₖ` for each component
+    /// `k`, where `Σᵢ` is the second moment of triangle `i` about its centroid.
+    pub area_quadrupole: [Matrix3<T>; 3],
+    /// Range of the cluster in [Bvh::order].
+    pub start: usize,
+    pub end: usize,
+    /// Indices of the child nodes. `None` for leaves.
+    pub children: Option<(usize, usize)>,
+}
+
+/// Bounding volume hierarchy over the triangles of a [TriMesh](crate::base::mesh::TriMesh).
+///
+/// Built with [TriMesh::with_bvh](crate::base::mesh::TriMesh::with_bvh).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bvh<T: Float> {
+    pub(crate) nodes: Vec<BvhNode<T>>,
+    /// Triangle indices sorted such that each node covers a contiguous range.
+    pub(crate) order: Vec<usize>,
+    theta: T,
+}
+
+#[inline]
+fn centroid<T: Float>(triangle: &Triangle<T>) -> Vector3<T> {
+    let [v1, v2, v3] = triangle.vertices();
+    (v1 + v2 + v3) / T::from_f64(3.0).unwrap()
+}
+
+#[inline]
+fn area_vector<T: Float>(triangle: &Triangle<T>) -> Vector3<T> {
+    let [v1, v2, v3] = triangle.vertices();
+    (v2 - v1).cross(&(v3 - v1)) / T::from_f64(2.0).unwrap()
+}
+
+/// Second moment of a uniform triangle about its centroid, per unit area.
+#[inline]
+fn spread<T: Float>(triangle: &Triangle<T>) -> Matrix3<T> {
+    let c = centroid(triangle);
+    triangle.vertices().iter().fold(Matrix3::zeros(), |acc, v| {
+        acc + (v - c) * (v - c).transpose()
+    }) / T::from_f64(12.0).unwrap()
+}
+
+/// Jacobian of `d / |d|³` with respect to `d`.
+#[inline]
+fn dipole_kernel<T: Float>(d: Vector3<T>, r2: T) -> Matrix3<T> {
+    let r3 = r2 * num_traits::Float::sqrt(r2);
+    let three = T::from_f64(3.0).unwrap();
+    (Matrix3::identity() - d * d.transpose() * (three / r2)) / r3
+}
+
+/// Second-order term `½ Σₐᵦ Qₐᵦ ∂ₐ∂ᵦ (d / |d|³)` of the expansion of `d / |d|³` for a
+/// symmetric second moment `Q`.
+#[inline]
+fn quadrupole_kernel<T: Float>(d: Vector3<T>, r2: T, q: &Matrix3<T>) -> Vector3<T> {
+    let r5 = r2 * r2 * num_traits::Float::sqrt(r2);
+    let (three, half) = (T::from_f64(3.0).unwrap(), T::from_f64(0.5).unwrap());
+    let five = T::from_f64(5.0).unwrap();
+    let qd = q * d;
+    -(d * (q.trace() - five * d.dot(&qd) / r2) + qd * T::from_f64(2.0).unwrap())
+        * (three * half / r5)
+}
+
+/// Current moments of a cluster for a given set of surface current densities.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CurrentMoments<T: Float> {
+    /// `Σ aᵢJᵢ`
+    monopole: Vector3<T>,
+    /// `Σ aᵢJᵢ (cᵢ - c)ᵀ`
+    dipole: Matrix3<T>,
+    /// `Σ aᵢJᵢₖ ((cᵢ - c)(cᵢ - c)ᵀ + Σᵢ)` for each component `k`
+    quadrupole: [Matrix3<T>; 3],
+}
+
+impl<T: Float> Bvh<T> {
+    /// Builds the hierarchy over `triangles` with opening angle `theta`.
+    pub fn new(triangles: &[Triangle<T>], theta: T) -> Self {
+        let centroids: Vec<Vector3<T>> = triangles.iter().map(centroid).collect();
+        let mut bvh = Self {
+            nodes: Vec::new(),
+            order: (0..triangles.len()).collect(),
+            theta,
+        };
+        if !triangles.is_empty() {
+            bvh.build(triangles, &centroids, 0, triangles.len());
+        }
+        bvh
+    }
+
+    /// Opening angle of the far-field approximation.
+    #[inline]
+    pub fn theta(&self) -> T {
+        self.theta
+    }
+
+    fn build(
+        &mut self,
+        triangles: &[Triangle<T>],
+        centroids: &[Vector3<T>],
+        start: usize,
+        end: usize,
+    ) -> usize {
+        let indices = &self.order[start..end];
+
+        let total_area = indices
+            .iter()
+            .fold(T::zero(), |acc, &i| acc + area_vector(&triangles[i]).norm());
+        let center = if total_area > T::zero() {
+            indices.iter().fold(Vector3::zeros(), |acc, &i| {
+                acc + centroids[i] * area_vector(&triangles[i]).norm()
+            }) / total_area
+        } else {
+            indices
+                .iter()
+                .fold(Vector3::zeros(), |acc, &i| acc + centroids[i])
+                / T::from_usize(indices.len()).unwrap()
+        };
+
+        let mut radius = T::zero();
+        let mut area_vec = Vector3::zeros();
+        let mut area_moment = Matrix3::zeros();
+        let mut area_quadrupole = [Matrix3::zeros(); 3];
+        let mut lower = Vector3::from_element(T::infinity());
+        let mut upper = Vector3::from_element(T::neg_infinity());
+        for &i in indices {
+            for v in triangles[i].vertices() {
+                radius = num_traits::Float::max(radius, (v - center).norm());
+            }
+            let a = area_vector(&triangles[i]);
+            area_vec += a;
+            let delta = centroids[i] - center;
+            area_moment += delta * a.transpose();
+            let outer = delta * delta.transpose() + spread(&triangles[i]);
+            for k in 0..3 {
+                area_quadrupole[k] += outer * a[k];
+            }
+            lower = lower.inf(&centroids[i]);
+            upper = upper.sup(&centroids[i]);
+        }
+
+        let id = self.nodes.len();
+        self.nodes.push(BvhNode {
+            center,
+            radius,
+            area_vector: area_vec,
+            area_moment,
+            area_quadrupole,
+            start,
+            end,
+            children: None,
+        });
+
+        if end - start > LEAF_SIZE {
+            let axis = (upper - lower).imax();
+            let mid = (start + end) / 2;
+            self.order[start..end].select_nth_unstable_by(mid - start, |&a, &b| {
+                centroids[a][axis]
+                    .partial_cmp(&centroids[b][axis])
+                    .unwrap_or(core::cmp::Ordering::Equal)
+            });
+            let left = self.build(triangles, centroids, start, mid);
+            let right = self.build(triangles, centroids, mid, end);
+            self.nodes[id].children = Some((left, right));
+        }
+
+        id
+    }
+
+    /// Folds over the hierarchy as seen from `point`.
+    ///
+    /// Calls `far(acc, node_index, d, r2)` for clusters satisfying the opening criterion,
+    /// where `d` is the vector from `point` to the cluster center and `r2 = |d|²`. Calls
+    /// `near(acc, triangle_index)` for triangles in the remaining leaves.
+    #[inline]
+    pub(crate) fn fold<A, N, F>(&self, point: Vector3<T>, init: A, near: N, far: F) -> A
+    where
+        N: Fn(A, usize) -> A,
+        F: Fn(A, usize, Vector3<T>, T) -> A,
+    {
+        let mut acc = init;
+        if self.nodes.is_empty() {
+            return acc;
+        }
+        let theta2 = self.theta * self.theta;
+        let mut stack = [0; STACK_SIZE];
+        let mut len = 1;
+        while len > 0 {
+            len -= 1;
+            let id = stack[len];
+            let node = &self.nodes[id];
+            let d = node.center - point;
+            let r2 = d.norm_squared();
+            if node.radius * node.radius < theta2 * r2 {
+                acc = far(acc, id, d, r2);
+            } else if let Some((left, right)) = node.children {
+                stack[len] = left;
+                stack[len + 1] = right;
+                len += 2;
+            } else {
+                acc = self.order[node.start..node.end]
+                    .iter()
+                    .fold(acc, |acc, &i| near(acc, i));
+            }
+        }
+        acc
+    }
+
+    /// Approximate solid angle of the cluster seen from a point, given the vector `d` from
+    /// the point to the cluster center.
+    #[inline]
+    pub(crate) fn far_solid_angle(&self, id: usize, d: Vector3<T>, r2: T) -> T {
+        let node = &self.nodes[id];
+        let r3 = r2 * num_traits::Float::sqrt(r2);
+        let quadrupole = (0..3).fold(T::zero(), |acc, k| {
+            acc + quadrupole_kernel(d, r2, &node.area_quadrupole[k])[k]
+        });
+        node.area_vector.dot(&d) / r3
+            + (dipole_kernel(d, r2) * node.area_moment).trace()
+            + quadrupole
+    }
+
+    /// Approximate B-field of the magnetic surface charge of a cluster, given the vector `d`
+    /// from the observer to the cluster center.
+    #[inline]
+    #[allow(non_snake_case)]
+    pub(crate) fn far_charge_B(
+        &self,
+        id: usize,
+        d: Vector3<T>,
+        r2: T,
+        polarization: Vector3<T>,
+    ) -> Vector3<T> {
+        let node = &self.nodes[id];
+        let r3 = r2 * num_traits::Float::sqrt(r2);
+        let charge = node.area_vector.dot(&polarization);
+        let dipole = node.area_moment * polarization;
+        let quadrupole = node.area_quadrupole[0] * polarization.x
+            + node.area_quadrupole[1] * polarization.y
+            + node.area_quadrupole[2] * polarization.z;
+        -(d * (charge / r3) + dipole_kernel(d, r2) * dipole + quadrupole_kernel(d, r2, &quadrupole))
+            / (T::from_f64(4.0).unwrap() * T::pi())
+    }
+
+    /// Current moments of each node for the given surface current densities. Triangles
+    /// without a current density carry no current.
+    pub(crate) fn current_moments(
+        &self,
+        triangles: &[Triangle<T>],
+        current_densities: &[Vector3<T>],
+    ) -> Vec<CurrentMoments<T>> {
+        let zero = CurrentMoments {
+            monopole: Vector3::zeros(),
+            dipole: Matrix3::zeros(),
+            quadrupole: [Matrix3::zeros(); 3],
+        };
+        self.nodes
+            .iter()
+            .map(|node| {
+                self.order[node.start..node.end]
+                    .iter()
+                    .filter(|&&i| i < current_densities.len())
+                    .fold(zero.clone(), |mut moments, &i| {
+                        let element = current_densities[i] * area_vector(&triangles[i]).norm();
+                        let delta = centroid(&triangles[i]) - node.center;
+                        let outer = delta * delta.transpose() + spread(&triangles[i]);
+                        moments.monopole += element;
+                        moments.dipole += element * delta.transpose();
+                        for k in 0..3 {
+                            moments.quadrupole[k] += outer * element[k];
+                        }
+                        moments
+                    })
+            })
+            .collect()
+    }
+
+    /// Approximate B-field of the surface current of a cluster, given the vector `d` from
+    /// the observer to the cluster center and the moments from [Bvh::current_moments].
+    #[inline]
+    #[allow(non_snake_case)]
+    pub(crate) fn far_current_B(
+        &self,
+        d: Vector3<T>,
+        r2: T,
+        moments: &CurrentMoments<T>,
+    ) -> Vector3<T> {
+        let r3 = r2 * num_traits::Float::sqrt(r2);
+        let kernel = dipole_kernel(d, r2);
+        let field = (0..3).fold(moments.monopole.cross(&d) / r3, |acc, k| {
+            acc + moments.dipole.column(k).cross(&kernel.column(k))
+                + Vector3::ith(k, T::one()).cross(&quadrupole_kernel(d, r2, &moments.quadrupole[k]))
+        });
+        -field * T::mu0_4pi()
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::{
+        base::{Source, mesh::TriMesh},
+        currents::{PlanarCoil, SpiralShape},
+        fields::sheet_current_B,
+        magnets::MeshMagnet,
+    };
+    use approx::assert_relative_eq;
+    use nalgebra::{Point3, UnitQuaternion, Vector3, point};
+
+    /// Cube of side 0.2 centered at the origin with each face split into `n × n` quads.
+    fn subdivided_cube(n: usize) -> TriMesh<f64> {
+        let mut vertices = Vec::new();
+        let mut faces = Vec::new();
+        for axis in 0..3 {
+            for sign in [-1.0, 1.0] {
+                let (u_axis, v_axis) = ((axis + 1) % 3, (axis + 2) % 3);
+                let offset = vertices.len();
+                for i in 0..=n {
+                    for j in 0..=n {
+                        let mut v = Vector3::zeros();
+                        v[axis] = 0.1 * sign;
+                        v[u_axis] = -0.1 + 0.2 * i as f64 / n as f64;
+                        v[v_axis] = -0.1 + 0.2 * j as f64 / n as f64;
+                        vertices.push(v);
+                    }
+                }
+                let id = |i: usize, j: usize| offset + i * (n + 1) + j;
+                for i in 0..n {
+                    for j in 0..n {
+                        let (a, b, c, d) = (id(i, j), id(i + 1, j), id(i + 1, j + 1), id(i, j + 1));
+                        if sign > 0.0 {
+                            faces.extend([[a, b, c], [a, c, d]]);
+                        } else {
+                            faces.extend([[a, c, b], [a, d, c]]);
+                        }
+                    }
+                }
+            }
+        }
+        TriMesh::new_unchecked(vertices, faces)
+    }
+
+    fn points() -> Vec<Point3<f64>> {
+        let mut points = Vec::new();
+        for i in 0..5 {
+            for j in 0..5 {
+                let x = -0.5 + 0.25 * i as f64;
+                let y = -0.45 + 0.225 * j as f64;
+                points.push(point![x, y, 0.03]);
+                points.push(point![x * 2.0, y * 2.0, 1.0]);
+            }
+        }
+        points
+    }
+
+    #[test]
+    fn test_mesh_exact_with_tiny_opening() {
+        let mesh = subdivided_cube(6);
+        let magnet = MeshMagnet::new(
+            [0.0; 3],
+            UnitQuaternion::identity(),
+            [0.1, 0.2, 0.3],
+            mesh.clone(),
+        );
+        let accelerated = MeshMagnet::new(
+            [0.0; 3],
+            UnitQuaternion::identity(),
+            [0.1, 0.2, 0.3],
+            mesh.with_bvh(1e-9),
+        );
+        for p in points() {
+            assert_relative_eq!(
+                accelerated.compute_B(p),
+                magnet.compute_B(p),
+                epsilon = 1e-14,
+                max_relative = 1e-12
+            );
+        }
+    }
+
+    #[test]
+    fn test_mesh_far_field() {
+        let mesh = subdivided_cube(12);
+        let magnet = MeshMagnet::new(
+            [0.0; 3],
+            UnitQuaternion::identity(),
+            [0.1, 0.2, 0.3],
+            mesh.clone(),
+        );
+        let accelerated = MeshMagnet::new(
+            [0.0; 3],
+            UnitQuaternion::identity(),
+            [0.1, 0.2, 0.3],
+            mesh.with_bvh(0.3),
+        );
+        let points = points();
+        let expected = magnet.compute_B_batch(&points);
+        let actual = accelerated.compute_B_batch(&points);
+        let scale = expected.iter().map(|e| e.norm()).fold(0.0, f64::max);
+        let max_error = actual
+            .iter()
+            .zip(&expected)
+            .map(|(a, e)| (a - e).norm() / scale)
+            .fold(0.0, f64::max);
+        assert!(max_error < 1e-3, "{max_error}");
+    }
+
+    #[test]
+    fn test_invalid_opening_angle() {
+        for theta in [0.0, -0.3, f64::NAN, f64::INFINITY] {
+            let result = subdivided_cube(2).try_with_bvh(theta);
+            assert!(
+                matches!(result, Err(crate::Error::InvalidGeometry(_))),
+                "{theta}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_winding_number() {
+        let mesh = subdivided_cube(12).with_bvh(0.5);
+        for p in points() {
+            let expected = p.coords.amax() < 0.1;
+            assert_eq!(mesh.contains(p), expected, "{p}");
+        }
+        assert!(mesh.contains(point![0.0, 0.0, 0.0]));
+        assert!(mesh.contains(point![0.099, 0.099, -0.099]));
+        assert!(!mesh.contains(point![0.101, 0.0, 0.0]));
+    }
+
+    #[test]
+    fn test_sheet_current_far_field() {
+        let sheet = PlanarCoil::new(SpiralShape::Square, 0.02, 5.0, 0.001)
+            .with_current(2.0)
+            .to_sheet_current();
+        let mut accelerated = sheet.clone();
+        accelerated.set_mesh(sheet.mesh().clone().with_bvh(0.3));
+
+        let points: Vec<_> = points().into_iter().map(|p| p * 0.1).collect();
+        let expected = sheet.compute_B_batch(&points);
+        let actual = accelerated.compute_B_batch(&points);
+        let scale = expected.iter().map(|e| e.norm()).fold(0.0, f64::max);
+        let max_error = actual
+            .iter()
+            .zip(&expected)
+            .map(|(a, e)| (a - e).norm() / scale)
+            .fold(0.0, f64::max);
+        assert!(max_error < 1e-3, "{max_error}");
+        assert_relative_eq!(
+            accelerated.compute_B(points[3]),
+            actual[3],
+            max_relative = 1e-12
+        );
+    }
+
+    #[test]
+    fn test_sheet_current_cached_moments() {
+        let sheet = PlanarCoil::new(SpiralShape::Square, 0.02, 5.0, 0.001)
+            .with_current(2.0)
+            .to_sheet_current();
+        let mut accelerated = sheet.clone();
+        accelerated.set_mesh(sheet.mesh().clone().with_bvh(0.3));
+        accelerated.set_current_densities(
+            sheet
+                .current_densities()
+                .iter()
+                .map(|j| j * 3.0)
+                .collect::<Vec<_>>(),
+        );
+
+        let point = point![0.01, -0.02, 0.05];
+        let expected = sheet_current_B(
+            point,
+            accelerated.position(),
+            accelerated.orientation(),
+            accelerated.current_densities(),
+            accelerated.mesh(),
+        );
+        assert_eq!(accelerated.compute_B(point), expected);
+        assert_eq!(accelerated.compute_B_batch(&[point]), [expected]);
+    }
+}
@@ -0,0 +1,723 @@
+/*
+ * Magba is licensed under The 3-Clause BSD, see LICENSE.
+ * Copyright 2025 Sira Pornsiriprasert <code@psira.me>
+ */
+
+//! Mesh file readers.
+
+#[cfg(any(feature = "io-obj", feature = "io-ply", feature = "io-msh"))]
+use std::io::{BufRead, Error, ErrorKind, Result};
+
+#[cfg(any(feature = "io-obj", feature = "io-ply", feature = "io-msh"))]
+use nalgebra::Vector3;
+
+use crate::base::{
+    Float,
+    mesh::{MeshTolerance, RepairOptions},
+};
+#[cfg(any(feature = "io-obj", feature = "io-ply", feature = "io-msh"))]
+use alloc::vec::Vec;
+
+/// Options for reading a [TriMesh](super::TriMesh) from a file.
+///
+/// # Examples
+///
+/// ```
+/// # use magba::base::mesh::{MeshReadOptions, RepairOptions};
+/// // File in millimeters, weld and orient the mesh before validation
+/// let options = MeshReadOptions::<f64>::default()
+///     .with_scale(1e-3)
+///     .with_repair(RepairOptions::default());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MeshReadOptions<T: Float> {
+    /// Factor converting file coordinates to meters, e.g., `1e-3` for files in millimeters.
+    pub scale: T,
+    /// Repair the mesh before validation. See [repair](super::repair).
+    pub repair: Option<RepairOptions<T>>,
+    /// Tolerances used by the validation.
+    pub tolerance: MeshTolerance<T>,
+}
+
+impl<T: Float> Default for MeshReadOptions<T> {
+    fn default() -> Self {
+        Self {
+            scale: T::one(),
+            repair: None,
+            tolerance: MeshTolerance::default(),
+        }
+    }
+}
+
+impl<T: Float> MeshReadOptions<T> {
+    /// Set the factor converting file coordinates to meters.
+    pub fn with_scale(mut self, scale: T) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Repair the mesh with the given options before validation.
+    pub fn with_repair(mut self, repair: RepairOptions<T>) -> Self {
+        self.repair = Some(repair);
+        self
+    }
+
+    /// Set the validation tolerances.
+    pub fn with_tolerance(mut self, tolerance: MeshTolerance<T>) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+}
+
+/// Vertices and faces read from a file.
+#[cfg(any(feature = "io-obj", feature = "io-ply"))]
+type RawMesh<T> = (Vec<Vector3<T>>, Vec<[usize; 3]>);
+
+#[cfg(any(feature = "io-obj", feature = "io-ply", feature = "io-msh"))]
+fn invalid_data(line: usize, msg: impl core::fmt::Display) -> Error {
+    Error::new(ErrorKind::InvalidData, format!("line {line}: {msg}"))
+}
+
+#[cfg(any(feature = "io-obj", feature = "io-ply", feature = "io-msh"))]
+fn to_vector<T: Float>(v: [f64; 3]) -> Vector3<T> {
+    Vector3::new(
+        T::from_f64(v[0]).unwrap(),
+        T::from_f64(v[1]).unwrap(),
+        T::from_f64(v[2]).unwrap(),
+    )
+}
+
+/// Triangulates a polygon as a fan around its first vertex.
+#[cfg(any(feature = "io-obj", feature = "io-ply"))]
+fn push_fan(faces: &mut Vec<[usize; 3]>, polygon: &[usize]) {
+    for i in 1..polygon.len().saturating_sub(1) {
+        faces.push([polygon[0], polygon[i], polygon[i + 1]]);
+    }
+}
+
+/// Reads vertices and faces from a Wavefront OBJ file.
+///
+/// Only `v` and `f` statements are used. Polygons are fan-triangulated, and texture and
+/// normal indices (`f v/vt/vn`) are ignored. Negative indices are relative to the end of
+/// the vertex list.
+#[cfg(feature = "io-obj")]
+pub(crate) fn read_obj<T: Float, R: BufRead>(reader: R) -> Result<RawMesh<T>> {
+    let mut vertices = Vec::new();
+    let mut faces = Vec::new();
+    let mut polygon = Vec::new();
+
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+        let line_no = i + 1;
+        let line = line.split('#').next().unwrap_or_default();
+        let mut tokens = line.split_whitespace();
+
+        match tokens.next() {
+            Some("v") => {
+                let mut v = [0.0; 3];
+                for c in v.iter_mut() {
+                    *c = tokens
+                        .next()
+                        .ok_or_else(|| {
+                            invalid_data(line_no, "vertex has fewer than 3 coordinates")
+                        })?
+                        .parse()
+                        .map_err(|e| invalid_data(line_no, e))?;
+                }
+                vertices.push(to_vector(v));
+            }
+            Some("f") => {
+                polygon.clear();
+                for token in tokens {
+                    let index: isize = token
+                        .split('/')
+                        .next()
+                        .unwrap_or_default()
+                        .parse()
+                        .map_err(|e| invalid_data(line_no, e))?;
+                    let index = match index {
+                        1.. => index as usize - 1,
+                        ..0 => vertices
+                            .len()
+                            .checked_sub(index.unsigned_abs())
+                            .ok_or_else(|| invalid_data(line_no, "vertex index out of range"))?,
+                        0 => return Err(invalid_data(line_no, "vertex index 0 is invalid")),
+                    };
+                    if index >= vertices.len() {
+                        return Err(invalid_data(line_no, "vertex index out of range"));
+                    }
+                    polygon.push(index);
+                }
+                if polygon.len() < 3 {
+                    return Err(invalid_data(line_no, "face has fewer than 3 vertices"));
+                }
+                push_fan(&mut faces, &polygon);
+            }
+            _ => {}
+        }
+    }
+
+    Ok((vertices, faces))
+}
+
+#[cfg(feature = "io-ply")]
+#[derive(Debug, Clone, Copy)]
+enum PlyScalar {
+    I8,
+    U8,
+    I16,
+    U16,
+    I32,
+    U32,
+    F32,
+    F64,
+}
+
+#[cfg(feature = "io-ply")]
+impl PlyScalar {
+    fn parse(name: &str) -> Option<Self> {
+        Some(match name {
+            "char" | "int8" => Self::I8,
+            "uchar" | "uint8" => Self::U8,
+            "short" | "int16" => Self::I16,
+            "ushort" | "uint16" => Self::U16,
+            "int" | "int32" => Self::I32,
+            "uint" | "uint32" => Self::U32,
+            "float" | "float32" => Self::F32,
+            "double" | "float64" => Self::F64,
+            _ => return None,
+        })
+    }
+
+    fn size(self) -> usize {
+        match self {
+            Self::I8 | Self::U8 => 1,
+            Self::I16 | Self::U16 => 2,
+            Self::I32 | Self::U32 | Self::F32 => 4,
+            Self::F64 => 8,
+        }
+    }
+
+    fn decode(self, bytes: &[u8], big_endian: bool) -> f64 {
+        macro_rules! decode {
+            ($ty:ty) => {{
+                let bytes = bytes.try_into().unwrap();
+                if big_endian {
+                    <$ty>::from_be_bytes(bytes) as f64
+                } else {
+                    <$ty>::from_le_bytes(bytes) as f64
+                }
+            }};
+        }
+        match self {
+            Self::I8 => decode!(i8),
+            Self::U8 => decode!(u8),
+            Self::I16 => decode!(i16),
+            Self::U16 => decode!(u16),
+            Self::I32 => decode!(i32),
+            Self::U32 => decode!(u32),
+            Self::F32 => decode!(f32),
+            Self::F64 => decode!(f64),
+        }
+    }
+}
+
+#[cfg(feature = "io-ply")]
+enum PlyProperty {
+    Scalar(PlyScalar),
+    List(PlyScalar, PlyScalar),
+}
+
+#[cfg(feature = "io-ply")]
+struct PlyElement {
+    name: String,
+    count: usize,
+    properties: Vec<(String, PlyProperty)>,
+}
+
+#[cfg(feature = "io-ply")]
+enum PlyBody<'a> {
+    Ascii(core::str::SplitWhitespace<'a>),
+    Binary { data: &'a [u8], big_endian: bool },
+}
+
+#[cfg(feature = "io-ply")]
+impl PlyBody<'_> {
+    fn next(&mut self, ty: PlyScalar) -> Result<f64> {
+        let eof = || Error::new(ErrorKind::UnexpectedEof, "unexpected end of PLY data");
+        match self {
+            Self::Ascii(tokens) => tokens
+                .next()
+                .ok_or_else(eof)?
+                .parse()
+                .map_err(|e| Error::new(ErrorKind::InvalidData, e)),
+            Self::Binary { data, big_endian } => {
+                let size = ty.size();
+                if data.len() < size {
+                    return Err(eof());
+                }
+                let (bytes, rest) = data.split_at(size);
+                *data = rest;
+                Ok(ty.decode(bytes, *big_endian))
+            }
+        }
+    }
+
+    /// Reads a list count or vertex index, which must be a non-negative integer.
+    fn next_index(&mut self, ty: PlyScalar) -> Result<usize> {
+        let value = self.next(ty)?;
+        #[allow(clippy::neg_cmp_op_on_partial_ord)]
+        if !(value >= 0.0 && value < usize::MAX as f64 && value.fract() == 0.0) {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("invalid PLY list count or index {value}"),
+            ));
+        }
+        Ok(value as usize)
+    }
+}
+
+/// Reads vertices and faces from a PLY file in ASCII or binary format.
+///
+/// Vertices are read from the `x`, `y`, and `z` properties of the `vertex` element, and
+/// faces from the `vertex_indices` (or `vertex_index`) list of the `face` element.
+/// Polygons are fan-triangulated. Other elements and properties are skipped.
+#[cfg(feature = "io-ply")]
+pub(crate) fn read_ply<T: Float, R: BufRead>(mut reader: R) -> Result<RawMesh<T>> {
+    let mut line = String::new();
+    let mut line_no = 0;
+    let mut next_line = |reader: &mut R, line: &mut String| -> Result<usize> {
+        line.clear();
+        if reader.read_line(line)? == 0 {
+            return Err(Error::new(
+                ErrorKind::UnexpectedEof,
+                "unexpected end of PLY header",
+            ));
+        }
+        line_no += 1;
+        Ok(line_no)
+    };
+
+    next_line(&mut reader, &mut line)?;
+    if line.trim() != "ply" {
+        return Err(invalid_data(1, "missing PLY magic number"));
+    }
+
+    let mut format = None;
+    let mut elements: Vec<PlyElement> = Vec::new();
+    loop {
+        let line_no = next_line(&mut reader, &mut line)?;
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        match tokens.as_slice() {
+            ["format", fmt, _] => {
+                format = Some(match *fmt {
+                    "ascii" => None,
+                    "binary_little_endian" => Some(false),
+                    "binary_big_endian" => Some(true),
+                    _ => return Err(invalid_data(line_no, format!("unknown format {fmt}"))),
+                })
+            }
+            ["element", name, count] => elements.push(PlyElement {
+                name: name.to_string(),
+                count: count.parse().map_err(|e| invalid_data(line_no, e))?,
+                properties: Vec::new(),
+            }),
+            ["property", "list", count_ty, item_ty, name] => {
+                let (Some(count_ty), Some(item_ty)) =
+                    (PlyScalar::parse(count_ty), PlyScalar::parse(item_ty))
+                else {
+                    return Err(invalid_data(line_no, "unknown property type"));
+                };
+                elements
+                    .last_mut()
+                    .ok_or_else(|| invalid_data(line_no, "property outside of an element"))?
+                    .properties
+                    .push((name.to_string(), PlyProperty::List(count_ty, item_ty)));
+            }
+            ["property", ty, name] => {
+                let ty = PlyScalar::parse(ty)
+                    .ok_or_else(|| invalid_data(line_no, "unknown property type"))?;
+                elements
+                    .last_mut()
+                    .ok_or_else(|| invalid_data(line_no, "property outside of an element"))?
+                    .properties
+                    .push((name.to_string(), PlyProperty::Scalar(ty)));
+            }
+            ["end_header"] => break,
+            ["comment", ..] | ["obj_info", ..] | [] => {}
+            _ => return Err(invalid_data(line_no, "invalid PLY header")),
+        }
+    }
+    let big_endian = format.ok_or_else(|| invalid_data(line_no, "missing PLY format"))?;
+
+    let mut data = Vec::new();
+    reader.read_to_end(&mut data)?;
+    let text;
+    let mut body = match big_endian {
+        None => {
+            text = String::from_utf8(data).map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+            PlyBody::Ascii(text.split_whitespace())
+        }
+        Some(big_endian) => PlyBody::Binary {
+            data: &data,
+            big_endian,
+        },
+    };
+
+    let mut vertices = Vec::new();
+    let mut faces = Vec::new();
+    let mut polygon = Vec::new();
+    for element in &elements {
+        for _ in 0..element.count {
+            let mut v = [0.0; 3];
+            polygon.clear();
+            for (name, property) in &element.properties {
+                match property {
+                    PlyProperty::Scalar(ty) => {
+                        let value = body.next(*ty)?;
+                        match name.as_str() {
+                            "x" => v[0] = value,
+                            "y" => v[1] = value,
+                            "z" => v[2] = value,
+                            _ => {}
+                        }
+                    }
+                    PlyProperty::List(count_ty, item_ty) => {
+                        let count = body.next_index(*count_ty)?;
+                        let is_indices = name == "vertex_indices" || name == "vertex_index";
+                        for _ in 0..count {
+                            if is_indices {
+                                polygon.push(body.next_index(*item_ty)?);
+                            } else {
+                                body.next(*item_ty)?;
+                            }
+                        }
+                    }
+                }
+            }
+
+            match element.name.as_str() {
+                "vertex" => vertices.push(to_vector(v)),
+                "face" => push_fan(&mut faces, &polygon),
+                _ => {}
+            }
+        }
+    }
+
+    if faces.iter().flatten().any(|&i| i >= vertices.len()) {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            "face vertex index out of range",
+        ));
+    }
+
+    Ok((vertices, faces))
+}
+
+/// Vertices and tetrahedra read from a file.
+#[cfg(feature = "io-msh")]
+type RawTetMesh<T> = (Vec<Vector3<T>>, Vec<[usize; 4]>);
+
+/// Reads nodes and tetrahedra from a Gmsh `.msh` version 4 ASCII file.
+///
+/// Linear (type 4) and quadratic (type 11) tetrahedra are read in file order, using their
+/// corner nodes. Other sections and element types are skipped.
+#[cfg(feature = "io-msh")]
+pub(crate) fn read_msh<T: Float, R: BufRead>(reader: R) -> Result<RawTetMesh<T>> {
+    use alloc::collections::BTreeMap;
+
+    let mut lines = reader
+        .lines()
+        .enumerate()
+        .map(|(i, line)| line.map(|line| (i + 1, line)))
+        .filter(|line| !matches!(line, Ok((_, line)) if line.trim().is_empty()));
+    let mut next_line = || -> Result<(usize, String)> {
+        lines.next().unwrap_or_else(|| {
+            Err(Error::new(
+                ErrorKind::UnexpectedEof,
+                "unexpected end of MSH file",
+            ))
+        })
+    };
+    fn numbers<N: core::str::FromStr>(line_no: usize, line: &str) -> Result<Vec<N>>
+    where
+        N::Err: core::fmt::Display,
+    {
+        line.split_whitespace()
+            .map(|token| token.parse().map_err(|e| invalid_data(line_no, e)))
+            .collect()
+    }
+
+    let mut node_index = BTreeMap::new();
+    let mut vertices = Vec::new();
+    let mut tetrahedra = Vec::new();
+    let mut has_format = false;
+
+    loop {
+        let (_, line) = match next_line() {
+            Ok(line) => line,
+            Err(e) if e.kind() == ErrorKind::UnexpectedEof && has_format => break,
+            Err(e) => return Err(e),
+        };
+        match line.trim() {
+            "$MeshFormat" => {
+                let (line_no, line) = next_line()?;
+                let mut tokens = line.split_whitespace();
+                let version: f64 = tokens
+                    .next()
+                    .unwrap_or_default()
+                    .parse()
+                    .map_err(|e| invalid_data(line_no, e))?;
+                if !(4.0..5.0).contains(&version) {
+                    return Err(invalid_data(
+                        line_no,
+                        format!("unsupported MSH version {version}"),
+                    ));
+                }
+                if tokens.next() != Some("0") {
+                    return Err(invalid_data(line_no, "binary MSH files are not supported"));
+                }
+                has_format = true;
+            }
+            "$Nodes" => {
+                let (line_no, line) = next_line()?;
+                let header: Vec<usize> = numbers(line_no, &line)?;
+                let num_blocks = *header
+                    .first()
+                    .ok_or_else(|| invalid_data(line_no, "invalid node header"))?;
+                for _ in 0..num_blocks {
+                    let (line_no, line) = next_line()?;
+                    let block: Vec<usize> = numbers(line_no, &line)?;
+                    let [dim, _, parametric, count] = block[..] else {
+                        return Err(invalid_data(line_no, "invalid node block"));
+                    };
+                    let mut tags = Vec::with_capacity(count);
+                    for _ in 0..count {
+                        let (line_no, line) = next_line()?;
+                        tags.push(
+                            line.trim()
+                                .parse::<usize>()
+                                .map_err(|e| invalid_data(line_no, e))?,
+                        );
+                    }
+                    for tag in tags {
+                        let (line_no, line) = next_line()?;
+                        let coords: Vec<f64> = numbers(line_no, &line)?;
+                        let expected = 3 + if parametric == 1 { dim.min(2) } else { 0 };
+                        if coords.len() < expected {
+                            return Err(invalid_data(line_no, "invalid node coordinates"));
+                        }
+                        node_index.insert(tag, vertices.len());
+                        vertices.push(to_vector([coords[0], coords[1], coords[2]]));
+                    }
+                }
+            }
+            "$Elements" => {
+                let (line_no, line) = next_line()?;
+                let header: Vec<usize> = numbers(line_no, &line)?;
+                let num_blocks = *header
+                    .first()
+                    .ok_or_else(|| invalid_data(line_no, "invalid element header"))?;
+                for _ in 0..num_blocks {
+                    let (line_no, line) = next_line()?;
+                    let block: Vec<usize> = numbers(line_no, &line)?;
+                    let [_, _, element_type, count] = block[..] else {
+                        return Err(invalid_data(line_no, "invalid element block"));
+                    };
+                    for _ in 0..count {
+                        let (line_no, line) = next_line()?;
+                        if element_type != 4 && element_type != 11 {
+                            continue;
+                        }
+                        let tags: Vec<usize> = numbers(line_no, &line)?;
+                        if tags.len() < 5 {
+                            return Err(invalid_data(
+                                line_no,
+                                "tetrahedron has fewer than 4 nodes",
+                            ));
+                        }
+                        let mut tet = [0; 4];
+                        for (index, tag) in tet.iter_mut().zip(&tags[1..5]) {
+                            *index = *node_index.get(tag).ok_or_else(|| {
+                                invalid_data(line_no, format!("unknown node {tag}"))
+                            })?;
+                        }
+                        tetrahedra.push(tet);
+                    }
+                }
+            }
+            // End of a parsed section
+            section if section.starts_with("$End") => {}
+            section if section.starts_with('$') => {
+                // Skip unused sections, e.g., $Entities and $PhysicalNames
+                let end = format!("$End{}", &section[1..]);
+                while next_line()?.1.trim() != end {}
+            }
+            _ => {}
+        }
+    }
+
+    Ok((vertices, tetrahedra))
+}
+
+#[cfg(all(test, any(feature = "io-obj", feature = "io-ply", feature = "io-msh")))]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "io-obj")]
+    #[test]
+    fn test_read_obj() {
+        let obj = "\
+# quad and triangle
+v 0 0 0
+v 1 0 0
+v 1 1 0 1.0
+v 0 1 0
+vn 0 0 1
+f 1/1/1 2/2/1 3/3/1 4/4/1
+f -4//1 -2//1 -1//1
+";
+        let (vertices, faces) = read_obj::<f64, _>(obj.as_bytes()).unwrap();
+        assert_eq!(vertices.len(), 4);
+        assert_eq!(vertices[2], Vector3::new(1.0, 1.0, 0.0));
+        assert_eq!(faces, vec![[0, 1, 2], [0, 2, 3], [0, 2, 3]]);
+
+        assert!(read_obj::<f64, _>("v 0 0 0\nf 1 2 3\n".as_bytes()).is_err());
+        assert!(read_obj::<f64, _>("v 0 0\n".as_bytes()).is_err());
+    }
+
+    #[cfg(feature = "io-ply")]
+    #[test]
+    fn test_read_ply() {
+        let header = |format: &str| {
+            format!(
+                "ply\nformat {format} 1.0\ncomment test\nelement vertex 4\n\
+                 property float x\nproperty float y\nproperty float z\nproperty uchar red\n\
+                 element face 1\nproperty list uchar int vertex_indices\nend_header\n"
+            )
+        };
+        let coords = [
+            [0.0f32, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [1.0, 1.0, 0.0],
+            [0.0, 1.0, 0.0],
+        ];
+
+        let mut ascii = header("ascii");
+        for c in coords {
+            ascii += &format!("{} {} {} 255\n", c[0], c[1], c[2]);
+        }
+        ascii += "4 0 1 2 3\n";
+        let expected = read_ply::<f64, _>(ascii.as_bytes()).unwrap();
+        assert_eq!(expected.0.len(), 4);
+        assert_eq!(expected.1, vec![[0, 1, 2], [0, 2, 3]]);
+
+        for (format, big_endian) in [("binary_little_endian", false), ("binary_big_endian", true)] {
+            let mut binary = header(format).into_bytes();
+            for c in coords {
+                for x in c {
+                    binary.extend(if big_endian {
+                        x.to_be_bytes()
+                    } else {
+                        x.to_le_bytes()
+                    });
+                }
+                binary.push(255);
+            }
+            binary.push(4);
+            for i in 0..4i32 {
+                binary.extend(if big_endian {
+                    i.to_be_bytes()
+                } else {
+                    i.to_le_bytes()
+                });
+            }
+            assert_eq!(read_ply::<f64, _>(binary.as_slice()).unwrap(), expected);
+        }
+
+        let truncated = &ascii[..ascii.len() - 4];
+        assert!(read_ply::<f64, _>(truncated.as_bytes()).is_err());
+
+        for face in [
+            "4 0 1 2 -3",
+            "4 0 1 2 2.5",
+            "4 0 1 2 nan",
+            "-4 0 1 2 3",
+            "4.5 0 1 2 3",
+        ] {
+            let invalid = ascii.replace("4 0 1 2 3", face);
+            let error = read_ply::<f64, _>(invalid.as_bytes()).unwrap_err();
+            assert_eq!(error.kind(), ErrorKind::InvalidData, "{face}");
+        }
+    }
+
+    #[cfg(feature = "io-obj")]
+    #[test]
+    fn test_obj_scale_and_repair() {
+        use crate::{base::mesh::TriMesh, testing_util::cube_mesh};
+
+        // Unindexed export in millimeters, as written by many CAD tools
+        let (vertices, faces) = cube_mesh(Vector3::zeros(), 20.0, false);
+        let mut obj = String::new();
+        for face in &faces {
+            for &i in face {
+                let v = vertices[i];
+                obj += &format!("v {} {} {}\n", v.x, v.y, v.z);
+            }
+            obj += "f -3 -2 -1\n";
+        }
+
+        let options = MeshReadOptions::default()
+            .with_scale(1e-3)
+            .with_repair(RepairOptions::default());
+        let mesh = TriMesh::<f64>::from_obj_with_options(obj.as_bytes(), &options).unwrap();
+        assert_eq!(mesh.vertices().len(), 8);
+        approx::assert_relative_eq!(mesh.volume(), 8e-6, epsilon = 1e-18);
+
+        assert!(TriMesh::<f64>::from_obj(obj.as_bytes()).is_err());
+    }
+
+    #[cfg(feature = "io-msh")]
+    #[test]
+    fn test_read_msh() {
+        let msh = "\
+$MeshFormat
+4.1 0 8
+$EndMeshFormat
+$PhysicalNames
+1
+3 1 \"magnet\"
+$EndPhysicalNames
+$Nodes
+2 5 1 5
+0 1 0 1
+1
+0 0 0
+3 1 0 4
+2
+3
+4
+5
+1 0 0
+0 1 0
+0 0 1
+1 1 1
+$EndNodes
+$Elements
+2 3 1 3
+2 1 2 1
+1 1 2 3
+3 1 4 2
+2 1 2 3 4
+3 2 3 4 5
+$EndElements
+";
+        let (vertices, tetrahedra) = read_msh::<f64, _>(msh.as_bytes()).unwrap();
+        assert_eq!(vertices.len(), 5);
+        assert_eq!(vertices[4], Vector3::new(1.0, 1.0, 1.0));
+        assert_eq!(tetrahedra, vec![[0, 1, 2, 3], [1, 2, 3, 4]]);
+
+        assert!(read_msh::<f64, _>(msh.replace("4.1 0 8", "2.2 0 8").as_bytes()).is_err());
+        assert!(read_msh::<f64, _>(msh.replace("3 2 3 4 5", "3 2 3 4 6").as_bytes()).is_err());
+    }
+}
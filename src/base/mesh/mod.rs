@@ -0,0 +1,771 @@
+/*
+ * Magba is licensed under The 3-Clause BSD, see LICENSE.
+ * Copyright 2025 Sira Pornsiriprasert <code@psira.me>
+ */
+
+use nalgebra::{Point3, Vector3};
+use openmesh::MeshError;
+
+use crate::{
+    Error,
+    base::{Float, bvh::Bvh},
+};
+use alloc::vec::Vec;
+
+#[cfg(any(
+    feature = "io-stl",
+    feature = "io-obj",
+    feature = "io-ply",
+    feature = "io-msh"
+))]
+mod io;
+#[cfg(any(
+    feature = "io-stl",
+    feature = "io-obj",
+    feature = "io-ply",
+    feature = "io-msh"
+))]
+pub use io::MeshReadOptions;
+mod repair;
+mod tessellate;
+mod tet;
+pub use repair::{MeshDiagnostics, MeshTolerance, RepairOptions, RepairSummary, diagnose, repair};
+pub(crate) use tessellate::march_tetrahedra;
+pub use tessellate::{Tessellate, mesh_sdf};
+pub use tet::TetMesh;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Triangle<T: Float> {
+    v1: Vector3<T>,
+    v2: Vector3<T>,
+    v3: Vector3<T>,
+}
+
+impl<T: Float> Triangle<T> {
+    #[inline]
+    pub fn new(v1: Vector3<T>, v2: Vector3<T>, v3: Vector3<T>) -> Self {
+        Self { v1, v2, v3 }
+    }
+
+    #[inline]
+    pub fn vertices(&self) -> [Vector3<T>; 3] {
+        [self.v1, self.v2, self.v3]
+    }
+
+    /// Signed solid angle subtended by the triangle at `point` (sr).
+    ///
+    /// Positive if the point lies behind the triangle, i.e., on the side opposite to the
+    /// normal defined by the right-hand rule.
+    ///
+    /// # References
+    ///
+    /// - Van Oosterom, A., and J. Strackee. “The Solid Angle of a Plane Triangle.” IEEE Transactions on Biomedical Engineering BME-30, no. 2 (February 1983): 125–26. <https://doi.org/10.1109/TBME.1983.325207>.
+    #[inline]
+    pub fn solid_angle(&self, point: Vector3<T>) -> T {
+        let (r1, r2, r3) = (self.v1 - point, self.v2 - point, self.v3 - point);
+        let (m1, m2, m3) = (r1.norm(), r2.norm(), r3.norm());
+
+        let numer = r1.dot(&r2.cross(&r3));
+        let denom = m1 * m2 * m3 + r1.dot(&r2) * m3 + r1.dot(&r3) * m2 + r2.dot(&r3) * m1;
+        T::from_f64(2.0).unwrap() * num_traits::Float::atan2(numer, denom)
+    }
+}
+
+/// Generalized winding number of the triangles around `point`.
+///
+/// The sum of the triangle solid angles divided by 4π. For a closed, outward-oriented
+/// mesh it is `1` inside and `0` outside, and it does not depend on a ray direction, so it
+/// stays robust for points aligned with mesh edges or vertices. Cavities (inward-oriented
+/// shells) count negatively, and overlapping bodies add up.
+///
+/// # References
+///
+/// - Jacobson, Alec, Ladislav Kavan, and Olga Sorkine-Hornung. “Robust Inside-Outside Segmentation Using Generalized Winding Numbers.” ACM Transactions on Graphics 32, no. 4 (July 2013): 1–12. <https://doi.org/10.1145/2461912.2461916>.
+#[inline]
+pub fn winding_number<T: Float>(triangles: &[Triangle<T>], point: Vector3<T>) -> T {
+    let total = triangles
+        .iter()
+        .fold(T::zero(), |acc, t| acc + t.solid_angle(point));
+    total / (T::from_f64(4.0).unwrap() * T::pi())
+}
+
+/// Möller–Trumbore ray–triangle intersection algorithm.
+#[inline]
+pub fn is_ray_hit<T: Float>(
+    triangle: Triangle<T>,
+    ray_origin: Vector3<T>,
+    ray_dir: Vector3<T>,
+    t_min: T,
+    t_max: T,
+) -> bool {
+    let eps = T::epsilon() * T::from(16.0).unwrap();
+    let e1 = triangle.v2 - triangle.v1;
+    let e2 = triangle.v3 - triangle.v1;
+    let p = ray_dir.cross(&e2);
+
+    let det = e1.dot(&p);
+    if num_traits::Float::abs(det) < eps {
+        return false;
+    }
+    let inv_det = T::one() / det;
+    let tvec = ray_origin - triangle.v1;
+
+    let u = tvec.dot(&p) * inv_det;
+    if u < T::zero() || u > T::one() {
+        return false;
+    }
+
+    let q = tvec.cross(&e1);
+    let v = ray_dir.dot(&q) * inv_det;
+    if v < T::zero() || u + v > T::one() {
+        return false;
+    }
+
+    let t = e2.dot(&q) * inv_det;
+    if !(t_min..=t_max).contains(&t) {
+        return false;
+    }
+
+    true
+}
+
+/// Groups faces into shells, i.e., sets of faces connected through shared vertices.
+///
+/// # Returns
+///
+/// - Face indices of each shell
+pub fn find_shells(faces: &[[usize; 3]]) -> Vec<Vec<usize>> {
+    let n_vertices = faces.iter().flatten().map(|&v| v + 1).max().unwrap_or(0);
+    let mut parent: Vec<usize> = (0..n_vertices).collect();
+
+    fn find(parent: &mut [usize], mut i: usize) -> usize {
+        while parent[i] != i {
+            parent[i] = parent[parent[i]];
+            i = parent[i];
+        }
+        i
+    }
+
+    for face in faces {
+        let root = find(&mut parent, face[0]);
+        for &v in &face[1..] {
+            let other = find(&mut parent, v);
+            parent[other] = root;
+        }
+    }
+
+    let mut shell_of_root: Vec<Option<usize>> = alloc::vec![None; n_vertices];
+    let mut shells: Vec<Vec<usize>> = Vec::new();
+    for (i, face) in faces.iter().enumerate() {
+        let root = find(&mut parent, face[0]);
+        let shell = *shell_of_root[root].get_or_insert_with(|| {
+            shells.push(Vec::new());
+            shells.len() - 1
+        });
+        shells[shell].push(i);
+    }
+    shells
+}
+
+/// Signed volume enclosed by the faces. Positive if the faces are oriented outwards.
+pub fn signed_volume<T: Float>(vertices: &[Vector3<T>], faces: &[[usize; 3]]) -> T {
+    let six_vol = faces.iter().fold(T::zero(), |acc, f| {
+        acc + vertices[f[0]].dot(&vertices[f[1]].cross(&vertices[f[2]]))
+    });
+    six_vol / T::from_f64(6.0).unwrap()
+}
+
+/// Finds shells whose orientation disagrees with their nesting depth.
+///
+/// Shells nested at an even depth must be oriented outwards and shells at an odd depth
+/// inwards. The depth of a shell is the number of other shells enclosing it.
+///
+/// # Returns
+///
+/// - Face indices of each misoriented shell
+pub(crate) fn misoriented_shells<T: Float>(
+    vertices: &[Vector3<T>],
+    faces: &[[usize; 3]],
+) -> Vec<Vec<usize>> {
+    let shells = find_shells(faces);
+    let shell_faces: Vec<Vec<[usize; 3]>> = shells
+        .iter()
+        .map(|shell| shell.iter().map(|&i| faces[i]).collect())
+        .collect();
+    let shell_triangles: Vec<Vec<Triangle<T>>> = shell_faces
+        .iter()
+        .map(|shell| {
+            shell
+                .iter()
+                .map(|f| Triangle::new(vertices[f[0]], vertices[f[1]], vertices[f[2]]))
+                .collect()
+        })
+        .collect();
+
+    let half = T::from_f64(0.5).unwrap();
+    shells
+        .into_iter()
+        .enumerate()
+        .filter(|(i, _)| {
+            let origin = vertices[shell_faces[*i][0][0]];
+            let depth = shell_triangles
+                .iter()
+                .enumerate()
+                .filter(|&(j, other)| {
+                    j != *i && num_traits::Float::abs(winding_number(other, origin)) > half
+                })
+                .count();
+
+            let is_outward = signed_volume(vertices, &shell_faces[*i]) > T::zero();
+            is_outward != (depth % 2 == 0)
+        })
+        .map(|(_, shell)| shell)
+        .collect()
+}
+
+/// Validates a closed triangular mesh that may consist of several shells.
+///
+/// The mesh may have several disjoint bodies and cavities (shells nested inside
+/// other shells). Every shell must be closed, manifold, and consistently oriented. Shells
+/// nested at an even depth (bodies) must be oriented outwards, while shells nested at an
+/// odd depth (cavities) must be oriented inwards, i.e., pointing away from the material.
+/// Shells must not intersect each other or themselves. Faces with twice their area below
+/// `atol` are degenerate.
+///
+/// # Returns
+///
+/// Returns a [MeshError] describing the first problem found. Use [diagnose] to list all
+/// problems.
+pub fn validate_shells<T: Float + core::iter::Sum>(
+    vertices: &[Vector3<T>],
+    faces: &[[usize; 3]],
+    atol: T,
+) -> Result<(), MeshError> {
+    use openmesh::core::{
+        EdgeMap, check_consistent_normals, check_intersecting, check_manifold,
+        check_zero_area_faces,
+    };
+
+    let v_val: Vec<openmesh::Vertex<T>> = vertices.iter().map(|&v| v.into()).collect();
+    let f_val: Vec<openmesh::Face> = faces.iter().map(|&f| f.into()).collect();
+
+    if check_zero_area_faces(&v_val, &f_val, atol) {
+        return Err(MeshError::ZeroAreaFace);
+    }
+
+    let edge_map = EdgeMap::from_faces(&f_val);
+    check_manifold(&edge_map)?;
+    if !check_consistent_normals(&edge_map) {
+        return Err(MeshError::InconsistentNormals);
+    }
+
+    if !misoriented_shells(vertices, faces).is_empty() {
+        return Err(MeshError::InwardNormals);
+    }
+
+    if check_intersecting(&v_val, &f_val) {
+        return Err(MeshError::SelfIntersecting);
+    }
+
+    Ok(())
+}
+
+/// Triangular mesh data structure with IO and validation handling.
+///
+/// Large meshes can be accelerated with a bounding volume hierarchy, see
+/// [TriMesh::with_bvh].
+#[derive(Debug, Clone)]
+pub struct TriMesh<T: Float> {
+    triangles: Vec<Triangle<T>>,
+    vertices: Vec<Vector3<T>>,
+    faces: Vec<[usize; 3]>,
+    edges: Vec<[usize; 2]>,
+    face_edges: Vec<[usize; 3]>,
+    bvh: Option<Bvh<T>>,
+}
+
+impl<T: Float> PartialEq for TriMesh<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.triangles == other.triangles
+    }
+}
+
+impl<T: Float + Eq> Eq for TriMesh<T> {}
+
+impl<T: Float + core::iter::Sum> TriMesh<T> {
+    /// Construct a [TriMesh] from vertices and faces.
+    ///
+    /// The mesh may consist of several disjoint bodies and cavities. See [validate_shells]
+    /// for the validation rules.
+    ///
+    /// # Returns
+    ///
+    /// Returns a [MeshError] if the mesh has holes, zero-faces, or shells with wrong orientation.
+    ///
+    /// # Notes
+    ///
+    /// To construct a [TriMesh] without validation, use [TriMesh::new_unchecked]
+    pub fn new<V, F>(vertices: V, faces: F) -> Result<Self, MeshError>
+    where
+        V: IntoIterator<Item = Vector3<T>>,
+        F: IntoIterator<Item = [usize; 3]>,
+    {
+        Self::new_with_tolerance(vertices, faces, MeshTolerance::default())
+    }
+
+    /// Construct a [TriMesh] from vertices and faces, validated with the given tolerance.
+    ///
+    /// See [TriMesh::new].
+    pub fn new_with_tolerance<V, F>(
+        vertices: V,
+        faces: F,
+        tolerance: MeshTolerance<T>,
+    ) -> Result<Self, MeshError>
+    where
+        V: IntoIterator<Item = Vector3<T>>,
+        F: IntoIterator<Item = [usize; 3]>,
+    {
+        let vertices: Vec<Vector3<T>> = vertices.into_iter().collect();
+        let faces: Vec<[usize; 3]> = faces.into_iter().collect();
+        validate_shells(&vertices, &faces, tolerance.area)?;
+
+        Ok(Self::new_unchecked(vertices, faces))
+    }
+
+    /// Repairs and validates a [TriMesh] from vertices and faces. See [repair].
+    ///
+    /// # Returns
+    ///
+    /// Returns a [MeshError] if the mesh is still invalid after the repair, e.g., if it has
+    /// holes or self-intersections.
+    pub fn new_repaired<V, F>(
+        vertices: V,
+        faces: F,
+        options: &RepairOptions<T>,
+    ) -> Result<Self, MeshError>
+    where
+        V: IntoIterator<Item = Vector3<T>>,
+        F: IntoIterator<Item = [usize; 3]>,
+    {
+        let vertices: Vec<Vector3<T>> = vertices.into_iter().collect();
+        let faces: Vec<[usize; 3]> = faces.into_iter().collect();
+        let (vertices, faces, _) = repair(&vertices, &faces, options);
+
+        Self::new_with_tolerance(vertices, faces, options.tolerance)
+    }
+
+    /// Reads and validates a [TriMesh] from an ASCII or binary STL reader.
+    ///
+    /// Coincident vertices are merged, so the faces share vertex indices.
+    #[cfg(feature = "io-stl")]
+    pub fn from_stl<R>(reader: &mut R) -> std::io::Result<Self>
+    where
+        R: std::io::Read + std::io::Seek,
+    {
+        Self::from_stl_with_options(reader, &MeshReadOptions::default())
+    }
+
+    /// Reads, repairs, and validates a [TriMesh] from an STL reader. See [repair].
+    #[cfg(feature = "io-stl")]
+    pub fn from_stl_repaired<R>(reader: &mut R, options: &RepairOptions<T>) -> std::io::Result<Self>
+    where
+        R: std::io::Read + std::io::Seek,
+    {
+        let options = MeshReadOptions::default()
+            .with_repair(*options)
+            .with_tolerance(options.tolerance);
+        Self::from_stl_with_options(reader, &options)
+    }
+
+    /// Reads a [TriMesh] from an STL reader with unit scaling and optional repair.
+    /// See [MeshReadOptions].
+    #[cfg(feature = "io-stl")]
+    pub fn from_stl_with_options<R>(
+        reader: &mut R,
+        options: &MeshReadOptions<T>,
+    ) -> std::io::Result<Self>
+    where
+        R: std::io::Read + std::io::Seek,
+    {
+        let mesh: openmesh::Mesh<T> =
+            openmesh::Mesh::from_stl(reader).map_err(|e| std::io::Error::other(e.to_string()))?;
+
+        let vertices: Vec<Vector3<T>> = mesh
+            .vertices
+            .into_iter()
+            .map(|v| Vector3::new(v.0, v.1, v.2))
+            .collect();
+        let faces: Vec<[usize; 3]> = mesh.faces.into_iter().map(|f| [f.0, f.1, f.2]).collect();
+        Self::from_read(vertices, faces, options)
+    }
+
+    /// Reads and validates a [TriMesh] from a Wavefront OBJ reader.
+    ///
+    /// Only vertex and face statements are used, and polygons are fan-triangulated.
+    #[cfg(feature = "io-obj")]
+    pub fn from_obj<R: std::io::BufRead>(reader: R) -> std::io::Result<Self> {
+        Self::from_obj_with_options(reader, &MeshReadOptions::default())
+    }
+
+    /// Reads a [TriMesh] from a Wavefront OBJ reader with unit scaling and optional repair.
+    /// See [MeshReadOptions].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use magba::base::mesh::{MeshReadOptions, TriMesh};
+    /// let obj = "
+    /// v -5 -5 -5
+    /// v 5 -5 -5
+    /// v 0 5 -5
+    /// v 0 0 5
+    /// f 1 3 2
+    /// f 1 2 4
+    /// f 2 3 4
+    /// f 1 4 3
+    /// ";
+    /// // Coordinates in millimeters
+    /// let options = MeshReadOptions::default().with_scale(1e-3);
+    /// let mesh = TriMesh::<f64>::from_obj_with_options(obj.as_bytes(), &options).unwrap();
+    /// assert_eq!(mesh.vertices()[3].z, 5e-3);
+    /// ```
+    #[cfg(feature = "io-obj")]
+    pub fn from_obj_with_options<R: std::io::BufRead>(
+        reader: R,
+        options: &MeshReadOptions<T>,
+    ) -> std::io::Result<Self> {
+        let (vertices, faces) = io::read_obj(reader)?;
+        Self::from_read(vertices, faces, options)
+    }
+
+    /// Reads and validates a [TriMesh] from an ASCII or binary PLY reader.
+    ///
+    /// Only the `vertex` and `face` elements are used, and polygons are fan-triangulated.
+    #[cfg(feature = "io-ply")]
+    pub fn from_ply<R: std::io::BufRead>(reader: R) -> std::io::Result<Self> {
+        Self::from_ply_with_options(reader, &MeshReadOptions::default())
+    }
+
+    /// Reads a [TriMesh] from a PLY reader with unit scaling and optional repair.
+    /// See [MeshReadOptions].
+    #[cfg(feature = "io-ply")]
+    pub fn from_ply_with_options<R: std::io::BufRead>(
+        reader: R,
+        options: &MeshReadOptions<T>,
+    ) -> std::io::Result<Self> {
+        let (vertices, faces) = io::read_ply(reader)?;
+        Self::from_read(vertices, faces, options)
+    }
+
+    #[cfg(any(feature = "io-stl", feature = "io-obj", feature = "io-ply"))]
+    fn from_read(
+        mut vertices: Vec<Vector3<T>>,
+        mut faces: Vec<[usize; 3]>,
+        options: &MeshReadOptions<T>,
+    ) -> std::io::Result<Self> {
+        if options.scale != T::one() {
+            vertices.iter_mut().for_each(|v| *v *= options.scale);
+        }
+        if let Some(repair_options) = &options.repair {
+            (vertices, faces, _) = repair(&vertices, &faces, repair_options);
+        }
+        Self::new_with_tolerance(vertices, faces, options.tolerance)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string()))
+    }
+}
+
+impl<T: Float> TriMesh<T> {
+    /// Construct a [TriMesh] from vertices and faces without validation.
+    #[inline]
+    pub fn new_unchecked<V, F>(vertices: V, faces: F) -> Self
+    where
+        V: IntoIterator<Item = Vector3<T>>,
+        F: IntoIterator<Item = [usize; 3]>,
+    {
+        let vertices: Vec<_> = vertices.into_iter().collect();
+        let faces: Vec<_> = faces.into_iter().collect();
+        let triangles = faces
+            .iter()
+            .map(|face| Triangle::new(vertices[face[0]], vertices[face[1]], vertices[face[2]]))
+            .collect();
+
+        let mut edge_ids = alloc::collections::BTreeMap::new();
+        let mut edges = Vec::new();
+        let face_edges = faces
+            .iter()
+            .map(|face| {
+                core::array::from_fn(|i| {
+                    let (a, b) = (face[i], face[(i + 1) % 3]);
+                    *edge_ids.entry((a.min(b), a.max(b))).or_insert_with(|| {
+                        edges.push([a.min(b), a.max(b)]);
+                        edges.len() - 1
+                    })
+                })
+            })
+            .collect();
+
+        Self {
+            triangles,
+            vertices,
+            faces,
+            edges,
+            face_edges,
+            bvh: None,
+        }
+    }
+
+    /// Builds a bounding volume hierarchy used to accelerate field computation and
+    /// inside/outside classification.
+    ///
+    /// Triangle clusters satisfying `radius < theta * distance` from the observer are
+    /// approximated by their multipole expansion up to the quadrupole term. A `theta` of
+    /// `0.3` typically keeps the error below 1e-3 of the peak field, and `0.1` below 1e-5.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `theta` is not positive and finite, see [try_with_bvh](Self::try_with_bvh).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use magba::base::mesh::TriMesh;
+    /// # use nalgebra::vector;
+    /// let vertices = vec![vector![-0.1, -0.1, -0.1], vector![0.1, -0.1, -0.1], vector![0.0, 0.1, -0.1], vector![0.0, 0.0, 0.1]];
+    /// let faces = vec![[0, 2, 1], [0, 1, 3], [1, 2, 3], [0, 3, 2]];
+    /// let mesh = TriMesh::new(vertices, faces).unwrap().with_bvh(0.3);
+    /// assert!(mesh.bvh().is_some());
+    /// ```
+    pub fn with_bvh(self, theta: T) -> Self {
+        self.try_with_bvh(theta)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Builds a bounding volume hierarchy, or returns [Error::InvalidGeometry] if `theta` is
+    /// not positive and finite. See [TriMesh::with_bvh].
+    pub fn try_with_bvh(mut self, theta: T) -> Result<Self, Error> {
+        self.try_build_bvh(theta)?;
+        Ok(self)
+    }
+
+    /// Builds a bounding volume hierarchy in place. See [TriMesh::with_bvh].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `theta` is not positive and finite, see [try_build_bvh](Self::try_build_bvh).
+    pub fn build_bvh(&mut self, theta: T) {
+        if let Err(error) = self.try_build_bvh(theta) {
+            panic!("{error}");
+        }
+    }
+
+    /// Builds a bounding volume hierarchy in place, or returns [Error::InvalidGeometry] if
+    /// `theta` is not positive and finite.
+    pub fn try_build_bvh(&mut self, theta: T) -> Result<(), Error> {
+        if !(theta > T::zero() && num_traits::Float::is_finite(theta)) {
+            return Err(Error::InvalidGeometry(
+                "BVH opening angle must be positive and finite.",
+            ));
+        }
+        self.bvh = Some(Bvh::new(&self.triangles, theta));
+        Ok(())
+    }
+
+    /// The bounding volume hierarchy, if built.
+    #[inline]
+    pub fn bvh(&self) -> Option<&Bvh<T>> {
+        self.bvh.as_ref()
+    }
+
+    #[inline]
+    pub fn triangles(&self) -> &[Triangle<T>] {
+        &self.triangles
+    }
+
+    #[inline]
+    pub fn vertices(&self) -> &[Vector3<T>] {
+        &self.vertices
+    }
+
+    #[inline]
+    pub fn faces(&self) -> &[[usize; 3]] {
+        &self.faces
+    }
+
+    /// Unique edges as vertex index pairs `[a, b]` with `a < b`.
+    #[inline]
+    pub fn edges(&self) -> &[[usize; 2]] {
+        &self.edges
+    }
+
+    /// Edge indices of each face, in the order `(v1, v2)`, `(v2, v3)`, `(v3, v1)`.
+    #[inline]
+    pub fn face_edges(&self) -> &[[usize; 3]] {
+        &self.face_edges
+    }
+
+    /// Lists all defects of the mesh. See [diagnose].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the distance tolerance is not positive and finite.
+    #[inline]
+    pub fn diagnose(&self, tolerance: MeshTolerance<T>) -> MeshDiagnostics {
+        diagnose(&self.vertices, &self.faces, tolerance)
+    }
+
+    /// Generalized winding number of the mesh around `point`. See [winding_number].
+    ///
+    /// Runs in O(log n) if the mesh has a bounding volume hierarchy.
+    #[inline]
+    pub fn winding_number(&self, point: Point3<T>) -> T {
+        let Some(bvh) = &self.bvh else {
+            return winding_number(&self.triangles, point.coords);
+        };
+
+        let total = bvh.fold(
+            point.coords,
+            T::zero(),
+            |acc, i| acc + self.triangles[i].solid_angle(point.coords),
+            |acc, id, d, r2| acc + bvh.far_solid_angle(id, d, r2),
+        );
+        total / (T::from_f64(4.0).unwrap() * T::pi())
+    }
+
+    /// Returns `true` if `point` lies inside the material of the mesh.
+    ///
+    /// Points inside cavities are outside. Points on the surface are classified
+    /// arbitrarily.
+    #[inline]
+    pub fn contains(&self, point: Point3<T>) -> bool {
+        self.winding_number(point) > T::from_f64(0.5).unwrap()
+    }
+
+    /// Net volume enclosed by the mesh (m³). Cavities are subtracted.
+    pub fn volume(&self) -> T {
+        let six_vol = self
+            .triangles
+            .iter()
+            .fold(T::zero(), |acc, t| acc + t.v1.dot(&t.v2.cross(&t.v3)));
+        six_vol / T::from_f64(6.0).unwrap()
+    }
+
+    /// Construct a [TriMesh] from triangles without validation.
+    ///
+    /// Triangles do not share vertices or edges.
+    #[inline]
+    pub fn from_triangles(triangles: Vec<Triangle<T>>) -> Self {
+        let vertices = triangles
+            .iter()
+            .flat_map(|t| t.vertices())
+            .collect::<Vec<_>>();
+        let faces = (0..triangles.len()).map(|i| [3 * i, 3 * i + 1, 3 * i + 2]);
+        Self::new_unchecked(vertices, faces)
+    }
+}
+
+impl<T: Float + core::iter::Sum> From<openmesh::Mesh<T>> for TriMesh<T> {
+    fn from(mesh: openmesh::Mesh<T>) -> Self {
+        let vertices: Vec<Vector3<T>> = mesh
+            .vertices
+            .into_iter()
+            .map(|v| Vector3::new(v.0, v.1, v.2))
+            .collect();
+        let faces: Vec<[usize; 3]> = mesh.faces.into_iter().map(|f| [f.0, f.1, f.2]).collect();
+        Self::new_unchecked(vertices, faces)
+    }
+}
+
+// MARK: Serde
+
+/// Serialized as `{ "vertices": [[x, y, z], ...], "faces": [[a, b, c], ...] }`, with the
+/// opening angle `bvh_theta` if a BVH is built, which is rebuilt when deserialized.
+///
+/// The mesh is not validated when deserialized, see [TriMesh::new_unchecked].
+#[cfg(feature = "serde")]
+impl<T: Float + serde::Serialize> serde::Serialize for TriMesh<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let theta = self.bvh.as_ref().map(Bvh::theta);
+        let mut state = serializer.serialize_struct("TriMesh", 2 + theta.is_some() as usize)?;
+        state.serialize_field("vertices", &self.vertices)?;
+        state.serialize_field("faces", &self.faces)?;
+        match theta {
+            Some(theta) => state.serialize_field("bvh_theta", &theta)?,
+            None => state.skip_field("bvh_theta")?,
+        }
+        state.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: Float + serde::Deserialize<'de>> serde::Deserialize<'de> for TriMesh<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(serde::Deserialize)]
+        #[serde(rename = "TriMesh", deny_unknown_fields)]
+        struct Repr<T: Float> {
+            vertices: Vec<Vector3<T>>,
+            faces: Vec<[usize; 3]>,
+            bvh_theta: Option<T>,
+        }
+
+        let Repr {
+            vertices,
+            faces,
+            bvh_theta,
+        } = Repr::deserialize(deserializer)?;
+        if faces.iter().flatten().any(|&i| i >= vertices.len()) {
+            return Err(serde::de::Error::custom("Face vertex index out of range."));
+        }
+        let mut mesh = Self::new_unchecked(vertices, faces);
+        if let Some(theta) = bvh_theta {
+            mesh.try_build_bvh(theta)
+                .map_err(serde::de::Error::custom)?;
+        }
+        Ok(mesh)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::testing_util::cube_mesh;
+    use nalgebra::{point, vector};
+
+    #[test]
+    fn test_contains_aligned_points() {
+        let (vertices, faces) = cube_mesh(Vector3::zeros(), 0.2, false);
+        let mesh = TriMesh::new(vertices, faces).unwrap();
+
+        // Rays along +x through these points hit face diagonals, edges, or vertices
+        assert!(mesh.contains(point![0.0, 0.0, 0.0]));
+        assert!(mesh.contains(point![0.05, 0.05, -0.05]));
+        assert!(!mesh.contains(point![-0.2, 0.1, 0.1]));
+        assert!(!mesh.contains(point![-0.2, 0.0, 0.0]));
+        assert!(!mesh.contains(point![-0.2, 0.1, 0.0]));
+        assert!(!mesh.contains(point![0.2, 0.0, 0.0]));
+    }
+
+    #[test]
+    fn test_winding_number_cavity() {
+        let (mut vertices, mut faces) = cube_mesh(Vector3::zeros(), 0.2, false);
+        let (inner_vertices, inner_faces) = cube_mesh(Vector3::zeros(), 0.1, true);
+        let offset = vertices.len();
+        vertices.extend(inner_vertices);
+        faces.extend(inner_faces.iter().map(|f| f.map(|i| i + offset)));
+        let mesh = TriMesh::new(vertices, faces).unwrap();
+
+        assert!(mesh.winding_number(point![0.0, 0.0, 0.0]).abs() < 1e-12);
+        assert!((mesh.winding_number(point![0.075, 0.0, 0.0]) - 1.0).abs() < 1e-12);
+        assert!(mesh.winding_number(point![0.3, 0.0, 0.0]).abs() < 1e-12);
+        assert!(!mesh.contains(point![0.0, 0.0, 0.0]));
+        assert!(mesh.contains(point![0.0, 0.075, 0.0]));
+    }
+
+    #[test]
+    fn test_solid_angle_sign() {
+        let triangle = Triangle::new(Vector3::x(), Vector3::y(), Vector3::z());
+        assert!(triangle.solid_angle(vector![0.0, 0.0, 0.0]) > 0.0);
+        assert!(triangle.solid_angle(vector![1.0, 1.0, 1.0]) < 0.0);
+    }
+}
@@ -0,0 +1,659 @@
+/*
+ * Magba is licensed under The 3-Clause BSD, see LICENSE.
+ * Copyright 2025 Sira Pornsiriprasert <code@psira.me>
+ */
+
+//! Mesh diagnostics and repair.
+
+use alloc::{collections::BTreeMap, vec, vec::Vec};
+use core::fmt::Display;
+
+use nalgebra::Vector3;
+use openmesh::MeshError;
+
+use crate::base::{Float, mesh::misoriented_shells};
+
+/// Tolerances used by mesh validation, diagnostics, and repair.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MeshTolerance<T: Float> {
+    /// Faces with twice their area below `area` are degenerate (m²).
+    pub area: T,
+    /// Vertices closer than `distance` are duplicates (m). Must be positive and finite.
+    pub distance: T,
+}
+
+impl<T: Float> Default for MeshTolerance<T> {
+    fn default() -> Self {
+        Self {
+            area: T::from_f64(1e-4).unwrap(),
+            distance: T::from_f64(1e-9).unwrap(),
+        }
+    }
+}
+
+/// Report of mesh defects. See [diagnose].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MeshDiagnostics {
+    /// Edges `[a, b]` with `a < b` used by only one face.
+    pub open_edges: Vec<[usize; 2]>,
+    /// Edges `[a, b]` with `a < b` used by more than two faces.
+    pub non_manifold_edges: Vec<[usize; 2]>,
+    /// Vertex pairs `[a, b]` with `a < b` closer than the distance tolerance, where `a` is the
+    /// first vertex at that position.
+    pub duplicate_vertices: Vec<[usize; 2]>,
+    /// Faces with repeated vertex indices or area below the area tolerance.
+    pub degenerate_faces: Vec<usize>,
+    /// Edges `[a, b]` with `a < b` traversed in the same direction by both adjacent faces.
+    pub inconsistent_edges: Vec<[usize; 2]>,
+    /// Faces of each shell oriented against its nesting depth, i.e., bodies with inward
+    /// normals or cavities with outward normals.
+    pub misoriented_shells: Vec<Vec<usize>>,
+    /// Pairs of non-adjacent faces `[i, j]` with `i < j` that intersect.
+    pub self_intersections: Vec<[usize; 2]>,
+}
+
+impl MeshDiagnostics {
+    /// Returns `true` if the mesh passes validation. Duplicate vertices are allowed.
+    #[inline]
+    pub fn is_valid(&self) -> bool {
+        self.error().is_none()
+    }
+
+    /// The [MeshError] that validation reports for this mesh, if any.
+    pub fn error(&self) -> Option<MeshError> {
+        if !self.degenerate_faces.is_empty() {
+            Some(MeshError::ZeroAreaFace)
+        } else if !self.open_edges.is_empty() {
+            Some(MeshError::OpenEdges)
+        } else if !self.non_manifold_edges.is_empty() {
+            Some(MeshError::NonManifold)
+        } else if !self.inconsistent_edges.is_empty() {
+            Some(MeshError::InconsistentNormals)
+        } else if !self.misoriented_shells.is_empty() {
+            Some(MeshError::InwardNormals)
+        } else if !self.self_intersections.is_empty() {
+            Some(MeshError::SelfIntersecting)
+        } else {
+            None
+        }
+    }
+}
+
+impl Display for MeshDiagnostics {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        writeln!(f, "open edges: {}", self.open_edges.len())?;
+        writeln!(f, "non-manifold edges: {}", self.non_manifold_edges.len())?;
+        writeln!(f, "duplicate vertices: {}", self.duplicate_vertices.len())?;
+        writeln!(f, "degenerate faces: {}", self.degenerate_faces.len())?;
+        writeln!(f, "inconsistent edges: {}", self.inconsistent_edges.len())?;
+        writeln!(f, "misoriented shells: {}", self.misoriented_shells.len())?;
+        write!(f, "self-intersections: {}", self.self_intersections.len())
+    }
+}
+
+/// Faces using each undirected edge, with `true` if the face traverses it from the lower
+/// to the higher vertex index.
+fn edge_uses(faces: &[[usize; 3]]) -> BTreeMap<[usize; 2], Vec<(usize, bool)>> {
+    let mut uses: BTreeMap<[usize; 2], Vec<(usize, bool)>> = BTreeMap::new();
+    for (i, face) in faces.iter().enumerate() {
+        for k in 0..3 {
+            let (a, b) = (face[k], face[(k + 1) % 3]);
+            if a != b {
+                uses.entry([a.min(b), a.max(b)])
+                    .or_default()
+                    .push((i, a < b));
+            }
+        }
+    }
+    uses
+}
+
+#[inline]
+fn is_degenerate<T: Float>(vertices: &[Vector3<T>], face: &[usize; 3], area: T) -> bool {
+    let [a, b, c] = *face;
+    a == b
+        || b == c
+        || c == a
+        || (vertices[b] - vertices[a])
+            .cross(&(vertices[c] - vertices[a]))
+            .norm()
+            < area
+}
+
+/// Maps each vertex to the first vertex within `distance` of it. Vertices with non-finite
+/// coordinates are never welded.
+///
+/// # Panics
+///
+/// Panics if `distance` is not positive and finite.
+fn weld_map<T: Float>(vertices: &[Vector3<T>], distance: T) -> Vec<usize> {
+    assert!(
+        distance > T::zero() && num_traits::Float::is_finite(distance),
+        "Weld distance must be positive and finite."
+    );
+    // Cells beyond the range of i64 are clamped, which only coarsens the grid there
+    let cell = |v: &Vector3<T>| {
+        v.map(|x| {
+            let cell = num_traits::Float::floor(x / distance);
+            cell.to_i64()
+                .unwrap_or(if cell > T::zero() { i64::MAX } else { i64::MIN })
+        })
+    };
+    let mut grid: BTreeMap<[i64; 3], Vec<usize>> = BTreeMap::new();
+    let mut map: Vec<usize> = (0..vertices.len()).collect();
+
+    for (i, v) in vertices.iter().enumerate() {
+        if !v.iter().all(|&x| num_traits::Float::is_finite(x)) {
+            continue;
+        }
+        let c = cell(v);
+        let mut found = None;
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                for dz in -1..=1 {
+                    let key = [
+                        c.x.saturating_add(dx),
+                        c.y.saturating_add(dy),
+                        c.z.saturating_add(dz),
+                    ];
+                    if let Some(candidates) = grid.get(&key) {
+                        for &j in candidates {
+                            if (vertices[j] - v).norm() <= distance
+                                && found.is_none_or(|f: usize| j < f)
+                            {
+                                found = Some(j);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        match found {
+            Some(j) => map[i] = j,
+            None => grid.entry([c.x, c.y, c.z]).or_default().push(i),
+        }
+    }
+    map
+}
+
+#[inline]
+fn aabb<T: Float>(vertices: &[Vector3<T>], face: &[usize; 3]) -> (Vector3<T>, Vector3<T>) {
+    let [a, b, c] = face.map(|i| vertices[i]);
+    (a.inf(&b).inf(&c), a.sup(&b).sup(&c))
+}
+
+/// Returns `true` if an edge of `t1` crosses the interior of `t2`.
+///
+/// The tolerances are relative to the size of the triangles, so the test does not depend
+/// on the unit of length.
+fn edges_cross_facet<T: Float>(t1: [Vector3<T>; 3], t2: [Vector3<T>; 3]) -> bool {
+    let eps = T::from_f64(1e-6).unwrap();
+    let size = t1
+        .iter()
+        .chain(&t2)
+        .map(|v| (v - t2[0]).norm())
+        .fold(T::zero(), num_traits::Float::max);
+    let n = (t2[2] - t2[0]).cross(&(t2[1] - t2[0]));
+    let n_norm = n.norm();
+    if n_norm <= eps * eps * size * size {
+        return false;
+    }
+    let n = n / n_norm;
+    // Signed distances to the plane of `t2`
+    let d = t1.map(|v| n.dot(&(v - t2[2])));
+    let tol = eps * size;
+    if d.iter().all(|&x| x > tol) || d.iter().all(|&x| x < -tol) {
+        return false;
+    }
+
+    (0..3).any(|k| {
+        let (i, j) = (k, (k + 1) % 3);
+        if d[i] * d[j] > T::zero()
+            || num_traits::Float::abs(d[i]) <= tol
+            || num_traits::Float::abs(d[j]) <= tol
+        {
+            return false;
+        }
+        let s = t1[i] - t1[j];
+        let r = t2.map(|v| v - t1[j]);
+        let v = [
+            r[0].dot(&r[1].cross(&s)),
+            r[1].dot(&r[2].cross(&s)),
+            r[2].dot(&r[0].cross(&s)),
+        ];
+        let s_norm = s.norm();
+        let near_edge = (0..3).any(|m| {
+            let scale = eps * s_norm * r[m].norm() * r[(m + 1) % 3].norm();
+            num_traits::Float::abs(v[m]) <= scale
+        });
+        if near_edge {
+            return false;
+        }
+        v.iter().all(|&x| x > T::zero()) || v.iter().all(|&x| x < T::zero())
+    })
+}
+
+/// Finds pairs of non-adjacent intersecting faces.
+fn self_intersections<T: Float>(vertices: &[Vector3<T>], faces: &[[usize; 3]]) -> Vec<[usize; 2]> {
+    let boxes: Vec<_> = faces.iter().map(|f| aabb(vertices, f)).collect();
+    let mut order: Vec<usize> = (0..faces.len()).collect();
+    order.sort_by(|&a, &b| {
+        boxes[a]
+            .0
+            .x
+            .partial_cmp(&boxes[b].0.x)
+            .unwrap_or(core::cmp::Ordering::Equal)
+    });
+
+    let mut pairs = Vec::new();
+    for (n, &i) in order.iter().enumerate() {
+        let (lo_i, hi_i) = boxes[i];
+        for &j in order[n + 1..].iter() {
+            let (lo_j, hi_j) = boxes[j];
+            if lo_j.x > hi_i.x {
+                break;
+            }
+            if lo_j.y > hi_i.y || lo_i.y > hi_j.y || lo_j.z > hi_i.z || lo_i.z > hi_j.z {
+                continue;
+            }
+            if faces[i].iter().any(|v| faces[j].contains(v)) {
+                continue;
+            }
+            let (t1, t2) = (faces[i].map(|v| vertices[v]), faces[j].map(|v| vertices[v]));
+            if edges_cross_facet(t1, t2) || edges_cross_facet(t2, t1) {
+                pairs.push([i.min(j), i.max(j)]);
+            }
+        }
+    }
+    pairs.sort_unstable();
+    pairs
+}
+
+/// Lists all defects of a triangular mesh.
+///
+/// Unlike validation, which stops at the first problem, diagnostics report every open,
+/// non-manifold, and inconsistently oriented edge, duplicate vertex, degenerate face,
+/// misoriented shell, and self-intersecting face pair.
+///
+/// # Examples
+///
+/// ```
+/// # use magba::base::mesh::{diagnose, MeshTolerance};
+/// # use nalgebra::vector;
+/// // Tetrahedron with a missing face
+/// let vertices = [vector![0.0, 0.0, 0.0], vector![1.0, 0.0, 0.0], vector![0.0, 1.0, 0.0], vector![0.0, 0.0, 1.0]];
+/// let faces = [[0, 2, 1], [0, 1, 3], [1, 2, 3]];
+///
+/// let report = diagnose(&vertices, &faces, MeshTolerance::default());
+/// assert_eq!(report.open_edges, vec![[0, 2], [0, 3], [2, 3]]);
+/// assert!(!report.is_valid());
+/// ```
+///
+/// # Panics
+///
+/// Panics if the distance tolerance is not positive and finite.
+pub fn diagnose<T: Float>(
+    vertices: &[Vector3<T>],
+    faces: &[[usize; 3]],
+    tolerance: MeshTolerance<T>,
+) -> MeshDiagnostics {
+    let mut report = MeshDiagnostics::default();
+
+    for (edge, uses) in edge_uses(faces) {
+        match uses.as_slice() {
+            [_] => report.open_edges.push(edge),
+            [(_, a), (_, b)] => {
+                if a == b {
+                    report.inconsistent_edges.push(edge);
+                }
+            }
+            _ => report.non_manifold_edges.push(edge),
+        }
+    }
+
+    report.duplicate_vertices = weld_map(vertices, tolerance.distance)
+        .into_iter()
+        .enumerate()
+        .filter(|&(i, j)| i != j)
+        .map(|(i, j)| [j, i])
+        .collect();
+
+    report.degenerate_faces = faces
+        .iter()
+        .enumerate()
+        .filter(|(_, f)| is_degenerate(vertices, f, tolerance.area))
+        .map(|(i, _)| i)
+        .collect();
+
+    report.misoriented_shells = misoriented_shells(vertices, faces);
+    report.self_intersections = self_intersections(vertices, faces);
+
+    report
+}
+
+/// Repair steps applied by [repair].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RepairOptions<T: Float> {
+    /// Merge vertices closer than the distance tolerance.
+    pub weld: bool,
+    /// Remove faces with repeated vertices or area below the area tolerance.
+    pub drop_degenerate: bool,
+    /// Orient faces consistently within each shell, with bodies pointing outwards and
+    /// cavities pointing inwards.
+    pub orient: bool,
+    pub tolerance: MeshTolerance<T>,
+}
+
+impl<T: Float> Default for RepairOptions<T> {
+    fn default() -> Self {
+        Self {
+            weld: true,
+            drop_degenerate: true,
+            orient: true,
+            tolerance: MeshTolerance::default(),
+        }
+    }
+}
+
+/// Summary of the changes made by [repair].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RepairSummary {
+    pub welded_vertices: usize,
+    pub removed_faces: usize,
+    pub flipped_faces: usize,
+}
+
+/// Flips faces such that every manifold edge is traversed in opposite directions by its
+/// two faces. Returns whether each face was flipped.
+fn orient_consistently(faces: &mut [[usize; 3]]) -> Vec<bool> {
+    let uses = edge_uses(faces);
+    let mut neighbors: Vec<Vec<(usize, bool)>> = vec![Vec::new(); faces.len()];
+    for edge_faces in uses.values() {
+        if let [(f, a), (g, b)] = edge_faces.as_slice() {
+            // Neighbors must be flipped relative to each other if they agree on direction
+            neighbors[*f].push((*g, a == b));
+            neighbors[*g].push((*f, a == b));
+        }
+    }
+
+    let mut flip: Vec<Option<bool>> = vec![None; faces.len()];
+    let mut stack = Vec::new();
+    for start in 0..faces.len() {
+        if flip[start].is_some() {
+            continue;
+        }
+        flip[start] = Some(false);
+        stack.push(start);
+        while let Some(f) = stack.pop() {
+            let f_flip = flip[f].unwrap();
+            for &(g, relative) in &neighbors[f] {
+                if flip[g].is_none() {
+                    flip[g] = Some(f_flip ^ relative);
+                    stack.push(g);
+                }
+            }
+        }
+    }
+
+    faces
+        .iter_mut()
+        .zip(flip)
+        .map(|(face, flip)| {
+            let flip = flip == Some(true);
+            if flip {
+                face.swap(1, 2);
+            }
+            flip
+        })
+        .collect()
+}
+
+/// Repairs a triangular mesh.
+///
+/// Applies, in order and as enabled in `options`: vertex welding, removal of degenerate
+/// faces, and orientation of faces. Unreferenced vertices are removed.
+///
+/// # Returns
+///
+/// - Repaired vertices and faces, and a summary of the changes
+///
+/// # Examples
+///
+/// ```
+/// # use magba::base::mesh::{repair, RepairOptions, TriMesh};
+/// # use nalgebra::vector;
+/// // Tetrahedron with unshared vertices and an inverted face, as often exported to STL
+/// let corners = [vector![0.0, 0.0, 0.0], vector![1.0, 0.0, 0.0], vector![0.0, 1.0, 0.0], vector![0.0, 0.0, 1.0]];
+/// let faces = [[0, 2, 1], [0, 1, 3], [1, 2, 3], [0, 2, 3]];
+/// let vertices: Vec<_> = faces.iter().flatten().map(|&i| corners[i]).collect();
+/// let faces: Vec<_> = (0..4).map(|i| [3 * i, 3 * i + 1, 3 * i + 2]).collect();
+/// assert!(TriMesh::new(vertices.clone(), faces.clone()).is_err());
+///
+/// let (vertices, faces, summary) = repair(&vertices, &faces, &RepairOptions::default());
+/// assert_eq!(summary.welded_vertices, 8);
+/// assert_eq!(summary.flipped_faces, 1);
+/// assert!(TriMesh::new(vertices, faces).is_ok());
+/// ```
+///
+/// # Panics
+///
+/// Panics if welding is enabled and the distance tolerance is not positive and finite.
+pub fn repair<T: Float>(
+    vertices: &[Vector3<T>],
+    faces: &[[usize; 3]],
+    options: &RepairOptions<T>,
+) -> (Vec<Vector3<T>>, Vec<[usize; 3]>, RepairSummary) {
+    let mut summary = RepairSummary::default();
+    let mut faces = faces.to_vec();
+
+    if options.weld {
+        let map = weld_map(vertices, options.tolerance.distance);
+        summary.welded_vertices = map.iter().enumerate().filter(|&(i, &j)| i != j).count();
+        faces.iter_mut().for_each(|f| *f = f.map(|v| map[v]));
+    }
+
+    if options.drop_degenerate {
+        let count = faces.len();
+        faces.retain(|f| !is_degenerate(vertices, f, options.tolerance.area));
+        summary.removed_faces = count - faces.len();
+    }
+
+    if options.orient {
+        let mut flipped = orient_consistently(&mut faces);
+        for shell in misoriented_shells(vertices, &faces) {
+            for i in shell {
+                faces[i].swap(1, 2);
+                flipped[i] = !flipped[i];
+            }
+        }
+        summary.flipped_faces = flipped.into_iter().filter(|&f| f).count();
+    }
+
+    // Remove unreferenced vertices
+    let mut new_index = vec![usize::MAX; vertices.len()];
+    let mut new_vertices = Vec::new();
+    for face in faces.iter_mut() {
+        for v in face.iter_mut() {
+            if new_index[*v] == usize::MAX {
+                new_index[*v] = new_vertices.len();
+                new_vertices.push(vertices[*v]);
+            }
+            *v = new_index[*v];
+        }
+    }
+
+    (new_vertices, faces, summary)
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::{base::mesh::TriMesh, testing_util::cube_mesh};
+    use nalgebra::vector;
+
+    type RawMesh = (Vec<Vector3<f64>>, Vec<[usize; 3]>);
+
+    /// Joins meshes into one vertex and face list.
+    fn join(meshes: &[RawMesh]) -> RawMesh {
+        let (mut vertices, mut faces) = (Vec::new(), Vec::new());
+        for (v, f) in meshes {
+            let offset = vertices.len();
+            vertices.extend(v);
+            faces.extend(f.iter().map(|f| f.map(|i| i + offset)));
+        }
+        (vertices, faces)
+    }
+
+    #[test]
+    fn test_diagnose_valid() {
+        let (vertices, faces) = cube_mesh(Vector3::zeros(), 0.2, false);
+        let report = diagnose(&vertices, &faces, MeshTolerance::default());
+        assert_eq!(report, MeshDiagnostics::default());
+        assert!(report.is_valid());
+    }
+
+    #[test]
+    fn test_diagnose_defects() {
+        let (mut vertices, mut faces) = cube_mesh(Vector3::zeros(), 0.2, false);
+        faces[0].swap(1, 2);
+        vertices.push(vertices[0]);
+        faces.push([0, 1, 1]);
+
+        let report = diagnose(&vertices, &faces, MeshTolerance::default());
+        // Edge [0, 1] of the flipped face is also used by the degenerate face
+        assert_eq!(report.inconsistent_edges.len(), 2);
+        assert_eq!(report.duplicate_vertices, vec![[0, 8]]);
+        assert_eq!(report.degenerate_faces, vec![12]);
+        assert!(report.open_edges.is_empty());
+        assert_eq!(report.non_manifold_edges, vec![[0, 1]]);
+        assert_eq!(report.error(), Some(MeshError::ZeroAreaFace));
+    }
+
+    #[test]
+    fn test_diagnose_shells() {
+        let (vertices, faces) = join(&[
+            cube_mesh(Vector3::zeros(), 0.2, false),
+            cube_mesh(Vector3::zeros(), 0.1, false),
+            cube_mesh(vector![0.5, 0.0, 0.0], 0.2, true),
+        ]);
+        let report = diagnose(&vertices, &faces, MeshTolerance::default());
+        assert_eq!(report.misoriented_shells.len(), 2);
+        assert_eq!(report.misoriented_shells[0], (12..24).collect::<Vec<_>>());
+        assert_eq!(report.error(), Some(MeshError::InwardNormals));
+    }
+
+    #[test]
+    fn test_diagnose_self_intersections() {
+        // Bar passing through a cube without enclosing any of its vertices
+        let (bar_vertices, bar_faces) = cube_mesh(Vector3::zeros(), 0.2, false);
+        let bar_vertices = bar_vertices
+            .into_iter()
+            .map(|v| vector![v.x * 3.0, v.y * 0.2, v.z * 0.2])
+            .collect();
+        let (vertices, faces) = join(&[
+            cube_mesh(Vector3::zeros(), 0.2, false),
+            (bar_vertices, bar_faces),
+        ]);
+        let report = diagnose(&vertices, &faces, MeshTolerance::default());
+        assert!(!report.self_intersections.is_empty());
+        assert!(
+            report
+                .self_intersections
+                .iter()
+                .all(|[i, j]| *i < 12 && *j >= 12)
+        );
+        assert_eq!(report.error(), Some(MeshError::SelfIntersecting));
+
+        // Same pairs at the scale of small magnets
+        for scale in [1e-2, 1e-3] {
+            let scaled: Vec<_> = vertices.iter().map(|v| v * scale).collect();
+            let tolerance = MeshTolerance {
+                area: 1e-4 * scale * scale,
+                distance: 1e-9 * scale,
+            };
+            let scaled_report = diagnose(&scaled, &faces, tolerance);
+            assert_eq!(scaled_report.self_intersections, report.self_intersections);
+        }
+    }
+
+    #[test]
+    fn test_weld_extreme_vertices() {
+        let vertices = [
+            vector![f64::NAN, 0.0, 0.0],
+            vector![f64::INFINITY, 0.0, 0.0],
+            vector![f64::INFINITY, 0.0, 0.0],
+            vector![1e300, 0.0, 0.0],
+            vector![1e300, 0.0, 0.0],
+            vector![-1e300, 0.0, 0.0],
+            vector![0.0, 0.0, 0.0],
+        ];
+        assert_eq!(
+            weld_map(&vertices, MeshTolerance::default().distance),
+            vec![0, 1, 2, 3, 3, 5, 6]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Weld distance must be positive and finite.")]
+    fn test_weld_zero_distance() {
+        let (vertices, faces) = cube_mesh(Vector3::zeros(), 0.2, false);
+        let tolerance = MeshTolerance {
+            distance: 0.0,
+            ..Default::default()
+        };
+        diagnose(&vertices, &faces, tolerance);
+    }
+
+    #[test]
+    fn test_repair() {
+        // Hollow cube exported as unshared triangles with random face orientations
+        let (vertices, faces) = join(&[
+            cube_mesh(Vector3::zeros(), 0.2, false),
+            cube_mesh(Vector3::zeros(), 0.1, false),
+        ]);
+        let mut soup_vertices = Vec::new();
+        let mut soup_faces = Vec::new();
+        for (i, face) in faces.iter().enumerate() {
+            let face = if i % 3 == 0 {
+                [face[0], face[2], face[1]]
+            } else {
+                *face
+            };
+            soup_faces.push([0, 1, 2].map(|k| soup_vertices.len() + k));
+            soup_vertices.extend(face.map(|v| vertices[v]));
+        }
+        soup_faces.push([0, 3, 0]);
+
+        let report = diagnose(&soup_vertices, &soup_faces, MeshTolerance::default());
+        assert_eq!(report.duplicate_vertices.len(), soup_vertices.len() - 16);
+        assert!(!report.open_edges.is_empty());
+
+        let (vertices, faces, summary) =
+            repair(&soup_vertices, &soup_faces, &RepairOptions::default());
+        assert_eq!(vertices.len(), 16);
+        assert_eq!(faces.len(), 24);
+        assert_eq!(summary.welded_vertices, soup_vertices.len() - 16);
+        assert_eq!(summary.removed_faces, 1);
+        // Outer shell: 4 inverted faces. Inner shell: 8 faces to match the inverted first face.
+        assert_eq!(summary.flipped_faces, 4 + 8);
+
+        let mesh = TriMesh::new(vertices, faces).unwrap();
+        assert!((mesh.volume() - (0.008 - 0.001)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_repair_disabled() {
+        let (vertices, mut faces) = cube_mesh(Vector3::zeros(), 0.2, true);
+        faces.push([0, 0, 1]);
+        let options = RepairOptions {
+            weld: false,
+            drop_degenerate: false,
+            orient: false,
+            ..Default::default()
+        };
+        let (new_vertices, new_faces, summary) = repair(&vertices, &faces, &options);
+        assert_eq!(summary, RepairSummary::default());
+        assert_eq!(new_faces.len(), faces.len());
+        assert_eq!(new_vertices.len(), vertices.len());
+    }
+}
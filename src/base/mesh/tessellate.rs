@@ -0,0 +1,599 @@
+/*
+ * Magba is licensed under The 3-Clause BSD, see LICENSE.
+ * Copyright 2025 Sira Pornsiriprasert <code@psira.me>
+ */
+
+//! Surface mesh generation from magnet primitives and signed distance functions.
+
+use alloc::{collections::BTreeMap, vec, vec::Vec};
+use nalgebra::{Matrix3, Vector3};
+use num_traits::Float as NumFloat;
+
+use crate::{
+    base::{Float, mesh::TriMesh},
+    magnets::{
+        CuboidMagnet, CylinderMagnet, MeshMagnet, PrismMagnet, SphereMagnet, TetrahedronMagnet,
+    },
+};
+
+/// Sources whose surface can be triangulated into a [TriMesh].
+pub trait Tessellate<T: Float> {
+    /// Triangulates the surface in the local frame of the source.
+    ///
+    /// The mesh is closed and oriented outwards. Its edges are at most about `resolution`
+    /// long (m), except where the flat faces of the source are already exact.
+    fn tessellate(&self, resolution: T) -> TriMesh<T>;
+}
+
+/// Indexed triangle list that merges vertices with equal keys.
+struct MeshBuilder<K> {
+    keys: BTreeMap<K, usize>,
+    faces: Vec<[usize; 3]>,
+}
+
+impl<K: Ord> MeshBuilder<K> {
+    fn new() -> Self {
+        Self {
+            keys: BTreeMap::new(),
+            faces: Vec::new(),
+        }
+    }
+
+    /// Returns the index of the vertex with `key`, pushing its position if it is new.
+    fn vertex<T: Float>(
+        &mut self,
+        positions: &mut Vec<Vector3<T>>,
+        key: K,
+        position: impl FnOnce() -> Vector3<T>,
+    ) -> usize {
+        *self.keys.entry(key).or_insert_with(|| {
+            positions.push(position());
+            positions.len() - 1
+        })
+    }
+
+    /// Pushes the quad `[a, b, c, d]` as two triangles with the same orientation.
+    fn quad(&mut self, [a, b, c, d]: [usize; 4]) {
+        self.faces.push([a, b, c]);
+        self.faces.push([a, c, d]);
+    }
+}
+
+fn divisions<T: Float>(length: T, resolution: T, min: usize) -> usize {
+    NumFloat::ceil(length / resolution)
+        .to_usize()
+        .unwrap_or(min)
+        .max(min)
+}
+
+impl<T: Float> Tessellate<T> for CuboidMagnet<T> {
+    fn tessellate(&self, resolution: T) -> TriMesh<T> {
+        let dimensions = self.dimensions();
+        let n = dimensions.map(|d| divisions(d, resolution, 1));
+        let position = |p: Vector3<usize>| {
+            Vector3::from_fn(|i, _| {
+                dimensions[i] * (T::from_usize(p[i]).unwrap() / T::from_usize(n[i]).unwrap())
+                    - dimensions[i] / T::from_f64(2.0).unwrap()
+            })
+        };
+
+        let mut builder = MeshBuilder::new();
+        let mut vertices = Vec::new();
+        for axis in 0..3 {
+            let (u, v) = ((axis + 1) % 3, (axis + 2) % 3);
+            for side in [0, n[axis]] {
+                for i in 0..n[u] {
+                    for j in 0..n[v] {
+                        let mut quad =
+                            [(i, j), (i + 1, j), (i + 1, j + 1), (i, j + 1)].map(|(a, b)| {
+                                let mut p = Vector3::zeros();
+                                p[axis] = side;
+                                p[u] = a;
+                                p[v] = b;
+                                builder.vertex(&mut vertices, [p.x, p.y, p.z], || position(p))
+                            });
+                        // u × v points along +axis
+                        if side == 0 {
+                            quad.reverse();
+                        }
+                        builder.quad(quad);
+                    }
+                }
+            }
+        }
+
+        TriMesh::new_unchecked(vertices, builder.faces)
+    }
+}
+
+/// Tessellates a surface of revolution around the z-axis, given the profile points
+/// `(r, z)` from the bottom to the top. Profile points with `r = 0` become poles.
+fn revolve<T: Float>(profile: &[(T, T)], resolution: T) -> TriMesh<T> {
+    let max_r = profile
+        .iter()
+        .fold(T::zero(), |acc, &(r, _)| NumFloat::max(acc, r));
+    let segments = divisions(T::two_pi() * max_r, resolution, 3);
+
+    let mut builder = MeshBuilder::new();
+    let mut vertices = Vec::new();
+    let mut ring = |builder: &mut MeshBuilder<[usize; 2]>, level: usize, s: usize| {
+        let (r, z) = profile[level];
+        let s = if r == T::zero() { 0 } else { s % segments };
+        builder.vertex(&mut vertices, [level, s], || {
+            let phi = T::two_pi() * T::from_usize(s).unwrap() / T::from_usize(segments).unwrap();
+            Vector3::new(r * NumFloat::cos(phi), r * NumFloat::sin(phi), z)
+        })
+    };
+
+    for level in 0..profile.len() - 1 {
+        for s in 0..segments {
+            // Going up the profile, then counterclockwise around z, points outwards
+            let quad = [
+                ring(&mut builder, level, s),
+                ring(&mut builder, level, s + 1),
+                ring(&mut builder, level + 1, s + 1),
+                ring(&mut builder, level + 1, s),
+            ];
+            if quad[0] == quad[1] {
+                builder.faces.push([quad[0], quad[2], quad[3]]);
+            } else if quad[2] == quad[3] {
+                builder.faces.push([quad[0], quad[1], quad[2]]);
+            } else {
+                builder.quad(quad);
+            }
+        }
+    }
+
+    TriMesh::new_unchecked(vertices, builder.faces)
+}
+
+impl<T: Float> Tessellate<T> for CylinderMagnet<T> {
+    fn tessellate(&self, resolution: T) -> TriMesh<T> {
+        let r = self.diameter() / T::from_f64(2.0).unwrap();
+        let h = self.height() / T::from_f64(2.0).unwrap();
+        let nr = divisions(r, resolution, 1);
+        let nz = divisions(self.height(), resolution, 1);
+        let fraction = |i: usize, n: usize| T::from_usize(i).unwrap() / T::from_usize(n).unwrap();
+
+        // Bottom cap outwards, side upwards, top cap inwards
+        let profile: Vec<(T, T)> = (0..nr)
+            .map(|i| (r * fraction(i, nr), -h))
+            .chain((0..=nz).map(|i| (r, -h + self.height() * fraction(i, nz))))
+            .chain((0..nr).rev().map(|i| (r * fraction(i, nr), h)))
+            .collect();
+
+        revolve(&profile, resolution)
+    }
+}
+
+impl<T: Float> Tessellate<T> for SphereMagnet<T> {
+    fn tessellate(&self, resolution: T) -> TriMesh<T> {
+        let r = self.diameter() / T::from_f64(2.0).unwrap();
+        let n = divisions(T::pi() * r, resolution, 2);
+
+        // From the south pole to the north pole
+        let profile: Vec<(T, T)> = (0..=n)
+            .map(|i| {
+                let theta = T::pi() * T::from_usize(n - i).unwrap() / T::from_usize(n).unwrap();
+                let sin = if i == 0 || i == n {
+                    T::zero()
+                } else {
+                    NumFloat::sin(theta)
+                };
+                (r * sin, r * NumFloat::cos(theta))
+            })
+            .collect();
+
+        revolve(&profile, resolution)
+    }
+}
+
+impl<T: Float> Tessellate<T> for TetrahedronMagnet<T> {
+    fn tessellate(&self, resolution: T) -> TriMesh<T> {
+        let mut v = self.vertices();
+        let det = Matrix3::from_columns(&[v[1] - v[0], v[2] - v[0], v[3] - v[0]]).determinant();
+        if det < T::zero() {
+            v.swap(2, 3);
+        }
+        let max_edge = [(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)]
+            .iter()
+            .fold(T::zero(), |acc, &(a, b)| {
+                NumFloat::max(acc, (v[b] - v[a]).norm())
+            });
+        let n = divisions(max_edge, resolution, 1);
+
+        // Vertices are keyed by their barycentric lattice coordinates
+        let mut builder = MeshBuilder::new();
+        let mut vertices = Vec::new();
+        for [p, q, r] in [[0, 2, 1], [0, 1, 3], [1, 2, 3], [0, 3, 2]] {
+            let mut vertex = |i: usize, j: usize| {
+                let mut key = [0; 4];
+                key[p] = n - i - j;
+                key[q] += i;
+                key[r] += j;
+                builder.vertex(&mut vertices, key, || {
+                    (0..4).fold(Vector3::zeros(), |acc, k| {
+                        acc + v[k] * (T::from_usize(key[k]).unwrap() / T::from_usize(n).unwrap())
+                    })
+                })
+            };
+            let mut faces = Vec::new();
+            for i in 0..n {
+                for j in 0..n - i {
+                    faces.push([vertex(i, j), vertex(i + 1, j), vertex(i, j + 1)]);
+                    if i + j + 1 < n {
+                        faces.push([vertex(i + 1, j), vertex(i + 1, j + 1), vertex(i, j + 1)]);
+                    }
+                }
+            }
+            builder.faces.extend(faces);
+        }
+
+        TriMesh::new_unchecked(vertices, builder.faces)
+    }
+}
+
+impl<T: Float> Tessellate<T> for PrismMagnet<T> {
+    fn tessellate(&self, resolution: T) -> TriMesh<T> {
+        let polygon = self.polygon();
+        let outline = polygon.vertices();
+        let nz = divisions(self.height(), resolution, 1);
+        let half = self.height() / T::from_f64(2.0).unwrap();
+
+        let mut builder = MeshBuilder::new();
+        let mut vertices = Vec::new();
+        let mut vertex = |builder: &mut MeshBuilder<[usize; 2]>, level: usize, i: usize| {
+            let i = i % outline.len();
+            builder.vertex(&mut vertices, [level, i], || {
+                let z = -half
+                    + self.height() * T::from_usize(level).unwrap() / T::from_usize(nz).unwrap();
+                Vector3::new(outline[i].x, outline[i].y, z)
+            })
+        };
+
+        for &[a, b, c] in polygon.triangles() {
+            let top = [a, b, c].map(|i| vertex(&mut builder, nz, i));
+            let bottom = [a, c, b].map(|i| vertex(&mut builder, 0, i));
+            builder.faces.extend([top, bottom]);
+        }
+        for level in 0..nz {
+            for i in 0..outline.len() {
+                let quad = [
+                    vertex(&mut builder, level, i),
+                    vertex(&mut builder, level, i + 1),
+                    vertex(&mut builder, level + 1, i + 1),
+                    vertex(&mut builder, level + 1, i),
+                ];
+                builder.quad(quad);
+            }
+        }
+
+        TriMesh::new_unchecked(vertices, builder.faces)
+    }
+}
+
+macro_rules! impl_to_mesh_magnet {
+    ($($magnet:ident),*) => {
+        $(
+            impl<T: Float> $magnet<T> {
+                /// Converts the magnet into a [MeshMagnet] with the same pose and polarization.
+                /// See [Tessellate::tessellate].
+                pub fn to_mesh_magnet(&self, resolution: T) -> MeshMagnet<T> {
+                    MeshMagnet::new(
+                        self.position(),
+                        self.orientation(),
+                        self.polarization(),
+                        self.tessellate(resolution),
+                    )
+                }
+            }
+        )*
+    };
+}
+
+impl_to_mesh_magnet!(
+    CuboidMagnet,
+    CylinderMagnet,
+    SphereMagnet,
+    TetrahedronMagnet,
+    PrismMagnet
+);
+
+/// The six tetrahedra of a grid cell, sharing the main diagonal from corner 0 to 7.
+/// Corner `c` is offset by `(c & 1, (c >> 1) & 1, (c >> 2) & 1)` cells.
+const CELL_TETRAHEDRA: [[usize; 4]; 6] = [
+    [0, 1, 3, 7],
+    [0, 1, 5, 7],
+    [0, 2, 3, 7],
+    [0, 2, 6, 7],
+    [0, 4, 5, 7],
+    [0, 4, 6, 7],
+];
+
+/// Meshes the surface `sdf(p) = 0` of a signed distance function within a box.
+///
+/// The box from `min` to `max` (m) is sampled on a regular grid with cells of about
+/// `resolution` (m), as in marching cubes. Each cell is split into six tetrahedra, which
+/// avoids the ambiguous cases of the marching cubes table, so the mesh is always closed,
+/// manifold, and oriented outwards. Negative values of `sdf` are inside. The surface is
+/// clipped by the box, which closes bodies that extend beyond it, though the clipped faces
+/// are only accurate to within one cell.
+///
+/// The result can be used directly in a [MeshMagnet].
+///
+/// # Examples
+///
+/// ```
+/// # use magba::base::mesh::mesh_sdf;
+/// # use nalgebra::{vector, Vector3};
+/// // Cylinder of radius 5 mm and height 4 mm with 0.5 mm fillets
+/// let (r, h, fillet) = (0.005, 0.002, 0.0005);
+/// let sdf = |p: Vector3<f64>| {
+///     let d = vector![p.xy().norm() - r + fillet, p.z.abs() - h + fillet];
+///     d.sup(&vector![0.0, 0.0]).norm() + d.max().min(0.0) - fillet
+/// };
+/// let mesh = mesh_sdf(sdf, vector![-0.006, -0.006, -0.003], vector![0.006, 0.006, 0.003], 2e-4);
+/// assert!(mesh.contains([0.0, 0.0, 0.0].into()));
+/// ```
+pub fn mesh_sdf<T, F>(sdf: F, min: Vector3<T>, max: Vector3<T>, resolution: T) -> TriMesh<T>
+where
+    T: Float,
+    F: Fn(Vector3<T>) -> T,
+{
+    let extent = max - min;
+    let n = extent.map(|e| divisions(e, resolution, 1));
+    let step = Vector3::from_fn(|i, _| extent[i] / T::from_usize(n[i]).unwrap());
+    let (nx, ny, nz) = (n.x + 1, n.y + 1, n.z + 1);
+    let index = |i: usize, j: usize, k: usize| i + nx * (j + ny * k);
+    let position = |id: usize| {
+        let (i, j, k) = (id % nx, (id / nx) % ny, id / (nx * ny));
+        min + Vector3::new(
+            step.x * T::from_usize(i).unwrap(),
+            step.y * T::from_usize(j).unwrap(),
+            step.z * T::from_usize(k).unwrap(),
+        )
+    };
+
+    // Samples on the box boundary are outside, which clips the surface by the box
+    let eps = step.norm() * T::from_f64(1e-12).unwrap();
+    let mut values = vec![T::zero(); nx * ny * nz];
+    for k in 0..nz {
+        for j in 0..ny {
+            for i in 0..nx {
+                let id = index(i, j, k);
+                let mut value = sdf(position(id));
+                let on_boundary = i == 0 || j == 0 || k == 0 || i == n.x || j == n.y || k == n.z;
+                if on_boundary || value == T::zero() || value.is_nan() {
+                    value = NumFloat::max(value, eps);
+                }
+                values[id] = value;
+            }
+        }
+    }
+
+    march_tetrahedra([nx, ny, nz], &values, position)
+}
+
+/// Triangulates the zero crossing of `values` sampled on a structured grid.
+///
+/// The grid has `shape` points in flat order, `i + nx * (j + ny * k)`, at `position(id)`.
+/// Each cell is split into six tetrahedra, and the faces are oriented from the negative
+/// to the positive samples. Values must be nonzero and not NaN.
+pub(crate) fn march_tetrahedra<T, P>(shape: [usize; 3], values: &[T], position: P) -> TriMesh<T>
+where
+    T: Float,
+    P: Fn(usize) -> Vector3<T>,
+{
+    let [nx, ny, nz] = shape;
+    let mut builder = MeshBuilder::new();
+    let mut vertices = Vec::new();
+    if nx < 2 || ny < 2 || nz < 2 {
+        return TriMesh::new_unchecked(vertices, builder.faces);
+    }
+    let index = |i: usize, j: usize, k: usize| i + nx * (j + ny * k);
+
+    // Crossings close to a grid point snap to it, so that the mesh has no sliver faces
+    let snap = T::from_f64(0.01).unwrap();
+    let crossing = |builder: &mut MeshBuilder<[usize; 2]>,
+                    vertices: &mut Vec<Vector3<T>>,
+                    a: usize,
+                    b: usize| {
+        let t = values[a] / (values[a] - values[b]);
+        if t < snap {
+            builder.vertex(vertices, [a, a], || position(a))
+        } else if t > T::one() - snap {
+            builder.vertex(vertices, [b, b], || position(b))
+        } else {
+            let key = if a < b { [a, b] } else { [b, a] };
+            builder.vertex(vertices, key, || {
+                position(a) + (position(b) - position(a)) * t
+            })
+        }
+    };
+    let centroid = |ids: &[usize]| {
+        ids.iter()
+            .fold(Vector3::zeros(), |acc, &id| acc + position(id))
+            / T::from_usize(ids.len()).unwrap()
+    };
+
+    for k in 0..nz - 1 {
+        for j in 0..ny - 1 {
+            for i in 0..nx - 1 {
+                let corners: [usize; 8] =
+                    core::array::from_fn(|c| index(i + (c & 1), j + ((c >> 1) & 1), k + (c >> 2)));
+                for tet in CELL_TETRAHEDRA {
+                    let (inside, outside): (Vec<usize>, Vec<usize>) = tet
+                        .iter()
+                        .map(|&c| corners[c])
+                        .partition(|&id| values[id] < T::zero());
+
+                    let edges: Vec<(usize, usize)> = match (inside.len(), outside.len()) {
+                        (1, 3) => outside.iter().map(|&o| (inside[0], o)).collect(),
+                        (3, 1) => inside.iter().map(|&i| (i, outside[0])).collect(),
+                        (2, 2) => vec![
+                            (inside[0], outside[0]),
+                            (inside[0], outside[1]),
+                            (inside[1], outside[1]),
+                            (inside[1], outside[0]),
+                        ],
+                        _ => continue,
+                    };
+                    let polygon: Vec<usize> = edges
+                        .iter()
+                        .map(|&(i, o)| crossing(&mut builder, &mut vertices, i, o))
+                        .collect();
+
+                    // Orient the normals from the inside to the outside corners
+                    let direction = centroid(&outside) - centroid(&inside);
+                    for k in 1..polygon.len() - 1 {
+                        let mut face = [polygon[0], polygon[k], polygon[k + 1]];
+                        if face[0] == face[1] || face[1] == face[2] || face[2] == face[0] {
+                            continue;
+                        }
+                        let [a, b, c] = face.map(|v| vertices[v]);
+                        if (b - a).cross(&(c - a)).dot(&direction) < T::zero() {
+                            face.swap(1, 2);
+                        }
+                        builder.faces.push(face);
+                    }
+                }
+            }
+        }
+    }
+
+    TriMesh::new_unchecked(vertices, builder.faces)
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::base::{
+        Source,
+        mesh::{MeshTolerance, diagnose},
+        polygon::Polygon,
+    };
+    use approx::assert_relative_eq;
+    use nalgebra::{Point3, UnitQuaternion, point, vector};
+
+    fn assert_closed(mesh: &TriMesh<f64>) {
+        let tolerance = MeshTolerance {
+            area: 1e-14,
+            distance: 1e-12,
+        };
+        let report = diagnose(mesh.vertices(), mesh.faces(), tolerance);
+        assert!(report.is_valid(), "{report}");
+    }
+
+    fn assert_field_close<S: Source<f64>>(source: &S, mesh: &MeshMagnet<f64>, rtol: f64) {
+        let points: [Point3<f64>; 3] = [
+            point![0.02, 0.01, 0.015],
+            point![-0.01, 0.03, -0.02],
+            point![0.001, -0.002, 0.001],
+        ];
+        for point in points {
+            let expected = source.compute_B(point);
+            assert_relative_eq!(
+                mesh.compute_B(point),
+                expected,
+                epsilon = rtol * expected.norm()
+            );
+        }
+    }
+
+    #[test]
+    fn test_flat_primitives_are_exact() {
+        let orientation = UnitQuaternion::from_scaled_axis(vector![0.3, -0.2, 0.5]);
+        let cuboid = CuboidMagnet::new(
+            [0.001, 0.0, 0.002],
+            orientation,
+            [0.2, 0.5, 1.0],
+            [0.01, 0.008, 0.006],
+        );
+        let mesh = cuboid.to_mesh_magnet(0.003);
+        assert_closed(mesh.mesh());
+        assert_relative_eq!(mesh.mesh().volume(), 0.01 * 0.008 * 0.006, epsilon = 1e-18);
+        assert_field_close(&cuboid, &mesh, 1e-9);
+
+        let tetrahedron = TetrahedronMagnet::new(
+            [0.0, 0.001, 0.0],
+            orientation,
+            [1.0, 0.0, -0.5],
+            [
+                vector![0.0, 0.0, 0.0],
+                vector![0.0, 0.0, 0.01],
+                vector![0.01, 0.0, 0.0],
+                vector![0.0, 0.01, 0.0],
+            ],
+        );
+        let mesh = tetrahedron.to_mesh_magnet(0.004);
+        assert_closed(mesh.mesh());
+        assert_field_close(&tetrahedron, &mesh, 1e-9);
+
+        let polygon = Polygon::new([
+            vector![0.0, 0.0],
+            vector![0.01, 0.0],
+            vector![0.01, 0.004],
+            vector![0.004, 0.004],
+            vector![0.004, 0.01],
+            vector![0.0, 0.01],
+        ]);
+        let prism = PrismMagnet::new([0.0; 3], orientation, [0.3, 0.3, 1.0], 0.005, polygon);
+        let mesh = prism.to_mesh_magnet(0.002);
+        assert_closed(mesh.mesh());
+        assert_field_close(&prism, &mesh, 1e-9);
+    }
+
+    #[test]
+    fn test_curved_primitives_converge() {
+        let orientation = UnitQuaternion::from_scaled_axis(vector![0.1, 0.4, 0.0]);
+        let cylinder = CylinderMagnet::new([0.0; 3], orientation, [0.5, 0.0, 1.0], 0.01, 0.006);
+        let mesh = cylinder.to_mesh_magnet(2e-4);
+        assert_closed(mesh.mesh());
+        assert_field_close(&cylinder, &mesh, 1e-3);
+
+        let sphere = SphereMagnet::new([0.0; 3], orientation, [0.0, 0.3, 1.0], 0.012);
+        let mesh = sphere.to_mesh_magnet(3e-4);
+        assert_closed(mesh.mesh());
+        assert_field_close(&sphere, &mesh, 2e-3);
+    }
+
+    #[test]
+    fn test_mesh_sdf_sphere() {
+        let r = 0.005;
+        let mesh = mesh_sdf(
+            |p: Vector3<f64>| p.norm() - r,
+            vector![-0.006, -0.006, -0.006],
+            vector![0.006, 0.006, 0.006],
+            2.5e-4,
+        );
+        assert_closed(&mesh);
+        let exact = 4.0 / 3.0 * core::f64::consts::PI * r.powi(3);
+        assert_relative_eq!(mesh.volume(), exact, max_relative = 1e-2);
+
+        let magnet = MeshMagnet::new([0.0; 3], UnitQuaternion::identity(), [0.0, 0.0, 1.0], mesh);
+        let sphere = SphereMagnet::new(
+            [0.0; 3],
+            UnitQuaternion::identity(),
+            [0.0, 0.0, 1.0],
+            2.0 * r,
+        );
+        assert_field_close(&sphere, &magnet, 2e-2);
+    }
+
+    #[test]
+    fn test_mesh_sdf_clipped_by_box() {
+        // Half-space clipped by the box becomes a closed slab
+        let mesh = mesh_sdf(
+            |p: Vector3<f64>| p.z,
+            vector![0.0, 0.0, -0.01],
+            vector![0.01, 0.01, 0.01],
+            0.0025,
+        );
+        assert_closed(&mesh);
+        // Clipped within one cell of the box faces, and exactly at the surface
+        let h = 0.0025;
+        let volume = mesh.volume();
+        assert!(volume <= 1e-6 * (1.0 + 1e-9));
+        assert!(volume >= (0.01 - 2.0 * h) * (0.01 - 2.0 * h) * (0.01 - h));
+    }
+}
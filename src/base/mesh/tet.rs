@@ -0,0 +1,264 @@
+/*
+ * Magba is licensed under The 3-Clause BSD, see LICENSE.
+ * Copyright 2025 Sira Pornsiriprasert <code@psira.me>
+ */
+
+//! Tetrahedral volume mesh.
+
+use alloc::vec::Vec;
+use nalgebra::{Matrix3, Vector3};
+
+#[cfg(feature = "io-msh")]
+use crate::base::mesh::MeshReadOptions;
+use crate::{Error, base::Float};
+
+/// Tetrahedral volume mesh.
+///
+/// The elements are stored positively oriented, together with the inverse of their edge
+/// matrix for the inside test of the field computation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TetMesh<T: Float> {
+    vertices: Vec<Vector3<T>>,
+    tetrahedra: Vec<[usize; 4]>,
+    inverses: Vec<Option<Matrix3<T>>>,
+}
+
+impl<T: Float> TetMesh<T> {
+    /// Construct a [TetMesh] from vertices and tetrahedra.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a tetrahedron refers to a vertex that does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use magba::base::mesh::TetMesh;
+    /// # use nalgebra::vector;
+    /// let vertices = vec![
+    ///     vector![0.0, 0.0, 0.0],
+    ///     vector![0.01, 0.0, 0.0],
+    ///     vector![0.0, 0.01, 0.0],
+    ///     vector![0.0, 0.0, 0.01],
+    ///     vector![0.01, 0.01, 0.01],
+    /// ];
+    /// let mesh = TetMesh::new(vertices, vec![[0, 1, 2, 3], [1, 2, 3, 4]]);
+    /// assert_eq!(mesh.tetrahedra().len(), 2);
+    /// ```
+    pub fn new<V, E>(vertices: V, tetrahedra: E) -> Self
+    where
+        V: IntoIterator<Item = Vector3<T>>,
+        E: IntoIterator<Item = [usize; 4]>,
+    {
+        Self::try_new(vertices, tetrahedra).unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Construct a [TetMesh], or return [Error::InvalidGeometry] if a tetrahedron refers
+    /// to a vertex that does not exist.
+    pub fn try_new<V, E>(vertices: V, tetrahedra: E) -> Result<Self, Error>
+    where
+        V: IntoIterator<Item = Vector3<T>>,
+        E: IntoIterator<Item = [usize; 4]>,
+    {
+        let vertices: Vec<Vector3<T>> = vertices.into_iter().collect();
+        let mut tetrahedra: Vec<[usize; 4]> = tetrahedra.into_iter().collect();
+        if tetrahedra.iter().flatten().any(|&i| i >= vertices.len()) {
+            return Err(Error::InvalidGeometry(
+                "Tetrahedron vertex index out of range.",
+            ));
+        }
+
+        let inverses = tetrahedra
+            .iter_mut()
+            .map(|tet| {
+                let edges = |tet: &[usize; 4]| {
+                    Matrix3::from_columns(&[
+                        vertices[tet[1]] - vertices[tet[0]],
+                        vertices[tet[2]] - vertices[tet[0]],
+                        vertices[tet[3]] - vertices[tet[0]],
+                    ])
+                };
+                if edges(tet).determinant() < T::zero() {
+                    tet.swap(2, 3);
+                }
+                edges(tet).try_inverse()
+            })
+            .collect();
+
+        Ok(Self {
+            vertices,
+            tetrahedra,
+            inverses,
+        })
+    }
+
+    /// Mesh vertices (m).
+    #[inline]
+    pub fn vertices(&self) -> &[Vector3<T>] {
+        &self.vertices
+    }
+
+    /// Vertex indices of each tetrahedron, positively oriented.
+    #[inline]
+    pub fn tetrahedra(&self) -> &[[usize; 4]] {
+        &self.tetrahedra
+    }
+
+    /// Vertex positions of the tetrahedron at `index`.
+    #[inline]
+    pub fn element(&self, index: usize) -> [Vector3<T>; 4] {
+        self.tetrahedra[index].map(|i| self.vertices[i])
+    }
+
+    /// Inverse edge matrix of the tetrahedron at `index`, or `None` if it is degenerate.
+    #[inline]
+    pub(crate) fn inverse(&self, index: usize) -> Option<Matrix3<T>> {
+        self.inverses[index]
+    }
+
+    /// Centroid of each tetrahedron (m).
+    pub fn centroids(&self) -> Vec<Vector3<T>> {
+        let quarter = T::from_f64(0.25).unwrap();
+        self.tetrahedra
+            .iter()
+            .map(|tet| tet.iter().map(|&i| self.vertices[i]).sum::<Vector3<T>>() * quarter)
+            .collect()
+    }
+
+    /// Total volume of the tetrahedra (m³).
+    pub fn volume(&self) -> T {
+        let sixth = T::from_f64(1.0 / 6.0).unwrap();
+        (0..self.tetrahedra.len())
+            .map(|i| {
+                let [a, b, c, d] = self.element(i);
+                (b - a).cross(&(c - a)).dot(&(d - a)) * sixth
+            })
+            .fold(T::zero(), |acc, v| acc + v)
+    }
+
+    /// Reads a [TetMesh] from a Gmsh `.msh` version 4 ASCII reader.
+    ///
+    /// Linear and quadratic tetrahedra are read in file order. Quadratic tetrahedra use
+    /// their corner nodes only. Other element types are skipped.
+    #[cfg(feature = "io-msh")]
+    pub fn from_msh<R: std::io::BufRead>(reader: R) -> std::io::Result<Self> {
+        Self::from_msh_with_options(reader, &MeshReadOptions::default())
+    }
+
+    /// Reads a [TetMesh] from a Gmsh `.msh` reader with unit scaling. See [TetMesh::from_msh].
+    ///
+    /// Only [MeshReadOptions::scale] applies to volume meshes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use magba::base::mesh::{MeshReadOptions, TetMesh};
+    /// let msh = "\
+    /// $MeshFormat
+    /// 4.1 0 8
+    /// $EndMeshFormat
+    /// $Nodes
+    /// 1 4 1 4
+    /// 3 1 0 4
+    /// 1
+    /// 2
+    /// 3
+    /// 4
+    /// 0 0 0
+    /// 10 0 0
+    /// 0 10 0
+    /// 0 0 10
+    /// $EndNodes
+    /// $Elements
+    /// 1 1 1 1
+    /// 3 1 4 1
+    /// 1 1 2 3 4
+    /// $EndElements
+    /// ";
+    /// // Coordinates in millimeters
+    /// let options = MeshReadOptions::default().with_scale(1e-3);
+    /// let mesh = TetMesh::<f64>::from_msh_with_options(msh.as_bytes(), &options).unwrap();
+    /// assert_eq!(mesh.vertices()[1].x, 0.01);
+    /// ```
+    #[cfg(feature = "io-msh")]
+    pub fn from_msh_with_options<R: std::io::BufRead>(
+        reader: R,
+        options: &MeshReadOptions<T>,
+    ) -> std::io::Result<Self> {
+        let (mut vertices, tetrahedra) = super::io::read_msh::<T, R>(reader)?;
+        if options.scale != T::one() {
+            vertices.iter_mut().for_each(|v| *v *= options.scale);
+        }
+        Ok(Self::new(vertices, tetrahedra))
+    }
+}
+
+// MARK: Serde
+
+/// Serialized as `{ "vertices": [[x, y, z], ...], "tetrahedra": [[a, b, c, d], ...] }`.
+#[cfg(feature = "serde")]
+impl<T: Float + serde::Serialize> serde::Serialize for TetMesh<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("TetMesh", 2)?;
+        state.serialize_field("vertices", &self.vertices)?;
+        state.serialize_field("tetrahedra", &self.tetrahedra)?;
+        state.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: Float + serde::Deserialize<'de>> serde::Deserialize<'de> for TetMesh<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(serde::Deserialize)]
+        #[serde(rename = "TetMesh", deny_unknown_fields)]
+        struct Repr<T: Float> {
+            vertices: Vec<Vector3<T>>,
+            tetrahedra: Vec<[usize; 4]>,
+        }
+
+        let Repr {
+            vertices,
+            tetrahedra,
+        } = Repr::deserialize(deserializer)?;
+        if tetrahedra.iter().flatten().any(|&i| i >= vertices.len()) {
+            return Err(serde::de::Error::custom(
+                "Tetrahedron vertex index out of range.",
+            ));
+        }
+        Ok(Self::new(vertices, tetrahedra))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::vector;
+
+    #[test]
+    fn test_orientation() {
+        let vertices = [
+            vector![0.0, 0.0, 0.0],
+            vector![1.0, 0.0, 0.0],
+            vector![0.0, 1.0, 0.0],
+            vector![0.0, 0.0, 1.0],
+        ];
+        let mesh = TetMesh::new(vertices, [[0, 1, 2, 3], [0, 1, 3, 2]]);
+        assert_eq!(mesh.tetrahedra(), &[[0, 1, 2, 3], [0, 1, 2, 3]]);
+        approx::assert_relative_eq!(mesh.volume(), 2.0 / 6.0);
+        assert_eq!(mesh.centroids()[0], vector![0.25, 0.25, 0.25]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_validation() {
+        TetMesh::<f64>::new([Vector3::zeros(); 3], [[0, 1, 2, 3]]);
+    }
+
+    #[test]
+    fn test_fallible_validation() {
+        let result = TetMesh::<f64>::try_new([Vector3::zeros(); 3], [[0, 1, 2, 3]]);
+        assert!(matches!(result, Err(Error::InvalidGeometry(_))));
+    }
+}
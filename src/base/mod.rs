@@ -19,20 +19,32 @@ pub(crate) mod transform;
 
 pub use observer::{Observer, SensorOutput};
 pub use source::Source;
+#[cfg(feature = "rayon")]
+pub(crate) use source::{CHUNK, PARALLEL_THRESHOLD};
 pub use transform::Transform;
 
+/// Object-safe [serde::Serialize] for [Source::serialize_custom], re-exported from
+/// [erased_serde](https://docs.rs/erased-serde).
+#[cfg(feature = "serde")]
+pub use erased_serde::Serialize as ErasedSerialize;
+
+#[cfg(feature = "alloc")]
+pub mod polygon;
+
+#[cfg(feature = "mesh")]
+pub mod bvh;
 #[cfg(feature = "mesh")]
 pub mod mesh;
 
 /// Wrapper trait for optional [dyn_clone::DynClone](https://docs.rs/dyn-clone/latest/dyn_clone/trait.DynClone.html).
 ///
-/// No-op if the `std` feature is disabled.
-#[cfg(feature = "std")]
+/// No-op if the `alloc` feature is disabled.
+#[cfg(feature = "alloc")]
 pub trait DynClone: dyn_clone::DynClone {}
-#[cfg(not(feature = "std"))]
+#[cfg(not(feature = "alloc"))]
 pub trait DynClone {}
 
-#[cfg(feature = "std")]
+#[cfg(feature = "alloc")]
 impl<T: dyn_clone::DynClone> DynClone for T {}
-#[cfg(not(feature = "std"))]
+#[cfg(not(feature = "alloc"))]
 impl<T> DynClone for T {}
@@ -8,11 +8,12 @@ use nalgebra::Vector3;
 
 use crate::{
     base::{DynClone, Float, Source, Transform},
-    crate_utils::need_std,
+    crate_utils::need_alloc,
 };
 
 /// Unified output for varying sensor types.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SensorOutput<T: Float = f64> {
     /// A single analog value (e.g., 1D Hall voltage, MR resistance).
     Scalar(T),
@@ -36,7 +37,6 @@ pub trait Observer<T: Float>: Transform<T> + Send + Sync + DynClone {
     }
 }
 
-#[cfg(feature = "std")]
 impl<T: Float> core::fmt::Display for dyn Observer<T> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         // Delegate to the trait method
@@ -46,7 +46,8 @@ impl<T: Float> core::fmt::Display for dyn Observer<T> {
 
 // MARK: Box<dyn Sensor>
 
-need_std!(
+need_alloc!(
+    use alloc::boxed::Box;
     use core::fmt::Display;
 
     use delegate::delegate;
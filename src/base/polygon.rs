@@ -0,0 +1,264 @@
+/*
+ * Magba is licensed under The 3-Clause BSD, see LICENSE.
+ * Copyright 2025 Sira Pornsiriprasert <code@psira.me>
+ */
+
+//! Simple polygons in the XY plane.
+
+use alloc::vec::Vec;
+use nalgebra::Vector2;
+
+use crate::{Error, base::Float};
+
+/// Simple polygon in the XY plane with its triangulation.
+///
+/// The polygon may be convex or concave, but its edges must not intersect.
+/// The vertices are stored counterclockwise.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polygon<T: Float> {
+    vertices: Vec<Vector2<T>>,
+    triangles: Vec<[usize; 3]>,
+}
+
+/// Twice the signed area of triangle `(a, b, c)`, positive if counterclockwise.
+#[inline]
+fn cross<T: Float>(a: Vector2<T>, b: Vector2<T>, c: Vector2<T>) -> T {
+    (b - a).perp(&(c - a))
+}
+
+impl<T: Float> Polygon<T> {
+    /// Construct a [Polygon] from its vertices in either winding order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the polygon has fewer than 3 vertices, zero area, or self-intersecting edges.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use magba::base::polygon::Polygon;
+    /// # use nalgebra::vector;
+    /// // L-shaped profile
+    /// let polygon = Polygon::new(vec![
+    ///     vector![0.0, 0.0],
+    ///     vector![0.02, 0.0],
+    ///     vector![0.02, 0.01],
+    ///     vector![0.01, 0.01],
+    ///     vector![0.01, 0.02],
+    ///     vector![0.0, 0.02],
+    /// ]);
+    /// assert_eq!(polygon.triangles().len(), 4);
+    /// ```
+    pub fn new(vertices: impl IntoIterator<Item = Vector2<T>>) -> Self {
+        Self::from_vertices(vertices.into_iter().collect())
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Construct a [Polygon], or return [Error::InvalidGeometry] where [Polygon::new]
+    /// panics.
+    pub fn try_new(vertices: impl IntoIterator<Item = Vector2<T>>) -> Result<Self, Error> {
+        Self::from_vertices(vertices.into_iter().collect()).map_err(Error::InvalidGeometry)
+    }
+
+    /// Validates and triangulates `vertices`, see [Polygon::new].
+    pub(crate) fn from_vertices(mut vertices: Vec<Vector2<T>>) -> Result<Self, &'static str> {
+        if vertices.len() < 3 {
+            return Err("Polygon must have at least 3 vertices.");
+        }
+
+        let area = Self::signed_area_of(&vertices);
+        if area == T::zero() {
+            return Err("Polygon must have non-zero area.");
+        }
+        if area < T::zero() {
+            vertices.reverse();
+        }
+
+        let triangles = Self::triangulate(&vertices).ok_or("Polygon edges must not intersect.")?;
+
+        Ok(Self {
+            vertices,
+            triangles,
+        })
+    }
+
+    fn signed_area_of(vertices: &[Vector2<T>]) -> T {
+        let n = vertices.len();
+        let twice = (0..n).fold(T::zero(), |acc, i| {
+            acc + vertices[i].perp(&vertices[(i + 1) % n])
+        });
+        twice * T::from_f64(0.5).unwrap()
+    }
+
+    /// Triangulates a counterclockwise simple polygon by ear clipping.
+    fn triangulate(vertices: &[Vector2<T>]) -> Option<Vec<[usize; 3]>> {
+        let mut remaining: Vec<usize> = (0..vertices.len()).collect();
+        let mut triangles = Vec::with_capacity(vertices.len() - 2);
+
+        while remaining.len() > 3 {
+            let n = remaining.len();
+            let ear = (0..n).find_map(|k| {
+                let [p, i, q] = [
+                    remaining[(k + n - 1) % n],
+                    remaining[k],
+                    remaining[(k + 1) % n],
+                ];
+                let (a, b, c) = (vertices[p], vertices[i], vertices[q]);
+                let turn = cross(a, b, c);
+                if turn < T::zero() {
+                    return None;
+                }
+                if turn == T::zero() {
+                    // Collinear vertex, remove without a triangle
+                    return Some((k, None));
+                }
+                let blocked = remaining.iter().any(|&j| {
+                    j != p
+                        && j != i
+                        && j != q
+                        && cross(a, b, vertices[j]) >= T::zero()
+                        && cross(b, c, vertices[j]) >= T::zero()
+                        && cross(c, a, vertices[j]) >= T::zero()
+                });
+                (!blocked).then_some((k, Some([p, i, q])))
+            })?;
+
+            if let Some(triangle) = ear.1 {
+                triangles.push(triangle);
+            }
+            remaining.remove(ear.0);
+        }
+
+        let [a, b, c] = [remaining[0], remaining[1], remaining[2]];
+        if cross(vertices[a], vertices[b], vertices[c]) <= T::zero() {
+            return None;
+        }
+        triangles.push([a, b, c]);
+
+        // A self-intersecting outline cannot be covered without overlap
+        let covered = triangles.iter().fold(T::zero(), |acc, &[a, b, c]| {
+            acc + cross(vertices[a], vertices[b], vertices[c])
+        }) * T::from_f64(0.5).unwrap();
+        let area = Self::signed_area_of(vertices);
+        let tolerance = T::from_f64(1e-9).unwrap() * area;
+        (num_traits::Float::abs(covered - area) <= tolerance).then_some(triangles)
+    }
+
+    /// Polygon vertices, counterclockwise (m).
+    #[inline]
+    pub fn vertices(&self) -> &[Vector2<T>] {
+        &self.vertices
+    }
+
+    /// Counterclockwise triangles as vertex indices.
+    #[inline]
+    pub fn triangles(&self) -> &[[usize; 3]] {
+        &self.triangles
+    }
+
+    /// Polygon area (m²).
+    pub fn area(&self) -> T {
+        Self::signed_area_of(&self.vertices)
+    }
+
+    /// Whether `point` is inside the polygon, using the even-odd rule.
+    pub fn contains(&self, point: Vector2<T>) -> bool {
+        let n = self.vertices.len();
+        let mut inside = false;
+        for i in 0..n {
+            let (a, b) = (self.vertices[i], self.vertices[(i + 1) % n]);
+            if (a.y > point.y) != (b.y > point.y) {
+                let x = a.x + (point.y - a.y) / (b.y - a.y) * (b.x - a.x);
+                if point.x < x {
+                    inside = !inside;
+                }
+            }
+        }
+        inside
+    }
+}
+
+// MARK: Serde
+
+/// Serialized as `{ "vertices": [[x, y], ...] }` and triangulated again when deserialized.
+#[cfg(feature = "serde")]
+impl<T: Float + serde::Serialize> serde::Serialize for Polygon<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("Polygon", 1)?;
+        state.serialize_field("vertices", &self.vertices)?;
+        state.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: Float + serde::Deserialize<'de>> serde::Deserialize<'de> for Polygon<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(serde::Deserialize)]
+        #[serde(rename = "Polygon", deny_unknown_fields)]
+        struct Repr<T: Float> {
+            vertices: Vec<Vector2<T>>,
+        }
+
+        let Repr { vertices } = Repr::deserialize(deserializer)?;
+        Self::from_vertices(vertices).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use nalgebra::vector;
+
+    #[test]
+    fn test_triangulate_concave() {
+        // Clockwise arrow-like outline
+        let polygon = Polygon::new([
+            vector![0.0, 0.0],
+            vector![1.0, 2.0],
+            vector![2.0, 0.0],
+            vector![1.0, 0.5],
+        ]);
+        assert_eq!(polygon.triangles().len(), 2);
+        assert_relative_eq!(polygon.area(), 1.5);
+        assert!(polygon.contains(vector![1.0, 1.0]));
+        assert!(!polygon.contains(vector![1.0, 0.25]));
+    }
+
+    #[test]
+    fn test_collinear_vertices() {
+        let polygon = Polygon::new([
+            vector![0.0, 0.0],
+            vector![0.5, 0.0],
+            vector![1.0, 0.0],
+            vector![1.0, 1.0],
+            vector![0.0, 1.0],
+        ]);
+        assert_relative_eq!(polygon.area(), 1.0);
+        let covered: f64 = polygon
+            .triangles()
+            .iter()
+            .map(|&[a, b, c]| {
+                0.5 * cross(
+                    polygon.vertices[a],
+                    polygon.vertices[b],
+                    polygon.vertices[c],
+                )
+            })
+            .sum();
+        assert_relative_eq!(covered, 1.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_self_intersecting() {
+        Polygon::new([
+            vector![0.0, 0.0],
+            vector![1.0, 1.0],
+            vector![1.0, 0.0],
+            vector![0.0, 1.0],
+        ]);
+    }
+}
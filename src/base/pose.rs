@@ -94,6 +94,57 @@ impl<T: RealField> From<Isometry3<T>> for Pose<T> {
     }
 }
 
+// MARK: Serde
+
+/// Serialized as `{ "position": [x, y, z], "orientation": [i, j, k, w] }`.
+#[cfg(feature = "serde")]
+impl<T: RealField + serde::Serialize> serde::Serialize for Pose<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("Pose", 2)?;
+        state.serialize_field("position", &self.isometry.translation.vector)?;
+        state.serialize_field("orientation", self.isometry.rotation.quaternion())?;
+        state.end()
+    }
+}
+
+/// Both fields default to the identity, and the orientation is normalized if needed.
+#[cfg(feature = "serde")]
+impl<'de, T: RealField + serde::Deserialize<'de>> serde::Deserialize<'de> for Pose<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use nalgebra::{Quaternion, Vector3};
+
+        #[derive(serde::Deserialize)]
+        #[serde(rename = "Pose", deny_unknown_fields)]
+        struct Repr<T: RealField> {
+            #[serde(default = "Vector3::zeros")]
+            position: Vector3<T>,
+            #[serde(default = "Quaternion::identity")]
+            orientation: Quaternion<T>,
+        }
+
+        let Repr {
+            position,
+            orientation,
+        } = Repr::deserialize(deserializer)?;
+        let norm: T = orientation.norm();
+        if !norm.is_finite() || norm <= T::default_epsilon() {
+            return Err(serde::de::Error::custom(
+                "Orientation must be a finite, non-zero quaternion.",
+            ));
+        }
+        // Keep unit quaternions bit-exact so that poses round-trip
+        let tolerance = T::default_epsilon() * nalgebra::convert::<f64, T>(4.0);
+        let orientation = if (norm - T::one()).abs() <= tolerance {
+            UnitQuaternion::new_unchecked(orientation)
+        } else {
+            UnitQuaternion::new_normalize(orientation)
+        };
+        Ok(Self::new(position, orientation))
+    }
+}
+
 crate::crate_utils::need_std!(
     // Optional or just empty
 );
@@ -8,9 +8,16 @@ use nalgebra::{Point3, RealField, Vector3};
 
 use crate::{
     base::{DynClone, Transform},
-    crate_utils::need_std,
+    crate_utils::need_alloc,
 };
 
+/// Number of points above which the default batch methods run in parallel.
+#[cfg(feature = "rayon")]
+pub(crate) const PARALLEL_THRESHOLD: usize = 1000;
+
+/// Points per stack buffer in the default chunked batch methods of [Source].
+pub(crate) const CHUNK: usize = 64;
+
 // MARK: Source
 
 #[enum_dispatch]
@@ -30,6 +37,8 @@ pub trait Source<T: RealField>: Transform<T> + Send + Sync + DynClone {
 
     /// Computes the magnetic field (B) at the given points in batch.
     ///
+    /// Allocates the output and fills it with [Source::compute_B_batch_into].
+    ///
     /// # Arguments
     ///
     /// - `points`: Slice of observer positions (m)
@@ -39,7 +48,271 @@ pub trait Source<T: RealField>: Transform<T> + Send + Sync + DynClone {
     /// - B-field vectors at each observer.
     #[allow(non_snake_case)]
     #[cfg(feature = "alloc")]
-    fn compute_B_batch(&self, points: &[Point3<T>]) -> alloc::vec::Vec<Vector3<T>>;
+    fn compute_B_batch(&self, points: &[Point3<T>]) -> alloc::vec::Vec<Vector3<T>> {
+        let mut out = alloc::vec![Vector3::zeros(); points.len()];
+        self.compute_B_batch_into(points, &mut out);
+        out
+    }
+
+    /// Computes the magnetic field (B) at the given points in batch, overwriting `out`.
+    ///
+    /// The default implementation calls [Source::compute_B] for each point, in parallel
+    /// for large batches if the `rayon` feature is enabled. Override this with a batch
+    /// field function where one exists.
+    ///
+    /// # Arguments
+    ///
+    /// - `points`: Slice of observer positions (m)
+    /// - `out`: Mutable slice to store the B-field vectors at each observer (T)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `out` and `points` differ in length.
+    #[allow(non_snake_case)]
+    fn compute_B_batch_into(&self, points: &[Point3<T>], out: &mut [Vector3<T>]) {
+        assert_eq!(
+            out.len(),
+            points.len(),
+            "Output slice length must match input vectors length."
+        );
+
+        let compute = |(o, p): (&mut Vector3<T>, &Point3<T>)| *o = self.compute_B(p.clone());
+
+        #[cfg(feature = "rayon")]
+        if points.len() > PARALLEL_THRESHOLD {
+            use rayon::prelude::*;
+
+            out.par_iter_mut().zip(points.par_iter()).for_each(compute);
+            return;
+        }
+
+        out.iter_mut().zip(points.iter()).for_each(compute);
+    }
+
+    /// Adds the magnetic field (B) at the given points to `out`.
+    ///
+    /// Used by collections to sum the fields of their children without allocating. The
+    /// default implementation computes the points in chunks into a stack buffer with
+    /// [Source::compute_B_batch_into], in parallel if the `rayon` feature is enabled.
+    ///
+    /// # Arguments
+    ///
+    /// - `points`: Slice of observer positions (m)
+    /// - `out`: Mutable slice to which the B-field vectors at each observer are added (T)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `out` and `points` differ in length.
+    #[allow(non_snake_case)]
+    fn add_B_batch_into(&self, points: &[Point3<T>], out: &mut [Vector3<T>]) {
+        assert_eq!(
+            out.len(),
+            points.len(),
+            "Output slice length must match input vectors length."
+        );
+
+        let add_chunk = |(out, points): (&mut [Vector3<T>], &[Point3<T>])| {
+            let mut buffer: [Vector3<T>; CHUNK] = core::array::from_fn(|_| Vector3::zeros());
+            let buffer = &mut buffer[..points.len()];
+            self.compute_B_batch_into(points, buffer);
+            out.iter_mut().zip(buffer.iter()).for_each(|(o, b)| *o += b);
+        };
+
+        #[cfg(feature = "rayon")]
+        if points.len() > PARALLEL_THRESHOLD {
+            use rayon::prelude::*;
+
+            out.par_chunks_mut(CHUNK)
+                .zip(points.par_chunks(CHUNK))
+                .for_each(add_chunk);
+            return;
+        }
+
+        out.chunks_mut(CHUNK)
+            .zip(points.chunks(CHUNK))
+            .for_each(add_chunk);
+    }
+
+    /// Computes the magnetic field (B) at points given as separate coordinate slices,
+    /// writing each component of B into its own slice.
+    ///
+    /// The points are gathered in chunks into a stack buffer for
+    /// [Source::compute_B_batch_into], in parallel if the `rayon` feature is enabled, so
+    /// nothing is allocated.
+    ///
+    /// # Arguments
+    ///
+    /// - `x`, `y`, `z`: Coordinates of the observer positions (m)
+    /// - `bx`, `by`, `bz`: Mutable slices to store the B-field components at each observer (T)
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slices differ in length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use magba::prelude::*;
+    /// let magnet = Dipole::default();
+    /// let (x, y, z) = ([0.0, 0.0], [0.0, 0.0], [0.02, 0.03]);
+    /// let (mut bx, mut by, mut bz) = ([0.0; 2], [0.0; 2], [0.0; 2]);
+    /// magnet.compute_B_batch_soa_into(&x, &y, &z, &mut bx, &mut by, &mut bz);
+    /// assert!(bz[0] > bz[1]);
+    /// ```
+    #[allow(non_snake_case)]
+    #[allow(clippy::too_many_arguments)]
+    fn compute_B_batch_soa_into(
+        &self,
+        x: &[T],
+        y: &[T],
+        z: &[T],
+        bx: &mut [T],
+        by: &mut [T],
+        bz: &mut [T],
+    ) {
+        assert!(
+            y.len() == x.len() && z.len() == x.len(),
+            "Lengths of input vectors must be equal."
+        );
+        assert!(
+            bx.len() == x.len() && by.len() == x.len() && bz.len() == x.len(),
+            "Output slice length must match input vectors length."
+        );
+
+        type Chunk<'a, T> = (
+            ((&'a mut [T], &'a mut [T]), &'a mut [T]),
+            ((&'a [T], &'a [T]), &'a [T]),
+        );
+        let soa_chunk = |(((bx, by), bz), ((x, y), z)): Chunk<T>| {
+            compute_chunk(
+                self,
+                x.len(),
+                |i| Point3::new(x[i].clone(), y[i].clone(), z[i].clone()),
+                |i, b| {
+                    bx[i] = b.x.clone();
+                    by[i] = b.y.clone();
+                    bz[i] = b.z.clone();
+                },
+            )
+        };
+
+        #[cfg(feature = "rayon")]
+        if x.len() > PARALLEL_THRESHOLD {
+            use rayon::prelude::*;
+
+            bx.par_chunks_mut(CHUNK)
+                .zip(by.par_chunks_mut(CHUNK))
+                .zip(bz.par_chunks_mut(CHUNK))
+                .zip(
+                    x.par_chunks(CHUNK)
+                        .zip(y.par_chunks(CHUNK))
+                        .zip(z.par_chunks(CHUNK)),
+                )
+                .for_each(soa_chunk);
+            return;
+        }
+
+        bx.chunks_mut(CHUNK)
+            .zip(by.chunks_mut(CHUNK))
+            .zip(bz.chunks_mut(CHUNK))
+            .zip(x.chunks(CHUNK).zip(y.chunks(CHUNK)).zip(z.chunks(CHUNK)))
+            .for_each(soa_chunk);
+    }
+
+    /// Computes the magnetic field (B) at points given as an interleaved
+    /// `[x0, y0, z0, x1, y1, z1, ...]` slice, writing B interleaved the same way.
+    ///
+    /// This is the memory layout of a `Matrix3xX`, a C array of `double[3]`, and a
+    /// C-contiguous NumPy array of shape `(n, 3)`. The points are gathered in chunks into a
+    /// stack buffer for [Source::compute_B_batch_into], in parallel if the `rayon` feature
+    /// is enabled, so nothing is allocated.
+    ///
+    /// # Arguments
+    ///
+    /// - `points`: Interleaved coordinates of the observer positions (m)
+    /// - `out`: Mutable slice to store the interleaved B-field vectors at each observer (T)
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `points` is not a multiple of 3 or differs from `out`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use magba::prelude::*;
+    /// let magnet = Dipole::default();
+    /// let points = [0.0, 0.0, 0.02, 0.0, 0.0, 0.03];
+    /// let mut out = [0.0; 6];
+    /// magnet.compute_B_batch_flat_into(&points, &mut out);
+    /// assert!(out[2] > out[5]);
+    /// ```
+    #[allow(non_snake_case)]
+    fn compute_B_batch_flat_into(&self, points: &[T], out: &mut [T]) {
+        assert!(
+            points.len().is_multiple_of(3),
+            "Length of interleaved points must be a multiple of 3."
+        );
+        assert_eq!(
+            out.len(),
+            points.len(),
+            "Output slice length must match input vectors length."
+        );
+
+        let flat_chunk = |(out, points): (&mut [T], &[T])| {
+            compute_chunk(
+                self,
+                points.len() / 3,
+                |i| Point3::from_slice(&points[3 * i..3 * i + 3]),
+                |i, b| out[3 * i..3 * i + 3].clone_from_slice(b.as_slice()),
+            )
+        };
+
+        #[cfg(feature = "rayon")]
+        if points.len() > 3 * PARALLEL_THRESHOLD {
+            use rayon::prelude::*;
+
+            out.par_chunks_mut(3 * CHUNK)
+                .zip(points.par_chunks(3 * CHUNK))
+                .for_each(flat_chunk);
+            return;
+        }
+
+        out.chunks_mut(3 * CHUNK)
+            .zip(points.chunks(3 * CHUNK))
+            .for_each(flat_chunk);
+    }
+
+    /// Computes the magnetic field (B) at the columns of `points`, returning B in the
+    /// columns of a matrix of the same shape.
+    ///
+    /// See [Source::compute_B_batch_flat_into], which writes into an existing matrix
+    /// through [as_mut_slice](nalgebra::Matrix::as_mut_slice).
+    ///
+    /// # Arguments
+    ///
+    /// - `points`: Observer positions as columns (m)
+    ///
+    /// # Returns
+    ///
+    /// - B-field vectors at each observer as columns.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use magba::prelude::*;
+    /// # use nalgebra::Matrix3xX;
+    /// let magnet = Dipole::default();
+    /// let points = Matrix3xX::from_column_slice(&[0.0, 0.0, 0.02, 0.0, 0.0, 0.03]);
+    /// let b = magnet.compute_B_matrix(&points);
+    /// assert_eq!(b.ncols(), 2);
+    /// ```
+    #[allow(non_snake_case)]
+    #[cfg(feature = "alloc")]
+    fn compute_B_matrix(&self, points: &nalgebra::Matrix3xX<T>) -> nalgebra::Matrix3xX<T> {
+        let mut out = nalgebra::Matrix3xX::zeros(points.ncols());
+        self.compute_B_batch_flat_into(points.as_slice(), out.as_mut_slice());
+        out
+    }
 
     /// A default formatter that behaves like Display.
     /// Last argument is the indentation, which is for SourceAssembly support.
@@ -47,9 +320,19 @@ pub trait Source<T: RealField>: Transform<T> + Send + Sync + DynClone {
     fn format(&self, f: &mut core::fmt::Formatter<'_>, _: &str) -> core::fmt::Result {
         write!(f, "Source at {}", self.pose())
     }
+
+    /// The registered name and the data of a custom source for serialization.
+    ///
+    /// Returns `None` by default, which makes a custom source fail to serialize. Override
+    /// this to return `Some((name, self))` and register the name with
+    /// [register_source](crate::collections::register_source) so that a
+    /// [SourceComponent::Custom](crate::collections::SourceComponent::Custom) round-trips.
+    #[cfg(feature = "serde")]
+    fn serialize_custom(&self) -> Option<(&'static str, &dyn crate::base::ErasedSerialize)> {
+        None
+    }
 }
 
-#[cfg(feature = "std")]
 impl<T: RealField> core::fmt::Display for dyn Source<T> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         // Delegate to the trait method
@@ -57,8 +340,27 @@ impl<T: RealField> core::fmt::Display for dyn Source<T> {
     }
 }
 
+// MARK: Chunks
+
+/// Computes B at up to [CHUNK] points with [Source::compute_B_batch_into] through stack
+/// buffers, reading the `i`-th point with `point` and writing its field with `store`.
+#[allow(non_snake_case)]
+fn compute_chunk<T: RealField, S: Source<T> + ?Sized>(
+    source: &S,
+    n: usize,
+    point: impl Fn(usize) -> Point3<T>,
+    mut store: impl FnMut(usize, &Vector3<T>),
+) {
+    let points: [Point3<T>; CHUNK] =
+        core::array::from_fn(|i| if i < n { point(i) } else { Point3::origin() });
+    let mut out: [Vector3<T>; CHUNK] = core::array::from_fn(|_| Vector3::zeros());
+    source.compute_B_batch_into(&points[..n], &mut out[..n]);
+    out[..n].iter().enumerate().for_each(|(i, b)| store(i, b));
+}
+
 // MARK: Box<dyn Source>
-need_std!(
+need_alloc!(
+    use alloc::{boxed::Box, vec::Vec};
     use core::fmt::Display;
 
     use dyn_clone::clone_trait_object;
@@ -90,7 +392,103 @@ need_std!(
                 fn compute_B(&self, point: Point3<T>) -> Vector3<T>;
                 #[cfg(feature = "alloc")]
                 fn compute_B_batch(&self, points: &[Point3<T>]) -> Vec<Vector3<T>>;
+                fn compute_B_batch_into(&self, points: &[Point3<T>], out: &mut [Vector3<T>]);
+                fn add_B_batch_into(&self, points: &[Point3<T>], out: &mut [Vector3<T>]);
+                #[allow(clippy::too_many_arguments)]
+                fn compute_B_batch_soa_into(&self, x: &[T], y: &[T], z: &[T], bx: &mut [T], by: &mut [T], bz: &mut [T]);
+                fn compute_B_batch_flat_into(&self, points: &[T], out: &mut [T]);
+                #[cfg(feature = "alloc")]
+                fn compute_B_matrix(&self, points: &nalgebra::Matrix3xX<T>) -> nalgebra::Matrix3xX<T>;
+                #[cfg(feature = "serde")]
+                fn serialize_custom(&self) -> Option<(&'static str, &dyn crate::base::ErasedSerialize)>;
             }
         );
     }
 );
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::{
+        collections::SourceAssembly,
+        magnets::{CuboidMagnet, Dipole},
+    };
+    use nalgebra::Matrix3xX;
+
+    fn sources() -> SourceAssembly {
+        let cuboid = CuboidMagnet::default()
+            .with_polarization([0.1, 0.2, 0.3])
+            .with_dimensions([0.02, 0.02, 0.03]);
+        let dipole: Box<dyn Source<f64>> =
+            Box::new(Dipole::default().with_position([0.01, 0.0, 0.0]));
+        SourceAssembly::from([cuboid]).with(dipole)
+    }
+
+    #[test]
+    fn test_flat_and_soa() {
+        let sources = sources();
+        // Past the parallel threshold of the flat layout, ending in a partial chunk
+        let points = Matrix3xX::from_fn(1100, |i, j| [0.03, 1e-5 * j as f64, -0.04][i]);
+        let expected = sources.compute_B_batch(
+            &points
+                .column_iter()
+                .map(|p| Point3::from(p.into_owned()))
+                .collect::<Vec<_>>(),
+        );
+        let expected = Matrix3xX::from_columns(&expected);
+
+        // Interleaved coordinates are the column-major storage of a `Matrix3xX`
+        let mut out = Matrix3xX::zeros(points.ncols());
+        sources.compute_B_batch_flat_into(points.as_slice(), out.as_mut_slice());
+        assert_eq!(out, expected);
+        assert_eq!(sources.compute_B_matrix(&points), expected);
+
+        // Separate coordinates are the rows
+        let [x, y, z] = [0, 1, 2].map(|i| points.row(i).iter().copied().collect::<Vec<_>>());
+        let [mut bx, mut by, mut bz] = [0, 1, 2].map(|_| vec![0.0; points.ncols()]);
+        sources.compute_B_batch_soa_into(&x, &y, &z, &mut bx, &mut by, &mut bz);
+        for (i, b) in [bx, by, bz].iter().enumerate() {
+            assert!(expected.row(i).iter().eq(b.iter()));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Output slice length must match input vectors length.")]
+    fn test_flat_output_length() {
+        sources().compute_B_batch_flat_into(&[0.0; 6], &mut [0.0; 3]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Output slice length must match input vectors length.")]
+    fn test_soa_output_length() {
+        let input = [0.0; 2];
+        sources().compute_B_batch_soa_into(
+            &input,
+            &input,
+            &input,
+            &mut [0.0; 2],
+            &mut [0.0; 1],
+            &mut [0.0; 2],
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Length of interleaved points must be a multiple of 3.")]
+    fn test_flat_length() {
+        sources().compute_B_batch_flat_into(&[0.0; 4], &mut [0.0; 4]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Lengths of input vectors must be equal.")]
+    fn test_soa_length() {
+        let mut out = [0.0; 2];
+        sources().compute_B_batch_soa_into(
+            &[0.0; 2],
+            &[0.0; 1],
+            &[0.0; 2],
+            &mut out.clone(),
+            &mut out.clone(),
+            &mut out,
+        );
+    }
+}
@@ -104,7 +104,6 @@ macro_rules! impl_transform {
 }
 pub(crate) use impl_transform;
 
-#[cfg(feature = "std")]
 macro_rules! impl_group_transform {
     ($name:ident < $( $args:ty ),* > where $( $bounds:tt )* ) => {
         impl< $( $bounds )* > $name< $( $args ),*> {
@@ -163,5 +162,4 @@ macro_rules! impl_group_transform {
         }
     };
 }
-#[cfg(feature = "std")]
 pub(crate) use impl_group_transform;
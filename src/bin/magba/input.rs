@@ -0,0 +1,185 @@
+/*
+ * Magba is licensed under The 3-Clause BSD, see LICENSE.
+ * Copyright 2025 Sira Pornsiriprasert <code@psira.me>
+ */
+
+//! Parsing of command-line values and point files.
+
+use std::io::{self, BufRead, Error, ErrorKind};
+
+use magba::analysis::{FieldGrid, GridAxis, GridLayout};
+use nalgebra::Point3;
+
+/// Parses `x,y,z` into three numbers.
+pub fn parse_vector(text: &str) -> Result<[f64; 3], String> {
+    let values = text
+        .split(',')
+        .map(|v| v.trim().parse::<f64>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("{e} in `{text}`"))?;
+    values
+        .try_into()
+        .map_err(|_| format!("Expected three comma-separated numbers, got `{text}`."))
+}
+
+/// Parses a grid such as `x=-10:10:21,y=0,z=-5:5:11` in the given layout.
+///
+/// Each axis is `start:stop:count` or a single coordinate. Omitted axes are fixed at
+/// zero. Coordinates are multiplied by `length` for distances and by `angle` for angles.
+pub fn parse_grid(
+    text: &str,
+    layout: GridLayout,
+    length: f64,
+    angle: f64,
+) -> Result<FieldGrid, String> {
+    let names = axis_names(layout);
+    let mut axes = [None; 3];
+    for item in text.split(',') {
+        let (name, spec) = item
+            .split_once('=')
+            .ok_or_else(|| format!("Expected `axis=start:stop:count`, got `{item}`."))?;
+        let name = name.trim();
+        let index = names.iter().position(|n| *n == name).ok_or_else(|| {
+            format!(
+                "Unknown axis `{name}`, expected one of {}.",
+                names.join(", ")
+            )
+        })?;
+        if axes[index].is_some() {
+            return Err(format!("Axis `{name}` is given twice."));
+        }
+        let scale = if is_angle(layout, index) {
+            angle
+        } else {
+            length
+        };
+        axes[index] = Some(parse_axis(spec, scale)?);
+    }
+    let axes = axes.map(|axis| axis.unwrap_or(GridAxis::fixed(0.0)));
+    Ok(FieldGrid::new(layout, axes))
+}
+
+fn axis_names(layout: GridLayout) -> [&'static str; 3] {
+    match layout {
+        GridLayout::Cartesian => ["x", "y", "z"],
+        GridLayout::Cylindrical => ["r", "phi", "z"],
+        GridLayout::Spherical => ["r", "theta", "phi"],
+    }
+}
+
+fn is_angle(layout: GridLayout, index: usize) -> bool {
+    match layout {
+        GridLayout::Cartesian => false,
+        GridLayout::Cylindrical => index == 1,
+        GridLayout::Spherical => index > 0,
+    }
+}
+
+fn parse_axis(spec: &str, scale: f64) -> Result<GridAxis<f64>, String> {
+    let number = |v: &str| {
+        v.trim()
+            .parse::<f64>()
+            .map_err(|e| format!("{e} in `{spec}`"))
+    };
+    match spec.split(':').collect::<Vec<_>>()[..] {
+        [value] => Ok(GridAxis::fixed(number(value)? * scale)),
+        [start, stop, count] => {
+            let count = count
+                .trim()
+                .parse::<usize>()
+                .ok()
+                .filter(|&n| n > 0)
+                .ok_or_else(|| format!("Count must be a positive integer in `{spec}`."))?;
+            Ok(GridAxis::new(
+                number(start)? * scale,
+                number(stop)? * scale,
+                count,
+            ))
+        }
+        _ => Err(format!(
+            "Expected `start:stop:count` or a single value, got `{spec}`."
+        )),
+    }
+}
+
+/// Reads points with three coordinates per line, separated by commas or whitespace.
+///
+/// Blank lines and lines starting with `#` are skipped, as is a non-numeric first line
+/// (a header). Coordinates are multiplied by `length`.
+pub fn read_points(reader: impl BufRead, length: f64) -> io::Result<Vec<Point3<f64>>> {
+    let mut points = Vec::new();
+    let mut first = true;
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let values: Result<Vec<f64>, _> = line
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|v| !v.is_empty())
+            .map(str::parse)
+            .collect();
+        let header = first;
+        first = false;
+        match values {
+            Ok(values) if values.len() == 3 => {
+                points.push(Point3::new(values[0], values[1], values[2]) * length);
+            }
+            Err(_) if header => continue,
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("line {}: Expected three coordinates.", i + 1),
+                ));
+            }
+        }
+    }
+    Ok(points)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use nalgebra::point;
+
+    #[test]
+    fn test_parse_vector() {
+        assert_eq!(parse_vector("1, -2.5,3e-3"), Ok([1.0, -2.5, 3e-3]));
+        assert!(parse_vector("1,2").is_err());
+        assert!(parse_vector("1,2,a").is_err());
+    }
+
+    #[test]
+    fn test_parse_grid() {
+        let grid = parse_grid("x=-10:10:21,z=5", GridLayout::Cartesian, 1e-3, 1.0).unwrap();
+        assert_eq!(grid.shape(), [21, 1, 1]);
+        assert_relative_eq!(grid.point([0, 0, 0]), point![-0.01, 0.0, 0.005]);
+
+        let grid = parse_grid("r=2,phi=0:180:3", GridLayout::Cylindrical, 1.0, 0.5).unwrap();
+        assert_eq!(grid.axes()[1].stop(), 90.0);
+        assert_eq!(grid.axes()[0].start(), 2.0);
+
+        for (spec, error) in [
+            ("x=0:1", "Expected `start:stop:count`"),
+            ("x=0:1:0", "Count must be a positive integer"),
+            ("w=1", "Unknown axis `w`"),
+            ("x=1,x=2", "Axis `x` is given twice."),
+            ("x", "Expected `axis=start:stop:count`"),
+        ] {
+            let result = parse_grid(spec, GridLayout::Cartesian, 1.0, 1.0);
+            assert!(result.unwrap_err().starts_with(error), "{spec}");
+        }
+    }
+
+    #[test]
+    fn test_read_points() {
+        let text = "x,y,z\n# comment\n1,2,3\n\n4 5 6\n";
+        let points = read_points(text.as_bytes(), 10.0).unwrap();
+        assert_eq!(points, [point![10.0, 20.0, 30.0], point![40.0, 50.0, 60.0]]);
+
+        let error = read_points("1,2,3\n4,5\n".as_bytes(), 1.0).unwrap_err();
+        assert_eq!(error.to_string(), "line 2: Expected three coordinates.");
+    }
+}
@@ -0,0 +1,685 @@
+/*
+ * Magba is licensed under The 3-Clause BSD, see LICENSE.
+ * Copyright 2025 Sira Pornsiriprasert <code@psira.me>
+ */
+
+//! Command-line tool for evaluating [scene files](magba::scene) without writing Rust.
+//!
+//! `magba field` computes B or H at a list of points or on a grid, and `magba read`
+//! reads the sensors of the scene, optionally while sweeping the sources along a
+//! translation and rotation. Build with `cargo install magba --features cli`.
+
+mod input;
+mod output;
+
+use std::{
+    fs::File,
+    io::{self, BufReader, BufWriter, Error, ErrorKind, Write},
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use clap::{ArgGroup, Args, Parser, Subcommand, ValueEnum};
+use magba::{
+    analysis::GridLayout,
+    base::Pose,
+    conversion::B_to_H,
+    export::vtk::{self, VtkFormat},
+    prelude::*,
+    scene::{SceneFile, Units},
+};
+use nalgebra::{Point3, Unit, UnitQuaternion, Vector3};
+
+use crate::output::{Progress, Table};
+
+/// Smallest number of points computed between progress updates.
+const MIN_CHUNK: usize = 4096;
+
+#[derive(Debug, Parser)]
+#[command(name = "magba", version)]
+#[command(about = "Evaluate the magnetic field and sensor readings of a scene file")]
+#[command(
+    long_about = "Evaluate the magnetic field and sensor readings of a scene file.
+
+Coordinates, distances, and angles on the command line are in the units of the scene file. \
+The output is in SI units: positions in m, B in T, H in A/m, and angles in rad."
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    /// Number of worker threads [default: all cores]
+    #[arg(long, global = true, value_parser = clap::value_parser!(u16).range(1..))]
+    threads: Option<u16>,
+    /// Do not print progress to stderr
+    #[arg(long, short, global = true)]
+    quiet: bool,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Compute the field of the sources at points or on a grid
+    ///
+    /// CSV and NPY output has the columns x, y, z followed by Bx, By, Bz and/or Hx, Hy,
+    /// Hz. VTK output has B, B/μ0, and |B| at the points.
+    Field(FieldArgs),
+    /// Read the sensors, optionally over a sweep of source poses
+    ///
+    /// Each row holds a step of the sweep: the step index, the translation dx, dy, dz and
+    /// the rotation angle so far (if swept), and the reading of each sensor. Vector
+    /// readings take three columns. Steps are evaluated in order, so latches keep their
+    /// state between steps.
+    Read(ReadArgs),
+}
+
+#[derive(Debug, Args)]
+#[command(group(ArgGroup::new("observers").args(["points", "grid"]).required(true)))]
+struct FieldArgs {
+    /// Scene file (.toml or .json)
+    scene: PathBuf,
+    /// File with x, y, z coordinates per line, or `-` for stdin
+    #[arg(long)]
+    points: Option<PathBuf>,
+    /// Grid of points as `axis=start:stop:count` or `axis=value`, e.g. `x=-10:10:21,y=0,z=2`
+    ///
+    /// The axes are x, y, z for a Cartesian grid, r, phi, z for a cylindrical grid, and r,
+    /// theta, phi for a spherical grid. Omitted axes are zero.
+    #[arg(long, allow_hyphen_values = true)]
+    grid: Option<String>,
+    /// Coordinate system of the grid
+    #[arg(long, value_enum, default_value_t = Layout::Cartesian, requires = "grid")]
+    layout: Layout,
+    /// Field to write as CSV or NPY
+    #[arg(long, value_enum, default_value_t = Quantity::B)]
+    quantity: Quantity,
+    #[command(flatten)]
+    output: OutputArgs,
+}
+
+#[derive(Debug, Args)]
+#[command(group(ArgGroup::new("motion").args(["translate", "rotate"]).multiple(true)))]
+struct ReadArgs {
+    /// Scene file (.toml or .json)
+    scene: PathBuf,
+    /// Translation over the whole sweep, as `x,y,z`
+    #[arg(long, allow_hyphen_values = true, value_parser = input::parse_vector)]
+    translate: Option<[f64; 3]>,
+    /// Rotation axis of the sweep, as `x,y,z`
+    #[arg(long, allow_hyphen_values = true, value_parser = input::parse_vector, requires = "angle")]
+    rotate: Option<[f64; 3]>,
+    /// Rotation angle over the whole sweep
+    #[arg(long, allow_hyphen_values = true, requires = "rotate")]
+    angle: Option<f64>,
+    /// Center of rotation, as `x,y,z` [default: position of the moved source]
+    #[arg(long, allow_hyphen_values = true, value_parser = input::parse_vector, requires = "rotate")]
+    anchor: Option<[f64; 3]>,
+    /// Number of sweep steps, both ends included
+    #[arg(long, default_value_t = 101, requires = "motion", value_parser = clap::value_parser!(u32).range(1..))]
+    steps: u32,
+    /// Index of the top-level source to move [default: all sources]
+    #[arg(long, requires = "motion")]
+    source: Option<usize>,
+    #[command(flatten)]
+    output: OutputArgs,
+}
+
+#[derive(Debug, Args)]
+struct OutputArgs {
+    /// Output file [default: stdout]
+    #[arg(long, short)]
+    output: Option<PathBuf>,
+    /// Output format [default: from the output extension, CSV for stdout]
+    #[arg(long, value_enum)]
+    format: Option<Format>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    Csv,
+    Npy,
+    /// Legacy VTK
+    Vtk,
+    /// XML VTK (.vtp for points, .vts for grids)
+    VtkXml,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Layout {
+    Cartesian,
+    Cylindrical,
+    Spherical,
+}
+
+impl From<Layout> for GridLayout {
+    fn from(layout: Layout) -> Self {
+        match layout {
+            Layout::Cartesian => GridLayout::Cartesian,
+            Layout::Cylindrical => GridLayout::Cylindrical,
+            Layout::Spherical => GridLayout::Spherical,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Quantity {
+    B,
+    H,
+    Both,
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(cli: Cli) -> io::Result<()> {
+    if let Some(threads) = cli.threads {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(threads.into())
+            .build_global()
+            .map_err(Error::other)?;
+    }
+    match cli.command {
+        Command::Field(args) => field(args, !cli.quiet),
+        Command::Read(args) => read(args, !cli.quiet),
+    }
+}
+
+// MARK: Field
+
+fn field(args: FieldArgs, progress: bool) -> io::Result<()> {
+    let format = args.output.format()?;
+    let scene = load_scene(&args.scene)?;
+    let Units { length, angle, .. } = *scene.units();
+    let (length, angle) = (length.in_meters(), angle.in_radians());
+
+    let (points, shape) = match (&args.points, &args.grid) {
+        (Some(path), _) => (read_points(path, length)?, None),
+        (None, Some(spec)) => {
+            let grid = input::parse_grid(spec, args.layout.into(), length, angle)
+                .map_err(|e| Error::new(ErrorKind::InvalidInput, e))?;
+            (grid.points(), Some(grid.shape()))
+        }
+        (None, None) => unreachable!("clap requires --points or --grid"),
+    };
+
+    let (sources, _) = scene.into_parts();
+    let b = compute_field(&sources, &points, progress);
+
+    match format {
+        Format::Csv | Format::Npy => {
+            let mut columns = vec!["x", "y", "z"];
+            if args.quantity != Quantity::H {
+                columns.extend(["Bx", "By", "Bz"]);
+            }
+            if args.quantity != Quantity::B {
+                columns.extend(["Hx", "Hy", "Hz"]);
+            }
+            let mut table = Table::new(columns.into_iter().map(String::from).collect());
+            for (point, b) in points.iter().zip(&b) {
+                let mut row = point.coords.as_slice().to_vec();
+                if args.quantity != Quantity::H {
+                    row.extend(b.iter());
+                }
+                if args.quantity != Quantity::B {
+                    row.extend(B_to_H(*b).iter());
+                }
+                table.push(&row);
+            }
+            args.output.write_table(&table, format)
+        }
+        Format::Vtk | Format::VtkXml => {
+            let format = if format == Format::Vtk {
+                VtkFormat::Legacy
+            } else {
+                VtkFormat::Xml
+            };
+            args.output.write(|writer| match shape {
+                Some(shape) => vtk::write_structured_grid(writer, shape, &points, &b, format),
+                None => vtk::write_poly_data(writer, &points, &b, format),
+            })
+        }
+    }
+}
+
+/// Computes the B-field in chunks with [Source::compute_B_batch_into], reporting progress.
+fn compute_field(
+    sources: &SourceAssembly,
+    points: &[Point3<f64>],
+    progress: bool,
+) -> Vec<Vector3<f64>> {
+    let mut progress = Progress::new("Computing field", points.len(), progress);
+    let mut b = vec![Vector3::zeros(); points.len()];
+    let chunk_size = (points.len() / 100).max(MIN_CHUNK);
+    for (chunk, b) in points.chunks(chunk_size).zip(b.chunks_mut(chunk_size)) {
+        sources.compute_B_batch_into(chunk, b);
+        progress.advance(chunk.len());
+    }
+    progress.finish();
+    b
+}
+
+// MARK: Read
+
+fn read(args: ReadArgs, progress: bool) -> io::Result<()> {
+    let format = args.output.format()?;
+    if matches!(format, Format::Vtk | Format::VtkXml) {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "Sensor readings can only be written as CSV or NPY.",
+        ));
+    }
+    let scene = load_scene(&args.scene)?;
+    let units = *scene.units();
+    let (sources, sensors) = scene.into_parts();
+    if sensors.iter().next().is_none() {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "Scene file has no sensors.",
+        ));
+    }
+    let sweep = Sweep::new(&args, &units, &sources)?;
+
+    let mut progress = Progress::new("Reading sensors", sweep.steps, progress);
+    let mut table: Option<Table> = None;
+    for step in 0..sweep.steps {
+        let readings = sensors.read_all(&sweep.apply(&sources, step));
+        let table = table.get_or_insert_with(|| Table::new(sweep.columns(&readings)));
+        let mut row = sweep.row(step);
+        for reading in readings {
+            match reading {
+                SensorOutput::Scalar(value) => row.push(value),
+                SensorOutput::Vector(value) => row.extend(value.iter()),
+                SensorOutput::Digital(value) => row.push(value as f64),
+            }
+        }
+        table.push(&row);
+        progress.advance(1);
+    }
+    progress.finish();
+
+    let table = table.expect("sweep has at least one step");
+    args.output.write_table(&table, format)
+}
+
+/// Poses of the moved sources over a sweep.
+///
+/// At step `i` of `n`, the sources are rotated by the fraction `i / (n - 1)` of the
+/// angle about the anchor, then translated by the same fraction of the translation.
+#[derive(Debug)]
+struct Sweep {
+    steps: usize,
+    source: Option<usize>,
+    start: Pose<f64>,
+    translation: Option<Vector3<f64>>,
+    rotation: Option<(Unit<Vector3<f64>>, f64)>,
+    anchor: Point3<f64>,
+}
+
+impl Sweep {
+    fn new(args: &ReadArgs, units: &Units, sources: &SourceAssembly) -> io::Result<Self> {
+        let length = units.length.in_meters();
+        let invalid = |msg: String| Error::new(ErrorKind::InvalidInput, msg);
+
+        let start = match args.source {
+            None => *sources.pose(),
+            Some(i) => {
+                let count = sources.iter().count();
+                if i >= count {
+                    return Err(invalid(format!(
+                        "Source index {i} is out of range for {count} top-level sources."
+                    )));
+                }
+                *sources[i].pose()
+            }
+        };
+        let rotation = match (args.rotate, args.angle) {
+            (Some(axis), Some(angle)) => {
+                let axis = Unit::try_new(Vector3::from(axis), 0.0)
+                    .ok_or_else(|| invalid("Rotation axis must be nonzero.".into()))?;
+                Some((axis, angle * units.angle.in_radians()))
+            }
+            _ => None,
+        };
+        let translation = args.translate.map(|d| Vector3::from(d) * length);
+        let moving = translation.is_some() || rotation.is_some();
+
+        Ok(Self {
+            steps: if moving { args.steps as usize } else { 1 },
+            source: args.source,
+            start,
+            translation,
+            rotation,
+            anchor: args
+                .anchor
+                .map_or(start.position(), |a| Point3::from(a) * length),
+        })
+    }
+
+    fn fraction(&self, step: usize) -> f64 {
+        if self.steps == 1 {
+            0.0
+        } else {
+            step as f64 / (self.steps - 1) as f64
+        }
+    }
+
+    /// Copy of `sources` with the moved sources at the pose of `step`.
+    fn apply(&self, sources: &SourceAssembly, step: usize) -> SourceAssembly {
+        let t = self.fraction(step);
+        let mut pose = self.start;
+        if let Some((axis, angle)) = self.rotation {
+            pose.rotate_anchor(
+                UnitQuaternion::from_axis_angle(&axis, t * angle),
+                self.anchor,
+            );
+        }
+        if let Some(translation) = self.translation {
+            pose.translate(translation * t);
+        }
+
+        let mut moved = sources.clone();
+        match self.source {
+            None => moved.set_pose(pose),
+            Some(i) => match &mut moved[i] {
+                SourceComponent::Assembly(assembly) => assembly.set_pose(pose),
+                component => component.set_pose(pose),
+            },
+        }
+        moved
+    }
+
+    fn columns(&self, readings: &[SensorOutput<f64>]) -> Vec<String> {
+        let mut columns = vec!["step".to_string()];
+        if self.translation.is_some() {
+            columns.extend(["dx", "dy", "dz"].map(String::from));
+        }
+        if self.rotation.is_some() {
+            columns.push("angle".into());
+        }
+        for (i, reading) in readings.iter().enumerate() {
+            match reading {
+                SensorOutput::Vector(_) => {
+                    columns.extend(["x", "y", "z"].map(|c| format!("sensor{i}_{c}")))
+                }
+                _ => columns.push(format!("sensor{i}")),
+            }
+        }
+        columns
+    }
+
+    fn row(&self, step: usize) -> Vec<f64> {
+        let t = self.fraction(step);
+        let mut row = vec![step as f64];
+        if let Some(translation) = self.translation {
+            row.extend((translation * t).iter());
+        }
+        if let Some((_, angle)) = self.rotation {
+            row.push(angle * t);
+        }
+        row
+    }
+}
+
+// MARK: Files
+
+fn load_scene(path: &Path) -> io::Result<SceneFile> {
+    SceneFile::load(path).map_err(|e| with_path(path, e))
+}
+
+fn read_points(path: &Path, length: f64) -> io::Result<Vec<Point3<f64>>> {
+    if path == Path::new("-") {
+        return input::read_points(io::stdin().lock(), length);
+    }
+    let file = File::open(path).map_err(|e| with_path(path, e))?;
+    input::read_points(BufReader::new(file), length).map_err(|e| with_path(path, e))
+}
+
+fn with_path(path: &Path, error: Error) -> Error {
+    Error::new(error.kind(), format!("{}: {error}", path.display()))
+}
+
+impl OutputArgs {
+    /// The chosen format, or the one matching the output extension.
+    fn format(&self) -> io::Result<Format> {
+        if let Some(format) = self.format {
+            return Ok(format);
+        }
+        let Some(path) = &self.output else {
+            return Ok(Format::Csv);
+        };
+        let extension = path.extension().and_then(|e| e.to_str());
+        match extension.map(str::to_ascii_lowercase).as_deref() {
+            Some("csv") => Ok(Format::Csv),
+            Some("npy") => Ok(Format::Npy),
+            Some("vtk") => Ok(Format::Vtk),
+            Some("vtp" | "vts") => Ok(Format::VtkXml),
+            _ => Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Cannot infer the format from the output extension, use --format.",
+            )),
+        }
+    }
+
+    fn write(&self, write: impl FnOnce(&mut dyn Write) -> io::Result<()>) -> io::Result<()> {
+        match &self.output {
+            Some(path) => {
+                let file = File::create(path).map_err(|e| with_path(path, e))?;
+                let mut writer = BufWriter::new(file);
+                write(&mut writer)?;
+                writer.flush()
+            }
+            None => {
+                let mut writer = BufWriter::new(io::stdout().lock());
+                write(&mut writer)?;
+                writer.flush()
+            }
+        }
+    }
+
+    fn write_table(&self, table: &Table, format: Format) -> io::Result<()> {
+        self.write(|writer| match format {
+            Format::Npy => table.write_npy(writer),
+            _ => table.write_csv(writer),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use std::fs;
+
+    const SCENE: &str = r#"
+[units]
+length = "mm"
+angle = "deg"
+
+[[sources]]
+type = "CylinderMagnet"
+polarization = [0, 0, 1]
+diameter = 4
+height = 2
+
+[[sensors]]
+type = "HallSwitch"
+position = [0, 0, 3]
+b_op = 0.01
+
+[[sensors]]
+type = "LinearHallSensor"
+position = [0, 0, 3]
+axis = [1, 0, 0]
+sensitivity = 0.05
+supply_voltage = 5
+"#;
+
+    fn setup(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("magba-cli-{name}-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("scene.toml"), SCENE).unwrap();
+        dir
+    }
+
+    fn run_args(args: &[&str]) -> io::Result<()> {
+        let cli = Cli::try_parse_from(["magba", "--quiet"].iter().chain(args)).unwrap();
+        run(cli)
+    }
+
+    fn read_csv(path: &Path) -> Vec<Vec<String>> {
+        fs::read_to_string(path)
+            .unwrap()
+            .lines()
+            .map(|line| line.split(',').map(String::from).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_field() {
+        let dir = setup("field");
+        let scene = dir.join("scene.toml");
+        let out = dir.join("field.csv");
+        fs::write(dir.join("points.csv"), "x,y,z\n0,0,3\n1,0,3\n").unwrap();
+        run_args(&[
+            "field",
+            scene.to_str().unwrap(),
+            "--points",
+            dir.join("points.csv").to_str().unwrap(),
+            "--quantity",
+            "both",
+            "-o",
+            out.to_str().unwrap(),
+        ])
+        .unwrap();
+
+        let rows = read_csv(&out);
+        assert_eq!(rows[0].join(","), "x,y,z,Bx,By,Bz,Hx,Hy,Hz");
+        assert_eq!(rows.len(), 3);
+        let magnet = CylinderMagnet::new(
+            [0.0; 3],
+            UnitQuaternion::identity(),
+            [0.0, 0.0, 1.0],
+            4e-3,
+            2e-3,
+        );
+        let values: Vec<f64> = rows[2].iter().map(|v| v.parse().unwrap()).collect();
+        let b = magnet.compute_B([1e-3, 0.0, 3e-3].into());
+        assert_relative_eq!(values[0], 1e-3);
+        assert_relative_eq!(Vector3::new(values[3], values[4], values[5]), b);
+        assert_relative_eq!(Vector3::new(values[6], values[7], values[8]), B_to_H(b));
+
+        let out = dir.join("field.vts");
+        let grid = "x=-3:3:5,y=-1:1:3,z=3";
+        run_args(&[
+            "field",
+            scene.to_str().unwrap(),
+            "--grid",
+            grid,
+            "-o",
+            out.to_str().unwrap(),
+        ])
+        .unwrap();
+        let xml = fs::read_to_string(&out).unwrap();
+        assert!(xml.contains(r#"<StructuredGrid WholeExtent="0 4 0 2 0 0""#));
+
+        let out = dir.join("field.npy");
+        run_args(&[
+            "field",
+            scene.to_str().unwrap(),
+            "--grid",
+            grid,
+            "-o",
+            out.to_str().unwrap(),
+        ])
+        .unwrap();
+        assert_eq!(fs::metadata(&out).unwrap().len(), 128 + 15 * 6 * 8);
+
+        let error = run_args(&[
+            "field",
+            scene.to_str().unwrap(),
+            "--grid",
+            "x=0:1",
+            "-o",
+            "f.csv",
+        ]);
+        assert!(
+            error
+                .unwrap_err()
+                .to_string()
+                .starts_with("Expected `start:stop:count`")
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_read_sweep() {
+        let dir = setup("read");
+        let scene = dir.join("scene.toml");
+        let out = dir.join("sweep.csv");
+        run_args(&[
+            "read",
+            scene.to_str().unwrap(),
+            "--translate",
+            "-10,0,0",
+            "--rotate",
+            "0,1,0",
+            "--angle",
+            "90",
+            "--steps",
+            "5",
+            "-o",
+            out.to_str().unwrap(),
+        ])
+        .unwrap();
+
+        let rows = read_csv(&out);
+        assert_eq!(rows[0].join(","), "step,dx,dy,dz,angle,sensor0,sensor1");
+        assert_eq!(rows.len(), 6);
+        assert_eq!(rows[3][0], "2");
+        assert_relative_eq!(rows[3][1].parse::<f64>().unwrap(), -0.005);
+        assert_relative_eq!(
+            rows[5][4].parse::<f64>().unwrap(),
+            core::f64::consts::FRAC_PI_2
+        );
+        // Switch is on above the magnet and off once it moved away
+        assert_eq!(rows[1][5], "1");
+        assert_eq!(rows[5][5], "0");
+
+        let out = dir.join("read.csv");
+        run_args(&["read", scene.to_str().unwrap(), "-o", out.to_str().unwrap()]).unwrap();
+        let rows = read_csv(&out);
+        assert_eq!(rows[0].join(","), "step,sensor0,sensor1");
+        assert_eq!(rows.len(), 2);
+
+        let error = run_args(&[
+            "read",
+            scene.to_str().unwrap(),
+            "--translate",
+            "1,0,0",
+            "--source",
+            "3",
+        ]);
+        assert_eq!(
+            error.unwrap_err().to_string(),
+            "Source index 3 is out of range for 1 top-level sources."
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_args() {
+        assert!(Cli::try_parse_from(["magba", "field", "s.toml"]).is_err());
+        assert!(
+            Cli::try_parse_from(["magba", "field", "s.toml", "--points", "p", "--grid", "x=0"])
+                .is_err()
+        );
+        assert!(Cli::try_parse_from(["magba", "read", "s.toml", "--steps", "3"]).is_err());
+        assert!(Cli::try_parse_from(["magba", "read", "s.toml", "--rotate", "0,0,1"]).is_err());
+        assert!(Cli::try_parse_from(["magba", "--threads", "0", "read", "s.toml"]).is_err());
+    }
+}
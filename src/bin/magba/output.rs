@@ -0,0 +1,191 @@
+/*
+ * Magba is licensed under The 3-Clause BSD, see LICENSE.
+ * Copyright 2025 Sira Pornsiriprasert <code@psira.me>
+ */
+
+//! Tabular output and progress reporting.
+
+use std::{
+    io::{self, IsTerminal, Write},
+    time::Instant,
+};
+
+/// Rows of numbers under named columns.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Table {
+    columns: Vec<String>,
+    values: Vec<f64>,
+}
+
+impl Table {
+    pub fn new(columns: Vec<String>) -> Self {
+        Self {
+            columns,
+            values: Vec::new(),
+        }
+    }
+
+    /// Appends a row.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the row length does not match the number of columns.
+    pub fn push(&mut self, row: &[f64]) {
+        if row.len() != self.columns.len() {
+            panic!("Row length must match the number of columns.");
+        }
+        self.values.extend_from_slice(row);
+    }
+
+    pub fn rows(&self) -> usize {
+        self.values.len() / self.columns.len().max(1)
+    }
+
+    /// Writes a header line and one line per row, with full precision.
+    pub fn write_csv(&self, mut writer: impl Write) -> io::Result<()> {
+        writeln!(writer, "{}", self.columns.join(","))?;
+        for row in self.values.chunks(self.columns.len().max(1)) {
+            let mut line = String::new();
+            for (i, value) in row.iter().enumerate() {
+                if i > 0 {
+                    line.push(',');
+                }
+                line.push_str(&value.to_string());
+            }
+            writeln!(writer, "{line}")?;
+        }
+        Ok(())
+    }
+
+    /// Writes a NumPy `.npy` v1.0 file with a 2D little-endian `f64` array.
+    pub fn write_npy(&self, mut writer: impl Write) -> io::Result<()> {
+        let mut header = format!(
+            "{{'descr': '<f8', 'fortran_order': False, 'shape': ({}, {}), }}",
+            self.rows(),
+            self.columns.len()
+        );
+        // Magic (6), version (2), and length (2) precede the header, and the whole
+        // preamble is padded to a multiple of 64 bytes, ending with a newline.
+        let padding = 63 - (10 + header.len()) % 64;
+        header.extend(std::iter::repeat_n(' ', padding));
+        header.push('\n');
+
+        writer.write_all(b"\x93NUMPY\x01\x00")?;
+        writer.write_all(&(header.len() as u16).to_le_bytes())?;
+        writer.write_all(header.as_bytes())?;
+        for value in &self.values {
+            writer.write_all(&value.to_le_bytes())?;
+        }
+        Ok(())
+    }
+}
+
+/// Progress of a task on stderr.
+///
+/// On a terminal, the percentage is updated in place. Otherwise, e.g., in CI logs, a line
+/// is printed every 10%.
+pub struct Progress {
+    label: &'static str,
+    total: usize,
+    done: usize,
+    shown: Option<usize>,
+    terminal: bool,
+    enabled: bool,
+    start: Instant,
+}
+
+impl Progress {
+    pub fn new(label: &'static str, total: usize, enabled: bool) -> Self {
+        Self {
+            label,
+            total,
+            done: 0,
+            shown: None,
+            terminal: io::stderr().is_terminal(),
+            enabled,
+            start: Instant::now(),
+        }
+    }
+
+    /// Marks `count` more items as done.
+    pub fn advance(&mut self, count: usize) {
+        self.done = (self.done + count).min(self.total);
+        if !self.enabled {
+            return;
+        }
+        let percent = (100 * self.done).checked_div(self.total).unwrap_or(100);
+        let step = if self.terminal { 1 } else { 10 };
+        if self
+            .shown
+            .is_some_and(|shown| percent / step == shown / step)
+        {
+            return;
+        }
+        self.shown = Some(percent);
+        let (done, total) = (self.done, self.total);
+        if self.terminal {
+            eprint!("\r{}: {percent:3}% ({done}/{total})", self.label);
+        } else {
+            eprintln!("{}: {percent:3}% ({done}/{total})", self.label);
+        }
+    }
+
+    /// Prints the elapsed time.
+    pub fn finish(self) {
+        if !self.enabled {
+            return;
+        }
+        if self.terminal {
+            eprintln!();
+        }
+        eprintln!(
+            "{}: done in {:.2} s",
+            self.label,
+            self.start.elapsed().as_secs_f64()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table() -> Table {
+        let mut table = Table::new(vec!["a".into(), "b".into()]);
+        table.push(&[1.0, -0.5]);
+        table.push(&[1e-9, 3.0]);
+        table
+    }
+
+    #[test]
+    fn test_csv() {
+        let mut out = Vec::new();
+        table().write_csv(&mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "a,b\n1,-0.5\n0.000000001,3\n"
+        );
+    }
+
+    #[test]
+    fn test_npy() {
+        let mut out = Vec::new();
+        table().write_npy(&mut out).unwrap();
+        assert_eq!(out.len(), 128 + 4 * 8);
+        assert_eq!(&out[..8], b"\x93NUMPY\x01\x00");
+        assert_eq!(u16::from_le_bytes([out[8], out[9]]), 118);
+        let header = std::str::from_utf8(&out[10..128]).unwrap();
+        assert!(header.starts_with("{'descr': '<f8', 'fortran_order': False, 'shape': (2, 2), }"));
+        assert!(header.ends_with(" \n"));
+        assert_eq!(
+            f64::from_le_bytes(out[128 + 8..128 + 16].try_into().unwrap()),
+            -0.5
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Row length must match the number of columns.")]
+    fn test_row_length() {
+        table().push(&[1.0]);
+    }
+}
@@ -0,0 +1,1037 @@
+/*
+ * Magba is licensed under The 3-Clause BSD, see LICENSE.
+ * Copyright 2025 Sira Pornsiriprasert <code@psira.me>
+ */
+
+//! C interface to sources, sensors, and their assemblies in `f64`.
+//!
+//! The declarations are in `include/magba.h`. Build the library with
+//!
+//! ```sh
+//! cargo rustc --release --lib --features capi --crate-type cdylib    # or staticlib
+//! ```
+//!
+//! and link it as `-lmagba`.
+//!
+//! # Conventions
+//!
+//! Objects are opaque handles created by `magba_*_new` and released by the matching
+//! `magba_*_free`. Every other function returns a [MagbaStatus]; on failure, nothing is
+//! written to the outputs and [magba_last_error] describes the problem. Panics never cross
+//! the boundary, they are reported as [MagbaStatus::Panic].
+//!
+//! Vectors are arrays of 3 doubles, and arrays of points or fields are `n` rows of 3
+//! doubles. Orientations are unit quaternions as 4 doubles `(x, y, z, w)`, as in SciPy and
+//! Magpylib, and are normalized on input. A `NULL` position means the origin and a `NULL`
+//! orientation means no rotation. All quantities are in SI units.
+//!
+//! # Safety
+//!
+//! Every pointer must be `NULL` or valid for the documented number of elements, and
+//! handles must come from this library and be freed only once. A handle may be shared
+//! between threads for reading, e.g., computing fields, but not while it is modified.
+//!
+//! # Examples
+//!
+//! ```c
+//! MagbaSource *magnet;
+//! const double polarization[3] = {0.0, 0.0, 1.0};
+//! magba_cylinder_magnet_new(NULL, NULL, polarization, 0.01, 0.02, &magnet);
+//!
+//! const double points[6] = {0.0, 0.0, 0.02, 0.005, 0.0, 0.02};
+//! double b[6];
+//! if (magba_source_compute_B(magnet, points, 2, b) != MAGBA_STATUS_OK) {
+//!     fprintf(stderr, "%s\n", magba_last_error());
+//! }
+//! magba_source_free(magnet);
+//! ```
+
+#![allow(clippy::missing_safety_doc)]
+
+use std::{
+    any::Any,
+    cell::RefCell,
+    ffi::{CString, c_char},
+    panic::{self, AssertUnwindSafe},
+    ptr, slice,
+};
+
+use nalgebra::{Point3, Quaternion, UnitQuaternion, Vector2, Vector3};
+
+use crate::{
+    Error,
+    base::{Observer, Pose, SensorOutput, Source, Transform, mesh::TriMesh, polygon::Polygon},
+    collections::{ObserverAssembly, ObserverComponent, SourceAssembly, SourceComponent},
+    currents::{CircularCurrent, PathCurrent},
+    magnets::{
+        CuboidMagnet, CylinderMagnet, Dipole, MeshMagnet, PrismMagnet, SphereMagnet,
+        TetrahedronMagnet,
+    },
+    sensors::hall_effect::{HallLatch, HallSwitch, LinearHallSensor},
+};
+
+/// Result of a call, where anything but `Ok` is a failure.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MagbaStatus {
+    Ok = 0,
+    /// A required pointer is `NULL`.
+    NullPointer = 1,
+    /// An argument is out of range or of the wrong kind.
+    InvalidArgument = 2,
+    /// The computation panicked.
+    Panic = 3,
+}
+
+/// A magnet, a current, or an assembly of sources.
+pub struct MagbaSource(SourceComponent<f64>);
+
+/// A sensor.
+pub struct MagbaSensor(ObserverComponent<f64>);
+
+/// An assembly of sensors.
+pub struct MagbaSensorAssembly(ObserverAssembly<f64>);
+
+// MARK: Errors
+
+thread_local! {
+    static LAST_ERROR: RefCell<CString> = RefCell::new(CString::default());
+}
+
+struct Failure {
+    status: MagbaStatus,
+    message: String,
+}
+
+impl Failure {
+    fn null(name: &str) -> Self {
+        Self {
+            status: MagbaStatus::NullPointer,
+            message: format!("`{name}` is null."),
+        }
+    }
+
+    fn invalid(message: impl Into<String>) -> Self {
+        Self {
+            status: MagbaStatus::InvalidArgument,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<Error> for Failure {
+    fn from(error: Error) -> Self {
+        Self::invalid(error.to_string())
+    }
+}
+
+/// Runs `f`, turning failures and panics into a status and the last error message.
+fn run(f: impl FnOnce() -> Result<(), Failure>) -> MagbaStatus {
+    let failure = match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => return MagbaStatus::Ok,
+        Ok(Err(failure)) => failure,
+        Err(payload) => Failure {
+            status: MagbaStatus::Panic,
+            message: panic_message(payload),
+        },
+    };
+    let message = CString::new(failure.message.replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|last| *last.borrow_mut() = message);
+    failure.status
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => payload
+            .downcast_ref::<&str>()
+            .map_or_else(|| "Unknown panic.".to_string(), |m| m.to_string()),
+    }
+}
+
+/// Returns the message of the last failed call on this thread, or an empty string.
+///
+/// The string stays valid until the next failed call on this thread.
+#[unsafe(no_mangle)]
+pub extern "C" fn magba_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| last.borrow().as_ptr())
+}
+
+// MARK: Arguments
+
+/// Number of values in `n` items of `width` values each.
+fn length(n: usize, width: usize, name: &str) -> Result<usize, Failure> {
+    n.checked_mul(width)
+        .ok_or_else(|| Failure::invalid(format!("`{name}` is too long.")))
+}
+
+/// Whether `len` values of `T` fit in a slice.
+fn fits<T>(len: usize) -> bool {
+    len <= isize::MAX as usize / size_of::<T>().max(1)
+}
+
+unsafe fn array<'a, T>(data: *const T, len: usize, name: &str) -> Result<&'a [T], Failure> {
+    if len == 0 {
+        Ok(&[])
+    } else if data.is_null() {
+        Err(Failure::null(name))
+    } else if !fits::<T>(len) {
+        Err(Failure::invalid(format!("`{name}` is too long.")))
+    } else {
+        Ok(unsafe { slice::from_raw_parts(data, len) })
+    }
+}
+
+unsafe fn array_mut<'a>(data: *mut f64, len: usize, name: &str) -> Result<&'a mut [f64], Failure> {
+    if len == 0 {
+        Ok(&mut [])
+    } else if data.is_null() {
+        Err(Failure::null(name))
+    } else if !fits::<f64>(len) {
+        Err(Failure::invalid(format!("`{name}` is too long.")))
+    } else {
+        Ok(unsafe { slice::from_raw_parts_mut(data, len) })
+    }
+}
+
+unsafe fn vectors(data: *const f64, n: usize, name: &str) -> Result<Vec<Vector3<f64>>, Failure> {
+    let values = unsafe { array(data, length(n, 3, name)?, name)? };
+    Ok(values
+        .chunks_exact(3)
+        .map(Vector3::from_column_slice)
+        .collect())
+}
+
+unsafe fn vector(data: *const f64, name: &str) -> Result<Vector3<f64>, Failure> {
+    if data.is_null() {
+        return Err(Failure::null(name));
+    }
+    Ok(unsafe { vectors(data, 1, name)? }[0])
+}
+
+unsafe fn pose(position: *const f64, orientation: *const f64) -> Result<Pose<f64>, Failure> {
+    let position = if position.is_null() {
+        Point3::origin()
+    } else {
+        unsafe { vector(position, "position")? }.into()
+    };
+    let orientation = if orientation.is_null() {
+        UnitQuaternion::identity()
+    } else {
+        let q = unsafe { array(orientation, 4, "orientation")? };
+        UnitQuaternion::try_new(Quaternion::new(q[3], q[0], q[1], q[2]), 0.0)
+            .filter(|q| q.coords.iter().all(|c| c.is_finite()))
+            .ok_or_else(|| Failure::invalid("Orientation must be a finite, nonzero quaternion."))?
+    };
+    Ok(Pose::new(position, orientation))
+}
+
+unsafe fn write_pose(
+    pose: &Pose<f64>,
+    position: *mut f64,
+    orientation: *mut f64,
+) -> Result<(), Failure> {
+    let position_out = unsafe { array_mut(position, 3, "position")? };
+    let orientation_out = unsafe { array_mut(orientation, 4, "orientation")? };
+    position_out.copy_from_slice(pose.position().coords.as_slice());
+    orientation_out.copy_from_slice(pose.orientation().coords.as_slice());
+    Ok(())
+}
+
+unsafe fn handle<'a, H>(handle: *const H, name: &str) -> Result<&'a H, Failure> {
+    unsafe { handle.as_ref() }.ok_or_else(|| Failure::null(name))
+}
+
+unsafe fn handle_mut<'a, H>(handle: *mut H, name: &str) -> Result<&'a mut H, Failure> {
+    unsafe { handle.as_mut() }.ok_or_else(|| Failure::null(name))
+}
+
+/// Builds an object and stores a new handle to it in `out`.
+fn create<H>(out: *mut *mut H, f: impl FnOnce() -> Result<H, Failure>) -> MagbaStatus {
+    run(|| {
+        if out.is_null() {
+            return Err(Failure::null("out"));
+        }
+        let object = f()?;
+        unsafe { *out = Box::into_raw(Box::new(object)) };
+        Ok(())
+    })
+}
+
+fn free<H>(handle: *mut H) {
+    if !handle.is_null() {
+        drop(unsafe { Box::from_raw(handle) });
+    }
+}
+
+fn scalar(output: SensorOutput<f64>) -> Result<f64, Failure> {
+    match output {
+        SensorOutput::Scalar(value) => Ok(value),
+        SensorOutput::Digital(value) => Ok(value as f64),
+        SensorOutput::Vector(_) => {
+            Err(Failure::invalid("Vector sensor outputs are not supported."))
+        }
+    }
+}
+
+// MARK: Sources
+
+/// Creates a [CylinderMagnet] with the diameter and height in m.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn magba_cylinder_magnet_new(
+    position: *const f64,
+    orientation: *const f64,
+    polarization: *const f64,
+    diameter: f64,
+    height: f64,
+    out: *mut *mut MagbaSource,
+) -> MagbaStatus {
+    create(out, || {
+        let pose = unsafe { pose(position, orientation)? };
+        let polarization = unsafe { vector(polarization, "polarization")? };
+        let magnet = CylinderMagnet::try_new(
+            pose.position(),
+            pose.orientation(),
+            polarization,
+            diameter,
+            height,
+        )?;
+        Ok(MagbaSource(magnet.into()))
+    })
+}
+
+/// Creates a [CuboidMagnet] with the side lengths `dimensions` (3) in m.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn magba_cuboid_magnet_new(
+    position: *const f64,
+    orientation: *const f64,
+    polarization: *const f64,
+    dimensions: *const f64,
+    out: *mut *mut MagbaSource,
+) -> MagbaStatus {
+    create(out, || {
+        let pose = unsafe { pose(position, orientation)? };
+        let polarization = unsafe { vector(polarization, "polarization")? };
+        let dimensions = unsafe { vector(dimensions, "dimensions")? };
+        let magnet = CuboidMagnet::try_new(
+            pose.position(),
+            pose.orientation(),
+            polarization,
+            dimensions,
+        )?;
+        Ok(MagbaSource(magnet.into()))
+    })
+}
+
+/// Creates a [SphereMagnet] with the diameter in m.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn magba_sphere_magnet_new(
+    position: *const f64,
+    orientation: *const f64,
+    polarization: *const f64,
+    diameter: f64,
+    out: *mut *mut MagbaSource,
+) -> MagbaStatus {
+    create(out, || {
+        let pose = unsafe { pose(position, orientation)? };
+        let polarization = unsafe { vector(polarization, "polarization")? };
+        let magnet =
+            SphereMagnet::try_new(pose.position(), pose.orientation(), polarization, diameter)?;
+        Ok(MagbaSource(magnet.into()))
+    })
+}
+
+/// Creates a [TetrahedronMagnet] from 4 `vertices` in m, relative to the position.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn magba_tetrahedron_magnet_new(
+    position: *const f64,
+    orientation: *const f64,
+    polarization: *const f64,
+    vertices: *const f64,
+    out: *mut *mut MagbaSource,
+) -> MagbaStatus {
+    create(out, || {
+        let pose = unsafe { pose(position, orientation)? };
+        let polarization = unsafe { vector(polarization, "polarization")? };
+        let vertices = unsafe { vectors(vertices, 4, "vertices")? };
+        let magnet = TetrahedronMagnet::new(
+            pose.position(),
+            pose.orientation(),
+            polarization,
+            [vertices[0], vertices[1], vertices[2], vertices[3]],
+        );
+        Ok(MagbaSource(magnet.into()))
+    })
+}
+
+/// Creates a [PrismMagnet] extruding a polygon of `n` vertices (2 doubles each) in m by
+/// the height along z.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn magba_prism_magnet_new(
+    position: *const f64,
+    orientation: *const f64,
+    polarization: *const f64,
+    height: f64,
+    polygon: *const f64,
+    n: usize,
+    out: *mut *mut MagbaSource,
+) -> MagbaStatus {
+    create(out, || {
+        let pose = unsafe { pose(position, orientation)? };
+        let polarization = unsafe { vector(polarization, "polarization")? };
+        let polygon = unsafe { array(polygon, length(n, 2, "polygon")?, "polygon")? };
+        let polygon = Polygon::try_new(polygon.chunks_exact(2).map(Vector2::from_column_slice))?;
+        let magnet = PrismMagnet::try_new(
+            pose.position(),
+            pose.orientation(),
+            polarization,
+            height,
+            polygon,
+        )?;
+        Ok(MagbaSource(magnet.into()))
+    })
+}
+
+/// Creates a [MeshMagnet] from `n_vertices` vertices in m and `n_faces` triangles of 3
+/// vertex indices each, wound counterclockwise seen from outside.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn magba_mesh_magnet_new(
+    position: *const f64,
+    orientation: *const f64,
+    polarization: *const f64,
+    vertices: *const f64,
+    n_vertices: usize,
+    faces: *const usize,
+    n_faces: usize,
+    out: *mut *mut MagbaSource,
+) -> MagbaStatus {
+    create(out, || {
+        let pose = unsafe { pose(position, orientation)? };
+        let polarization = unsafe { vector(polarization, "polarization")? };
+        let vertices = unsafe { vectors(vertices, n_vertices, "vertices")? };
+        let faces = unsafe { array(faces, length(n_faces, 3, "faces")?, "faces")? };
+        if faces.iter().any(|&i| i >= n_vertices) {
+            return Err(Failure::invalid("Face vertex index out of range."));
+        }
+        let faces = faces.chunks_exact(3).map(|f| [f[0], f[1], f[2]]);
+        let mesh = TriMesh::new(vertices, faces).map_err(Error::from)?;
+        let magnet = MeshMagnet::new(pose.position(), pose.orientation(), polarization, mesh);
+        Ok(MagbaSource(magnet.into()))
+    })
+}
+
+/// Creates a magnetic [Dipole] with the moment in A·m².
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn magba_dipole_new(
+    position: *const f64,
+    orientation: *const f64,
+    moment: *const f64,
+    out: *mut *mut MagbaSource,
+) -> MagbaStatus {
+    create(out, || {
+        let pose = unsafe { pose(position, orientation)? };
+        let moment = unsafe { vector(moment, "moment")? };
+        let dipole = Dipole::new(pose.position(), pose.orientation(), moment);
+        Ok(MagbaSource(dipole.into()))
+    })
+}
+
+/// Creates a [CircularCurrent] loop with the diameter in m and the current in A.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn magba_circular_current_new(
+    position: *const f64,
+    orientation: *const f64,
+    diameter: f64,
+    current: f64,
+    out: *mut *mut MagbaSource,
+) -> MagbaStatus {
+    create(out, || {
+        let pose = unsafe { pose(position, orientation)? };
+        let loop_ =
+            CircularCurrent::try_new(pose.position(), pose.orientation(), diameter, current)?;
+        Ok(MagbaSource(loop_.into()))
+    })
+}
+
+/// Creates a [PathCurrent] through `n` vertices in m with the current in A.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn magba_path_current_new(
+    position: *const f64,
+    orientation: *const f64,
+    current: f64,
+    vertices: *const f64,
+    n: usize,
+    out: *mut *mut MagbaSource,
+) -> MagbaStatus {
+    create(out, || {
+        let pose = unsafe { pose(position, orientation)? };
+        let vertices = unsafe { vectors(vertices, n, "vertices")? };
+        let path = PathCurrent::new(pose.position(), pose.orientation(), current, vertices);
+        Ok(MagbaSource(path.into()))
+    })
+}
+
+/// Creates an empty [SourceAssembly].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn magba_source_assembly_new(
+    position: *const f64,
+    orientation: *const f64,
+    out: *mut *mut MagbaSource,
+) -> MagbaStatus {
+    create(out, || {
+        let pose = unsafe { pose(position, orientation)? };
+        let assembly = SourceAssembly::new(
+            pose.position(),
+            pose.orientation(),
+            Vec::<SourceComponent>::new(),
+        );
+        Ok(MagbaSource(assembly.into()))
+    })
+}
+
+/// Moves `component` into `assembly`, keeping its global pose.
+///
+/// On success, `component` belongs to the assembly and must not be used or freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn magba_source_assembly_add(
+    assembly: *mut MagbaSource,
+    component: *mut MagbaSource,
+) -> MagbaStatus {
+    run(|| {
+        if component.is_null() {
+            return Err(Failure::null("component"));
+        }
+        if ptr::eq(assembly, component) {
+            return Err(Failure::invalid("An assembly cannot contain itself."));
+        }
+        let SourceComponent::Assembly(assembly) =
+            &mut unsafe { handle_mut(assembly, "assembly")? }.0
+        else {
+            return Err(Failure::invalid("`assembly` is not a source assembly."));
+        };
+        assembly.push(unsafe { Box::from_raw(component) }.0);
+        Ok(())
+    })
+}
+
+/// Frees a source. `NULL` is ignored.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn magba_source_free(source: *mut MagbaSource) {
+    free(source)
+}
+
+/// Sets the pose of a source. An assembly moves its components along.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn magba_source_set_pose(
+    source: *mut MagbaSource,
+    position: *const f64,
+    orientation: *const f64,
+) -> MagbaStatus {
+    run(|| {
+        let source = unsafe { handle_mut(source, "source")? };
+        let pose = unsafe { pose(position, orientation)? };
+        match &mut source.0 {
+            SourceComponent::Assembly(assembly) => assembly.set_pose(pose),
+            component => component.set_pose(pose),
+        }
+        Ok(())
+    })
+}
+
+/// Writes the position (3) and orientation (4) of a source.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn magba_source_get_pose(
+    source: *const MagbaSource,
+    position: *mut f64,
+    orientation: *mut f64,
+) -> MagbaStatus {
+    run(|| {
+        let source = unsafe { handle(source, "source")? };
+        unsafe { write_pose(source.0.pose(), position, orientation) }
+    })
+}
+
+/// Computes the B-field (T) of a source at `n` points (m) into `b` (`n` × 3).
+#[unsafe(no_mangle)]
+#[allow(non_snake_case)]
+pub unsafe extern "C" fn magba_source_compute_B(
+    source: *const MagbaSource,
+    points: *const f64,
+    n: usize,
+    b: *mut f64,
+) -> MagbaStatus {
+    run(|| {
+        let source = unsafe { handle(source, "source")? };
+        let len = length(n, 3, "points")?;
+        let points = unsafe { array(points, len, "points")? };
+        let out = unsafe { array_mut(b, len, "b")? };
+        source.0.compute_B_batch_flat_into(points, out);
+        Ok(())
+    })
+}
+
+// MARK: Sensors
+
+/// Creates a [LinearHallSensor] with the sensitivity in V/T and the supply voltage in V.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn magba_linear_hall_sensor_new(
+    position: *const f64,
+    orientation: *const f64,
+    sensitive_axis: *const f64,
+    sensitivity: f64,
+    supply_voltage: f64,
+    out: *mut *mut MagbaSensor,
+) -> MagbaStatus {
+    create(out, || {
+        let pose = unsafe { pose(position, orientation)? };
+        let axis = unsafe { vector(sensitive_axis, "sensitive_axis")? };
+        let sensor = LinearHallSensor::try_new(
+            pose.position(),
+            pose.orientation(),
+            axis,
+            sensitivity,
+            supply_voltage,
+        )?;
+        Ok(MagbaSensor(sensor.into()))
+    })
+}
+
+/// Creates a [HallSwitch] with the operate point `b_op` in T.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn magba_hall_switch_new(
+    position: *const f64,
+    orientation: *const f64,
+    sensitive_axis: *const f64,
+    b_op: f64,
+    out: *mut *mut MagbaSensor,
+) -> MagbaStatus {
+    create(out, || {
+        let pose = unsafe { pose(position, orientation)? };
+        let axis = unsafe { vector(sensitive_axis, "sensitive_axis")? };
+        let sensor = HallSwitch::try_new(pose.position(), pose.orientation(), axis, b_op)?;
+        Ok(MagbaSensor(sensor.into()))
+    })
+}
+
+/// Creates a [HallLatch] with the operate and release points `b_op` and `b_rp` in T.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn magba_hall_latch_new(
+    position: *const f64,
+    orientation: *const f64,
+    sensitive_axis: *const f64,
+    b_op: f64,
+    b_rp: f64,
+    out: *mut *mut MagbaSensor,
+) -> MagbaStatus {
+    create(out, || {
+        let pose = unsafe { pose(position, orientation)? };
+        let axis = unsafe { vector(sensitive_axis, "sensitive_axis")? };
+        let sensor = HallLatch::try_new(pose.position(), pose.orientation(), axis, b_op, b_rp)?;
+        Ok(MagbaSensor(sensor.into()))
+    })
+}
+
+/// Frees a sensor. `NULL` is ignored.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn magba_sensor_free(sensor: *mut MagbaSensor) {
+    free(sensor)
+}
+
+/// Sets the pose of a sensor.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn magba_sensor_set_pose(
+    sensor: *mut MagbaSensor,
+    position: *const f64,
+    orientation: *const f64,
+) -> MagbaStatus {
+    run(|| {
+        let sensor = unsafe { handle_mut(sensor, "sensor")? };
+        sensor.0.set_pose(unsafe { pose(position, orientation)? });
+        Ok(())
+    })
+}
+
+/// Writes the position (3) and orientation (4) of a sensor.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn magba_sensor_get_pose(
+    sensor: *const MagbaSensor,
+    position: *mut f64,
+    orientation: *mut f64,
+) -> MagbaStatus {
+    run(|| {
+        let sensor = unsafe { handle(sensor, "sensor")? };
+        unsafe { write_pose(sensor.0.pose(), position, orientation) }
+    })
+}
+
+/// Reads a sensor in the field of a source into `value`: the voltage (V) of a linear Hall
+/// sensor, or 1 or 0 for the state of a switch or latch.
+///
+/// A latch remembers its state between reads.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn magba_sensor_read(
+    sensor: *const MagbaSensor,
+    source: *const MagbaSource,
+    value: *mut f64,
+) -> MagbaStatus {
+    run(|| {
+        let sensor = unsafe { handle(sensor, "sensor")? };
+        let source = unsafe { handle(source, "source")? };
+        let value = unsafe { handle_mut(value, "value")? };
+        *value = scalar(sensor.0.read(&source.0))?;
+        Ok(())
+    })
+}
+
+// MARK: Sensor Assemblies
+
+/// Creates an empty [ObserverAssembly].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn magba_sensor_assembly_new(
+    position: *const f64,
+    orientation: *const f64,
+    out: *mut *mut MagbaSensorAssembly,
+) -> MagbaStatus {
+    create(out, || {
+        let pose = unsafe { pose(position, orientation)? };
+        let assembly = ObserverAssembly::new(
+            pose.position(),
+            pose.orientation(),
+            Vec::<ObserverComponent>::new(),
+        );
+        Ok(MagbaSensorAssembly(assembly))
+    })
+}
+
+/// Moves `sensor` into `assembly`, keeping its global pose.
+///
+/// On success, `sensor` belongs to the assembly and must not be used or freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn magba_sensor_assembly_add(
+    assembly: *mut MagbaSensorAssembly,
+    sensor: *mut MagbaSensor,
+) -> MagbaStatus {
+    run(|| {
+        let assembly = unsafe { handle_mut(assembly, "assembly")? };
+        if sensor.is_null() {
+            return Err(Failure::null("sensor"));
+        }
+        assembly.0.push(unsafe { Box::from_raw(sensor) }.0);
+        Ok(())
+    })
+}
+
+/// Writes the number of sensors in an assembly to `len`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn magba_sensor_assembly_len(
+    assembly: *const MagbaSensorAssembly,
+    len: *mut usize,
+) -> MagbaStatus {
+    run(|| {
+        let assembly = unsafe { handle(assembly, "assembly")? };
+        *unsafe { handle_mut(len, "len")? } = assembly.0.iter().count();
+        Ok(())
+    })
+}
+
+/// Frees a sensor assembly and its sensors. `NULL` is ignored.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn magba_sensor_assembly_free(assembly: *mut MagbaSensorAssembly) {
+    free(assembly)
+}
+
+/// Sets the pose of a sensor assembly, moving its sensors along.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn magba_sensor_assembly_set_pose(
+    assembly: *mut MagbaSensorAssembly,
+    position: *const f64,
+    orientation: *const f64,
+) -> MagbaStatus {
+    run(|| {
+        let assembly = unsafe { handle_mut(assembly, "assembly")? };
+        assembly.0.set_pose(unsafe { pose(position, orientation)? });
+        Ok(())
+    })
+}
+
+/// Writes the position (3) and orientation (4) of a sensor assembly.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn magba_sensor_assembly_get_pose(
+    assembly: *const MagbaSensorAssembly,
+    position: *mut f64,
+    orientation: *mut f64,
+) -> MagbaStatus {
+    run(|| {
+        let assembly = unsafe { handle(assembly, "assembly")? };
+        unsafe { write_pose(assembly.0.pose(), position, orientation) }
+    })
+}
+
+/// Reads all `n` sensors of an assembly in the field of a source into `values`, as
+/// [magba_sensor_read]. `n` must match the number of sensors.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn magba_sensor_assembly_read(
+    assembly: *const MagbaSensorAssembly,
+    source: *const MagbaSource,
+    values: *mut f64,
+    n: usize,
+) -> MagbaStatus {
+    run(|| {
+        let assembly = unsafe { handle(assembly, "assembly")? };
+        let source = unsafe { handle(source, "source")? };
+        let len = assembly.0.iter().count();
+        if n != len {
+            return Err(Failure::invalid(format!(
+                "The assembly has {len} sensors, but `n` is {n}."
+            )));
+        }
+        let out = unsafe { array_mut(values, n, "values")? };
+        let values = assembly
+            .0
+            .read_all(&source.0)
+            .into_iter()
+            .map(scalar)
+            .collect::<Result<Vec<_>, _>>()?;
+        out.copy_from_slice(&values);
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::CStr;
+
+    use approx::assert_relative_eq;
+
+    use super::*;
+
+    fn last_error() -> String {
+        unsafe { CStr::from_ptr(magba_last_error()) }
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    #[test]
+    fn test_compute_b() {
+        let mut magnet = ptr::null_mut();
+        let pol = [0.1, 0.2, 1.0];
+        let orientation = [0.0, 0.0, 2f64.sqrt(), 2f64.sqrt()];
+        unsafe {
+            assert_eq!(
+                magba_cylinder_magnet_new(
+                    ptr::null(),
+                    orientation.as_ptr(),
+                    pol.as_ptr(),
+                    0.01,
+                    0.02,
+                    &mut magnet
+                ),
+                MagbaStatus::Ok
+            );
+            let points = [0.0, 0.0, 0.02, 0.005, 0.001, -0.03];
+            let mut b = [0.0; 6];
+            assert_eq!(
+                magba_source_compute_B(magnet, points.as_ptr(), 2, b.as_mut_ptr()),
+                MagbaStatus::Ok
+            );
+
+            let expected = (*magnet).0.compute_B_batch(&[
+                Point3::new(0.0, 0.0, 0.02),
+                Point3::new(0.005, 0.001, -0.03),
+            ]);
+            assert_relative_eq!(b[..3], *expected[0].as_slice());
+            assert_relative_eq!(b[3..], *expected[1].as_slice());
+
+            let mut position = [0.0; 3];
+            let mut orientation = [0.0; 4];
+            magba_source_get_pose(magnet, position.as_mut_ptr(), orientation.as_mut_ptr());
+            assert_relative_eq!(
+                orientation[..],
+                [0.0, 0.0, 0.5f64.sqrt(), 0.5f64.sqrt()][..]
+            );
+            magba_source_free(magnet);
+        }
+    }
+
+    #[test]
+    fn test_assembly() {
+        unsafe {
+            let mut assembly = ptr::null_mut();
+            magba_source_assembly_new(ptr::null(), ptr::null(), &mut assembly);
+            let mut dipole = ptr::null_mut();
+            let moment = [0.0, 0.0, 1.0];
+            let position = [0.0, 0.0, 1.0];
+            magba_dipole_new(position.as_ptr(), ptr::null(), moment.as_ptr(), &mut dipole);
+            assert_eq!(magba_source_assembly_add(assembly, dipole), MagbaStatus::Ok);
+
+            let shift = [1.0, 0.0, 0.0];
+            magba_source_set_pose(assembly, shift.as_ptr(), ptr::null());
+            let SourceComponent::Assembly(inner) = &(*assembly).0 else {
+                panic!("Expected an assembly.");
+            };
+            assert_eq!(inner[0].pose().position(), Point3::new(1.0, 0.0, 1.0));
+
+            assert_eq!(
+                magba_source_assembly_add(dipole, assembly),
+                MagbaStatus::InvalidArgument
+            );
+            assert_eq!(last_error(), "`assembly` is not a source assembly.");
+            magba_source_free(assembly);
+        }
+    }
+
+    #[test]
+    fn test_sensors() {
+        unsafe {
+            let mut magnet = ptr::null_mut();
+            let pol = [0.0, 0.0, 1.0];
+            let dim = [0.01, 0.01, 0.01];
+            magba_cuboid_magnet_new(
+                ptr::null(),
+                ptr::null(),
+                pol.as_ptr(),
+                dim.as_ptr(),
+                &mut magnet,
+            );
+
+            let mut assembly = ptr::null_mut();
+            magba_sensor_assembly_new(ptr::null(), ptr::null(), &mut assembly);
+            let axis = [0.0, 0.0, 1.0];
+            let position = [0.0, 0.0, 0.01];
+            let mut sensor = ptr::null_mut();
+            magba_linear_hall_sensor_new(
+                position.as_ptr(),
+                ptr::null(),
+                axis.as_ptr(),
+                10.0,
+                5.0,
+                &mut sensor,
+            );
+            let mut voltage = 0.0;
+            magba_sensor_read(sensor, magnet, &mut voltage);
+            magba_sensor_assembly_add(assembly, sensor);
+            magba_hall_switch_new(
+                position.as_ptr(),
+                ptr::null(),
+                axis.as_ptr(),
+                0.01,
+                &mut sensor,
+            );
+            magba_sensor_assembly_add(assembly, sensor);
+
+            let mut values = [0.0; 2];
+            assert_eq!(
+                magba_sensor_assembly_read(assembly, magnet, values.as_mut_ptr(), 2),
+                MagbaStatus::Ok
+            );
+            assert_eq!(values, [voltage, 1.0]);
+            assert!(voltage > 2.5);
+            assert_eq!(
+                magba_sensor_assembly_read(assembly, magnet, values.as_mut_ptr(), 3),
+                MagbaStatus::InvalidArgument
+            );
+            assert_eq!(last_error(), "The assembly has 2 sensors, but `n` is 3.");
+
+            magba_sensor_assembly_free(assembly);
+            magba_source_free(magnet);
+        }
+    }
+
+    #[test]
+    fn test_overflowing_lengths() {
+        let mut magnet = ptr::null_mut();
+        let pol = [0.0, 0.0, 1.0];
+        let vertices = [0.0; 3];
+        let faces = [0; 3];
+        unsafe {
+            assert_eq!(
+                magba_mesh_magnet_new(
+                    ptr::null(),
+                    ptr::null(),
+                    pol.as_ptr(),
+                    vertices.as_ptr(),
+                    usize::MAX / 2,
+                    faces.as_ptr(),
+                    1,
+                    &mut magnet
+                ),
+                MagbaStatus::InvalidArgument
+            );
+            assert_eq!(last_error(), "`vertices` is too long.");
+            assert_eq!(
+                magba_mesh_magnet_new(
+                    ptr::null(),
+                    ptr::null(),
+                    pol.as_ptr(),
+                    vertices.as_ptr(),
+                    1,
+                    faces.as_ptr(),
+                    usize::MAX / 3 + 1,
+                    &mut magnet
+                ),
+                MagbaStatus::InvalidArgument
+            );
+            assert_eq!(last_error(), "`faces` is too long.");
+            assert!(magnet.is_null());
+
+            magba_dipole_new(ptr::null(), ptr::null(), pol.as_ptr(), &mut magnet);
+            let mut b = [0.0; 3];
+            assert_eq!(
+                magba_source_compute_B(
+                    magnet,
+                    vertices.as_ptr(),
+                    usize::MAX / 3 + 1,
+                    b.as_mut_ptr()
+                ),
+                MagbaStatus::InvalidArgument
+            );
+            assert_eq!(last_error(), "`points` is too long.");
+            assert_eq!(
+                magba_source_compute_B(magnet, vertices.as_ptr(), usize::MAX / 6, b.as_mut_ptr()),
+                MagbaStatus::InvalidArgument
+            );
+            magba_source_free(magnet);
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut magnet = ptr::null_mut();
+        let pol = [0.0, 0.0, 1.0];
+        unsafe {
+            assert_eq!(
+                magba_sphere_magnet_new(ptr::null(), ptr::null(), ptr::null(), 1.0, &mut magnet),
+                MagbaStatus::NullPointer
+            );
+            assert_eq!(last_error(), "`polarization` is null.");
+
+            let zero = [0.0; 4];
+            assert_eq!(
+                magba_sphere_magnet_new(ptr::null(), zero.as_ptr(), pol.as_ptr(), 1.0, &mut magnet),
+                MagbaStatus::InvalidArgument
+            );
+
+            assert_eq!(
+                magba_sphere_magnet_new(ptr::null(), ptr::null(), pol.as_ptr(), -1.0, &mut magnet),
+                MagbaStatus::InvalidArgument
+            );
+            assert_eq!(last_error(), "Diameter cannot be negative.");
+            assert!(magnet.is_null());
+
+            let square = [0.0, 0.0, 1.0, 0.0];
+            assert_eq!(
+                magba_prism_magnet_new(
+                    ptr::null(),
+                    ptr::null(),
+                    pol.as_ptr(),
+                    1.0,
+                    square.as_ptr(),
+                    2,
+                    &mut magnet
+                ),
+                MagbaStatus::InvalidArgument
+            );
+            assert_eq!(last_error(), "Polygon must have at least 3 vertices.");
+        }
+    }
+}
@@ -16,6 +16,7 @@
 //! | **Components** | Uniform type (unless wrapped in an enum like `Magnet`) | Heterogeneous |
 //! | **Nesting** | Not supported | Supported (only for `SourceAssembly`) |
 //! | **Custom Types** | Not supported | Supported |
+//! | **Features** | None, also in `no_std` without `alloc` | `alloc` |
 //!
 //! # Convenience Macros
 //!
@@ -30,18 +31,29 @@ pub(crate) use macros::sources;
 
 mod node;
 mod observer_array;
+#[cfg(feature = "alloc")]
 mod observer_assembly;
+#[cfg(feature = "alloc")]
 mod observer_component;
+#[cfg(all(feature = "serde", feature = "std"))]
+mod registry;
 mod source_array;
+#[cfg(feature = "alloc")]
 mod source_assembly;
+#[cfg(feature = "alloc")]
 mod source_component;
 
-use node::Node;
 pub use observer_array::ObserverArray;
+#[cfg(feature = "alloc")]
 pub use observer_assembly::ObserverAssembly;
+#[cfg(feature = "alloc")]
 pub use observer_component::ObserverComponent;
+#[cfg(all(feature = "serde", feature = "std"))]
+pub use registry::register_source;
 pub use source_array::SourceArray;
+#[cfg(feature = "alloc")]
 pub use source_assembly::SourceAssembly;
+#[cfg(feature = "alloc")]
 pub use source_component::SourceComponent;
 
 mod utils;
@@ -122,7 +122,7 @@ impl<S: Observer<T>, const N: usize, T: Float> FromIterator<S> for ObserverArray
 
 impl<'a, S: Observer<T>, const N: usize, T: Float> IntoIterator for &'a ObserverArray<S, N, T> {
     type Item = &'a S;
-    type IntoIter = std::iter::Map<std::slice::Iter<'a, Node<S, T>>, fn(&'a Node<S, T>) -> &'a S>;
+    type IntoIter = core::iter::Map<core::slice::Iter<'a, Node<S, T>>, fn(&'a Node<S, T>) -> &'a S>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.nodes.iter().map(|n| &n.component)
@@ -131,7 +131,7 @@ impl<'a, S: Observer<T>, const N: usize, T: Float> IntoIterator for &'a Observer
 
 impl<S: Observer<T>, const N: usize, T: Float> IntoIterator for ObserverArray<S, N, T> {
     type Item = S;
-    type IntoIter = std::iter::Map<std::array::IntoIter<Node<S, T>, N>, fn(Node<S, T>) -> S>;
+    type IntoIter = core::iter::Map<core::array::IntoIter<Node<S, T>, N>, fn(Node<S, T>) -> S>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.nodes.into_iter().map(|n| n.component)
@@ -165,7 +165,7 @@ impl<S: Observer<T> + PartialEq, T: Float, const N: usize> PartialEq for Observe
 // MARK: Display
 
 impl<S: Observer<T>, const N: usize, T: Float> Display for ObserverArray<S, N, T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         writeln!(
             f,
             "ObserverArray ({} children) at {}",
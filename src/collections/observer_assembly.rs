@@ -3,8 +3,9 @@
  * Copyright 2025 Sira Pornsiriprasert <code@psira.me>
  */
 
+use alloc::{vec, vec::Vec};
+use core::fmt::Display;
 use core::ops::{Index, IndexMut};
-use std::fmt::Display;
 
 use nalgebra::{Point3, Translation3, UnitQuaternion};
 
@@ -13,7 +14,7 @@ use crate::{
         Float, Observer, Pose, SensorOutput, Source, Transform,
         transform::{impl_group_transform, impl_transform},
     },
-    collections::{Node, ObserverArray, ObserverComponent},
+    collections::{ObserverArray, ObserverComponent, node::Node},
 };
 
 // MARK: Base
@@ -136,8 +137,8 @@ impl<T: Float> From<&[ObserverComponent<T>]> for ObserverAssembly<T> {
 
 impl<'a, T: Float> IntoIterator for &'a ObserverAssembly<T> {
     type Item = &'a ObserverComponent<T>;
-    type IntoIter = std::iter::Map<
-        std::slice::Iter<'a, Node<ObserverComponent<T>, T>>,
+    type IntoIter = core::iter::Map<
+        core::slice::Iter<'a, Node<ObserverComponent<T>, T>>,
         fn(&'a Node<ObserverComponent<T>, T>) -> &'a ObserverComponent<T>,
     >;
 
@@ -198,7 +199,7 @@ impl_group_transform!(ObserverAssembly<T> where T: Float);
 // MARK: Display
 
 impl<T: Float> ObserverAssembly<T> {
-    pub fn format(&self, f: &mut std::fmt::Formatter<'_>, indent: &str) -> std::fmt::Result {
+    pub fn format(&self, f: &mut core::fmt::Formatter<'_>, indent: &str) -> core::fmt::Result {
         writeln!(
             f,
             "ObserverAssembly ({} children) at {}",
@@ -213,11 +214,45 @@ impl<T: Float> ObserverAssembly<T> {
 }
 
 impl<T: Float> Display for ObserverAssembly<T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         self.format(f, "")
     }
 }
 
+// MARK: Serde
+
+/// Serialized as `{ "pose": ..., "components": [...] }`, with the components at their
+/// global poses.
+#[cfg(feature = "serde")]
+impl<T: Float + serde::Serialize> serde::Serialize for ObserverAssembly<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let components: Vec<&ObserverComponent<T>> = self.components().collect();
+        let mut state = serializer.serialize_struct("ObserverAssembly", 2)?;
+        state.serialize_field("pose", &self.pose)?;
+        state.serialize_field("components", &components)?;
+        state.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: Float + serde::Deserialize<'de>> serde::Deserialize<'de> for ObserverAssembly<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(serde::Deserialize)]
+        #[serde(rename = "ObserverAssembly", deny_unknown_fields)]
+        struct Repr<T: Float> {
+            #[serde(default = "Pose::default")]
+            pose: Pose<T>,
+            #[serde(default = "Vec::new")]
+            components: Vec<ObserverComponent<T>>,
+        }
+
+        let Repr { pose, components } = Repr::deserialize(deserializer)?;
+        Ok(Self::new(pose.position(), pose.orientation(), components))
+    }
+}
+
 // MARK: PartialEq
 
 impl<T: Float> PartialEq for ObserverAssembly<T> {
@@ -3,6 +3,7 @@
  * Copyright 2025 Sira Pornsiriprasert <code@psira.me>
  */
 
+use alloc::boxed::Box;
 use enum_dispatch::enum_dispatch;
 
 use crate::{
@@ -55,3 +56,26 @@ macro_rules! impl_transitive_from {
 impl_transitive_from!(LinearHallSensor, HallLatch, HallSwitch);
 
 impl<T: Float> Eq for ObserverComponent<T> {}
+
+// MARK: Serde
+
+/// Sensors are serialized tagged by their type name, e.g., `{ "type": "HallSwitch", ... }`.
+/// Custom observers cannot be serialized.
+#[cfg(feature = "serde")]
+impl<T: Float + serde::Serialize> serde::Serialize for ObserverComponent<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Sensor(sensor) => sensor.serialize(serializer),
+            Self::Custom(_) => Err(serde::ser::Error::custom(
+                "Custom observers cannot be serialized.",
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: Float + serde::Deserialize<'de>> serde::Deserialize<'de> for ObserverComponent<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Sensor::deserialize(deserializer).map(Self::Sensor)
+    }
+}
@@ -0,0 +1,166 @@
+/*
+ * Magba is licensed under The 3-Clause BSD, see LICENSE.
+ * Copyright 2025 Sira Pornsiriprasert <code@psira.me>
+ */
+
+//! Registry of custom source types for deserialization.
+
+use std::{
+    any::{Any, TypeId},
+    collections::BTreeMap,
+    sync::RwLock,
+};
+
+use serde::de::{self, DeserializeOwned, DeserializeSeed, MapAccess, Visitor};
+
+use crate::base::{Float, Source};
+
+type DeserializeFn<T> = for<'de> fn(
+    &mut dyn erased_serde::Deserializer<'de>,
+) -> Result<Box<dyn Source<T>>, erased_serde::Error>;
+
+/// Deserializers by source name and float type.
+static REGISTRY: RwLock<BTreeMap<(String, TypeId), Box<dyn Any + Send + Sync>>> =
+    RwLock::new(BTreeMap::new());
+
+/// Registers the custom source `S` under `name` for deserialization.
+///
+/// A [SourceComponent::Custom](crate::collections::SourceComponent::Custom) is serialized
+/// as `{ "type": "Custom", "name": ..., "source": ... }` with the name and data returned by
+/// [Source::serialize_custom], which `S` overrides. Deserializing it looks up the name, so
+/// it must be registered for each float type `T` before deserializing. Registering a
+/// name again replaces its source type.
+///
+/// # Examples
+///
+/// ```
+/// # use magba::base::{ErasedSerialize, Pose, Source, Transform};
+/// # use magba::collections::{SourceAssembly, register_source};
+/// # use nalgebra::{Point3, Vector3};
+/// /// Uniform background field.
+/// #[derive(Clone, serde::Serialize, serde::Deserialize)]
+/// struct Background {
+///     pose: Pose,
+///     field: Vector3<f64>,
+/// }
+///
+/// impl Transform<f64> for Background {
+///     fn pose(&self) -> &Pose {
+///         &self.pose
+///     }
+///
+///     fn pose_mut(&mut self) -> &mut Pose {
+///         &mut self.pose
+///     }
+/// }
+///
+/// impl Source<f64> for Background {
+///     fn compute_B(&self, _: Point3<f64>) -> Vector3<f64> {
+///         self.field
+///     }
+///
+///     fn compute_B_batch_into(&self, _: &[Point3<f64>], out: &mut [Vector3<f64>]) {
+///         out.fill(self.field);
+///     }
+///
+///     fn serialize_custom(&self) -> Option<(&'static str, &dyn ErasedSerialize)> {
+///         Some(("Background", self))
+///     }
+/// }
+///
+/// register_source::<Background, f64>("Background");
+///
+/// let background = Background { pose: Pose::default(), field: Vector3::new(0.0, 0.0, 5e-5) };
+/// let sources = SourceAssembly::default().with(Box::new(background) as Box<dyn Source<f64>>);
+/// let json = serde_json::to_string(&sources).unwrap();
+/// let sources: SourceAssembly = serde_json::from_str(&json).unwrap();
+/// assert_eq!(sources.compute_B(Point3::origin()), Vector3::new(0.0, 0.0, 5e-5));
+/// ```
+pub fn register_source<S, T>(name: &'static str)
+where
+    S: Source<T> + DeserializeOwned + 'static,
+    T: Float,
+{
+    let deserialize: DeserializeFn<T> =
+        |deserializer| Ok(Box::new(erased_serde::deserialize::<S>(deserializer)?));
+    REGISTRY
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert((name.to_string(), TypeId::of::<T>()), Box::new(deserialize));
+}
+
+fn registered<T: Float>(name: &str) -> Option<DeserializeFn<T>> {
+    let registry = REGISTRY.read().unwrap_or_else(|e| e.into_inner());
+    registry
+        .get(&(name.to_string(), TypeId::of::<T>()))
+        .and_then(|deserialize| deserialize.downcast_ref::<DeserializeFn<T>>())
+        .copied()
+}
+
+/// Custom source deserialized from `{ "name": ..., "source": ... }` by its registered name.
+pub(crate) struct CustomSource<T: Float>(pub Box<dyn Source<T>>);
+
+impl<'de, T: Float> serde::Deserialize<'de> for CustomSource<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(serde::Deserialize)]
+        #[serde(field_identifier, rename_all = "lowercase")]
+        enum Field {
+            Name,
+            Source,
+        }
+
+        struct Seed<T: Float>(DeserializeFn<T>);
+
+        impl<'de, T: Float> DeserializeSeed<'de> for Seed<T> {
+            type Value = Box<dyn Source<T>>;
+
+            fn deserialize<D: serde::Deserializer<'de>>(
+                self,
+                deserializer: D,
+            ) -> Result<Self::Value, D::Error> {
+                let mut erased = <dyn erased_serde::Deserializer>::erase(deserializer);
+                (self.0)(&mut erased).map_err(de::Error::custom)
+            }
+        }
+
+        struct CustomVisitor<T: Float>(core::marker::PhantomData<T>);
+
+        impl<'de, T: Float> Visitor<'de> for CustomVisitor<T> {
+            type Value = CustomSource<T>;
+
+            fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                f.write_str("a custom source with a name and its data")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut name: Option<String> = None;
+                let mut source = None;
+                while let Some(field) = map.next_key()? {
+                    match field {
+                        Field::Name => name = Some(map.next_value()?),
+                        Field::Source => {
+                            let name = name.as_deref().ok_or_else(|| {
+                                de::Error::custom("Custom source name must precede its data.")
+                            })?;
+                            let deserialize = registered::<T>(name).ok_or_else(|| {
+                                de::Error::custom(format!(
+                                    "Custom source `{name}` is not registered."
+                                ))
+                            })?;
+                            source = Some(map.next_value_seed(Seed(deserialize))?);
+                        }
+                    }
+                }
+                source
+                    .map(CustomSource)
+                    .ok_or_else(|| de::Error::missing_field("source"))
+            }
+        }
+
+        deserializer.deserialize_struct(
+            "Custom",
+            &["name", "source"],
+            CustomVisitor(core::marker::PhantomData),
+        )
+    }
+}
@@ -147,7 +147,7 @@ impl<S: Source<T>, const N: usize, T: Float> FromIterator<S> for SourceArray<S,
 
 impl<'a, S: Source<T>, const N: usize, T: Float> IntoIterator for &'a SourceArray<S, N, T> {
     type Item = &'a S;
-    type IntoIter = std::iter::Map<std::slice::Iter<'a, Node<S, T>>, fn(&'a Node<S, T>) -> &'a S>;
+    type IntoIter = core::iter::Map<core::slice::Iter<'a, Node<S, T>>, fn(&'a Node<S, T>) -> &'a S>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.nodes.iter().map(|n| &n.component)
@@ -156,7 +156,7 @@ impl<'a, S: Source<T>, const N: usize, T: Float> IntoIterator for &'a SourceArra
 
 impl<S: Source<T>, const N: usize, T: Float> IntoIterator for SourceArray<S, N, T> {
     type Item = S;
-    type IntoIter = std::iter::Map<std::array::IntoIter<Node<S, T>, N>, fn(Node<S, T>) -> S>;
+    type IntoIter = core::iter::Map<core::array::IntoIter<Node<S, T>, N>, fn(Node<S, T>) -> S>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.nodes.into_iter().map(|n| n.component)
@@ -191,7 +191,7 @@ impl<S: Source<T> + PartialEq, T: Float, const N: usize> PartialEq for SourceArr
 // MARK: Display
 
 impl<S: Source<T>, const N: usize, T: Float> Display for SourceArray<S, N, T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         writeln!(
             f,
             "SourceArray ({} children) at {}",
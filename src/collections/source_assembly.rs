@@ -3,8 +3,9 @@
  * Copyright 2025 Sira Pornsiriprasert <code@psira.me>
  */
 
+use alloc::{vec, vec::Vec};
+use core::fmt::Display;
 use core::ops::{Index, IndexMut};
-use std::fmt::Display;
 
 use nalgebra::{Point3, Translation3, UnitQuaternion, Vector3};
 
@@ -150,8 +151,8 @@ impl<T: Float> From<&[SourceComponent<T>]> for SourceAssembly<T> {
 
 impl<'a, T: Float> IntoIterator for &'a SourceAssembly<T> {
     type Item = &'a SourceComponent<T>;
-    type IntoIter = std::iter::Map<
-        std::slice::Iter<'a, Node<SourceComponent<T>, T>>,
+    type IntoIter = core::iter::Map<
+        core::slice::Iter<'a, Node<SourceComponent<T>, T>>,
         fn(&'a Node<SourceComponent<T>, T>) -> &'a SourceComponent<T>,
     >;
 
@@ -216,7 +217,7 @@ impl<T: Float> Source<T> for SourceAssembly<T> {
 
     // MARK: Display
 
-    fn format(&self, f: &mut std::fmt::Formatter<'_>, indent: &str) -> std::fmt::Result {
+    fn format(&self, f: &mut core::fmt::Formatter<'_>, indent: &str) -> core::fmt::Result {
         writeln!(
             f,
             "SourceAssembly ({} children) at {}",
@@ -231,11 +232,45 @@ impl<T: Float> Source<T> for SourceAssembly<T> {
 }
 
 impl<T: Float> Display for SourceAssembly<T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         self.format(f, "")
     }
 }
 
+// MARK: Serde
+
+/// Serialized as `{ "pose": ..., "components": [...] }`, with the components at their
+/// global poses.
+#[cfg(feature = "serde")]
+impl<T: Float + serde::Serialize> serde::Serialize for SourceAssembly<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let components: Vec<&SourceComponent<T>> = self.components().collect();
+        let mut state = serializer.serialize_struct("SourceAssembly", 2)?;
+        state.serialize_field("pose", &self.pose)?;
+        state.serialize_field("components", &components)?;
+        state.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: Float + serde::Deserialize<'de>> serde::Deserialize<'de> for SourceAssembly<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(serde::Deserialize)]
+        #[serde(rename = "SourceAssembly", deny_unknown_fields)]
+        struct Repr<T: Float> {
+            #[serde(default = "Pose::default")]
+            pose: Pose<T>,
+            #[serde(default = "Vec::new")]
+            components: Vec<SourceComponent<T>>,
+        }
+
+        let Repr { pose, components } = Repr::deserialize(deserializer)?;
+        Ok(Self::new(pose.position(), pose.orientation(), components))
+    }
+}
+
 // MARK: PartialEq
 
 impl<T: Float> PartialEq for SourceAssembly<T> {
@@ -398,6 +433,7 @@ mod field_tests {
 
     use super::*;
     use crate::{magnets::*, testing_util::*};
+    use approx::assert_relative_eq;
     use nalgebra::Translation3;
 
     fn assembly() -> SourceAssembly {
@@ -451,6 +487,56 @@ mod field_tests {
         sources.set_position([0.01, 0.015, 0.02]);
         test_B_magnet!(@small, &sources, "cuboid-sources-translate-rotate.csv", 2e-13);
     }
+
+    #[test]
+    fn test_batch_into() {
+        let background: Box<dyn Source<f64>> =
+            Box::new(StableFieldMagnet::new([0.0, 0.0, 5e-5].into()));
+        let array = SourceArray::new(
+            [0.0, 0.0, -0.02],
+            UnitQuaternion::identity(),
+            [
+                Dipole::default(),
+                Dipole::default().with_position([0.01, 0.0, 0.0]),
+            ],
+        );
+        let mut sources = assembly();
+        sources.push(SourceAssembly::from([assembly(), assembly()]));
+        sources.push(array);
+        sources.push(background);
+
+        // Few points for parallelism over components, many for parallelism over points with
+        // a partial chunk
+        for n in [10, 2500] {
+            let points: Vec<_> = (0..n)
+                .map(|i| Point3::new(0.03, 1e-5 * i as f64, 0.04))
+                .collect();
+            let expected: Vec<_> = points.iter().map(|&p| sources.compute_B(p)).collect();
+
+            let mut out = vec![Vector3::new(1.0, 2.0, 3.0); points.len()];
+            sources.compute_B_batch_into(&points, &mut out);
+            let batch = sources.compute_B_batch(&points);
+            out.iter()
+                .zip(&expected)
+                .zip(batch)
+                .for_each(|((b, expected), batch)| {
+                    assert_relative_eq!(b, expected, epsilon = 1e-15, max_relative = 1e-12);
+                    assert_relative_eq!(batch, expected, epsilon = 1e-15, max_relative = 1e-12);
+                });
+
+            sources.add_B_batch_into(&points, &mut out);
+            out.iter().zip(&expected).for_each(|(b, expected)| {
+                assert_relative_eq!(b, &(2.0 * expected), epsilon = 1e-15, max_relative = 1e-12)
+            });
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Output slice length must match input vectors length.")]
+    fn test_batch_into_length() {
+        let points = [Point3::origin(); 2];
+        assembly().add_B_batch_into(&points, &mut [Vector3::zeros(); 3]);
+    }
 }
 
 #[cfg(test)]
@@ -515,3 +601,161 @@ mod heterogeneous_collection_tests {
         test_B_magnet!(@small, &sources, "multi-sources-translate-rotate.csv", 1e-10);
     }
 }
+
+// MARK: Test Serde
+
+#[cfg(all(test, feature = "serde", feature = "mesh"))]
+mod serde_tests {
+    use std::f64::consts::{FRAC_PI_3, PI};
+
+    use nalgebra::{point, vector};
+
+    use super::*;
+    use crate::{
+        base::{Observer, SensorOutput, mesh::Tessellate, polygon::Polygon},
+        collections::{ObserverAssembly, sources},
+        currents::*,
+        magnets::*,
+        sensors::hall_effect::{HallLatch, HallSwitch, LinearHallSensor},
+    };
+
+    fn sources() -> SourceAssembly {
+        let rotation = UnitQuaternion::from_scaled_axis([PI / 3.0, PI / 4.0, PI / 5.0].into());
+        let prism = PrismMagnet::default().with_polygon(Polygon::new([
+            vector![0.0, 0.0],
+            vector![0.02, 0.0],
+            vector![0.0, 0.01],
+        ]));
+        let mesh = MeshMagnet::new(
+            [0.0, 0.0, 0.05],
+            rotation,
+            [0.0, 0.0, 1.0],
+            CuboidMagnet::default()
+                .with_dimensions([0.01; 3])
+                .tessellate(1.0)
+                .with_bvh(0.5),
+        );
+        let nested = sources!(
+            CircularCurrent::new([0.0, 0.01, 0.0], rotation, 0.02, 1.5),
+            PathCurrent::new(
+                [0.0; 3],
+                UnitQuaternion::identity(),
+                2.0,
+                vec![vector![0.0, 0.0, 0.0], vector![0.01, 0.0, 0.0]],
+            ),
+        )
+        .with_position([0.01, 0.0, 0.0])
+        .with_orientation(UnitQuaternion::from_scaled_axis(
+            [0.0, 0.0, FRAC_PI_3].into(),
+        ));
+
+        sources!(
+            CylinderMagnet::new([0.005, 0.01, 0.015], rotation, [0.1, 0.2, 0.3], 0.04, 0.05),
+            Dipole::default().with_moment([0.4, 0.5, 0.6]),
+            prism,
+            mesh,
+            nested,
+        )
+        .with_position([0.0, 0.0, -0.01])
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let sources = sources();
+        let json = serde_json::to_string(&sources).unwrap();
+        let restored: SourceAssembly = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored, sources);
+        assert_eq!(serde_json::to_string(&restored).unwrap(), json);
+        let point = point![0.01, 0.02, 0.03];
+        approx::assert_relative_eq!(
+            restored.compute_B(point),
+            sources.compute_B(point),
+            max_relative = 1e-12
+        );
+        let SourceComponent::Magnet(Magnet::Mesh(mesh)) = &restored[3] else {
+            panic!("Expected a mesh magnet.");
+        };
+        assert!(mesh.mesh().bvh().is_some());
+    }
+
+    #[test]
+    fn test_tagged_format() {
+        let json = r#"{
+            "pose": { "position": [0.0, 0.0, 0.01] },
+            "components": [
+                { "type": "CylinderMagnet", "diameter": 0.01, "height": 0.005 },
+                {
+                    "type": "CircularCurrent",
+                    "pose": { "orientation": [0.0, 0.0, 0.0, 2.0] },
+                    "current": 2.0
+                }
+            ]
+        }"#;
+        let sources: SourceAssembly = serde_json::from_str(json).unwrap();
+        let cylinder = CylinderMagnet::default()
+            .with_diameter(0.01)
+            .with_height(0.005);
+        let current = CircularCurrent::default().with_current(2.0);
+        // Components keep their global poses
+        let expected = SourceAssembly::new(
+            point![0.0, 0.0, 0.01],
+            UnitQuaternion::identity(),
+            [SourceComponent::from(cylinder), current.into()],
+        );
+        assert_eq!(sources, expected);
+
+        let error = serde_json::from_str::<SourceComponent>(
+            r#"{ "type": "CuboidMagnet", "dimensions": [-1.0, 1.0, 1.0] }"#,
+        )
+        .unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("Dimensions must be non-negative.")
+        );
+        assert!(
+            serde_json::from_str::<SourceComponent>(
+                r#"{ "type": "Dipole", "moments": [1.0, 0.0, 0.0] }"#
+            )
+            .is_err()
+        );
+        let error = serde_json::from_str::<SourceComponent>(
+            r#"{ "type": "Custom", "name": "Unknown", "source": {} }"#,
+        )
+        .unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("Custom source `Unknown` is not registered.")
+        );
+    }
+
+    #[test]
+    fn test_observers() {
+        let latch = HallLatch::new(
+            [0.0; 3],
+            UnitQuaternion::identity(),
+            [1.0, 0.0, 0.0],
+            0.02,
+            0.01,
+        );
+        let observers: ObserverAssembly = [
+            crate::collections::ObserverComponent::from(LinearHallSensor::default()),
+            HallSwitch::default().into(),
+            latch.into(),
+        ]
+        .into();
+        let json = serde_json::to_string(&observers).unwrap();
+        let restored: ObserverAssembly = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, observers);
+
+        let outputs = restored.read_all(&sources());
+        let json = serde_json::to_string(&outputs).unwrap();
+        assert_eq!(
+            serde_json::from_str::<Vec<SensorOutput>>(&json).unwrap(),
+            outputs
+        );
+        assert_eq!(restored[1].read(&sources()), outputs[1]);
+    }
+}
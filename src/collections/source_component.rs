@@ -3,6 +3,7 @@
  * Copyright 2025 Sira Pornsiriprasert <code@psira.me>
  */
 
+use alloc::boxed::Box;
 use enum_dispatch::enum_dispatch;
 
 use crate::{
@@ -10,13 +11,16 @@ use crate::{
     collections::{SourceArray, SourceAssembly},
     currents::{CircularCurrent, Current, PathCurrent, TriangleCurrent},
     magnets::{
-        CuboidMagnet, CylinderMagnet, Dipole, Magnet, SphereMagnet, TetrahedronMagnet,
+        CuboidMagnet, CylinderMagnet, Dipole, Magnet, PrismMagnet, SphereMagnet, TetrahedronMagnet,
         TriangleMagnet,
     },
 };
 
 #[cfg(feature = "mesh")]
-use crate::{currents::SheetCurrent, magnets::MeshMagnet};
+use crate::{
+    currents::SheetCurrent,
+    magnets::{MeshMagnet, TetMeshMagnet},
+};
 use nalgebra::{Point3, Vector3};
 
 #[derive(Debug, Clone)]
@@ -105,8 +109,10 @@ impl_transitive_from_magnet!(
     TetrahedronMagnet
 );
 
+impl_transitive_from_magnet!(PrismMagnet);
+
 #[cfg(feature = "mesh")]
-impl_transitive_from_magnet!(MeshMagnet);
+impl_transitive_from_magnet!(MeshMagnet, TetMeshMagnet);
 
 impl_transitive_from_current!(CircularCurrent, PathCurrent, TriangleCurrent);
 
@@ -123,3 +129,91 @@ where
         SourceAssembly::from(value).into()
     }
 }
+
+// MARK: Serde
+
+/// Serialized tagged by the type name, e.g., `{ "type": "CylinderMagnet", ... }` or
+/// `{ "type": "SourceAssembly", ... }`. Custom sources are serialized as
+/// `{ "type": "Custom", "name": ..., "source": ... }`, see
+/// [register_source](crate::collections::register_source).
+#[cfg(feature = "serde")]
+impl<T: Float + serde::Serialize> serde::Serialize for SourceComponent<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(serde::Serialize)]
+        #[serde(tag = "type")]
+        enum Tagged<'a, T: Float> {
+            SourceAssembly(&'a SourceAssembly<T>),
+            Custom {
+                name: &'static str,
+                source: &'a dyn crate::base::ErasedSerialize,
+            },
+        }
+
+        match self {
+            Self::Magnet(magnet) => magnet.serialize(serializer),
+            Self::Current(current) => current.serialize(serializer),
+            Self::Assembly(assembly) => Tagged::SourceAssembly(assembly).serialize(serializer),
+            Self::Custom(source) => {
+                let (name, source) = source.serialize_custom().ok_or_else(|| {
+                    serde::ser::Error::custom("Custom source does not support serialization.")
+                })?;
+                let custom: Tagged<T> = Tagged::Custom { name, source };
+                custom.serialize(serializer)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: Float + serde::Deserialize<'de>> serde::Deserialize<'de> for SourceComponent<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[cfg(feature = "std")]
+        use crate::collections::registry::CustomSource;
+
+        #[derive(serde::Deserialize)]
+        #[serde(tag = "type")]
+        enum Tagged<T: Float> {
+            CylinderMagnet(CylinderMagnet<T>),
+            CuboidMagnet(CuboidMagnet<T>),
+            Dipole(Dipole<T>),
+            PrismMagnet(PrismMagnet<T>),
+            SphereMagnet(SphereMagnet<T>),
+            TetrahedronMagnet(TetrahedronMagnet<T>),
+            TriangleMagnet(TriangleMagnet<T>),
+            #[cfg(feature = "mesh")]
+            MeshMagnet(MeshMagnet<T>),
+            #[cfg(feature = "mesh")]
+            TetMeshMagnet(TetMeshMagnet<T>),
+            CircularCurrent(CircularCurrent<T>),
+            PathCurrent(PathCurrent<T>),
+            #[cfg(feature = "mesh")]
+            SheetCurrent(SheetCurrent<T>),
+            TriangleCurrent(TriangleCurrent<T>),
+            SourceAssembly(SourceAssembly<T>),
+            #[cfg(feature = "std")]
+            Custom(CustomSource<T>),
+        }
+
+        Ok(match Tagged::deserialize(deserializer)? {
+            Tagged::CylinderMagnet(source) => source.into(),
+            Tagged::CuboidMagnet(source) => source.into(),
+            Tagged::Dipole(source) => source.into(),
+            Tagged::PrismMagnet(source) => source.into(),
+            Tagged::SphereMagnet(source) => source.into(),
+            Tagged::TetrahedronMagnet(source) => source.into(),
+            Tagged::TriangleMagnet(source) => source.into(),
+            #[cfg(feature = "mesh")]
+            Tagged::MeshMagnet(source) => source.into(),
+            #[cfg(feature = "mesh")]
+            Tagged::TetMeshMagnet(source) => source.into(),
+            Tagged::CircularCurrent(source) => source.into(),
+            Tagged::PathCurrent(source) => source.into(),
+            #[cfg(feature = "mesh")]
+            Tagged::SheetCurrent(source) => source.into(),
+            Tagged::TriangleCurrent(source) => source.into(),
+            Tagged::SourceAssembly(source) => source.into(),
+            #[cfg(feature = "std")]
+            Tagged::Custom(CustomSource(source)) => Self::Custom(source),
+        })
+    }
+}
@@ -13,38 +13,53 @@ macro_rules! impl_group_compute_B {
         }
 
         #[inline]
-        fn compute_B_batch(&self, points: &[Point3<T>]) -> Vec<Vector3<T>> {
+        fn compute_B_batch_into(&self, points: &[Point3<T>], out: &mut [Vector3<T>]) {
+            out.fill(Vector3::zeros());
+            self.add_B_batch_into(points, out);
+        }
+
+        /// Adds the field of each component in turn, so no buffer is allocated per
+        /// component. Large batches are parallelized over points within each component.
+        /// Smaller batches are parallelized over components if the `rayon` feature is
+        /// enabled, summing chunks of points in stack buffers.
+        #[inline]
+        fn add_B_batch_into(&self, points: &[Point3<T>], out: &mut [Vector3<T>]) {
+            assert_eq!(
+                out.len(),
+                points.len(),
+                "Output slice length must match input vectors length."
+            );
+
             #[cfg(feature = "rayon")]
-            {
+            if points.len() <= crate::base::PARALLEL_THRESHOLD && self.nodes.len() > 1 {
+                use crate::base::CHUNK;
                 use rayon::prelude::*;
 
-                // Parallel iterate over nodes directly to avoid collecting into a Vec
-                self.nodes
-                    .par_iter()
-                    .map(|node| node.component.compute_B_batch(points))
-                    .reduce(
-                        || vec![Vector3::zeros(); points.len()],
-                        |mut acc, child_batch| {
-                            acc.iter_mut()
-                                .zip(child_batch)
-                                .for_each(|(sum, b)| *sum += b);
+                for (out, points) in out.chunks_mut(CHUNK).zip(points.chunks(CHUNK)) {
+                    let sum = self
+                        .nodes
+                        .par_iter()
+                        .fold(
+                            || [Vector3::zeros(); CHUNK],
+                            |mut acc, node| {
+                                node.component
+                                    .add_B_batch_into(points, &mut acc[..points.len()]);
+                                acc
+                            },
+                        )
+                        .reduce_with(|mut acc, other| {
+                            acc.iter_mut().zip(other).for_each(|(sum, b)| *sum += b);
                             acc
-                        },
-                    )
+                        });
+                    if let Some(sum) = sum {
+                        out.iter_mut().zip(sum).for_each(|(o, b)| *o += b);
+                    }
+                }
+                return;
             }
 
-            #[cfg(not(feature = "rayon"))]
-            {
-                // Standard sequential fold
-                self.components()
-                    .fold(vec![Vector3::zeros(); points.len()], |mut acc, source| {
-                        let child_batch = source.compute_B_batch(points);
-                        acc.iter_mut()
-                            .zip(child_batch)
-                            .for_each(|(sum, b)| *sum += b);
-                        acc
-                    })
-            }
+            self.components()
+                .for_each(|source| source.add_B_batch_into(points, out));
         }
     };
 }
@@ -65,7 +80,16 @@ pub(crate) fn write_tree<'a, I: 'a>(
         write!(f, " {}{}{}: ", indent, branch, i)?;
 
         let extension = if is_last { "    " } else { "│   " };
-        let next_indent = format!("{}{}", indent, extension);
+        #[cfg(feature = "alloc")]
+        let next_indent = alloc::format!("{}{}", indent, extension);
+        #[cfg(not(feature = "alloc"))]
+        let next_indent = {
+            use core::fmt::Write;
+
+            let mut next_indent = Indent::default();
+            write!(next_indent, "{}{}", indent, extension)?;
+            next_indent
+        };
 
         // Delegate to the provided closure
         format_leaf(leaf, f, &next_indent)?;
@@ -76,3 +100,45 @@ pub(crate) fn write_tree<'a, I: 'a>(
     }
     Ok(())
 }
+
+/// Stack buffer for the indentation of [write_tree] without `alloc`.
+///
+/// Fits 16 nesting levels. Deeper trees fail to format.
+#[cfg(not(feature = "alloc"))]
+struct Indent {
+    bytes: [u8; 96],
+    len: usize,
+}
+
+#[cfg(not(feature = "alloc"))]
+impl Default for Indent {
+    fn default() -> Self {
+        Self {
+            bytes: [0; 96],
+            len: 0,
+        }
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+impl core::fmt::Write for Indent {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        let end = self.len + s.len();
+        self.bytes
+            .get_mut(self.len..end)
+            .ok_or(core::fmt::Error)?
+            .copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+impl core::ops::Deref for Indent {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        // Only whole strings are written, so the bytes are valid UTF-8.
+        core::str::from_utf8(&self.bytes[..self.len]).unwrap_or_default()
+    }
+}
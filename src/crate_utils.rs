@@ -29,6 +29,7 @@ macro_rules! need_unstable {
     ($($body:item)*) => {
             #[cfg(feature = "unstable")]
             mod unstable {
+                #[allow(unused_imports)]
                 use super::*;
                 $(
                     #[cfg_attr(docsrs, doc(cfg(feature = "unstable")))]
@@ -81,6 +82,10 @@ need_alloc! {
     pub(crate) fn format_vertices_count<T: Float>(_: &mut Formatter, v: &[Vector3<T>]) -> alloc::string::String {
         alloc::format!("{}", v.len())
     }
+
+    pub(crate) fn format_polygon_count<T: Float>(_: &mut Formatter, polygon: &crate::base::polygon::Polygon<T>) -> alloc::string::String {
+        alloc::format!("{}", polygon.vertices().len())
+    }
 }
 
 #[cfg(feature = "mesh")]
@@ -91,6 +96,14 @@ pub(crate) fn format_trimesh_count<T: Float>(
     alloc::format!("{}", mesh.triangles().len())
 }
 
+#[cfg(feature = "mesh")]
+pub(crate) fn format_tetmesh_count<T: Float>(
+    _f: &mut Formatter,
+    mesh: &crate::base::mesh::TetMesh<T>,
+) -> alloc::string::String {
+    alloc::format!("{}", mesh.tetrahedra().len())
+}
+
 macro_rules! assert_eq_lens {
     ($str_err:expr, [$ref_vec:expr $(, $vec:expr)+]) => {
         {
@@ -132,6 +145,34 @@ macro_rules! impl_parallel {
             }
         }
     };
+    ($func:ident, rayon_threshold: $threshold:expr, input: $inputs:expr, output: $out:expr, args: [$($func_args:expr),* $(,)?], scratch: $scratch:expr) => {
+        {
+            assert_eq!($out.len(), $inputs.len(), "Output slice length must match input vectors length.");
+
+            #[cfg(feature = "rayon")]
+            {
+                if $inputs.len() > $threshold {
+                    use rayon::prelude::*;
+                    $out.par_iter_mut()
+                        .zip($inputs.par_iter())
+                        .for_each_init(|| $scratch, |scratch, (o, p)| *o = $func(*p, $($func_args,)* scratch));
+                } else {
+                    let mut scratch = $scratch;
+                    $out.iter_mut()
+                        .zip($inputs.iter())
+                        .for_each(|(o, p)| *o = $func(*p, $($func_args,)* &mut scratch));
+                }
+            }
+
+            #[cfg(not(feature = "rayon"))]
+            {
+                let mut scratch = $scratch;
+                $out.iter_mut()
+                    .zip($inputs.iter())
+                    .for_each(|(o, p)| *o = $func(*p, $($func_args,)* &mut scratch));
+            }
+        }
+    };
 }
 pub(crate) use impl_parallel;
 
@@ -180,6 +221,13 @@ pub(crate) use impl_parallel_sum;
 /// This macro handles the creation of the struct, constructor logic (including `Into` conversions),
 /// input validation, getters/setters, and the implementation of the `Source` trait.
 ///
+/// A `validate` expression may refer to the other arguments, which are references to the
+/// current values in the setters.
+///
+/// The optional `cache` block declares fields derived from the arguments. They are
+/// recomputed whenever an argument is set, skipped by serde, and passed by reference to
+/// `field_fn` and its batch counterpart after the arguments.
+///
 /// # Example
 ///
 /// ```text
@@ -194,6 +242,9 @@ pub(crate) use impl_parallel_sum;
 ///             error "Bad dim.",
 ///         lucky_number: T
 ///     }
+///     cache: {
+///         volume: T = dimensions.product(),
+///     }
 ///     arg_display: "pol={}, dim={}, lucky={}";
 ///     arg_fmt: [format_vector3, format_float]
 ///     docs: {
@@ -235,7 +286,7 @@ macro_rules! define_source {
         }
     };
 
-    (@setters $struct_name:ident, $(
+    (@setters $struct_name:ident, $all:tt, $(
         (
             $arg:ident,
             $arg_type:ty,
@@ -246,32 +297,105 @@ macro_rules! define_source {
     )*) => {
         impl<T: crate::base::Float> $struct_name<T> {
             $(
-                // Setters
-                concat_idents::concat_idents!(fn_name = set_, $arg {
-                    #[inline]
-                    pub fn fn_name(&mut self, $arg: $crate::crate_utils::define_source!(@arg_type_decl $arg_type $(, $is_value)?)) {
-                        let $arg: $arg_type = $crate::crate_utils::define_source!(@arg_into $arg $(, $is_value)?);
-                        $(
-                            #[allow(clippy::neg_cmp_op_on_partial_ord)]
-                            if !($validate) {
-                                panic!($error);
-                            }
-                        )?
-                        self.$arg = $arg;
-                    }
+                $crate::crate_utils::define_source!(@setter $all $arg, $arg_type, [$(@$is_value)?], [$($validate)?], [$($error)?]);
+            )*
+        }
+    };
+
+    (@setter [$($all:ident),*] $arg:ident, $arg_type:ty, [$(@$is_value:ident)?], [], []) => {
+        // Setters
+        concat_idents::concat_idents!(fn_name = set_, $arg {
+            #[inline]
+            pub fn fn_name(&mut self, $arg: $crate::crate_utils::define_source!(@arg_type_decl $arg_type $(, $is_value)?)) {
+                self.$arg = $crate::crate_utils::define_source!(@arg_into $arg $(, $is_value)?);
+                self.refresh_cache();
+            }
+        });
+
+        // Buliders (with setters)
+        concat_idents::concat_idents!(fn_name = with_, $arg {
+            #[inline]
+            pub fn fn_name(mut self, $arg: $crate::crate_utils::define_source!(@arg_type_decl $arg_type $(, $is_value)?)) -> Self {
+                concat_idents::concat_idents!(ident = set_, $arg {
+                    self.ident($arg);
                 });
+                self
+            }
+        });
+    };
 
-                // Buliders (with setters)
-                concat_idents::concat_idents!(fn_name = with_, $arg {
-                    #[inline]
-                    pub fn fn_name(mut self, $arg: $crate::crate_utils::define_source!(@arg_type_decl $arg_type $(, $is_value)?)) -> Self {
-                        concat_idents::concat_idents!(ident = set_, $arg {
-                            self.ident($arg);
-                        });
-                        self
+    (@setter [$($all:ident),*] $arg:ident, $arg_type:ty, [$(@$is_value:ident)?], [$validate:expr], [$error:literal]) => {
+        // Fallible setters
+        concat_idents::concat_idents!(fn_name = try_set_, $arg {
+            #[doc = concat!("Sets `", stringify!($arg), "`, or returns [Error::InvalidGeometry](crate::Error::InvalidGeometry) if it is invalid.")]
+            #[inline]
+            pub fn fn_name(&mut self, $arg: $crate::crate_utils::define_source!(@arg_type_decl $arg_type $(, $is_value)?)) -> Result<(), crate::Error> {
+                let value: $arg_type = $crate::crate_utils::define_source!(@arg_into $arg $(, $is_value)?);
+                // The validation may refer to the other arguments
+                #[allow(unused_variables)]
+                let Self { $($all,)* .. } = &*self;
+                let $arg = value;
+                #[allow(clippy::neg_cmp_op_on_partial_ord)]
+                if !($validate) {
+                    return Err(crate::Error::InvalidGeometry($error));
+                }
+                self.$arg = $arg;
+                self.refresh_cache();
+                Ok(())
+            }
+        });
+
+        // Setters
+        concat_idents::concat_idents!(fn_name = set_, $arg {
+            #[inline]
+            pub fn fn_name(&mut self, $arg: $crate::crate_utils::define_source!(@arg_type_decl $arg_type $(, $is_value)?)) {
+                concat_idents::concat_idents!(ident = try_set_, $arg {
+                    if let Err(error) = self.ident($arg) {
+                        panic!("{error}");
                     }
                 });
-            )*
+            }
+        });
+
+        // Fallible builders
+        concat_idents::concat_idents!(fn_name = try_with_, $arg {
+            #[doc = concat!("Returns the source with `", stringify!($arg), "`, or [Error::InvalidGeometry](crate::Error::InvalidGeometry) if it is invalid.")]
+            #[inline]
+            pub fn fn_name(mut self, $arg: $crate::crate_utils::define_source!(@arg_type_decl $arg_type $(, $is_value)?)) -> Result<Self, crate::Error> {
+                concat_idents::concat_idents!(ident = try_set_, $arg {
+                    self.ident($arg)?;
+                });
+                Ok(self)
+            }
+        });
+
+        // Buliders (with setters)
+        concat_idents::concat_idents!(fn_name = with_, $arg {
+            #[inline]
+            pub fn fn_name(mut self, $arg: $crate::crate_utils::define_source!(@arg_type_decl $arg_type $(, $is_value)?)) -> Self {
+                concat_idents::concat_idents!(ident = set_, $arg {
+                    self.ident($arg);
+                });
+                self
+            }
+        });
+    };
+
+    // MARK: Cache
+    (@refresh_cache [$($arg:ident),*] []) => {
+        #[inline]
+        fn refresh_cache(&mut self) {}
+    };
+
+    (@refresh_cache [$($arg:ident),*] [$($cache:ident = $cache_init:expr),+]) => {
+        /// Recomputes the cached values derived from the arguments.
+        fn refresh_cache(&mut self) {
+            let ($($cache,)+) = {
+                #[allow(unused_variables)]
+                let Self { $($arg,)* .. } = &*self;
+                ($($cache_init,)+)
+            };
+            $(self.$cache = $cache;)+
         }
     };
 
@@ -286,6 +410,11 @@ macro_rules! define_source {
                 $(; validate $validate:expr; error $error:literal)?
             ),* $(,)?
         }
+        $(
+            cache: {
+                $($cache:ident : $cache_type:ty = $cache_init:expr),* $(,)?
+            }
+        )?
         arg_display: $arg_display:expr;
         arg_fmt: [ $($arg_fmt:ident),* $(,)? ]
 
@@ -295,16 +424,21 @@ macro_rules! define_source {
     } => {
         $(#[$meta])*
         #[derive(Debug, Clone, PartialEq, Eq)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize))]
         pub struct $name<T: crate::base::Float = f64> {
             pose: crate::base::Pose<T>,
             $(
                 $arg: $arg_type,
             )*
+            $($(
+                #[cfg_attr(feature = "serde", serde(skip))]
+                $cache: $cache_type,
+            )*)?
         }
 
         $crate::crate_utils::define_source!(@getters $name, $(($arg, $arg_type, [$(@$is_value)?]))*);
 
-        $crate::crate_utils::define_source!(@setters $name, $(
+        $crate::crate_utils::define_source!(@setters $name, [$($arg),*], $(
             (
                 $arg,
                 $arg_type,
@@ -317,6 +451,10 @@ macro_rules! define_source {
         impl<T: crate::base::Float> $name<T> {
             // MARK: New
             $(#[$new_docs])*
+            ///
+            /// # Panics
+            ///
+            /// Panics if an argument is invalid, see [try_new](Self::try_new).
             pub fn new(
                 position: impl Into<nalgebra::Point3<T>>,
                 orientation: nalgebra::UnitQuaternion<T>,
@@ -324,6 +462,17 @@ macro_rules! define_source {
                     $arg: $crate::crate_utils::define_source!(@arg_type_decl $arg_type $(, $is_value)?)
                 ),*
             ) -> Self {
+                Self::try_new(position, orientation, $($arg),*).unwrap_or_else(|error| panic!("{error}"))
+            }
+
+            #[doc = concat!("Constructs a [", stringify!($name), "], or returns [Error::InvalidGeometry](crate::Error::InvalidGeometry) if an argument is invalid.")]
+            pub fn try_new(
+                position: impl Into<nalgebra::Point3<T>>,
+                orientation: nalgebra::UnitQuaternion<T>,
+                $(
+                    $arg: $crate::crate_utils::define_source!(@arg_type_decl $arg_type $(, $is_value)?)
+                ),*
+            ) -> Result<Self, crate::Error> {
                 let pose = crate::base::Pose::new(position.into(), orientation.into());
 
                 $(
@@ -335,26 +484,34 @@ macro_rules! define_source {
                     $(
                         #[allow(clippy::neg_cmp_op_on_partial_ord)]
                         if !($validate) {
-                            panic!($error);
+                            return Err(crate::Error::InvalidGeometry($error));
                         }
                     )?
                 )*
 
-                $name {
+                let mut source = $name {
                     pose,
-                    $($arg),*
-                }
+                    $($arg,)*
+                    $($($cache: Default::default(),)*)?
+                };
+                source.refresh_cache();
+                Ok(source)
             }
 
+            $crate::crate_utils::define_source!(@refresh_cache [$($arg),*] [$($($cache = $cache_init),*)?]);
+
             crate::base::pose::impl_pose_methods!();
         }
 
         impl<T: crate::base::Float> Default for $name<T> {
             fn default() -> Self {
-                Self {
+                let mut source = Self {
                     pose: Default::default(),
-                    $($arg: $arg_default),*
-                }
+                    $($arg: $arg_default,)*
+                    $($($cache: Default::default(),)*)?
+                };
+                source.refresh_cache();
+                source
             }
         }
 
@@ -366,24 +523,21 @@ macro_rules! define_source {
                     self.position(),
                     self.orientation(),
                     $( $crate::crate_utils::define_source!(@pass_arg self.$arg $(, $is_value)?), )*
+                    $($( &self.$cache, )*)?
                 )
             }
 
-            #[cfg(feature = "alloc")]
-            fn compute_B_batch(&self, points: &[nalgebra::Point3<T>]) -> alloc::vec::Vec<nalgebra::Vector3<T>> {
-                let mut out = alloc::vec![nalgebra::Vector3::zeros(); points.len()];
-
+            fn compute_B_batch_into(&self, points: &[nalgebra::Point3<T>], out: &mut [nalgebra::Vector3<T>]) {
                 concat_idents::concat_idents!(fn_name = $field_fn, _batch {
                     crate::fields::fn_name(
                         points,
                         self.position(),
                         self.orientation(),
                         $( $crate::crate_utils::define_source!(@pass_arg self.$arg $(, $is_value)?), )*
-                        &mut out,
+                        $($( &self.$cache, )*)?
+                        out,
                     );
                 });
-
-                out
             }
 
             // MARK: Display
@@ -414,6 +568,57 @@ macro_rules! define_source {
                 <Self as crate::base::Source<T>>::format(self, f, "")
             }
         }
+
+        // MARK: Serde
+        // Omitted fields take their defaults, and the fields are validated as in `new`.
+        #[cfg(feature = "serde")]
+        concat_idents::concat_idents!(mod_name = serde_, $name {
+            #[allow(non_snake_case)]
+            mod mod_name {
+                #[allow(unused_imports)]
+                use super::*;
+
+                #[derive(serde::Deserialize)]
+                #[serde(default, deny_unknown_fields)]
+                pub(super) struct $name<T: crate::base::Float> {
+                    pose: crate::base::Pose<T>,
+                    $($arg: $arg_type,)*
+                }
+
+                impl<T: crate::base::Float> Default for $name<T> {
+                    fn default() -> Self {
+                        Self {
+                            pose: Default::default(),
+                            $($arg: $arg_default),*
+                        }
+                    }
+                }
+
+                impl<'de, T> serde::Deserialize<'de> for super::$name<T>
+                where
+                    T: crate::base::Float + serde::Deserialize<'de>,
+                {
+                    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                        let $name { pose, $($arg),* } = $name::deserialize(deserializer)?;
+                        $(
+                            $(
+                                #[allow(clippy::neg_cmp_op_on_partial_ord)]
+                                if !($validate) {
+                                    return Err(serde::de::Error::custom($error));
+                                }
+                            )?
+                        )*
+                        let mut source = Self {
+                            pose,
+                            $($arg,)*
+                            $($($cache: Default::default(),)*)?
+                        };
+                        source.refresh_cache();
+                        Ok(source)
+                    }
+                }
+            }
+        });
     }
 }
 pub(crate) use define_source;
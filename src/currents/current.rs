@@ -3,9 +3,6 @@
  * Copyright 2025 Sira Pornsiriprasert <code@psira.me>
  */
 
-#[cfg(feature = "std")]
-use derive_more::Display;
-
 use enum_dispatch::enum_dispatch;
 use nalgebra::{Point3, Vector3};
 
@@ -21,13 +18,24 @@ use crate::base::{Float, Pose, Source, Transform};
 
 /// Current source variants.
 #[derive(Clone, Debug, PartialEq, Eq)]
-#[cfg_attr(feature = "std", derive(Display))]
 #[enum_dispatch(Source<T>, Transform<T>,)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type"))]
 pub enum Current<T: Float = f64> {
+    #[cfg_attr(feature = "serde", serde(rename = "CircularCurrent"))]
     Circular(CircularCurrent<T>),
     #[cfg(feature = "alloc")]
+    #[cfg_attr(feature = "serde", serde(rename = "PathCurrent"))]
     Path(PathCurrent<T>),
     #[cfg(feature = "mesh")]
+    #[cfg_attr(feature = "serde", serde(rename = "SheetCurrent"))]
     Sheet(SheetCurrent<T>),
+    #[cfg_attr(feature = "serde", serde(rename = "TriangleCurrent"))]
     Triangle(TriangleCurrent<T>),
 }
+
+impl<T: Float> core::fmt::Display for Current<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        <Self as Source<T>>::format(self, f, "")
+    }
+}
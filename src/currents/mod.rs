@@ -9,6 +9,8 @@ mod circular;
 mod current;
 #[cfg(feature = "alloc")]
 mod path;
+#[cfg(feature = "alloc")]
+mod planar_coil;
 #[cfg(feature = "mesh")]
 mod sheet;
 mod triangle;
@@ -17,6 +19,8 @@ pub use circular::CircularCurrent;
 pub use current::Current;
 #[cfg(feature = "alloc")]
 pub use path::PathCurrent;
+#[cfg(feature = "alloc")]
+pub use planar_coil::{PlanarCoil, SpiralShape};
 #[cfg(feature = "mesh")]
 pub use sheet::SheetCurrent;
 pub use triangle::TriangleCurrent;
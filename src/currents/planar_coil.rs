@@ -0,0 +1,666 @@
+/*
+ * Magba is licensed under The 3-Clause BSD, see LICENSE.
+ * Copyright 2025 Sira Pornsiriprasert <code@psira.me>
+ */
+
+//! Generators for planar (PCB) spiral coils.
+
+use alloc::vec::Vec;
+use nalgebra::{Rotation2, UnitQuaternion, Vector2, Vector3};
+use num_traits::Float as NumFloat;
+
+use crate::{Error, base::Float, currents::PathCurrent};
+
+/// Outline of a planar spiral.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SpiralShape<T: Float = f64> {
+    /// Square spiral with sides parallel to the local x and y axes.
+    Square,
+    /// Hexagonal spiral with two sides parallel to the local y axis and two corners on it.
+    Hexagonal,
+    /// Circular spiral approximated by `segments` straight segments per turn.
+    Circular { segments: usize },
+    /// Racetrack (stadium) spiral with straight sides of `straight_length` (m) along the
+    /// local x axis and semicircular ends approximated by `arc_segments` segments per half turn.
+    Racetrack {
+        straight_length: T,
+        arc_segments: usize,
+    },
+}
+
+/// Generator for planar spiral coils, such as PCB coils.
+///
+/// The coil is built in the local XY plane and centered at the origin. Each copper layer
+/// holds one spiral. Even layers (0, 2, ...) spiral inward and odd layers spiral outward,
+/// mirrored so that the current circulates in the same direction (counterclockwise seen
+/// from +z for a positive current) on every layer. Consecutive layers are connected by
+/// vertical vias, alternating between the inner and the outer end of the spirals.
+///
+/// The spiral dimensions refer to the trace centerline, except `outer_diameter` which is the
+/// outer extent of the copper, i.e., the outer centerline is at `(outer_diameter - trace_width) / 2`
+/// from the center. For square and hexagonal spirals, the diameter is measured across flats.
+///
+/// # Examples
+///
+/// ```
+/// # use magba::currents::{PlanarCoil, SpiralShape};
+/// # use magba::prelude::*;
+/// # use nalgebra::point;
+/// let coil = PlanarCoil::new(
+///     SpiralShape::Square,    // shape
+///     0.02,                   // outer diameter (m)
+///     5.0,                    // turns per layer
+///     0.5e-3,                 // pitch (m)
+/// )
+/// .with_trace_width(0.2e-3)              // trace width (m)
+/// .with_layers(vec![0.0, -1.6e-3])       // copper layers z-positions (m)
+/// .with_current(0.1);                    // current (A)
+///
+/// let path = coil.to_path_current();
+/// let b_field = path.compute_B(point![0.0, 0.0, 1e-3]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlanarCoil<T: Float = f64> {
+    shape: SpiralShape<T>,
+    outer_diameter: T,
+    turns: T,
+    pitch: T,
+    trace_width: T,
+    layers: Vec<T>,
+    current: T,
+}
+
+impl<T: Float> PlanarCoil<T> {
+    // MARK: New
+
+    /// Construct a single-layer [PlanarCoil] at `z = 0` with a trace width of half the pitch
+    /// and a current of 1 A.
+    ///
+    /// # Panics
+    ///
+    /// - If `outer_diameter`, `turns`, or `pitch` is not positive.
+    /// - If the spiral does not fit in the outer diameter.
+    /// - If a circular or racetrack shape has zero segments.
+    pub fn new(shape: SpiralShape<T>, outer_diameter: T, turns: T, pitch: T) -> Self {
+        Self::try_new(shape, outer_diameter, turns, pitch).unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Construct a [PlanarCoil], or return [Error::InvalidGeometry] where
+    /// [PlanarCoil::new] panics.
+    pub fn try_new(
+        shape: SpiralShape<T>,
+        outer_diameter: T,
+        turns: T,
+        pitch: T,
+    ) -> Result<Self, Error> {
+        let invalid = |message| Err(Error::InvalidGeometry(message));
+        match shape {
+            SpiralShape::Circular { segments } if segments < 3 => {
+                return invalid("Circular spiral must have at least 3 segments per turn.");
+            }
+            SpiralShape::Racetrack {
+                straight_length,
+                arc_segments,
+            } => {
+                if straight_length < T::zero() {
+                    return invalid("Straight length cannot be negative.");
+                }
+                if arc_segments == 0 {
+                    return invalid("Racetrack spiral must have at least 1 arc segment.");
+                }
+            }
+            _ => {}
+        }
+        if outer_diameter <= T::zero() {
+            return invalid("Outer diameter must be positive.");
+        }
+        if turns <= T::zero() {
+            return invalid("Turns must be positive.");
+        }
+        if pitch <= T::zero() {
+            return invalid("Pitch must be positive.");
+        }
+
+        let coil = Self {
+            shape,
+            outer_diameter,
+            turns,
+            pitch,
+            trace_width: pitch / T::from_f64(2.0).unwrap(),
+            layers: alloc::vec![T::zero()],
+            current: T::one(),
+        };
+        coil.validate_fit()?;
+        Ok(coil)
+    }
+
+    fn validate_fit(&self) -> Result<(), Error> {
+        let invalid = |message| Err(Error::InvalidGeometry(message));
+        if self.trace_width <= T::zero() {
+            return invalid("Trace width must be positive.");
+        }
+        if self.trace_width >= self.pitch {
+            return invalid("Trace width must be smaller than pitch.");
+        }
+        if self.inner_radius() <= self.trace_width / T::from_f64(2.0).unwrap() {
+            return invalid("Spiral does not fit in the outer diameter.");
+        }
+        Ok(())
+    }
+
+    // MARK: Getters
+
+    #[inline]
+    pub fn shape(&self) -> SpiralShape<T> {
+        self.shape
+    }
+
+    #[inline]
+    pub fn outer_diameter(&self) -> T {
+        self.outer_diameter
+    }
+
+    #[inline]
+    pub fn turns(&self) -> T {
+        self.turns
+    }
+
+    #[inline]
+    pub fn pitch(&self) -> T {
+        self.pitch
+    }
+
+    #[inline]
+    pub fn trace_width(&self) -> T {
+        self.trace_width
+    }
+
+    /// z-positions of the copper layers (m).
+    #[inline]
+    pub fn layers(&self) -> &[T] {
+        &self.layers
+    }
+
+    #[inline]
+    pub fn current(&self) -> T {
+        self.current
+    }
+
+    /// Centerline radius (or apothem) of the outermost turn (m).
+    #[inline]
+    pub fn outer_radius(&self) -> T {
+        (self.outer_diameter - self.trace_width) / T::from_f64(2.0).unwrap()
+    }
+
+    /// Centerline radius (or apothem) of the innermost turn (m).
+    #[inline]
+    pub fn inner_radius(&self) -> T {
+        self.outer_radius() - self.pitch * self.turns
+    }
+
+    // MARK: With setters
+
+    /// # Panics
+    ///
+    /// If the trace width is not positive or not smaller than the pitch.
+    #[inline]
+    pub fn with_trace_width(self, trace_width: T) -> Self {
+        self.try_with_trace_width(trace_width)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Return the coil with `trace_width`, or [Error::InvalidGeometry] if it is not
+    /// positive, not smaller than the pitch, or does not fit.
+    #[inline]
+    pub fn try_with_trace_width(mut self, trace_width: T) -> Result<Self, Error> {
+        self.trace_width = trace_width;
+        self.validate_fit()?;
+        Ok(self)
+    }
+
+    /// Set the z-positions of the copper layers (m), in the order the current visits them.
+    ///
+    /// # Panics
+    ///
+    /// If `layers` is empty.
+    #[inline]
+    pub fn with_layers(self, layers: impl Into<Vec<T>>) -> Self {
+        self.try_with_layers(layers)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Return the coil with the copper layers, or [Error::InvalidGeometry] if `layers` is
+    /// empty.
+    #[inline]
+    pub fn try_with_layers(mut self, layers: impl Into<Vec<T>>) -> Result<Self, Error> {
+        let layers = layers.into();
+        if layers.is_empty() {
+            return Err(Error::InvalidGeometry("Coil must have at least one layer."));
+        }
+        self.layers = layers;
+        Ok(self)
+    }
+
+    #[inline]
+    pub fn with_current(mut self, current: T) -> Self {
+        self.current = current;
+        self
+    }
+
+    // MARK: Geometry
+
+    /// Number of polygon sides (or segments) per turn.
+    fn sides(&self) -> usize {
+        match self.shape {
+            SpiralShape::Square => 4,
+            SpiralShape::Hexagonal => 6,
+            SpiralShape::Circular { segments } => segments,
+            SpiralShape::Racetrack { arc_segments, .. } => 2 * arc_segments,
+        }
+    }
+
+    /// Centerline point of the spiral at angle `theta` (rad) for the radius (or apothem) `r`.
+    fn spiral_point(&self, theta: T, r: T) -> Vector2<T> {
+        let two_pi = T::two_pi();
+        match self.shape {
+            SpiralShape::Square | SpiralShape::Hexagonal => {
+                // Distance to a polygon side along the ray at theta
+                let step = two_pi / T::from_usize(self.sides()).unwrap();
+                let side = NumFloat::round(theta / step) * step;
+                let radius = r / NumFloat::cos(theta - side);
+                Vector2::new(NumFloat::cos(theta), NumFloat::sin(theta)) * radius
+            }
+            SpiralShape::Circular { .. } => {
+                Vector2::new(NumFloat::cos(theta), NumFloat::sin(theta)) * r
+            }
+            SpiralShape::Racetrack {
+                straight_length, ..
+            } => {
+                let half = straight_length / T::from_f64(2.0).unwrap();
+                let center = if NumFloat::cos(theta) >= T::zero() {
+                    half
+                } else {
+                    -half
+                };
+                Vector2::new(center + r * NumFloat::cos(theta), r * NumFloat::sin(theta))
+            }
+        }
+    }
+
+    /// Centerline vertices of the base spiral in the XY plane, running inward
+    /// counterclockwise and ending on the positive x axis.
+    fn base_spiral(&self) -> Vec<Vector2<T>> {
+        let two_pi = T::two_pi();
+        let step = two_pi / T::from_usize(self.sides()).unwrap();
+        let theta_start = -two_pi * self.turns;
+        let r_in = self.inner_radius();
+        let radius = |theta: T| r_in - self.pitch * theta / two_pi;
+
+        // Polygon corners are offset by half a step so that sides are axis-aligned.
+        // Racetrack arcs start at pi/2, where the straight sections begin.
+        let offset = match self.shape {
+            SpiralShape::Square | SpiralShape::Hexagonal => step / T::from_f64(2.0).unwrap(),
+            SpiralShape::Circular { .. } => T::zero(),
+            SpiralShape::Racetrack { .. } => T::frac_pi_2(),
+        };
+        let eps = step * T::from_f64(1e-9).unwrap();
+
+        let mut vertices = Vec::new();
+        vertices.push(self.spiral_point(theta_start, radius(theta_start)));
+
+        let first = NumFloat::ceil((theta_start - offset + eps) / step)
+            .to_i64()
+            .unwrap();
+        let last = NumFloat::floor((-offset - eps) / step).to_i64().unwrap();
+        for k in first..=last {
+            let theta = offset + step * T::from_i64(k).unwrap();
+            let r = radius(theta);
+
+            match self.shape {
+                SpiralShape::Racetrack {
+                    straight_length,
+                    arc_segments,
+                } if k.rem_euclid(arc_segments as i64) == 0 => {
+                    // Straight section between the two arcs at the top or the bottom
+                    let half = straight_length / T::from_f64(2.0).unwrap();
+                    let (x, y) = if k.rem_euclid(2 * arc_segments as i64) == 0 {
+                        (half, r)
+                    } else {
+                        (-half, -r)
+                    };
+                    vertices.push(Vector2::new(x, y));
+                    vertices.push(Vector2::new(-x, y));
+                }
+                _ => vertices.push(self.spiral_point(theta, r)),
+            }
+        }
+
+        vertices.push(self.spiral_point(T::zero(), r_in));
+        vertices
+    }
+
+    /// Centerline vertices of each layer (m), in the order the current flows.
+    ///
+    /// The first vertex of each layer coincides in XY with the last vertex of the previous layer,
+    /// where the via is placed. If the turns are not a multiple of 1/2, the layers are rotated
+    /// about the z axis to keep the vias aligned.
+    pub fn layer_vertices(&self) -> Vec<Vec<Vector3<T>>> {
+        let base = self.base_spiral();
+        let mirrored: Vec<Vector2<T>> =
+            base.iter().rev().map(|v| Vector2::new(v.x, -v.y)).collect();
+
+        let mut layers: Vec<Vec<Vector3<T>>> = Vec::with_capacity(self.layers.len());
+        for (i, &z) in self.layers.iter().enumerate() {
+            let spiral = if i % 2 == 0 { &base } else { &mirrored };
+
+            let rotation = match layers.last().and_then(|l| l.last()) {
+                Some(prev) => {
+                    let start = spiral[0];
+                    Rotation2::new(
+                        NumFloat::atan2(prev.y, prev.x) - NumFloat::atan2(start.y, start.x),
+                    )
+                }
+                None => Rotation2::identity(),
+            };
+
+            layers.push(
+                spiral
+                    .iter()
+                    .map(|v| {
+                        let v = rotation * v;
+                        Vector3::new(v.x, v.y, z)
+                    })
+                    .collect(),
+            );
+        }
+        layers
+    }
+
+    /// Vertices of a single current path through all layers and vias (m).
+    pub fn path_vertices(&self) -> Vec<Vector3<T>> {
+        self.layer_vertices().into_iter().flatten().collect()
+    }
+
+    // MARK: Sources
+
+    /// Model the coil as a single filamentary [PathCurrent] through all layers and vias.
+    pub fn to_path_current(&self) -> PathCurrent<T> {
+        PathCurrent::new(
+            [T::zero(); 3],
+            UnitQuaternion::identity(),
+            self.current,
+            self.path_vertices(),
+        )
+    }
+
+    /// Model the vias between consecutive layers as straight [PathCurrent] segments.
+    pub fn via_currents(&self) -> Vec<PathCurrent<T>> {
+        let layers = self.layer_vertices();
+        layers
+            .windows(2)
+            .map(|pair| {
+                let from = *pair[0].last().unwrap();
+                let to = pair[1][0];
+                PathCurrent::new(
+                    [T::zero(); 3],
+                    UnitQuaternion::identity(),
+                    self.current,
+                    alloc::vec![from, to],
+                )
+            })
+            .collect()
+    }
+
+    /// Model the coil as a [SourceAssembly](crate::collections::SourceAssembly) with
+    /// one [PathCurrent] per layer and one per via.
+    #[cfg(feature = "alloc")]
+    pub fn to_assembly(&self) -> crate::collections::SourceAssembly<T> {
+        let mut assembly = crate::collections::SourceAssembly::default();
+        let layers = self.layer_vertices();
+        let mut vias = self.via_currents().into_iter();
+        for (i, vertices) in layers.into_iter().enumerate() {
+            if i > 0 {
+                assembly.push(vias.next().unwrap());
+            }
+            assembly.push(PathCurrent::new(
+                [T::zero(); 3],
+                UnitQuaternion::identity(),
+                self.current,
+                vertices,
+            ));
+        }
+        assembly
+    }
+
+    /// Model the traces with finite width as a [SheetCurrent](crate::currents::SheetCurrent).
+    ///
+    /// Each trace segment is a quadrilateral strip of width `trace_width` with miter joints,
+    /// carrying a homogeneous surface current density of `current / trace_width` along the
+    /// segment. The vias are not included; combine with [PlanarCoil::via_currents] if needed.
+    #[cfg(feature = "mesh")]
+    pub fn to_sheet_current(&self) -> crate::currents::SheetCurrent<T> {
+        let half_width = self.trace_width / T::from_f64(2.0).unwrap();
+        let density = self.current / self.trace_width;
+
+        let mut vertices = Vec::new();
+        let mut faces = Vec::new();
+        let mut current_densities = Vec::new();
+
+        for layer in self.layer_vertices() {
+            let n = layer.len();
+            let dirs: Vec<Vector3<T>> = layer
+                .windows(2)
+                .map(|s| (s[1] - s[0]).normalize())
+                .collect();
+            let normal = |d: Vector3<T>| Vector3::new(-d.y, d.x, T::zero());
+
+            // Offset the centerline to both sides with miter joints
+            let base = vertices.len();
+            for i in 0..n {
+                let offset = if i == 0 {
+                    normal(dirs[0]) * half_width
+                } else if i == n - 1 {
+                    normal(dirs[n - 2]) * half_width
+                } else {
+                    let n0 = normal(dirs[i - 1]);
+                    let miter = (n0 + normal(dirs[i])).normalize();
+                    miter * (half_width / miter.dot(&n0))
+                };
+                vertices.push(layer[i] + offset);
+                vertices.push(layer[i] - offset);
+            }
+
+            for (i, dir) in dirs.iter().enumerate() {
+                let (l0, r0) = (base + 2 * i, base + 2 * i + 1);
+                let (l1, r1) = (l0 + 2, r0 + 2);
+                faces.push([r0, r1, l1]);
+                faces.push([r0, l1, l0]);
+                current_densities.push(dir * density);
+                current_densities.push(dir * density);
+            }
+        }
+
+        crate::currents::SheetCurrent::new(
+            [T::zero(); 3],
+            UnitQuaternion::identity(),
+            current_densities,
+            crate::base::mesh::TriMesh::new_unchecked(vertices, faces),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{base::Source, currents::CircularCurrent};
+    use approx::assert_relative_eq;
+    use nalgebra::point;
+
+    #[test]
+    fn test_spiral_dimensions() {
+        let coil = PlanarCoil::new(SpiralShape::Square, 0.02, 4.0, 1e-3).with_trace_width(0.4e-3);
+        let vertices = coil.path_vertices();
+
+        // Starts at the outer centerline, ends at the inner centerline on the x axis
+        let max = vertices
+            .iter()
+            .map(|v| v.x.abs().max(v.y.abs()))
+            .fold(0.0, f64::max);
+        assert_relative_eq!(max, 0.0098, epsilon = 1e-12);
+        assert_relative_eq!(
+            *vertices.last().unwrap(),
+            Vector3::new(0.0058, 0.0, 0.0),
+            epsilon = 1e-12
+        );
+
+        // Square spiral has 4 corners per turn
+        assert_eq!(vertices.len(), 2 + 16);
+    }
+
+    #[test]
+    fn test_hexagonal_corners() {
+        let coil = PlanarCoil::new(SpiralShape::Hexagonal, 0.02, 2.0, 1e-3);
+        let vertices = coil.path_vertices();
+        let corners = &vertices[1..vertices.len() - 1];
+        assert_eq!(corners.len(), 12);
+
+        // Corners at 30° + k·60°, so the sides crossing the x axis are parallel to y
+        assert!(corners.iter().any(|v| v.x.abs() < 1e-12 && v.y > 0.0));
+        assert!(corners.iter().all(|v| v.y.abs() > 1e-3));
+        assert_relative_eq!(corners[0].y / corners[0].x, 30f64.to_radians().tan());
+    }
+
+    #[test]
+    fn test_layers_connected_by_vias() {
+        for shape in [
+            SpiralShape::Square,
+            SpiralShape::Hexagonal,
+            SpiralShape::Circular { segments: 24 },
+            SpiralShape::Racetrack {
+                straight_length: 0.01,
+                arc_segments: 8,
+            },
+        ] {
+            let coil =
+                PlanarCoil::new(shape, 0.02, 3.25, 1e-3).with_layers(vec![0.0, -1e-3, -2e-3]);
+            let layers = coil.layer_vertices();
+            for pair in layers.windows(2) {
+                let from = pair[0].last().unwrap();
+                let to = pair[1][0];
+                assert_relative_eq!(from.xy(), to.xy(), epsilon = 1e-12);
+            }
+            assert_eq!(coil.via_currents().len(), 2);
+        }
+    }
+
+    #[test]
+    fn test_racetrack_straights() {
+        let coil = PlanarCoil::new(
+            SpiralShape::Racetrack {
+                straight_length: 0.01,
+                arc_segments: 6,
+            },
+            0.02,
+            2.0,
+            1e-3,
+        );
+        let vertices = coil.path_vertices();
+        let straights = vertices
+            .windows(2)
+            .filter(|s| (s[1] - s[0]).norm() > 0.0099 && (s[1].y - s[0].y).abs() < 1e-15)
+            .count();
+        assert_eq!(straights, 4);
+    }
+
+    #[test]
+    fn test_layers_add_up() {
+        // Current circulates in the same direction on both layers
+        let single = PlanarCoil::new(SpiralShape::Circular { segments: 64 }, 0.02, 5.0, 1e-3);
+        let double = single.clone().with_layers(vec![0.0, 0.0]);
+        let point = point![0.0, 0.0, 0.01];
+        let b_single = single.to_path_current().compute_B(point);
+        let b_double = double.to_path_current().compute_B(point);
+
+        assert!(b_single.z > 0.0);
+        assert_relative_eq!(b_double.z, b_single.z * 2.0, max_relative = 1e-12);
+    }
+
+    #[test]
+    fn test_circular_spiral_matches_loops() {
+        let coil = PlanarCoil::new(SpiralShape::Circular { segments: 360 }, 0.02, 4.0, 1e-3)
+            .with_trace_width(0.5e-3)
+            .with_current(2.0);
+
+        // Loops at the mean radius of each turn
+        let point = point![0.001, 0.002, 0.015];
+        let expected = (0..4).fold(Vector3::zeros(), |acc, i| {
+            let r = coil.outer_radius() - 1e-3 * (i as f64 + 0.5);
+            let circle = CircularCurrent::new([0.0; 3], UnitQuaternion::identity(), 2.0 * r, 2.0);
+            acc + circle.compute_B(point)
+        });
+        let b = coil.to_path_current().compute_B(point);
+
+        // The spiral differs from concentric loops mostly in the radial components
+        assert_relative_eq!(b.z, expected.z, max_relative = 1e-2);
+    }
+
+    #[cfg(feature = "mesh")]
+    #[test]
+    fn test_sheet_current_matches_path_far_away() {
+        let coil = PlanarCoil::new(SpiralShape::Hexagonal, 0.02, 3.0, 1e-3)
+            .with_trace_width(0.6e-3)
+            .with_current(0.5);
+        let point = point![0.002, 0.003, 0.05];
+
+        let b_path = coil.to_path_current().compute_B(point);
+        let b_sheet = coil.to_sheet_current().compute_B(point);
+        assert_relative_eq!(b_sheet, b_path, max_relative = 1e-2);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_assembly_matches_path() {
+        let coil = PlanarCoil::new(SpiralShape::Square, 0.02, 3.0, 1e-3)
+            .with_layers(vec![0.0, -0.8e-3, -1.6e-3, -2.4e-3]);
+        let point = point![0.003, -0.002, 0.004];
+
+        let assembly = coil.to_assembly();
+        assert_eq!(assembly.components().count(), 7);
+        assert_relative_eq!(
+            assembly.compute_B(point),
+            coil.to_path_current().compute_B(point),
+            max_relative = 1e-10
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_input_validation() {
+        let _ = PlanarCoil::new(SpiralShape::Square, 0.01, 10.0, 1e-3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_trace_width_validation() {
+        let _ = PlanarCoil::new(SpiralShape::Square, 0.02, 3.0, 1e-3).with_trace_width(1e-3);
+    }
+
+    #[test]
+    fn test_fallible_validation() {
+        let result = PlanarCoil::try_new(SpiralShape::Square, 0.01, 10.0, 1e-3);
+        assert!(matches!(
+            result,
+            Err(Error::InvalidGeometry(
+                "Spiral does not fit in the outer diameter."
+            ))
+        ));
+
+        let coil = PlanarCoil::try_new(SpiralShape::Square, 0.02, 3.0, 1e-3).unwrap();
+        assert!(coil.clone().try_with_trace_width(1e-3).is_err());
+        assert!(coil.clone().try_with_layers(Vec::new()).is_err());
+        assert_eq!(
+            coil.try_with_trace_width(0.4e-3).unwrap().trace_width(),
+            0.4e-3
+        );
+    }
+}
@@ -10,17 +10,22 @@ use nalgebra::Vector3;
 use openmesh::MeshError;
 
 use crate::base::Float;
+use crate::base::bvh::CurrentMoments;
 use crate::base::mesh::TriMesh;
 use crate::crate_utils::define_source;
+use crate::fields::sheet_current_moments;
 
 define_source! {
     /// A meshed current sheet.
     SheetCurrent
-    field_fn: sheet_current_B
+    field_fn: sheet_current_B_clustered
     args: {
         current_densities: @ref Vec<Vector3<T>> = Vec::new(),
         mesh: @ref TriMesh<T> = TriMesh::new_unchecked(Vec::new(), Vec::new()),
     }
+    cache: {
+        moments: Vec<CurrentMoments<T>> = sheet_current_moments(current_densities, mesh),
+    }
     arg_display: "current_densities_count: {}, triangles count: {}";
     arg_fmt: [format_vertices_count, format_trimesh_count]
     docs: {
@@ -92,6 +97,46 @@ impl<T: Float + core::iter::Sum> SheetCurrent<T> {
             trimesh,
         ))
     }
+
+    /// Validates and constructs a [`SheetCurrent`] from a Wavefront OBJ reader.
+    ///
+    /// To scale or repair the mesh on import, read it with
+    /// [TriMesh::from_obj_with_options] instead.
+    #[cfg(feature = "io-obj")]
+    #[inline]
+    pub fn from_obj<R: std::io::BufRead>(
+        reader: R,
+        current_densities: Vec<Vector3<T>>,
+    ) -> std::io::Result<Self> {
+        let trimesh = TriMesh::from_obj(reader)?;
+
+        Ok(Self::new(
+            [T::zero(); 3],
+            nalgebra::UnitQuaternion::identity(),
+            current_densities,
+            trimesh,
+        ))
+    }
+
+    /// Validates and constructs a [`SheetCurrent`] from a PLY reader.
+    ///
+    /// To scale or repair the mesh on import, read it with
+    /// [TriMesh::from_ply_with_options] instead.
+    #[cfg(feature = "io-ply")]
+    #[inline]
+    pub fn from_ply<R: std::io::BufRead>(
+        reader: R,
+        current_densities: Vec<Vector3<T>>,
+    ) -> std::io::Result<Self> {
+        let trimesh = TriMesh::from_ply(reader)?;
+
+        Ok(Self::new(
+            [T::zero(); 3],
+            nalgebra::UnitQuaternion::identity(),
+            current_densities,
+            trimesh,
+        ))
+    }
 }
 
 #[cfg(all(test, feature = "std"))]
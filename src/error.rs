@@ -0,0 +1,118 @@
+/*
+ * Magba is licensed under The 3-Clause BSD, see LICENSE.
+ * Copyright 2025 Sira Pornsiriprasert <code@psira.me>
+ */
+
+//! Crate-wide error type.
+
+use core::fmt::{self, Display, Formatter};
+
+/// Error returned by the fallible constructors and setters, such as `try_new`,
+/// `try_set_*`, and `try_with_*`.
+///
+/// The panicking counterparts, such as `new`, panic with the [Display] message of this
+/// error.
+///
+/// # Examples
+///
+/// ```
+/// # use magba::Error;
+/// # use magba::magnets::CylinderMagnet;
+/// # use nalgebra::UnitQuaternion;
+/// let result = CylinderMagnet::try_new(
+///     [0.0, 0.0, 0.0],
+///     UnitQuaternion::identity(),
+///     [0.0, 0.0, 1.0],
+///     -0.01,
+///     0.02,
+/// );
+/// assert!(matches!(result, Err(Error::InvalidGeometry(_))));
+/// assert_eq!(result.unwrap_err().to_string(), "Diameter cannot be negative.");
+/// ```
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// Dimensions or shape of a source out of range, e.g., a negative diameter.
+    InvalidGeometry(&'static str),
+    /// Sensor parameters out of range, e.g., a non-positive supply voltage.
+    InvalidSensor(&'static str),
+    /// Mesh that fails validation.
+    #[cfg(feature = "mesh")]
+    Mesh(openmesh::MeshError),
+    /// Failure to read or write a file.
+    #[cfg(feature = "std")]
+    Io(std::io::Error),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidGeometry(message) | Self::InvalidSensor(message) => f.write_str(message),
+            // The Display of MeshError ends with a newline and is only available with std.
+            #[cfg(all(feature = "mesh", feature = "std"))]
+            Self::Mesh(error) => write!(f, "Invalid mesh: {}.", error.to_string().trim_end()),
+            #[cfg(all(feature = "mesh", not(feature = "std")))]
+            Self::Mesh(error) => write!(f, "Invalid mesh: {error:?}."),
+            #[cfg(feature = "std")]
+            Self::Io(error) => error.fmt(f),
+        }
+    }
+}
+
+impl core::error::Error for Error {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            #[cfg(feature = "std")]
+            Self::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "mesh")]
+impl From<openmesh::MeshError> for Error {
+    fn from(error: openmesh::MeshError) -> Self {
+        Self::Mesh(error)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for Error {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<Error> for std::io::Error {
+    /// Keeps the inner error of [Error::Io], and wraps others as
+    /// [InvalidData](std::io::ErrorKind::InvalidData).
+    fn from(error: Error) -> Self {
+        match error {
+            Error::Io(error) => error,
+            error => Self::new(std::io::ErrorKind::InvalidData, error),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            Error::InvalidSensor("B_OP must be greater than B_RP.").to_string(),
+            "B_OP must be greater than B_RP."
+        );
+        #[cfg(feature = "mesh")]
+        assert_eq!(
+            Error::from(openmesh::MeshError::OpenEdges).to_string(),
+            "Invalid mesh: Open edges."
+        );
+
+        let io = std::io::Error::from(Error::InvalidGeometry("Pitch must be positive."));
+        assert_eq!(io.kind(), std::io::ErrorKind::InvalidData);
+        assert_eq!(io.to_string(), "Pitch must be positive.");
+    }
+}
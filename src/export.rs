@@ -0,0 +1,808 @@
+/*
+ * Magba is licensed under The 3-Clause BSD, see LICENSE.
+ * Copyright 2025 Sira Pornsiriprasert <code@psira.me>
+ */
+
+//! Geometry export of sources and sensors for review in CAD viewers.
+//!
+//! A [Scene] collects the geometry of each component of a [SourceAssembly] and an
+//! [ObserverAssembly] at its global pose, and writes it to STL, OBJ, or glTF.
+//!
+//! | Component | Geometry |
+//! |-----------|----------|
+//! | Cuboid, cylinder, sphere, tetrahedron, prism magnets | Tessellated surface, see [Tessellate] |
+//! | Triangle and mesh magnets, triangle and sheet currents | Their triangles |
+//! | Tetrahedral mesh magnets | Boundary of the mesh |
+//! | Dipoles | Sphere of [ExportOptions::marker_size] diameter |
+//! | Circular and path currents | Tubes of [ExportOptions::wire_radius] |
+//! | Sensors | Pyramid pointing along the sensitive axis |
+//!
+//! Magnets are colored by their polarization direction, mapping the x, y, and z
+//! components of the unit vector from [-1, 1] to red, green, and blue. Custom sources and
+//! observers have no known geometry and are skipped, except that custom observers are
+//! shown as markers pointing along their local z-axis.
+//!
+//! # Examples
+//!
+//! ```
+//! # use magba::prelude::*;
+//! # use magba::export::{ExportOptions, Scene};
+//! # use magba::sensors::hall_effect::LinearHallSensor;
+//! # use nalgebra::UnitQuaternion;
+//! let sources = SourceAssembly::default()
+//!     .with(CylinderMagnet::new([0.0, 0.0, 0.0], UnitQuaternion::identity(), [0.0, 0.0, 1.0], 0.01, 0.005))
+//!     .with(CuboidMagnet::new([0.02, 0.0, 0.0], UnitQuaternion::identity(), [1.0, 0.0, 0.0], [0.01; 3]));
+//! let sensors = ObserverAssembly::default().with(LinearHallSensor::default().with_position([0.0, 0.0, 0.01]));
+//!
+//! let options = ExportOptions::default().with_resolution(5e-4);
+//! let scene = Scene::new(&sources, Some(&sensors), &options);
+//!
+//! let mut gltf = Vec::new();
+//! scene.write_gltf(&mut gltf).unwrap();
+//! ```
+
+#[cfg(feature = "io-vtk")]
+pub mod vtk;
+
+use std::io::{self, Write};
+
+use nalgebra::{Isometry3, Point3, UnitQuaternion, Vector3};
+use num_traits::Float as NumFloat;
+
+use crate::{
+    base::{Float, Transform, mesh::Tessellate, mesh::TetMesh, mesh::TriMesh},
+    collections::{ObserverAssembly, ObserverComponent, SourceAssembly, SourceComponent},
+    currents::Current,
+    magnets::{CylinderMagnet, Magnet, SphereMagnet},
+    sensors::Sensor,
+};
+
+/// Color of current-carrying conductors (copper).
+const CURRENT_COLOR: [f32; 3] = [0.72, 0.45, 0.2];
+/// Color of sensor markers.
+const SENSOR_COLOR: [f32; 3] = [0.9, 0.8, 0.1];
+/// Color of magnets without polarization.
+const NEUTRAL_COLOR: [f32; 3] = [0.6, 0.6, 0.6];
+
+/// Options for building a [Scene].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportOptions<T: Float> {
+    /// Maximum edge length of tessellated surfaces (m).
+    pub resolution: T,
+    /// Radius of the tubes drawn along circular and path currents (m).
+    pub wire_radius: T,
+    /// Size of dipole and sensor markers (m).
+    pub marker_size: T,
+    /// Factor applied to all coordinates on export, e.g. `1000.0` to write millimeters.
+    pub scale: T,
+}
+
+impl<T: Float> Default for ExportOptions<T> {
+    fn default() -> Self {
+        Self {
+            resolution: T::from_f64(1e-3).unwrap(),
+            wire_radius: T::from_f64(2e-4).unwrap(),
+            marker_size: T::from_f64(1e-3).unwrap(),
+            scale: T::one(),
+        }
+    }
+}
+
+impl<T: Float> ExportOptions<T> {
+    pub fn with_resolution(mut self, resolution: T) -> Self {
+        self.resolution = resolution;
+        self
+    }
+
+    pub fn with_wire_radius(mut self, wire_radius: T) -> Self {
+        self.wire_radius = wire_radius;
+        self
+    }
+
+    pub fn with_marker_size(mut self, marker_size: T) -> Self {
+        self.marker_size = marker_size;
+        self
+    }
+
+    pub fn with_scale(mut self, scale: T) -> Self {
+        self.scale = scale;
+        self
+    }
+}
+
+/// Named, colored triangle mesh of one component in global coordinates.
+#[derive(Debug, Clone)]
+pub struct SceneObject<T: Float> {
+    name: String,
+    color: [f32; 3],
+    vertices: Vec<Vector3<T>>,
+    faces: Vec<[usize; 3]>,
+}
+
+impl<T: Float> SceneObject<T> {
+    /// Object name, which is the component path in the assemblies, e.g. `sources/2/0/CuboidMagnet`.
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// RGB color, each channel from 0 to 1.
+    #[inline]
+    pub fn color(&self) -> [f32; 3] {
+        self.color
+    }
+
+    /// Vertices in global coordinates, scaled by [ExportOptions::scale].
+    #[inline]
+    pub fn vertices(&self) -> &[Vector3<T>] {
+        &self.vertices
+    }
+
+    /// Counterclockwise triangles as vertex indices.
+    #[inline]
+    pub fn faces(&self) -> &[[usize; 3]] {
+        &self.faces
+    }
+}
+
+/// Exportable geometry of sources and sensors. See the [module](self) documentation.
+#[derive(Debug, Clone)]
+pub struct Scene<T: Float> {
+    objects: Vec<SceneObject<T>>,
+}
+
+impl<T: Float> Default for Scene<T> {
+    fn default() -> Self {
+        Self {
+            objects: Vec::new(),
+        }
+    }
+}
+
+/// Maps a direction to a color, with zero vectors in gray.
+fn direction_color<T: Float>(direction: Vector3<T>) -> [f32; 3] {
+    match direction.try_normalize(T::zero()) {
+        Some(unit) => [0, 1, 2].map(|i| (0.5 + 0.5 * unit[i].to_f64().unwrap()) as f32),
+        None => NEUTRAL_COLOR,
+    }
+}
+
+/// Outward boundary faces of a tetrahedral mesh.
+fn tet_mesh_boundary<T: Float>(mesh: &TetMesh<T>) -> Vec<[usize; 3]> {
+    let mut counts = std::collections::BTreeMap::new();
+    // Tetrahedra are positively oriented, so these faces point outwards
+    let faces = mesh
+        .tetrahedra()
+        .iter()
+        .flat_map(|&[a, b, c, d]| [[a, c, b], [a, b, d], [a, d, c], [b, c, d]]);
+    for face in faces.clone() {
+        let mut key = face;
+        key.sort_unstable();
+        *counts.entry(key).or_insert(0usize) += 1;
+    }
+    faces
+        .filter(|face| {
+            let mut key = *face;
+            key.sort_unstable();
+            counts[&key] == 1
+        })
+        .collect()
+}
+
+/// Square pyramid of `size` with its apex along `axis`, in local coordinates.
+fn pyramid<T: Float>(axis: Vector3<T>, size: T) -> TriMesh<T> {
+    let axis = axis.try_normalize(T::zero()).unwrap_or_else(Vector3::z);
+    let helper = if NumFloat::abs(axis.x) < T::from_f64(0.9).unwrap() {
+        Vector3::x()
+    } else {
+        Vector3::y()
+    };
+    let u = axis.cross(&helper).normalize() * (size / T::from_f64(2.0).unwrap());
+    let v = axis.cross(&u);
+    let base = -axis * (size / T::from_f64(2.0).unwrap());
+    let vertices = vec![
+        base + u + v,
+        base - u + v,
+        base - u - v,
+        base + u - v,
+        axis * (size / T::from_f64(2.0).unwrap()),
+    ];
+    let faces = vec![
+        [0, 2, 1],
+        [0, 3, 2],
+        [0, 1, 4],
+        [1, 2, 4],
+        [2, 3, 4],
+        [3, 0, 4],
+    ];
+    TriMesh::new_unchecked(vertices, faces)
+}
+
+/// Closed tubes of `radius` along the segments of a polyline, in local coordinates.
+fn tubes<T: Float>(
+    points: &[Vector3<T>],
+    radius: T,
+    resolution: T,
+) -> (Vec<Vector3<T>>, Vec<[usize; 3]>) {
+    let mut vertices = Vec::new();
+    let mut faces = Vec::new();
+    for segment in points.windows(2) {
+        let direction = segment[1] - segment[0];
+        let length = direction.norm();
+        if length == T::zero() {
+            continue;
+        }
+        let orientation = UnitQuaternion::rotation_between(&Vector3::z(), &direction)
+            .unwrap_or_else(|| UnitQuaternion::from_axis_angle(&Vector3::x_axis(), T::pi()));
+        let center = (segment[0] + segment[1]) / T::from_f64(2.0).unwrap();
+        let cylinder = CylinderMagnet::new(
+            center,
+            orientation,
+            Vector3::zeros(),
+            radius * T::from_f64(2.0).unwrap(),
+            length,
+        );
+        let mesh = cylinder.tessellate(resolution);
+        let isometry = Isometry3::from_parts(center.into(), orientation);
+        let offset = vertices.len();
+        vertices.extend(
+            mesh.vertices()
+                .iter()
+                .map(|v| (isometry * Point3::from(*v)).coords),
+        );
+        faces.extend(mesh.faces().iter().map(|f| f.map(|i| i + offset)));
+    }
+    (vertices, faces)
+}
+
+impl<T: Float> Scene<T> {
+    /// Builds a [Scene] from sources and, optionally, sensors.
+    pub fn new(
+        sources: &SourceAssembly<T>,
+        observers: Option<&ObserverAssembly<T>>,
+        options: &ExportOptions<T>,
+    ) -> Self {
+        let mut scene = Self::default();
+        scene.add_sources("sources", sources, options);
+        if let Some(observers) = observers {
+            scene.add_observers("sensors", observers, options);
+        }
+        scene
+    }
+
+    /// Scene objects in export order.
+    #[inline]
+    pub fn objects(&self) -> &[SceneObject<T>] {
+        &self.objects
+    }
+
+    /// Adds each component of `sources`, naming the objects under `prefix`.
+    pub fn add_sources(
+        &mut self,
+        prefix: &str,
+        sources: &SourceAssembly<T>,
+        options: &ExportOptions<T>,
+    ) {
+        for (i, component) in sources.iter().enumerate() {
+            let isometry = component.pose().as_isometry();
+            match component {
+                SourceComponent::Magnet(magnet) => {
+                    self.add_magnet(&format!("{prefix}/{i}"), magnet, isometry, options)
+                }
+                SourceComponent::Current(current) => {
+                    self.add_current(&format!("{prefix}/{i}"), current, isometry, options)
+                }
+                SourceComponent::Assembly(assembly) => {
+                    self.add_sources(&format!("{prefix}/{i}"), assembly, options)
+                }
+                SourceComponent::Custom(_) => {}
+            }
+        }
+    }
+
+    /// Adds a sensor marker for each component of `observers`, naming the objects under `prefix`.
+    pub fn add_observers(
+        &mut self,
+        prefix: &str,
+        observers: &ObserverAssembly<T>,
+        options: &ExportOptions<T>,
+    ) {
+        for (i, component) in observers.iter().enumerate() {
+            let (kind, axis) = match component {
+                ObserverComponent::Sensor(Sensor::LinearHall(sensor)) => {
+                    ("LinearHallSensor", sensor.sensitive_axis())
+                }
+                ObserverComponent::Sensor(Sensor::HallSwitch(sensor)) => {
+                    ("HallSwitch", *sensor.sensitive_axis())
+                }
+                ObserverComponent::Sensor(Sensor::HallLatch(sensor)) => {
+                    ("HallLatch", *sensor.sensitive_axis())
+                }
+                ObserverComponent::Custom(_) => ("Observer", Vector3::z()),
+            };
+            let mesh = pyramid(axis, options.marker_size);
+            self.push(
+                format!("{prefix}/{i}/{kind}"),
+                SENSOR_COLOR,
+                mesh.vertices(),
+                mesh.faces(),
+                component.pose().as_isometry(),
+                options,
+            );
+        }
+    }
+
+    fn add_magnet(
+        &mut self,
+        name: &str,
+        magnet: &Magnet<T>,
+        isometry: &Isometry3<T>,
+        options: &ExportOptions<T>,
+    ) {
+        let resolution = options.resolution;
+        let (kind, color, mesh) = match magnet {
+            Magnet::Cuboid(m) => (
+                "CuboidMagnet",
+                direction_color(m.polarization()),
+                m.tessellate(resolution),
+            ),
+            Magnet::Cylinder(m) => (
+                "CylinderMagnet",
+                direction_color(m.polarization()),
+                m.tessellate(resolution),
+            ),
+            Magnet::Sphere(m) => (
+                "SphereMagnet",
+                direction_color(m.polarization()),
+                m.tessellate(resolution),
+            ),
+            Magnet::Tetrahedron(m) => (
+                "TetrahedronMagnet",
+                direction_color(m.polarization()),
+                m.tessellate(resolution),
+            ),
+            Magnet::Prism(m) => (
+                "PrismMagnet",
+                direction_color(m.polarization()),
+                m.tessellate(resolution),
+            ),
+            Magnet::Dipole(m) => {
+                let marker = SphereMagnet::<T>::default().with_diameter(options.marker_size);
+                (
+                    "Dipole",
+                    direction_color(m.moment()),
+                    marker.tessellate(resolution),
+                )
+            }
+            Magnet::Triangle(m) => (
+                "TriangleMagnet",
+                direction_color(m.polarization()),
+                TriMesh::new_unchecked(m.vertices().to_vec(), vec![[0, 1, 2]]),
+            ),
+            Magnet::Mesh(m) => (
+                "MeshMagnet",
+                direction_color(m.polarization()),
+                m.mesh().clone(),
+            ),
+            Magnet::TetMesh(m) => {
+                let mean = m
+                    .polarizations()
+                    .iter()
+                    .fold(Vector3::zeros(), |acc, p| acc + p);
+                let mesh = m.mesh();
+                (
+                    "TetMeshMagnet",
+                    direction_color(mean),
+                    TriMesh::new_unchecked(mesh.vertices().to_vec(), tet_mesh_boundary(mesh)),
+                )
+            }
+        };
+        self.push(
+            format!("{name}/{kind}"),
+            color,
+            mesh.vertices(),
+            mesh.faces(),
+            isometry,
+            options,
+        );
+    }
+
+    fn add_current(
+        &mut self,
+        name: &str,
+        current: &Current<T>,
+        isometry: &Isometry3<T>,
+        options: &ExportOptions<T>,
+    ) {
+        let (kind, vertices, faces) = match current {
+            Current::Circular(c) => {
+                let radius = c.diameter() / T::from_f64(2.0).unwrap();
+                let segments = NumFloat::ceil(T::two_pi() * radius / options.resolution)
+                    .to_usize()
+                    .unwrap_or(8)
+                    .max(8);
+                let points: Vec<Vector3<T>> = (0..=segments)
+                    .map(|i| {
+                        let phi = T::two_pi() * T::from_usize(i).unwrap()
+                            / T::from_usize(segments).unwrap();
+                        Vector3::new(
+                            radius * NumFloat::cos(phi),
+                            radius * NumFloat::sin(phi),
+                            T::zero(),
+                        )
+                    })
+                    .collect();
+                let (vertices, faces) = tubes(&points, options.wire_radius, options.resolution);
+                ("CircularCurrent", vertices, faces)
+            }
+            Current::Path(c) => {
+                let (vertices, faces) =
+                    tubes(c.vertices(), options.wire_radius, options.resolution);
+                ("PathCurrent", vertices, faces)
+            }
+            Current::Triangle(c) => ("TriangleCurrent", c.vertices().to_vec(), vec![[0, 1, 2]]),
+            Current::Sheet(c) => (
+                "SheetCurrent",
+                c.mesh().vertices().to_vec(),
+                c.mesh().faces().to_vec(),
+            ),
+        };
+        self.push(
+            format!("{name}/{kind}"),
+            CURRENT_COLOR,
+            &vertices,
+            &faces,
+            isometry,
+            options,
+        );
+    }
+
+    fn push(
+        &mut self,
+        name: String,
+        color: [f32; 3],
+        vertices: &[Vector3<T>],
+        faces: &[[usize; 3]],
+        isometry: &Isometry3<T>,
+        options: &ExportOptions<T>,
+    ) {
+        if faces.is_empty() {
+            return;
+        }
+        let vertices = vertices
+            .iter()
+            .map(|v| (isometry * Point3::from(*v)).coords * options.scale)
+            .collect();
+        self.objects.push(SceneObject {
+            name,
+            color,
+            vertices,
+            faces: faces.to_vec(),
+        });
+    }
+}
+
+// MARK: Writers
+
+impl<T: Float> Scene<T> {
+    /// Writes the scene as ASCII STL, with one named solid per object.
+    ///
+    /// STL has no colors.
+    pub fn write_stl<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        for object in &self.objects {
+            let name = object.name.replace(char::is_whitespace, "_");
+            writeln!(writer, "solid {name}")?;
+            for face in &object.faces {
+                let [a, b, c] = face.map(|i| object.vertices[i].map(|x| x.to_f64().unwrap()));
+                let normal = (b - a)
+                    .cross(&(c - a))
+                    .try_normalize(0.0)
+                    .unwrap_or_default();
+                writeln!(
+                    writer,
+                    "  facet normal {:e} {:e} {:e}",
+                    normal.x, normal.y, normal.z
+                )?;
+                writeln!(writer, "    outer loop")?;
+                for v in [a, b, c] {
+                    writeln!(writer, "      vertex {:e} {:e} {:e}", v.x, v.y, v.z)?;
+                }
+                writeln!(writer, "    endloop")?;
+                writeln!(writer, "  endfacet")?;
+            }
+            writeln!(writer, "endsolid {name}")?;
+        }
+        Ok(())
+    }
+
+    /// Writes the scene as Wavefront OBJ, with one named object and material per object.
+    ///
+    /// Vertex colors are written after the coordinates, which most viewers read. Pass the
+    /// file name of the material library written by [Scene::write_mtl] as `mtllib` to
+    /// reference it from the OBJ file.
+    pub fn write_obj<W: Write>(&self, mut writer: W, mtllib: Option<&str>) -> io::Result<()> {
+        if let Some(mtllib) = mtllib {
+            writeln!(writer, "mtllib {mtllib}")?;
+        }
+        let mut offset = 1;
+        for (i, object) in self.objects.iter().enumerate() {
+            let name = object.name.replace(char::is_whitespace, "_");
+            let [r, g, b] = object.color;
+            writeln!(writer, "o {name}")?;
+            writeln!(writer, "usemtl material_{i}")?;
+            for v in &object.vertices {
+                let [x, y, z] = [v.x, v.y, v.z].map(|x| x.to_f64().unwrap());
+                writeln!(writer, "v {x:e} {y:e} {z:e} {r} {g} {b}")?;
+            }
+            for face in &object.faces {
+                let [a, b, c] = face.map(|i| i + offset);
+                writeln!(writer, "f {a} {b} {c}")?;
+            }
+            offset += object.vertices.len();
+        }
+        Ok(())
+    }
+
+    /// Writes the material library for [Scene::write_obj].
+    pub fn write_mtl<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        for (i, object) in self.objects.iter().enumerate() {
+            let [r, g, b] = object.color;
+            writeln!(writer, "newmtl material_{i}")?;
+            writeln!(writer, "Kd {r} {g} {b}")?;
+        }
+        Ok(())
+    }
+
+    /// Writes the scene as a self-contained glTF 2.0 JSON file (`.gltf`).
+    ///
+    /// Each object becomes a named node with its own mesh and material. The buffer is
+    /// embedded as a base64 data URI. The nodes are children of a root node that rotates
+    /// the z-up coordinates of Magba into the y-up convention of glTF.
+    pub fn write_gltf<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let mut buffer: Vec<u8> = Vec::new();
+        let mut buffer_views = Vec::new();
+        let mut accessors = Vec::new();
+        let mut meshes = Vec::new();
+        let mut materials = Vec::new();
+        let mut nodes = Vec::new();
+
+        for (i, object) in self.objects.iter().enumerate() {
+            let positions: Vec<[f32; 3]> = object
+                .vertices
+                .iter()
+                .map(|v| [v.x, v.y, v.z].map(|x| x.to_f32().unwrap()))
+                .collect();
+            let min = positions.iter().fold([f32::INFINITY; 3], |acc, p| {
+                [0, 1, 2].map(|k| acc[k].min(p[k]))
+            });
+            let max = positions.iter().fold([f32::NEG_INFINITY; 3], |acc, p| {
+                [0, 1, 2].map(|k| acc[k].max(p[k]))
+            });
+
+            let offset = buffer.len();
+            buffer.extend(positions.iter().flatten().flat_map(|x| x.to_le_bytes()));
+            buffer_views.push(format!(
+                r#"{{"buffer":0,"byteOffset":{offset},"byteLength":{},"target":34962}}"#,
+                buffer.len() - offset
+            ));
+            accessors.push(format!(
+                r#"{{"bufferView":{},"componentType":5126,"count":{},"type":"VEC3","min":[{},{},{}],"max":[{},{},{}]}}"#,
+                2 * i,
+                positions.len(),
+                min[0], min[1], min[2], max[0], max[1], max[2]
+            ));
+
+            let offset = buffer.len();
+            buffer.extend(
+                object
+                    .faces
+                    .iter()
+                    .flatten()
+                    .flat_map(|&index| (index as u32).to_le_bytes()),
+            );
+            buffer_views.push(format!(
+                r#"{{"buffer":0,"byteOffset":{offset},"byteLength":{},"target":34963}}"#,
+                buffer.len() - offset
+            ));
+            accessors.push(format!(
+                r#"{{"bufferView":{},"componentType":5125,"count":{},"type":"SCALAR"}}"#,
+                2 * i + 1,
+                3 * object.faces.len()
+            ));
+
+            let name = json_string(&object.name);
+            let [r, g, b] = object.color;
+            materials.push(format!(
+                r#"{{"name":{name},"pbrMetallicRoughness":{{"baseColorFactor":[{r},{g},{b},1],"metallicFactor":0,"roughnessFactor":0.8}},"doubleSided":true}}"#
+            ));
+            meshes.push(format!(
+                r#"{{"name":{name},"primitives":[{{"attributes":{{"POSITION":{}}},"indices":{},"material":{i}}}]}}"#,
+                2 * i,
+                2 * i + 1
+            ));
+            nodes.push(format!(r#"{{"name":{name},"mesh":{i}}}"#));
+        }
+
+        let children = (0..self.objects.len())
+            .map(|i| i.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        let half = core::f64::consts::FRAC_1_SQRT_2;
+        nodes.push(format!(
+            r#"{{"name":"magba","rotation":[{},0,0,{half}],"children":[{children}]}}"#,
+            -half
+        ));
+
+        write!(
+            writer,
+            r#"{{"asset":{{"version":"2.0","generator":"magba"}},"scene":0,"scenes":[{{"nodes":[{}]}}],"nodes":[{}],"meshes":[{}],"materials":[{}],"accessors":[{}],"bufferViews":[{}],"buffers":[{{"byteLength":{},"uri":"data:application/octet-stream;base64,{}"}}]}}"#,
+            self.objects.len(),
+            nodes.join(","),
+            meshes.join(","),
+            materials.join(","),
+            accessors.join(","),
+            buffer_views.join(","),
+            buffer.len(),
+            base64(&buffer)
+        )?;
+        writeln!(writer)
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const TABLE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |acc, (i, &b)| acc | (b as u32) << (16 - 8 * i));
+        for k in 0..4 {
+            if k <= chunk.len() {
+                out.push(TABLE[(n >> (18 - 6 * k) & 63) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        currents::CircularCurrent,
+        magnets::{CuboidMagnet, Dipole},
+        sensors::hall_effect::LinearHallSensor,
+    };
+    use approx::assert_relative_eq;
+    use nalgebra::vector;
+
+    fn scene() -> Scene<f64> {
+        let cuboid = CuboidMagnet::new(
+            [0.01, 0.0, 0.0],
+            UnitQuaternion::from_axis_angle(&Vector3::z_axis(), core::f64::consts::FRAC_PI_2),
+            [1.0, 0.0, 0.0],
+            [0.004, 0.002, 0.002],
+        );
+        let nested = SourceAssembly::default()
+            .with(Dipole::default())
+            .with(CircularCurrent::new(
+                [0.0, 0.0, 0.01],
+                UnitQuaternion::identity(),
+                0.01,
+                1.0,
+            ))
+            .with_position([0.0, 0.0, 0.02]);
+        let sources = SourceAssembly::default().with(cuboid).with(nested);
+        let sensors = ObserverAssembly::default().with(LinearHallSensor::new(
+            [0.0; 3],
+            UnitQuaternion::identity(),
+            [1.0, 0.0, 0.0],
+            1.0,
+            5.0,
+        ));
+        Scene::new(&sources, Some(&sensors), &ExportOptions::default())
+    }
+
+    #[test]
+    fn test_objects_at_global_pose() {
+        let scene = scene();
+        let names: Vec<&str> = scene.objects().iter().map(|o| o.name()).collect();
+        assert_eq!(
+            names,
+            [
+                "sources/0/CuboidMagnet",
+                "sources/1/0/Dipole",
+                "sources/1/1/CircularCurrent",
+                "sensors/0/LinearHallSensor",
+            ]
+        );
+
+        // Rotated cuboid spans y instead of x, and is colored red by its polarization along x
+        let cuboid = &scene.objects()[0];
+        let max = cuboid
+            .vertices()
+            .iter()
+            .fold(Vector3::repeat(f64::MIN), |acc, v| acc.sup(v));
+        assert_relative_eq!(max, vector![0.011, 0.002, 0.001], epsilon = 1e-12);
+        assert_eq!(cuboid.color(), [1.0, 0.5, 0.5]);
+
+        // Dipole moves with its assembly, and the ring is at the global z of the current
+        let dipole = &scene.objects()[1];
+        let center =
+            dipole.vertices().iter().sum::<Vector3<f64>>() / dipole.vertices().len() as f64;
+        assert_relative_eq!(center, vector![0.0, 0.0, 0.02], epsilon = 1e-9);
+        for v in scene.objects()[2].vertices() {
+            assert!((v.z - 0.03).abs() <= 2e-4 + 1e-12);
+        }
+
+        // Sensor marker points along x
+        let sensor = &scene.objects()[3];
+        assert_relative_eq!(
+            sensor.vertices()[4],
+            vector![5e-4, 0.0, 0.0],
+            epsilon = 1e-15
+        );
+    }
+
+    #[test]
+    fn test_writers() {
+        let scene = scene();
+        let faces: usize = scene.objects().iter().map(|o| o.faces().len()).sum();
+
+        let mut stl = Vec::new();
+        scene.write_stl(&mut stl).unwrap();
+        let stl = String::from_utf8(stl).unwrap();
+        assert_eq!(stl.matches("facet normal").count(), faces);
+        assert!(stl.starts_with("solid sources/0/CuboidMagnet\n"));
+
+        let mut obj = Vec::new();
+        scene.write_obj(&mut obj, Some("scene.mtl")).unwrap();
+        let obj = String::from_utf8(obj).unwrap();
+        assert_eq!(obj.lines().filter(|l| l.starts_with("f ")).count(), faces);
+        assert_eq!(obj.lines().filter(|l| l.starts_with("o ")).count(), 4);
+
+        let mut gltf = Vec::new();
+        scene.write_gltf(&mut gltf).unwrap();
+        let gltf: serde_json::Value = serde_json::from_slice(&gltf).unwrap();
+        assert_eq!(gltf["nodes"].as_array().unwrap().len(), 5);
+        assert_eq!(gltf["nodes"][0]["name"], "sources/0/CuboidMagnet");
+        let indices: u64 = gltf["accessors"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|a| a["type"] == "SCALAR")
+            .map(|a| a["count"].as_u64().unwrap())
+            .sum();
+        assert_eq!(indices as usize, 3 * faces);
+        let uri = gltf["buffers"][0]["uri"].as_str().unwrap();
+        let length = gltf["buffers"][0]["byteLength"].as_u64().unwrap() as usize;
+        assert_eq!(uri.split(',').nth(1).unwrap().len(), length.div_ceil(3) * 4);
+    }
+
+    #[test]
+    fn test_base64() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foob"), "Zm9vYg==");
+    }
+}
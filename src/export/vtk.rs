@@ -0,0 +1,652 @@
+/*
+ * Magba is licensed under The 3-Clause BSD, see LICENSE.
+ * Copyright 2025 Sira Pornsiriprasert <code@psira.me>
+ */
+
+//! VTK export of field samples and geometry for ParaView.
+//!
+//! Field samples are written as point data with three arrays: `B` (T), `B_over_mu0` (A/m),
+//! and `|B|` (T). `B_over_mu0` equals the H-field outside magnetized material only. Inside a
+//! magnet, H is `(B - J) / μ0` for the polarization `J`.
+//!
+//! | Data | Writer | Legacy | XML |
+//! |------|--------|--------|-----|
+//! | Regular grid | [write_image_data] | `.vtk` | `.vti` |
+//! | Curvilinear grid | [write_structured_grid] | `.vtk` | `.vts` |
+//! | [GridField] of any layout | [write_grid_field] | `.vtk` | `.vts` |
+//! | Scattered points | [write_poly_data] | `.vtk` | `.vtp` |
+//! | Geometry of a [Scene] | [write_unstructured_grid] | `.vtk` | `.vtu` |
+//!
+//! All files are written in ASCII.
+//!
+//! # Examples
+//!
+//! ```
+//! # use magba::prelude::*;
+//! # use magba::export::vtk::{ImageData, VtkFormat, write_image_data};
+//! # use nalgebra::{point, vector};
+//! let magnet = CylinderMagnet::default();
+//! let grid = ImageData::new(point![-2.1, -2.1, -2.1], vector![0.4, 0.4, 0.4], [11, 11, 11]);
+//! let b = magnet.compute_B_batch(&grid.points());
+//!
+//! let mut file = Vec::new(); // e.g. std::fs::File::create("field.vti")?
+//! write_image_data(&mut file, &grid, &b, VtkFormat::Xml).unwrap();
+//! ```
+
+use std::io::{self, Write};
+
+use nalgebra::{Point3, Vector3};
+
+use crate::{analysis::GridField, base::Float, conversion::B_to_H, export::Scene};
+
+/// VTK file format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VtkFormat {
+    /// Legacy `.vtk` format.
+    Legacy,
+    /// XML format, `.vti`, `.vts`, `.vtp`, or `.vtu` depending on the dataset.
+    Xml,
+}
+
+/// Regular grid with axis-aligned spacing (VTK ImageData).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImageData<T: Float> {
+    /// Position of the first grid point (m).
+    pub origin: Point3<T>,
+    /// Distance between grid points along each axis (m).
+    pub spacing: Vector3<T>,
+    /// Number of grid points along each axis.
+    pub dimensions: [usize; 3],
+}
+
+impl<T: Float> ImageData<T> {
+    pub fn new(origin: Point3<T>, spacing: Vector3<T>, dimensions: [usize; 3]) -> Self {
+        Self {
+            origin,
+            spacing,
+            dimensions,
+        }
+    }
+
+    /// Number of grid points.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.dimensions.iter().product()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Grid points in VTK order, with x varying fastest and z slowest.
+    pub fn points(&self) -> Vec<Point3<T>> {
+        let [nx, ny, nz] = self.dimensions;
+        let mut points = Vec::with_capacity(self.len());
+        for k in 0..nz {
+            for j in 0..ny {
+                for i in 0..nx {
+                    let index = Vector3::new(
+                        T::from_usize(i).unwrap(),
+                        T::from_usize(j).unwrap(),
+                        T::from_usize(k).unwrap(),
+                    );
+                    points.push(self.origin + self.spacing.component_mul(&index));
+                }
+            }
+        }
+        points
+    }
+}
+
+fn invalid_input(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, msg)
+}
+
+fn check_len(points: usize, b: usize) -> io::Result<()> {
+    if points != b {
+        return Err(invalid_input(
+            "Number of B-field vectors must match the number of points.",
+        ));
+    }
+    Ok(())
+}
+
+fn f64_of<T: Float>(x: T) -> f64 {
+    x.to_f64().unwrap()
+}
+
+fn write_vectors<W: Write, T: Float>(writer: &mut W, vectors: &[Vector3<T>]) -> io::Result<()> {
+    for v in vectors {
+        writeln!(
+            writer,
+            "{:e} {:e} {:e}",
+            f64_of(v.x),
+            f64_of(v.y),
+            f64_of(v.z)
+        )?;
+    }
+    Ok(())
+}
+
+fn write_scalars<W: Write, T: Float>(writer: &mut W, values: &[T]) -> io::Result<()> {
+    for &x in values {
+        writeln!(writer, "{:e}", f64_of(x))?;
+    }
+    Ok(())
+}
+
+fn extent(dimensions: [usize; 3]) -> String {
+    let [nx, ny, nz] = dimensions.map(|n| n.saturating_sub(1));
+    format!("0 {nx} 0 {ny} 0 {nz}")
+}
+
+/// B, B/μ0, and |B| arrays of the field samples.
+struct FieldArrays<T: Float> {
+    b: Vec<Vector3<T>>,
+    b_over_mu0: Vec<Vector3<T>>,
+    norm: Vec<T>,
+}
+
+impl<T: Float> FieldArrays<T> {
+    fn new(b: &[Vector3<T>]) -> Self {
+        Self {
+            b: b.to_vec(),
+            b_over_mu0: b.iter().map(|&b| B_to_H(b)).collect(),
+            norm: b.iter().map(|b| b.norm()).collect(),
+        }
+    }
+
+    fn write_legacy<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writeln!(writer, "POINT_DATA {}", self.b.len())?;
+        writeln!(writer, "VECTORS B double")?;
+        write_vectors(writer, &self.b)?;
+        writeln!(writer, "VECTORS B_over_mu0 double")?;
+        write_vectors(writer, &self.b_over_mu0)?;
+        writeln!(writer, "SCALARS |B| double 1")?;
+        writeln!(writer, "LOOKUP_TABLE default")?;
+        write_scalars(writer, &self.norm)
+    }
+
+    fn write_xml<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writeln!(writer, r#"<PointData Vectors="B" Scalars="|B|">"#)?;
+        writeln!(
+            writer,
+            r#"<DataArray type="Float64" Name="B" NumberOfComponents="3" format="ascii">"#
+        )?;
+        write_vectors(writer, &self.b)?;
+        writeln!(writer, "</DataArray>")?;
+        writeln!(
+            writer,
+            r#"<DataArray type="Float64" Name="B_over_mu0" NumberOfComponents="3" format="ascii">"#
+        )?;
+        write_vectors(writer, &self.b_over_mu0)?;
+        writeln!(writer, "</DataArray>")?;
+        writeln!(
+            writer,
+            r#"<DataArray type="Float64" Name="|B|" format="ascii">"#
+        )?;
+        write_scalars(writer, &self.norm)?;
+        writeln!(writer, "</DataArray>")?;
+        writeln!(writer, "</PointData>")
+    }
+}
+
+fn write_legacy_header<W: Write>(writer: &mut W, dataset: &str) -> io::Result<()> {
+    writeln!(writer, "# vtk DataFile Version 3.0")?;
+    writeln!(writer, "magba")?;
+    writeln!(writer, "ASCII")?;
+    writeln!(writer, "DATASET {dataset}")
+}
+
+fn write_xml_header<W: Write>(writer: &mut W, dataset: &str, attributes: &str) -> io::Result<()> {
+    writeln!(writer, r#"<?xml version="1.0"?>"#)?;
+    writeln!(
+        writer,
+        r#"<VTKFile type="{dataset}" version="1.0" byte_order="LittleEndian" header_type="UInt64">"#
+    )?;
+    if attributes.is_empty() {
+        writeln!(writer, "<{dataset}>")
+    } else {
+        writeln!(writer, "<{dataset} {attributes}>")
+    }
+}
+
+fn write_xml_footer<W: Write>(writer: &mut W, dataset: &str) -> io::Result<()> {
+    writeln!(writer, "</Piece>")?;
+    writeln!(writer, "</{dataset}>")?;
+    writeln!(writer, "</VTKFile>")
+}
+
+fn write_xml_points<W: Write, T: Float>(writer: &mut W, points: &[Vector3<T>]) -> io::Result<()> {
+    writeln!(writer, "<Points>")?;
+    writeln!(
+        writer,
+        r#"<DataArray type="Float64" NumberOfComponents="3" format="ascii">"#
+    )?;
+    write_vectors(writer, points)?;
+    writeln!(writer, "</DataArray>")?;
+    writeln!(writer, "</Points>")
+}
+
+fn write_xml_ints<W: Write>(
+    writer: &mut W,
+    kind: &str,
+    name: &str,
+    values: impl IntoIterator<Item = usize>,
+) -> io::Result<()> {
+    writeln!(
+        writer,
+        r#"<DataArray type="{kind}" Name="{name}" format="ascii">"#
+    )?;
+    for value in values {
+        writeln!(writer, "{value}")?;
+    }
+    writeln!(writer, "</DataArray>")
+}
+
+/// Writes field samples on a regular grid, `b` being the B-field (T) at [ImageData::points].
+///
+/// Returns an error of kind [io::ErrorKind::InvalidInput] if the lengths do not match.
+pub fn write_image_data<W: Write, T: Float>(
+    mut writer: W,
+    grid: &ImageData<T>,
+    b: &[Vector3<T>],
+    format: VtkFormat,
+) -> io::Result<()> {
+    check_len(grid.len(), b.len())?;
+    let [nx, ny, nz] = grid.dimensions;
+    let origin = grid.origin.coords.map(f64_of);
+    let spacing = grid.spacing.map(f64_of);
+    let arrays = FieldArrays::new(b);
+
+    match format {
+        VtkFormat::Legacy => {
+            write_legacy_header(&mut writer, "STRUCTURED_POINTS")?;
+            writeln!(writer, "DIMENSIONS {nx} {ny} {nz}")?;
+            writeln!(
+                writer,
+                "ORIGIN {:e} {:e} {:e}",
+                origin.x, origin.y, origin.z
+            )?;
+            writeln!(
+                writer,
+                "SPACING {:e} {:e} {:e}",
+                spacing.x, spacing.y, spacing.z
+            )?;
+            arrays.write_legacy(&mut writer)
+        }
+        VtkFormat::Xml => {
+            let extent = extent(grid.dimensions);
+            write_xml_header(
+                &mut writer,
+                "ImageData",
+                &format!(
+                    r#"WholeExtent="{extent}" Origin="{:e} {:e} {:e}" Spacing="{:e} {:e} {:e}""#,
+                    origin.x, origin.y, origin.z, spacing.x, spacing.y, spacing.z
+                ),
+            )?;
+            writeln!(writer, r#"<Piece Extent="{extent}">"#)?;
+            arrays.write_xml(&mut writer)?;
+            write_xml_footer(&mut writer, "ImageData")
+        }
+    }
+}
+
+/// Writes field samples on a curvilinear grid of `dimensions` points.
+///
+/// `points` are in VTK order, with the first index varying fastest, and `b` is the
+/// B-field (T) at each point. Returns an error of kind [io::ErrorKind::InvalidInput] if
+/// the lengths do not match.
+pub fn write_structured_grid<W: Write, T: Float>(
+    mut writer: W,
+    dimensions: [usize; 3],
+    points: &[Point3<T>],
+    b: &[Vector3<T>],
+    format: VtkFormat,
+) -> io::Result<()> {
+    if dimensions.iter().product::<usize>() != points.len() {
+        return Err(invalid_input(
+            "Number of points must match the grid dimensions.",
+        ));
+    }
+    check_len(points.len(), b.len())?;
+    let coords: Vec<Vector3<T>> = points.iter().map(|p| p.coords).collect();
+    let arrays = FieldArrays::new(b);
+    let [nx, ny, nz] = dimensions;
+
+    match format {
+        VtkFormat::Legacy => {
+            write_legacy_header(&mut writer, "STRUCTURED_GRID")?;
+            writeln!(writer, "DIMENSIONS {nx} {ny} {nz}")?;
+            writeln!(writer, "POINTS {} double", points.len())?;
+            write_vectors(&mut writer, &coords)?;
+            arrays.write_legacy(&mut writer)
+        }
+        VtkFormat::Xml => {
+            let extent = extent(dimensions);
+            write_xml_header(
+                &mut writer,
+                "StructuredGrid",
+                &format!(r#"WholeExtent="{extent}""#),
+            )?;
+            writeln!(writer, r#"<Piece Extent="{extent}">"#)?;
+            arrays.write_xml(&mut writer)?;
+            write_xml_points(&mut writer, &coords)?;
+            write_xml_footer(&mut writer, "StructuredGrid")
+        }
+    }
+}
+
+/// Writes a [GridField] as a curvilinear grid. See [write_structured_grid].
+pub fn write_grid_field<W: Write, T: Float>(
+    writer: W,
+    field: &GridField<T>,
+    format: VtkFormat,
+) -> io::Result<()> {
+    write_structured_grid(
+        writer,
+        field.shape(),
+        field.points(),
+        field.values(),
+        format,
+    )
+}
+
+/// Writes field samples at scattered points as vertices, `b` being the B-field (T) at
+/// each point.
+///
+/// Returns an error of kind [io::ErrorKind::InvalidInput] if the lengths do not match.
+pub fn write_poly_data<W: Write, T: Float>(
+    mut writer: W,
+    points: &[Point3<T>],
+    b: &[Vector3<T>],
+    format: VtkFormat,
+) -> io::Result<()> {
+    check_len(points.len(), b.len())?;
+    let coords: Vec<Vector3<T>> = points.iter().map(|p| p.coords).collect();
+    let arrays = FieldArrays::new(b);
+    let n = points.len();
+
+    match format {
+        VtkFormat::Legacy => {
+            write_legacy_header(&mut writer, "POLYDATA")?;
+            writeln!(writer, "POINTS {n} double")?;
+            write_vectors(&mut writer, &coords)?;
+            writeln!(writer, "VERTICES {n} {}", 2 * n)?;
+            for i in 0..n {
+                writeln!(writer, "1 {i}")?;
+            }
+            arrays.write_legacy(&mut writer)
+        }
+        VtkFormat::Xml => {
+            write_xml_header(&mut writer, "PolyData", "")?;
+            writeln!(
+                writer,
+                r#"<Piece NumberOfPoints="{n}" NumberOfVerts="{n}" NumberOfLines="0" NumberOfStrips="0" NumberOfPolys="0">"#
+            )?;
+            arrays.write_xml(&mut writer)?;
+            write_xml_points(&mut writer, &coords)?;
+            writeln!(writer, "<Verts>")?;
+            write_xml_ints(&mut writer, "Int64", "connectivity", 0..n)?;
+            write_xml_ints(&mut writer, "Int64", "offsets", 1..=n)?;
+            writeln!(writer, "</Verts>")?;
+            write_xml_footer(&mut writer, "PolyData")
+        }
+    }
+}
+
+/// Writes the geometry of a [Scene] as triangle cells.
+///
+/// Each cell has the index of its scene object as `object_id`, and the object color as
+/// `color`. XML files list the object names in the order of `object_id` in a comment.
+pub fn write_unstructured_grid<W: Write, T: Float>(
+    mut writer: W,
+    scene: &Scene<T>,
+    format: VtkFormat,
+) -> io::Result<()> {
+    let objects = scene.objects();
+    let vertices: Vec<Vector3<T>> = objects
+        .iter()
+        .flat_map(|o| o.vertices().iter().copied())
+        .collect();
+    let mut cells = Vec::new();
+    let mut object_ids = Vec::new();
+    let mut offset = 0;
+    for (id, object) in objects.iter().enumerate() {
+        cells.extend(object.faces().iter().map(|f| f.map(|i| i + offset)));
+        object_ids.extend(core::iter::repeat_n(id, object.faces().len()));
+        offset += object.vertices().len();
+    }
+    let colors = object_ids.iter().map(|&id| objects[id].color());
+    let names = objects
+        .iter()
+        .map(|o| o.name().replace(char::is_whitespace, "_"))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let (n, m) = (vertices.len(), cells.len());
+
+    match format {
+        VtkFormat::Legacy => {
+            write_legacy_header(&mut writer, "UNSTRUCTURED_GRID")?;
+            writeln!(writer, "POINTS {n} double")?;
+            write_vectors(&mut writer, &vertices)?;
+            writeln!(writer, "CELLS {m} {}", 4 * m)?;
+            for [a, b, c] in &cells {
+                writeln!(writer, "3 {a} {b} {c}")?;
+            }
+            writeln!(writer, "CELL_TYPES {m}")?;
+            for _ in 0..m {
+                writeln!(writer, "5")?;
+            }
+            writeln!(writer, "CELL_DATA {m}")?;
+            writeln!(writer, "SCALARS object_id int 1")?;
+            writeln!(writer, "LOOKUP_TABLE default")?;
+            for id in &object_ids {
+                writeln!(writer, "{id}")?;
+            }
+            writeln!(writer, "COLOR_SCALARS color 3")?;
+            for [r, g, b] in colors {
+                writeln!(writer, "{r} {g} {b}")?;
+            }
+            Ok(())
+        }
+        VtkFormat::Xml => {
+            writeln!(writer, r#"<?xml version="1.0"?>"#)?;
+            writeln!(
+                writer,
+                "<!-- magba objects: {} -->",
+                names.replace("--", "__")
+            )?;
+            writeln!(
+                writer,
+                r#"<VTKFile type="UnstructuredGrid" version="1.0" byte_order="LittleEndian" header_type="UInt64">"#
+            )?;
+            writeln!(writer, "<UnstructuredGrid>")?;
+            writeln!(
+                writer,
+                r#"<Piece NumberOfPoints="{n}" NumberOfCells="{m}">"#
+            )?;
+            writeln!(writer, r#"<CellData Scalars="object_id">"#)?;
+            write_xml_ints(
+                &mut writer,
+                "Int32",
+                "object_id",
+                object_ids.iter().copied(),
+            )?;
+            writeln!(
+                writer,
+                r#"<DataArray type="Float32" Name="color" NumberOfComponents="3" format="ascii">"#
+            )?;
+            for [r, g, b] in colors {
+                writeln!(writer, "{r} {g} {b}")?;
+            }
+            writeln!(writer, "</DataArray>")?;
+            writeln!(writer, "</CellData>")?;
+            write_xml_points(&mut writer, &vertices)?;
+            writeln!(writer, "<Cells>")?;
+            writeln!(
+                writer,
+                r#"<DataArray type="Int64" Name="connectivity" format="ascii">"#
+            )?;
+            for [a, b, c] in &cells {
+                writeln!(writer, "{a} {b} {c}")?;
+            }
+            writeln!(writer, "</DataArray>")?;
+            write_xml_ints(&mut writer, "Int64", "offsets", (1..=m).map(|i| 3 * i))?;
+            write_xml_ints(&mut writer, "UInt8", "types", core::iter::repeat_n(5, m))?;
+            writeln!(writer, "</Cells>")?;
+            write_xml_footer(&mut writer, "UnstructuredGrid")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        base::Source,
+        collections::SourceAssembly,
+        export::ExportOptions,
+        magnets::{CuboidMagnet, CylinderMagnet},
+    };
+    use nalgebra::{point, vector};
+
+    /// Numbers in the body of a legacy section, after its header line.
+    fn section(file: &str, header: &str) -> Vec<f64> {
+        file.lines()
+            .skip_while(|l| !l.starts_with(header))
+            .skip(1)
+            .take_while(|l| !l.starts_with(char::is_alphabetic))
+            .flat_map(|l| l.split_whitespace().map(|x| x.parse::<f64>().unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn test_image_data() {
+        let magnet = CylinderMagnet::default();
+        let grid = ImageData::new(point![-1.0, -1.0, 2.0], vector![1.0, 1.0, 0.5], [3, 3, 2]);
+        let points = grid.points();
+        assert_eq!(points.len(), 18);
+        assert_eq!(points[1], point![0.0, -1.0, 2.0]);
+        assert_eq!(points[17], point![1.0, 1.0, 2.5]);
+        let b = magnet.compute_B_batch(&points);
+
+        let mut legacy = Vec::new();
+        write_image_data(&mut legacy, &grid, &b, VtkFormat::Legacy).unwrap();
+        let legacy = String::from_utf8(legacy).unwrap();
+        assert!(legacy.contains("DIMENSIONS 3 3 2\n"));
+        let b_out = section(&legacy, "VECTORS B");
+        let b_over_mu0_out = section(&legacy, "VECTORS B_over_mu0");
+        let norm_out = section(&legacy, "LOOKUP_TABLE");
+        assert_eq!(b_out.len(), 54);
+        for i in 0..18 {
+            let expected = b[i].iter().copied().collect::<Vec<_>>();
+            assert_eq!(&b_out[3 * i..3 * i + 3], &expected);
+            approx::assert_relative_eq!(
+                b_over_mu0_out[3 * i + 2],
+                b[i].z / f64::mu0(),
+                max_relative = 1e-15
+            );
+            approx::assert_relative_eq!(norm_out[i], b[i].norm());
+        }
+
+        let mut xml = Vec::new();
+        write_image_data(&mut xml, &grid, &b, VtkFormat::Xml).unwrap();
+        let xml = String::from_utf8(xml).unwrap();
+        assert!(xml.contains(r#"<ImageData WholeExtent="0 2 0 2 0 1""#));
+        assert_eq!(xml.matches("<DataArray").count(), 3);
+        assert!(xml.trim_end().ends_with("</VTKFile>"));
+
+        assert_eq!(
+            write_image_data(&mut Vec::new(), &grid, &b[1..], VtkFormat::Xml)
+                .unwrap_err()
+                .kind(),
+            io::ErrorKind::InvalidInput
+        );
+    }
+
+    #[test]
+    fn test_points_and_structured_grid() {
+        let magnet = CuboidMagnet::default();
+        let points = [
+            point![1.0, 0.0, 0.0],
+            point![0.0, 2.0, 0.0],
+            point![0.0, 0.0, 3.0],
+            point![1.0, 1.0, 1.0],
+        ];
+        let b = magnet.compute_B_batch(&points);
+
+        let mut legacy = Vec::new();
+        write_poly_data(&mut legacy, &points, &b, VtkFormat::Legacy).unwrap();
+        let legacy = String::from_utf8(legacy).unwrap();
+        assert_eq!(
+            section(&legacy, "POINTS 4"),
+            [1.0, 0.0, 0.0, 0.0, 2.0, 0.0, 0.0, 0.0, 3.0, 1.0, 1.0, 1.0]
+        );
+        assert_eq!(
+            section(&legacy, "VERTICES"),
+            [1.0, 0.0, 1.0, 1.0, 1.0, 2.0, 1.0, 3.0]
+        );
+
+        let mut xml = Vec::new();
+        write_poly_data(&mut xml, &points, &b, VtkFormat::Xml).unwrap();
+        assert!(
+            String::from_utf8(xml)
+                .unwrap()
+                .contains(r#"NumberOfVerts="4""#)
+        );
+
+        let mut xml = Vec::new();
+        write_structured_grid(&mut xml, [2, 2, 1], &points, &b, VtkFormat::Xml).unwrap();
+        assert!(
+            String::from_utf8(xml)
+                .unwrap()
+                .contains(r#"<StructuredGrid WholeExtent="0 1 0 1 0 0">"#)
+        );
+        let field =
+            crate::analysis::FieldGrid::line([0.0; 3], [1.0, 0.0, 0.0], 4).compute_B(&magnet);
+        let mut legacy = Vec::new();
+        write_grid_field(&mut legacy, &field, VtkFormat::Legacy).unwrap();
+        assert!(
+            String::from_utf8(legacy)
+                .unwrap()
+                .contains("DIMENSIONS 4 1 1\n")
+        );
+
+        assert!(
+            write_structured_grid(&mut Vec::new(), [2, 2, 2], &points, &b, VtkFormat::Legacy)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_unstructured_grid() {
+        let sources = SourceAssembly::default()
+            .with(CuboidMagnet::default())
+            .with(CuboidMagnet::default().with_position([2.0, 0.0, 0.0]));
+        let scene = Scene::new(
+            &sources,
+            None,
+            &ExportOptions::default().with_resolution(1.0),
+        );
+        let cells: usize = scene.objects().iter().map(|o| o.faces().len()).sum();
+        assert_eq!(cells, 24);
+
+        let mut legacy = Vec::new();
+        write_unstructured_grid(&mut legacy, &scene, VtkFormat::Legacy).unwrap();
+        let legacy = String::from_utf8(legacy).unwrap();
+        assert!(legacy.contains("CELLS 24 96\n"));
+        let ids = section(&legacy, "LOOKUP_TABLE");
+        assert_eq!(ids.iter().filter(|&&id| id == 1.0).count(), 12);
+        // The second cuboid is shifted along x
+        let points = section(&legacy, "POINTS");
+        assert_eq!(points[3 * 8], 1.5);
+
+        let mut xml = Vec::new();
+        write_unstructured_grid(&mut xml, &scene, VtkFormat::Xml).unwrap();
+        let xml = String::from_utf8(xml).unwrap();
+        assert!(xml.contains(r#"<Piece NumberOfPoints="16" NumberOfCells="24">"#));
+        assert!(xml.contains("sources/1/CuboidMagnet"));
+    }
+}
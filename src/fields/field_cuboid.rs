@@ -284,6 +284,17 @@ pub fn cuboid_B_batch<T: RealField + Copy>(
     dimensions: Vector3<T>,
     out: &mut [Vector3<T>],
 ) {
+    #[cfg(feature = "simd")]
+    if super::simd::try_simd!(cuboid_B_batch(
+        points,
+        position,
+        orientation,
+        polarization,
+        dimensions;
+        out
+    )) {
+        return;
+    }
     impl_parallel!(
         cuboid_B,
         rayon_threshold: 50,
@@ -402,6 +402,18 @@ pub fn cylinder_B_batch<T: Float>(
     height: T,
     out: &mut [Vector3<T>],
 ) {
+    #[cfg(feature = "simd")]
+    if super::simd::try_simd!(cylinder_B_batch(
+        points,
+        position,
+        orientation,
+        polarization,
+        diameter,
+        height;
+        out
+    )) {
+        return;
+    }
     impl_parallel!(
         cylinder_B,
         rayon_threshold: 100,
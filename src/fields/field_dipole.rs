@@ -158,6 +158,10 @@ pub fn dipole_B_batch<T: Float>(
     moment: Vector3<T>,
     out: &mut [Vector3<T>],
 ) {
+    #[cfg(feature = "simd")]
+    if super::simd::try_simd!(dipole_B_batch(points, position, orientation, moment; out)) {
+        return;
+    }
     impl_parallel!(
         dipole_B, rayon_threshold: 2500, input: points, output: out, args: [position, orientation, moment]
     )
@@ -5,25 +5,107 @@
 
 //! Analytical B-field computation for homogeneously magnetized triangular mesh.
 
+use alloc::vec::Vec;
 use nalgebra::{Point3, UnitQuaternion, Vector3};
 
 use crate::{
     base::{
         Float,
         coordinate::compute_in_local,
-        mesh::{TriMesh, Triangle, is_ray_hit},
+        mesh::{TriMesh, winding_number},
     },
     crate_utils::{impl_parallel, impl_parallel_sum},
-    fields::field_triangle::local_triangle_B,
+    fields::field_triangle::{edge_integral, local_triangle_B, solid_angle},
 };
 
+/// Observer–vertex vectors, their norms, and the edge integrals of [for_each_face_integral],
+/// kept between calls so that a batch allocates them once per thread instead of once per
+/// observer point.
+pub(crate) struct FaceScratch<T> {
+    r_vecs: Vec<Vector3<T>>,
+    r_mags: Vec<T>,
+    edge_terms: Vec<Vector3<T>>,
+}
+
+impl<T> FaceScratch<T> {
+    pub(crate) const fn new() -> Self {
+        Self {
+            r_vecs: Vec::new(),
+            r_mags: Vec::new(),
+            edge_terms: Vec::new(),
+        }
+    }
+}
+
+/// Computes the surface integral `∫ (r - r') / |r - r'|³ dA'` of each mesh face seen from
+/// observer `r` at point in local frame.
+///
+/// The observer–vertex vectors and the edge integrals are evaluated once per vertex and
+/// per edge, and shared by the adjacent faces. The integral of each face is passed to
+/// `f(face_index, unit_normal, integral)`. Degenerate faces are skipped.
+#[allow(non_snake_case)]
+pub(crate) fn for_each_face_integral<T, F>(
+    point: Point3<T>,
+    mesh: &TriMesh<T>,
+    scratch: &mut FaceScratch<T>,
+    mut f: F,
+) where
+    T: Float,
+    F: FnMut(usize, Vector3<T>, Vector3<T>),
+{
+    let vertices = mesh.vertices();
+    let FaceScratch {
+        r_vecs,
+        r_mags,
+        edge_terms,
+    } = scratch;
+
+    r_vecs.clear();
+    r_vecs.extend(vertices.iter().map(|v| v - point.coords));
+    r_mags.clear();
+    r_mags.extend(r_vecs.iter().map(|r| r.norm()));
+
+    edge_terms.clear();
+    edge_terms.extend(mesh.edges().iter().map(|&[a, b]| {
+        let L = vertices[b] - vertices[a];
+        L * edge_integral(r_mags[a], r_vecs[a].dot(&L), L.norm())
+    }));
+
+    for (i, (face, face_edges)) in mesh.faces().iter().zip(mesh.face_edges()).enumerate() {
+        let n_cross =
+            (vertices[face[1]] - vertices[face[0]]).cross(&(vertices[face[2]] - vertices[face[0]]));
+        let n_norm = n_cross.norm();
+        if n_norm == T::zero() {
+            continue;
+        }
+        let n = n_cross / n_norm;
+
+        let omega = solid_angle(&face.map(|v| r_vecs[v]), &face.map(|v| r_mags[v]));
+        let PQR = (0..3).fold(Vector3::zeros(), |acc, k| {
+            let term = edge_terms[face_edges[k]];
+            if face[k] < face[(k + 1) % 3] {
+                acc + term
+            } else {
+                acc - term
+            }
+        });
+
+        f(i, n, n * omega - n.cross(&PQR));
+    }
+}
+
 /// Computes B-field of a homogeneously magnetized mesh at point in local frame.
 ///
 /// # Arguments
 ///
 /// - `point`: Observer position (m)
 /// - `polarization`: Polarization vector (T)
-/// - `triangles`: Triangles forming the mesh in local coords (m)
+/// - `mesh`: Mesh in local coords (m)
+///
+/// The mesh may contain several bodies and cavities. Points are classified by the
+/// generalized winding number, so cavities (inward-oriented shells) carry no polarization
+/// and overlapping bodies superpose. If the mesh has a bounding volume hierarchy, distant
+/// triangle clusters are approximated by their multipole expansion (see [TriMesh::with_bvh]).
 ///
 /// # Returns
 ///
@@ -33,24 +115,58 @@ use crate::{
 pub fn local_mesh_B<T: Float>(
     point: Point3<T>,
     polarization: Vector3<T>,
-    triangles: &[Triangle<T>],
+    mesh: &TriMesh<T>,
 ) -> Vector3<T> {
-    let mut b_total = Vector3::zeros();
-
-    let ray_origin = point.coords;
-    let ray_dir = Vector3::new(T::one(), T::zero(), T::zero());
-
-    let mut intersections = 0;
-    triangles.iter().for_each(|&triangle| {
-        b_total += local_triangle_B(point, polarization, triangle.vertices());
+    local_mesh_B_with_scratch(point, polarization, mesh, &mut FaceScratch::new())
+}
 
-        if is_ray_hit(triangle, ray_origin, ray_dir, T::zero(), T::infinity()) {
-            intersections += 1;
+/// Computes B-field of a homogeneously magnetized mesh at point in local frame, reusing the
+/// buffers in `scratch`.
+#[inline]
+#[allow(non_snake_case)]
+fn local_mesh_B_with_scratch<T: Float>(
+    point: Point3<T>,
+    polarization: Vector3<T>,
+    mesh: &TriMesh<T>,
+    scratch: &mut FaceScratch<T>,
+) -> Vector3<T> {
+    let triangles = mesh.triangles();
+    let (mut b_total, winding) = match mesh.bvh() {
+        Some(bvh) => {
+            let (b, solid_angle) = bvh.fold(
+                point.coords,
+                (Vector3::zeros(), T::zero()),
+                |(b, omega), i| {
+                    let triangle = triangles[i];
+                    (
+                        b + local_triangle_B(point, polarization, triangle.vertices()),
+                        omega + triangle.solid_angle(point.coords),
+                    )
+                },
+                |(b, omega), id, d, r2| {
+                    (
+                        b + bvh.far_charge_B(id, d, r2, polarization),
+                        omega + bvh.far_solid_angle(id, d, r2),
+                    )
+                },
+            );
+            (b, solid_angle / (T::from_f64(4.0).unwrap() * T::pi()))
+        }
+        None => {
+            let mut b = Vector3::zeros();
+            for_each_face_integral(point, mesh, scratch, |_, n, integral| {
+                b += integral * n.dot(&polarization);
+            });
+            (
+                b / (T::from_f64(4.0).unwrap() * T::pi()),
+                winding_number(triangles, point.coords),
+            )
         }
-    });
+    };
 
-    if intersections % 2 != 0 {
-        b_total += polarization;
+    let winding = num_traits::Float::round(winding);
+    if winding != T::zero() {
+        b_total += polarization * winding;
     }
 
     b_total
@@ -64,7 +180,7 @@ pub fn local_mesh_B<T: Float>(
 /// - `position`: Element center/position (m)
 /// - `orientation`: Element orientation in unit quaternion
 /// - `polarization`: Polarization vector (T)
-/// - `triangles`: Triangles forming the mesh
+/// - `mesh`: Triangular mesh in local coords (m)
 ///
 /// # Returns
 ///
@@ -83,7 +199,28 @@ pub fn mesh_B<T: Float>(
         point,
         position,
         orientation,
-        (polarization, mesh.triangles()),
+        (polarization, mesh),
+    )
+}
+
+/// Computes B-field of a homogeneously magnetized mesh at point (x, y, z), reusing the
+/// buffers in `scratch`.
+#[inline]
+#[allow(non_snake_case)]
+fn mesh_B_with_scratch<T: Float>(
+    point: Point3<T>,
+    position: Point3<T>,
+    orientation: UnitQuaternion<T>,
+    polarization: Vector3<T>,
+    mesh: &TriMesh<T>,
+    scratch: &mut FaceScratch<T>,
+) -> Vector3<T> {
+    compute_in_local!(
+        local_mesh_B_with_scratch,
+        point,
+        position,
+        orientation,
+        (polarization, mesh, scratch),
     )
 }
 
@@ -95,7 +232,7 @@ pub fn mesh_B<T: Float>(
 /// - `position`: Element position (m)
 /// - `orientation`: Element orientation in unit quaternion
 /// - `polarization`: Polarization vector (T)
-/// - `triangles`: Triangles forming the mesh
+/// - `mesh`: Triangular mesh in local coords (m)
 /// - `out`: Mutable slice to store the B-field vectors at each observer (T)
 #[allow(non_snake_case)]
 pub fn mesh_B_batch<T: Float>(
@@ -107,11 +244,12 @@ pub fn mesh_B_batch<T: Float>(
     out: &mut [Vector3<T>],
 ) {
     impl_parallel!(
-        mesh_B,
+        mesh_B_with_scratch,
         rayon_threshold: 100,
         input: points,
         output: out,
-        args: [position, orientation, polarization, mesh]
+        args: [position, orientation, polarization, mesh],
+        scratch: FaceScratch::new()
     )
 }
 
@@ -142,3 +280,84 @@ pub fn sum_multiple_mesh_B<T: Float>(
         |pos, p, o, pol, mesh| mesh_B(*pos, *p, *o, *pol, mesh)
     )
 }
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::{
+        currents::{PlanarCoil, SpiralShape},
+        fields::{
+            field_sheet_current::local_sheet_current_B,
+            field_triangle_current::local_triangle_current_B,
+        },
+        testing_util::cube_mesh,
+    };
+    use approx::assert_relative_eq;
+    use nalgebra::{point, vector};
+
+    fn points() -> [Point3<f64>; 5] {
+        [
+            point![0.0, 0.0, 0.0],
+            point![0.05, -0.02, 0.01],
+            point![0.3, 0.1, -0.2],
+            point![-0.1, 0.15, 0.1],
+            point![0.002, 0.003, 0.0005],
+        ]
+    }
+
+    #[test]
+    fn test_shared_edges() {
+        let (vertices, faces) = cube_mesh(Vector3::zeros(), 0.2, false);
+        let mesh = TriMesh::new(vertices, faces).unwrap();
+        assert_eq!(mesh.edges().len(), 18);
+        for (face, edges) in mesh.faces().iter().zip(mesh.face_edges()) {
+            for k in 0..3 {
+                let [a, b] = mesh.edges()[edges[k]];
+                let (u, v) = (face[k], face[(k + 1) % 3]);
+                assert_eq!([a, b], [u.min(v), u.max(v)]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_mesh_kernel() {
+        let (vertices, faces) = cube_mesh(vector![0.01, 0.0, -0.02], 0.2, false);
+        let mesh = TriMesh::new(vertices, faces).unwrap();
+        let polarization = vector![0.1, -0.2, 0.3];
+
+        for p in points() {
+            let per_triangle = mesh.triangles().iter().fold(Vector3::zeros(), |acc, t| {
+                acc + local_triangle_B(p, polarization, t.vertices())
+            }) + polarization * mesh.winding_number(p).round();
+            assert_relative_eq!(
+                local_mesh_B(p, polarization, &mesh),
+                per_triangle,
+                epsilon = 1e-15,
+                max_relative = 1e-12
+            );
+        }
+    }
+
+    #[test]
+    fn test_sheet_current_kernel() {
+        let sheet = PlanarCoil::new(SpiralShape::Hexagonal, 0.02, 3.0, 0.002).to_sheet_current();
+        let (current_densities, mesh) = (sheet.current_densities(), sheet.mesh());
+
+        for p in points() {
+            let p = p * 0.1 + vector![0.0, 0.0, 0.001];
+            let per_triangle = mesh
+                .triangles()
+                .iter()
+                .zip(current_densities)
+                .fold(Vector3::zeros(), |acc, (t, &j)| {
+                    acc + local_triangle_current_B(p, j, &t.vertices())
+                });
+            assert_relative_eq!(
+                local_sheet_current_B(p, current_densities, mesh),
+                per_triangle,
+                epsilon = 1e-15,
+                max_relative = 1e-9
+            );
+        }
+    }
+}
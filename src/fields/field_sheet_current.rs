@@ -5,15 +5,23 @@
 
 //! Analytical B-field computation for a triangular mesh carrying surface current.
 
+use alloc::vec::Vec;
 use nalgebra::{Point3, UnitQuaternion, Vector3};
 
 use crate::{
-    base::{Float, coordinate::compute_in_local, mesh::TriMesh},
+    base::{Float, bvh::CurrentMoments, coordinate::compute_in_local, mesh::TriMesh},
     crate_utils::{impl_parallel, impl_parallel_sum},
-    fields::field_triangle_current::local_triangle_current_B,
+    fields::{
+        field_mesh::{FaceScratch, for_each_face_integral},
+        field_triangle_current::local_triangle_current_B,
+    },
 };
 
 /// Computes B-field of a current sheet mesh at point in local frame.
+///
+/// If the mesh has a bounding volume hierarchy, distant triangle clusters are approximated
+/// by their multipole expansion (see [TriMesh::with_bvh]). The cluster moments are computed
+/// on each call; [SheetCurrent](crate::currents::SheetCurrent) caches them instead.
 #[inline]
 #[allow(non_snake_case)]
 pub fn local_sheet_current_B<T: Float>(
@@ -21,17 +29,93 @@ pub fn local_sheet_current_B<T: Float>(
     current_densities: &[Vector3<T>],
     mesh: &TriMesh<T>,
 ) -> Vector3<T> {
-    let mut b_total = Vector3::zeros();
+    if let Some(bvh) = mesh.bvh() {
+        let moments = bvh.current_moments(mesh.triangles(), current_densities);
+        return local_sheet_current_B_clustered(point, current_densities, mesh, &moments);
+    }
 
-    let count = core::cmp::min(current_densities.len(), mesh.triangles().len());
-    for (i, &triangle) in mesh.triangles().iter().take(count).enumerate() {
-        let j = current_densities[i];
-        if j != Vector3::zeros() {
-            b_total += local_triangle_current_B(point, j, &triangle.vertices());
+    local_sheet_current_B_with_scratch(point, current_densities, mesh, &mut FaceScratch::new())
+}
+
+/// Computes B-field of a current sheet mesh without using its bounding volume hierarchy at
+/// point in local frame, reusing the buffers in `scratch`.
+#[inline]
+#[allow(non_snake_case)]
+fn local_sheet_current_B_with_scratch<T: Float>(
+    point: Point3<T>,
+    current_densities: &[Vector3<T>],
+    mesh: &TriMesh<T>,
+    scratch: &mut FaceScratch<T>,
+) -> Vector3<T> {
+    let mut b_total = Vector3::zeros();
+    for_each_face_integral(point, mesh, scratch, |i, n, integral| {
+        if let Some(&j) = current_densities.get(i) {
+            b_total += (j - n * n.dot(&j)).cross(&integral);
         }
-    }
+    });
+
+    b_total * T::mu0_4pi()
+}
+
+/// Computes B-field of a current sheet mesh with a bounding volume hierarchy at point in
+/// local frame, given the current moments of each cluster.
+#[inline]
+#[allow(non_snake_case)]
+fn local_sheet_current_B_clustered<T: Float>(
+    point: Point3<T>,
+    current_densities: &[Vector3<T>],
+    mesh: &TriMesh<T>,
+    moments: &[CurrentMoments<T>],
+) -> Vector3<T> {
+    let Some(bvh) = mesh.bvh() else {
+        return local_sheet_current_B(point, current_densities, mesh);
+    };
+    let triangles = mesh.triangles();
+
+    bvh.fold(
+        point.coords,
+        Vector3::zeros(),
+        |b, i| match current_densities.get(i) {
+            Some(&j) if j != Vector3::zeros() => {
+                b + local_triangle_current_B(point, j, &triangles[i].vertices())
+            }
+            _ => b,
+        },
+        |b, id, d, r2| b + bvh.far_current_B(d, r2, &moments[id]),
+    )
+}
 
-    b_total
+/// Current moments of each cluster in the bounding volume hierarchy of the mesh, or none
+/// if the mesh has no hierarchy.
+pub(crate) fn sheet_current_moments<T: Float>(
+    current_densities: &[Vector3<T>],
+    mesh: &TriMesh<T>,
+) -> Vec<CurrentMoments<T>> {
+    mesh.bvh()
+        .map(|bvh| bvh.current_moments(mesh.triangles(), current_densities))
+        .unwrap_or_default()
+}
+
+/// Computes B-field of a current sheet mesh with precomputed cluster moments at point (x, y, z).
+///
+/// `moments` must come from [sheet_current_moments] for the same densities and mesh.
+#[inline]
+#[allow(non_snake_case)]
+pub(crate) fn sheet_current_B_clustered<T: Float>(
+    point: Point3<T>,
+    position: Point3<T>,
+    orientation: UnitQuaternion<T>,
+    current_densities: &[Vector3<T>],
+    mesh: &TriMesh<T>,
+    moments: &[CurrentMoments<T>],
+) -> Vector3<T> {
+    compute_in_local!(
+        local_sheet_current_B_clustered,
+        point,
+        position,
+        orientation,
+        (current_densities, mesh, moments),
+    )
 }
 
 /// Computes B-field of a current sheet mesh at point (x, y, z).
@@ -53,6 +137,27 @@ pub fn sheet_current_B<T: Float>(
     )
 }
 
+/// Computes B-field of a current sheet mesh without a bounding volume hierarchy at point
+/// (x, y, z), reusing the buffers in `scratch`.
+#[inline]
+#[allow(non_snake_case)]
+fn sheet_current_B_with_scratch<T: Float>(
+    point: Point3<T>,
+    position: Point3<T>,
+    orientation: UnitQuaternion<T>,
+    current_densities: &[Vector3<T>],
+    mesh: &TriMesh<T>,
+    scratch: &mut FaceScratch<T>,
+) -> Vector3<T> {
+    compute_in_local!(
+        local_sheet_current_B_with_scratch,
+        point,
+        position,
+        orientation,
+        (current_densities, mesh, scratch),
+    )
+}
+
 /// Computes B-field at points in global frame for a current sheet mesh.
 #[allow(non_snake_case)]
 pub fn sheet_current_B_batch<T: Float>(
@@ -63,12 +168,51 @@ pub fn sheet_current_B_batch<T: Float>(
     mesh: &TriMesh<T>,
     out: &mut [Vector3<T>],
 ) {
+    if mesh.bvh().is_some() {
+        let moments = sheet_current_moments(current_densities, mesh);
+        return sheet_current_B_clustered_batch(
+            points,
+            position,
+            orientation,
+            current_densities,
+            mesh,
+            &moments,
+            out,
+        );
+    }
+
+    impl_parallel!(
+        sheet_current_B_with_scratch,
+        rayon_threshold: 100,
+        input: points,
+        output: out,
+        args: [position, orientation, current_densities, mesh],
+        scratch: FaceScratch::new()
+    )
+}
+
+/// Computes B-field at points in global frame for a current sheet mesh with precomputed
+/// cluster moments.
+#[allow(non_snake_case)]
+pub(crate) fn sheet_current_B_clustered_batch<T: Float>(
+    points: &[Point3<T>],
+    position: Point3<T>,
+    orientation: UnitQuaternion<T>,
+    current_densities: &[Vector3<T>],
+    mesh: &TriMesh<T>,
+    moments: &[CurrentMoments<T>],
+    out: &mut [Vector3<T>],
+) {
+    if mesh.bvh().is_none() {
+        return sheet_current_B_batch(points, position, orientation, current_densities, mesh, out);
+    }
+
     impl_parallel!(
-        sheet_current_B,
+        sheet_current_B_clustered,
         rayon_threshold: 100,
         input: points,
         output: out,
-        args: [position, orientation, current_densities, mesh]
+        args: [position, orientation, current_densities, mesh, moments]
     )
 }
 
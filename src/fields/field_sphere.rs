@@ -114,6 +114,17 @@ pub fn sphere_B_batch<T: Float>(
     diameter: T,
     out: &mut [Vector3<T>],
 ) {
+    #[cfg(feature = "simd")]
+    if super::simd::try_simd!(sphere_B_batch(
+        points,
+        position,
+        orientation,
+        polarization,
+        diameter;
+        out
+    )) {
+        return;
+    }
     impl_parallel!(
         sphere_B,
         rayon_threshold: 3100,
@@ -0,0 +1,221 @@
+/*
+ * Magba is licensed under The 3-Clause BSD, see LICENSE.
+ * Copyright 2025 Sira Pornsiriprasert <code@psira.me>
+ */
+
+//! Analytical B-field computation for tetrahedral meshes with per-element polarization.
+
+use nalgebra::{Point3, UnitQuaternion, Vector3};
+
+use crate::{
+    base::{Float, coordinate::compute_in_local, mesh::TetMesh},
+    crate_utils::{impl_parallel, impl_parallel_sum},
+    fields::field_tetrahedron::local_tetrahedron_B_precomputed,
+};
+
+/// Computes B-field of a tetrahedral mesh at point in local frame.
+///
+/// Each tetrahedron is homogeneously magnetized with its own polarization vector.
+/// Tetrahedra without a polarization vector are skipped.
+///
+/// # Arguments
+///
+/// - `point`: Observer position (m)
+/// - `polarizations`: Polarization vector of each tetrahedron (T)
+/// - `mesh`: Tetrahedral mesh in local coords (m)
+///
+/// # Returns
+///
+/// - B-field vector (T) at point (x, y, z)
+#[inline]
+#[allow(non_snake_case)]
+pub fn local_tet_mesh_B<T: Float>(
+    point: Point3<T>,
+    polarizations: &[Vector3<T>],
+    mesh: &TetMesh<T>,
+) -> Vector3<T> {
+    polarizations
+        .iter()
+        .take(mesh.tetrahedra().len())
+        .enumerate()
+        .filter(|(_, pol)| **pol != Vector3::zeros())
+        .fold(Vector3::zeros(), |acc, (i, &pol)| {
+            acc + local_tetrahedron_B_precomputed(point, pol, mesh.element(i), mesh.inverse(i))
+        })
+}
+
+/// Computes B-field of a tetrahedral mesh at point (x, y, z).
+///
+/// # Arguments
+///
+/// - `point`: Observer position (m)
+/// - `position`: Element position (m)
+/// - `orientation`: Element orientation in unit quaternion
+/// - `polarizations`: Polarization vector of each tetrahedron (T)
+/// - `mesh`: Tetrahedral mesh in local coords (m)
+///
+/// # Returns
+///
+/// - B-field vector (T) at point (x, y, z)
+#[inline]
+#[allow(non_snake_case)]
+pub fn tet_mesh_B<T: Float>(
+    point: Point3<T>,
+    position: Point3<T>,
+    orientation: UnitQuaternion<T>,
+    polarizations: &[Vector3<T>],
+    mesh: &TetMesh<T>,
+) -> Vector3<T> {
+    compute_in_local!(
+        local_tet_mesh_B,
+        point,
+        position,
+        orientation,
+        (polarizations, mesh),
+    )
+}
+
+/// Computes B-field at points in global frame for a tetrahedral mesh.
+///
+/// # Arguments
+///
+/// - `points`: Observer positions (m)
+/// - `position`: Element position (m)
+/// - `orientation`: Element orientation in unit quaternion
+/// - `polarizations`: Polarization vector of each tetrahedron (T)
+/// - `mesh`: Tetrahedral mesh in local coords (m)
+/// - `out`: Mutable slice to store the B-field vectors at each observer (T)
+#[allow(non_snake_case)]
+pub fn tet_mesh_B_batch<T: Float>(
+    points: &[Point3<T>],
+    position: Point3<T>,
+    orientation: UnitQuaternion<T>,
+    polarizations: &[Vector3<T>],
+    mesh: &TetMesh<T>,
+    out: &mut [Vector3<T>],
+) {
+    impl_parallel!(
+        tet_mesh_B,
+        rayon_threshold: 100,
+        input: points,
+        output: out,
+        args: [position, orientation, polarizations, mesh]
+    )
+}
+
+/// Computes B-field at each given points in global frame for multiple tetrahedral meshes.
+///
+/// # Arguments
+///
+/// - `points`: Observer positions (m)
+/// - `positions`: Element positions (m)
+/// - `orientations`: Element orientations in unit quaternion
+/// - `polarizations_list`: Polarization vectors of each mesh (T)
+/// - `meshes`: Tetrahedral meshes in local coords (m)
+/// - `out`: Mutable slice to store the net B-field vectors at each observer (T)
+#[allow(non_snake_case)]
+pub fn sum_multiple_tet_mesh_B<T: Float>(
+    points: &[Point3<T>],
+    positions: &[Point3<T>],
+    orientations: &[UnitQuaternion<T>],
+    polarizations_list: &[alloc::vec::Vec<Vector3<T>>],
+    meshes: &[&TetMesh<T>],
+    out: &mut [Vector3<T>],
+) {
+    impl_parallel_sum!(
+        out,
+        points,
+        10,
+        [positions, orientations, polarizations_list, meshes],
+        |pos, p, o, pols, mesh| tet_mesh_B(*pos, *p, *o, pols, mesh)
+    )
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::fields::field_tetrahedron::local_tetrahedron_B;
+    use approx::assert_relative_eq;
+    use nalgebra::{point, vector};
+
+    #[test]
+    fn test_tet_mesh_matches_tetrahedra() {
+        let vertices = [
+            vector![0.0, 0.0, 0.0],
+            vector![0.01, 0.0, 0.0],
+            vector![0.0, 0.01, 0.0],
+            vector![0.0, 0.0, 0.01],
+            vector![0.01, 0.01, 0.01],
+        ];
+        // Second tetrahedron is negatively oriented
+        let tetrahedra = [[0, 1, 2, 3], [1, 3, 2, 4]];
+        let polarizations = [vector![0.0, 0.0, 1.0], vector![0.3, -0.2, 0.5]];
+        let mesh = TetMesh::new(vertices, tetrahedra);
+
+        for point in [
+            point![0.02, 0.01, -0.01],
+            point![0.002, 0.002, 0.002],
+            point![0.006, 0.006, 0.006],
+        ] {
+            let expected = tetrahedra
+                .iter()
+                .zip(polarizations)
+                .map(|(tet, pol)| local_tetrahedron_B(point, pol, tet.map(|i| vertices[i])))
+                .sum::<Vector3<f64>>();
+            assert_relative_eq!(
+                local_tet_mesh_B(point, &polarizations, &mesh),
+                expected,
+                epsilon = 1e-14
+            );
+        }
+    }
+
+    #[test]
+    fn test_sum_multiple_tet_mesh_b() {
+        use crate::testing_util::impl_test_sum_multiple;
+        let points = &[
+            point![5.0, 6.0, 7.0],
+            point![4.0, 3.0, 2.0],
+            point![0.5, 0.25, 0.125],
+        ];
+        let positions = &[point![1.0, 2.0, 3.0], point![0.0, 0.0, 0.0]];
+        let orientations = &[
+            UnitQuaternion::from_scaled_axis(vector![1.0, 0.6, 0.4]),
+            UnitQuaternion::identity(),
+        ];
+        let polarizations_list = &[
+            vec![vector![0.45, 0.3, 0.15]],
+            vec![vector![1.0, 2.0, 3.0], vector![0.0, 0.0, 1.0]],
+        ];
+        let mesh1 = TetMesh::new(
+            [
+                vector![0.0, 0.0, 0.0],
+                vector![0.0, 0.0, 1.0],
+                vector![1.0, 0.0, 0.0],
+                vector![0.0, 1.0, 0.0],
+            ],
+            [[0, 1, 2, 3]],
+        );
+        let mesh2 = TetMesh::new(
+            [
+                vector![0.0, 0.0, 0.0],
+                vector![1.0, 0.0, 0.0],
+                vector![0.0, 1.0, 0.0],
+                vector![0.0, 0.0, 1.0],
+                vector![1.0, 1.0, 1.0],
+            ],
+            [[0, 1, 2, 3], [1, 2, 3, 4]],
+        );
+        let meshes = &[&mesh1, &mesh2];
+
+        impl_test_sum_multiple!(
+            sum_multiple_tet_mesh_B,
+            1e-15,
+            points,
+            positions,
+            orientations,
+            (polarizations_list, meshes),
+            |p, pos, ori, pols, mesh| tet_mesh_B(p, pos, ori, &pols, &mesh)
+        );
+    }
+}
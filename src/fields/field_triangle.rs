@@ -21,7 +21,7 @@ use crate::{
 #[inline]
 #[allow(non_snake_case)]
 #[replace_float_literals(T::from_f64(literal).unwrap())]
-fn solid_angle<T: Float>(r_vecs: &[Vector3<T>; 3], r_mags: &[T; 3]) -> T {
+pub(crate) fn solid_angle<T: Float>(r_vecs: &[Vector3<T>; 3], r_mags: &[T; 3]) -> T {
     let N = r_vecs[2].dot(&r_vecs[1].cross(&r_vecs[0]));
 
     let D = r_mags[0] * r_mags[1] * r_mags[2]
@@ -39,6 +39,28 @@ fn solid_angle<T: Float>(r_vecs: &[Vector3<T>; 3], r_mags: &[T; 3]) -> T {
     }
 }
 
+/// Computes the edge integral `(1/l) ∫ ds / |x(s) - p|` along an edge of length `l`
+/// given the distance `r_mag` from observer `p` to the edge start and `b = (start - p)·L`.
+///
+/// The integral does not depend on the edge direction, so it can be shared by the two
+/// faces adjacent to an edge.
+#[inline]
+#[allow(non_snake_case)]
+#[replace_float_literals(T::from_f64(literal).unwrap())]
+pub(crate) fn edge_integral<T: Float>(r_mag: T, b: T, l_mag: T) -> T {
+    let bl_val = b / l_mag;
+    let ind = NumFloat::abs(r_mag + bl_val);
+
+    if ind > 1.0e-12 {
+        (1.0 / l_mag)
+            * NumFloat::ln(
+                (NumFloat::sqrt(l_mag * l_mag + 2.0 * b + r_mag * r_mag) + l_mag + bl_val) / ind,
+            )
+    } else {
+        -(1.0 / l_mag) * NumFloat::ln(NumFloat::abs(l_mag - r_mag) / r_mag)
+    }
+}
+
 /// Computes B-field of a homogeneously magnetized triangular surface at point in local frame.
 ///
 /// The charge is proportional to the projection of the polarization vectors onto the
@@ -101,26 +123,8 @@ pub fn local_triangle_B<T: Float>(
     ];
 
     let mut PQR = Vector3::zeros();
-
     for i in 0..3 {
-        let bl_val = b_vals[i] / l_mags[i];
-        let ind = NumFloat::abs(r_mags[i] + bl_val);
-
-        let I = if ind > 1.0e-12 {
-            (1.0 / l_mags[i])
-                * NumFloat::ln(
-                    (NumFloat::sqrt(
-                        l_mags[i] * l_mags[i] + 2.0 * b_vals[i] + r_mags[i] * r_mags[i],
-                    ) + l_mags[i]
-                        + bl_val)
-                        / ind,
-                )
-        } else {
-            -(1.0 / l_mags[i]) * NumFloat::ln(NumFloat::abs(l_mags[i] - r_mags[i]) / r_mags[i])
-        };
-
-        // Accumulate I * L
-        PQR += L[i] * I;
+        PQR += L[i] * edge_integral(r_mags[i], b_vals[i], l_mags[i]);
     }
 
     let mut B = (n * solid_angle(&r_vecs, &r_mags) - n.cross(&PQR)) * sigma;
@@ -40,6 +40,12 @@
 //!    such as `fields::field_cuboid::cuboid_B_batch`.
 //! 5. Recompile and install Magba with the adjusted threshold locally.
 //!
+//! ## Vectorization
+//!
+//! With the `simd` feature, [cuboid_B_batch], [cylinder_B_batch], [dipole_B_batch], and
+//! [sphere_B_batch] evaluate several `f64` or `f32` observers per instruction using the
+//! kernels in `fields::simd`.
+//!
 //! # Examples
 //!
 //! ```
@@ -71,12 +77,16 @@ mod field_cylinder;
 mod field_dipole;
 #[cfg(feature = "alloc")]
 mod field_path_current;
+#[cfg(feature = "alloc")]
+mod field_prism;
 #[cfg(feature = "mesh")]
 mod field_sheet_current;
 mod field_sphere;
 mod field_tetrahedron;
 mod field_triangle;
 mod field_triangle_current;
+#[cfg(feature = "simd")]
+pub mod simd;
 
 pub use field_circular::{circular_B, circular_B_batch, sum_multiple_circular_B};
 pub use field_cuboid::{cuboid_B, cuboid_B_batch, sum_multiple_cuboid_B};
@@ -84,10 +94,16 @@ pub use field_cylinder::{cylinder_B, cylinder_B_batch, sum_multiple_cylinder_B};
 pub use field_dipole::{dipole_B, dipole_B_batch, sum_multiple_dipole_B};
 #[cfg(feature = "alloc")]
 pub use field_path_current::{path_current_B, path_current_B_batch, sum_multiple_path_current_B};
+#[cfg(feature = "alloc")]
+pub use field_prism::{prism_B, prism_B_batch, sum_multiple_prism_B};
 #[cfg(feature = "mesh")]
 pub use field_sheet_current::{
     sheet_current_B, sheet_current_B_batch, sum_multiple_sheet_current_B,
 };
+#[cfg(feature = "mesh")]
+pub(crate) use field_sheet_current::{
+    sheet_current_B_clustered, sheet_current_B_clustered_batch, sheet_current_moments,
+};
 pub use field_sphere::{sphere_B, sphere_B_batch, sum_multiple_sphere_B};
 pub use field_tetrahedron::{sum_multiple_tetrahedron_B, tetrahedron_B, tetrahedron_B_batch};
 pub use field_triangle::{sum_multiple_triangle_B, triangle_B, triangle_B_batch};
@@ -100,18 +116,27 @@ mod field_mesh;
 #[cfg(feature = "mesh")]
 pub use field_mesh::{mesh_B, mesh_B_batch, sum_multiple_mesh_B};
 
+#[cfg(feature = "mesh")]
+mod field_tet_mesh;
+#[cfg(feature = "mesh")]
+pub use field_tet_mesh::{sum_multiple_tet_mesh_B, tet_mesh_B, tet_mesh_B_batch};
+
 crate::crate_utils::need_unstable! {
     pub use field_cuboid::local_cuboid_B;
     pub use field_cylinder::{
         cylinder_B_cyl, local_cylinder_B, unit_axial_cylinder_B_cyl, unit_diametric_cylinder_B_cyl,
     };
     pub use field_dipole::local_dipole_B;
+    #[cfg(feature = "alloc")]
+    pub use field_prism::local_prism_B;
     pub use field_sphere::local_sphere_B;
     pub use field_tetrahedron::{local_tetrahedron_B, tetrahedron_B_precomputed, precompute_tetrahedron};
     pub use field_triangle::local_triangle_B;
 
     #[cfg(feature = "mesh")]
     pub use field_mesh::local_mesh_B;
+    #[cfg(feature = "mesh")]
+    pub use field_tet_mesh::local_tet_mesh_B;
 
     #[cfg(feature = "alloc")]
     pub use field_path_current::local_path_current_B;
@@ -0,0 +1,327 @@
+/*
+ * Magba is licensed under The 3-Clause BSD, see LICENSE.
+ * Copyright 2025 Sira Pornsiriprasert <code@psira.me>
+ */
+
+//! Vectorized B-field computation for cylindrical magnets.
+
+use core::f64::consts::{FRAC_PI_2, PI};
+
+use nalgebra::{Point3, UnitQuaternion, Vector3};
+
+use super::{Lanes, SimdFloat, Vector3Lanes, c, impl_simd_batch, splat};
+
+/// Vectorized Bulirsch's complete elliptic integral `cel(kc, p, a, b)`.
+///
+/// Both the `p > 0` and `p <= 0` initializations are computed and selected per lane.
+/// The iteration stops once all lanes converge; lanes that do not converge within the
+/// iteration limit are NaN.
+#[inline]
+fn cel<T: SimdFloat>(kc: T::Lanes, p: T::Lanes, a: T::Lanes, b: T::Lanes) -> T::Lanes {
+    let one = T::Lanes::one();
+    let two = c::<T>(2.0);
+    let ca = splat(T::ca());
+
+    let mut kc = kc.abs();
+    let mut e = kc;
+    let mut m = one;
+
+    let positive = p.gt(T::Lanes::zero());
+    let (pp_pos, bb_pos) = {
+        let pp = p.sqrt();
+        (pp, b / pp)
+    };
+    let (pp_neg, aa_neg, bb_neg) = {
+        let f = kc * kc;
+        let q = one - f;
+        let g = one - p;
+        let f = f - p;
+        let q = (b - a * p) * q;
+        let pp = (f / g).sqrt();
+        let aa = (a - b) / g;
+        (pp, aa, -q / (g * g * pp) + aa * pp)
+    };
+    let mut pp = T::Lanes::select(positive, pp_pos, pp_neg);
+    let mut aa = T::Lanes::select(positive, a, aa_neg);
+    let mut bb = T::Lanes::select(positive, bb_pos, bb_neg);
+
+    let mut done = T::Lanes::zero();
+    let mut ans = c::<T>(f64::NAN);
+    for _ in 0..10 {
+        let f = aa;
+        aa = bb / pp + aa;
+        let g = e / pp;
+        bb = (f * g + bb) * two;
+        pp = g + pp;
+        let g = m;
+        m = kc + m;
+
+        let converged = !(g - kc).abs().gt(g * ca);
+        ans = T::Lanes::select(
+            converged & !done,
+            c::<T>(FRAC_PI_2) * (aa * m + bb) / (m * (m + pp)),
+            ans,
+        );
+        done = done | converged;
+        if done.all() {
+            break;
+        }
+
+        kc = e.sqrt() * two;
+        e = kc * m;
+    }
+    ans
+}
+
+/// Complete elliptic integral of the first kind with parameter `m <= 0`.
+#[inline]
+fn ellipk<T: SimdFloat>(m: T::Lanes) -> T::Lanes {
+    let one = T::Lanes::one();
+    cel::<T>((one - m).sqrt(), one, one, one)
+}
+
+/// Complete elliptic integral of the second kind with parameter `m <= 0`.
+#[inline]
+fn ellipe<T: SimdFloat>(m: T::Lanes) -> T::Lanes {
+    let one = T::Lanes::one();
+    cel::<T>((one - m).sqrt(), one, one, one - m)
+}
+
+/// Vectorized [unit_axial_cylinder_B_cyl](crate::fields::cylinder_B), returning `(br, bz)`.
+#[inline]
+#[allow(non_snake_case)]
+fn unit_axial_cylinder_B_cyl<T: SimdFloat>(
+    r: T::Lanes,
+    z: T::Lanes,
+    z0: T::Lanes,
+) -> (T::Lanes, T::Lanes) {
+    let one = T::Lanes::one();
+    let (zp, zm) = (z + z0, z - z0);
+    let (rp, rm) = (one + r, one - r);
+
+    let (zp2, zm2) = (zp * zp, zm * zm);
+    let (rp2, rm2) = (rp * rp, rm * rm);
+
+    let sq0 = (zm2 + rp2).sqrt();
+    let sq1 = (zp2 + rp2).sqrt();
+
+    let kp = ((zp2 + rm2) / (zp2 + rp2)).sqrt();
+    let km = ((zm2 + rm2) / (zm2 + rp2)).sqrt();
+
+    let gamma = rm / rp;
+    let gamma2 = gamma * gamma;
+
+    let pi = c::<T>(PI);
+    let br = (cel::<T>(kp, one, one, -one) / sq1 - cel::<T>(km, one, one, -one) / sq0) / pi;
+    let bz = (zp * cel::<T>(kp, gamma2, one, gamma) / sq1
+        - zm * cel::<T>(km, gamma2, one, gamma) / sq0)
+        / (rp * pi);
+    (br, bz)
+}
+
+/// Vectorized [unit_diametric_cylinder_B_cyl](crate::fields::cylinder_B), taking
+/// `cos(phi)` and `sin(phi)` instead of `phi`.
+///
+/// The Taylor series for small `r` and the general case are each computed only if some
+/// lane needs them.
+#[inline]
+#[allow(non_snake_case)]
+fn unit_diametric_cylinder_B_cyl<T: SimdFloat>(
+    r: T::Lanes,
+    cos_phi: T::Lanes,
+    sin_phi: T::Lanes,
+    z: T::Lanes,
+    z0: T::Lanes,
+) -> Vector3Lanes<T::Lanes> {
+    let zero = T::Lanes::zero();
+    let one = T::Lanes::one();
+    let (zp, zm) = (z + z0, z - z0);
+    let (zp2, zm2) = (zp * zp, zm * zm);
+    let r2 = r * r;
+
+    let is_small = r.lt(c::<T>(5e-2));
+
+    let taylor = if is_small.any() {
+        let (zp4, zm4) = (zp2 * zp2, zm2 * zm2);
+        let (zpp, zmm) = (zp2 + one, zm2 + one);
+        let (zpp2, zmm2) = (zpp * zpp, zmm * zmm);
+        let (zpp3, zmm3) = (zpp2 * zpp, zmm2 * zmm);
+        let (zpp4, zmm4) = (zpp3 * zpp, zmm3 * zmm);
+        let (zpp5, zmm5) = (zpp4 * zpp, zmm4 * zmm);
+        let (sqrt_p, sqrt_m) = (zpp.sqrt(), zmm.sqrt());
+        let (frac1, frac2) = (zp / sqrt_p, zm / sqrt_m);
+
+        let r3 = r2 * r;
+        let r4 = r3 * r;
+        let r5 = r4 * r;
+
+        let (three, four, eight) = (c::<T>(3.0), c::<T>(4.0), c::<T>(8.0));
+        let term1 = frac1 - frac2;
+        let term2 = (frac1 / zpp2 - frac2 / zmm2) * r2 / eight;
+        let term3 = ((three - four * zp2) * frac1 / zpp4 - (three - four * zm2) * frac2 / zmm4)
+            / c::<T>(64.0)
+            * r4;
+
+        let br = -cos_phi / four * (term1 + c::<T>(9.0) * term2 + c::<T>(25.0) * term3);
+        let bphi = sin_phi / four * (term1 + three * term2 + c::<T>(5.0) * term3);
+        let bz = -cos_phi / four
+            * (r * (one / zpp / sqrt_p - one / zmm / sqrt_m)
+                + c::<T>(3.0 / 8.0)
+                    * r3
+                    * ((one - four * zp2) / zpp3 / sqrt_p - (one - four * zm2) / zmm3 / sqrt_m)
+                + c::<T>(15.0 / 64.0)
+                    * r5
+                    * ((one - c::<T>(12.0) * zp2 + eight * zp4) / zpp5 / sqrt_p
+                        - (one - c::<T>(12.0) * zm2 + eight * zm4) / zmm5 / sqrt_m));
+        Vector3Lanes::new(br, bphi, bz)
+    } else {
+        Vector3Lanes::new(zero, zero, zero)
+    };
+
+    let general = if !is_small.all() {
+        let two = c::<T>(2.0);
+        let (rp, rm) = (r + one, r - one);
+        let (rp2, rm2) = (rp * rp, rm * rm);
+
+        let (ap2, am2) = (zp2 + rm2, zm2 + rm2);
+        let (ap, am) = (ap2.sqrt(), am2.sqrt());
+
+        let four_r = c::<T>(4.0) * r;
+        let (argp, argm) = (-four_r / ap2, -four_r / am2);
+
+        // Special case r = r0
+        let is_rim = rm.eq(zero);
+        let argc = T::Lanes::select(is_rim, c::<T>(1e16), -four_r / rm2);
+        let one_over_rm = T::Lanes::select(is_rim, zero, one / rm);
+
+        let (ellk_p, ellk_m) = (ellipk::<T>(argp), ellipk::<T>(argm));
+        let (elle_p, elle_m) = (ellipe::<T>(argp), ellipe::<T>(argm));
+        let (ellpi_p, ellpi_m) = (
+            cel::<T>((one - argp).sqrt(), one - argc, one, one),
+            cel::<T>((one - argm).sqrt(), one - argc, one, one),
+        );
+
+        let four_pi_r2 = c::<T>(4.0 * PI) * r2;
+        let br = -cos_phi / four_pi_r2
+            * (-zm * am * elle_m + zp * ap * elle_p + zm / am * (two + zm2) * ellk_m
+                - zp / ap * (two + zp2) * ellk_p
+                + (zm / am * ellpi_m - zp / ap * ellpi_p) * rp * (r2 + one) * one_over_rm);
+
+        let bphi = sin_phi / four_pi_r2
+            * (zm * am * elle_m - zp * ap * elle_p - zm / am * (two + zm2 + two * r2) * ellk_m
+                + zp / ap * (two + zp2 + two * r2) * ellk_p
+                + zm / am * rp2 * ellpi_m
+                - zp / ap * rp2 * ellpi_p);
+
+        let bz = -cos_phi / (c::<T>(2.0 * PI) * r)
+            * (am * elle_m - ap * elle_p - (one + zm2 + r2) / am * ellk_m
+                + (one + zp2 + r2) / ap * ellk_p);
+        Vector3Lanes::new(br, bphi, bz)
+    } else {
+        Vector3Lanes::new(zero, zero, zero)
+    };
+
+    Vector3Lanes::new(
+        T::Lanes::select(is_small, taylor.x, general.x),
+        T::Lanes::select(is_small, taylor.y, general.y),
+        T::Lanes::select(is_small, taylor.z, general.z),
+    )
+}
+
+/// Vectorized [local_cylinder_B](crate::fields::cylinder_B) in the local frame.
+#[inline]
+#[allow(non_snake_case)]
+fn local_cylinder_B<T: SimdFloat>(
+    p: Vector3Lanes<T::Lanes>,
+    polarization: Vector3<T>,
+    radius: T,
+    height: T,
+) -> Vector3Lanes<T::Lanes> {
+    let zero = T::Lanes::zero();
+    let one = T::Lanes::one();
+
+    // Cylindrical coordinates, with phi = atan2(y, x) = 0 on the axis
+    let r = (p.x * p.x + p.y * p.y).sqrt();
+    let on_axis = r.eq(zero);
+    let cos_phi = T::Lanes::select(on_axis, one, p.x / r);
+    let sin_phi = T::Lanes::select(on_axis, zero, p.y / r);
+
+    let pol_r = num_traits::Float::hypot(polarization.x, polarization.y);
+    let pol_z = polarization.z;
+
+    // Scale invariance
+    let half_height = height / T::from_f64(2.0).unwrap();
+    let recip_radius = splat(T::one() / radius);
+    let (r_scaled, z_scaled) = (r * recip_radius, p.z * recip_radius);
+    let z0 = half_height / radius;
+
+    let mut br = zero;
+    let mut bphi = zero;
+    let mut bz = zero;
+
+    // M = Mz + Mr (Caciagli et al., 2018)
+    if pol_z != T::zero() {
+        let (br_axial, bz_axial) = unit_axial_cylinder_B_cyl::<T>(r_scaled, z_scaled, splat(z0));
+        br = br + br_axial * splat(pol_z);
+        bz = bz + bz_axial * splat(pol_z);
+    }
+
+    if pol_r != T::zero() {
+        // cos and sin of phi - theta, with theta the polarization angle on the XY plane
+        let (cos_theta, sin_theta) = (splat(polarization.x / pol_r), splat(polarization.y / pol_r));
+        let cos_rel = cos_phi * cos_theta + sin_phi * sin_theta;
+        let sin_rel = sin_phi * cos_theta - cos_phi * sin_theta;
+        let b = unit_diametric_cylinder_B_cyl::<T>(r_scaled, cos_rel, sin_rel, z_scaled, splat(z0));
+        br = br + b.x * splat(pol_r);
+        bphi = bphi + b.y * splat(pol_r);
+        bz = bz + b.z * splat(pol_r);
+    }
+
+    // Zero on the cylinder edge (rim)
+    let rtol = c::<T>(1e-15);
+    let is_on_edge =
+        (r_scaled - one).abs().lt(rtol) & (z_scaled.abs() - splat(z0)).abs().lt(rtol * splat(z0));
+    let (br, bphi, bz) = (
+        T::Lanes::select(is_on_edge, zero, br),
+        T::Lanes::select(is_on_edge, zero, bphi),
+        T::Lanes::select(is_on_edge, zero, bz),
+    );
+
+    let bx = br * cos_phi - bphi * sin_phi;
+    let by = br * sin_phi + bphi * cos_phi;
+
+    // Add the polarization inside the magnet
+    let is_inside = r.le(splat(radius)) & p.z.abs().le(splat(half_height));
+    Vector3Lanes::new(
+        T::Lanes::select(is_inside, bx + splat(polarization.x), bx),
+        T::Lanes::select(is_inside, by + splat(polarization.y), by),
+        bz,
+    )
+}
+
+/// Vectorized [cylinder_B_batch](crate::fields::cylinder_B_batch).
+///
+/// # Arguments
+///
+/// - `points`: Observer positions in global frame (m)
+/// - `position`: Magnet position (m)
+/// - `orientation`: Magnet orientation as unit quaternion
+/// - `polarization`: Polarization vector (T)
+/// - `diameter`: Cylinder diameter (m)
+/// - `height`: Cylinder height (m)
+/// - `out`: Mutable slice to store the B-field vectors at each observer (T)
+#[allow(non_snake_case)]
+pub fn cylinder_B_batch<T: SimdFloat>(
+    points: &[Point3<T>],
+    position: Point3<T>,
+    orientation: UnitQuaternion<T>,
+    polarization: Vector3<T>,
+    diameter: T,
+    height: T,
+    out: &mut [Vector3<T>],
+) {
+    let radius = diameter / T::from_f64(2.0).unwrap();
+    impl_simd_batch(points, position, orientation, out, 100, |p| {
+        local_cylinder_B(p, polarization, radius, height)
+    })
+}
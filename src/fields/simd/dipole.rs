@@ -0,0 +1,70 @@
+/*
+ * Magba is licensed under The 3-Clause BSD, see LICENSE.
+ * Copyright 2025 Sira Pornsiriprasert <code@psira.me>
+ */
+
+//! Vectorized B-field computation for magnet dipole moment.
+
+use nalgebra::{Point3, UnitQuaternion, Vector3};
+
+use super::{Lanes, SimdFloat, Vector3Lanes, c, impl_simd_batch, splat};
+
+/// Vectorized [local_dipole_B](crate::fields::dipole_B) in the local frame.
+#[inline]
+#[allow(non_snake_case)]
+fn local_dipole_B<T: SimdFloat>(
+    p: Vector3Lanes<T::Lanes>,
+    moment: Vector3<T>,
+) -> Vector3Lanes<T::Lanes> {
+    let (mx, my, mz) = (splat(moment.x), splat(moment.y), splat(moment.z));
+    let r2 = p.x * p.x + p.y * p.y + p.z * p.z;
+    let r3 = r2 * r2.sqrt();
+    let mu0_4pi = splat(T::mu0_4pi());
+
+    let dot = (mx * p.x + my * p.y + mz * p.z) * c::<T>(3.0) / (r2 * r3);
+    let recip_r3 = T::Lanes::one() / r3;
+
+    // At the dipole, each component is infinite with the sign of the moment
+    let at_origin = r2.eq(T::Lanes::zero());
+    let singular = |m: T| {
+        splat(if m > T::zero() {
+            T::infinity()
+        } else if m == T::zero() {
+            T::zero()
+        } else {
+            T::neg_infinity()
+        })
+    };
+    let component = |p: T::Lanes, m: T| {
+        let b = (p * dot - splat(m) * recip_r3) * mu0_4pi;
+        T::Lanes::select(at_origin, singular(m), b)
+    };
+
+    Vector3Lanes::new(
+        component(p.x, moment.x),
+        component(p.y, moment.y),
+        component(p.z, moment.z),
+    )
+}
+
+/// Vectorized [dipole_B_batch](crate::fields::dipole_B_batch).
+///
+/// # Arguments
+///
+/// - `points`: Observer positions (m)
+/// - `position`: Magnet position (m)
+/// - `orientation`: Magnet orientation in unit quaternion
+/// - `moment`: Magnetic dipole moment vector (A·m²)
+/// - `out`: Mutable slice to store the B-field vectors at each observer (T)
+#[allow(non_snake_case)]
+pub fn dipole_B_batch<T: SimdFloat>(
+    points: &[Point3<T>],
+    position: Point3<T>,
+    orientation: UnitQuaternion<T>,
+    moment: Vector3<T>,
+    out: &mut [Vector3<T>],
+) {
+    impl_simd_batch(points, position, orientation, out, 2500, |p| {
+        local_dipole_B(p, moment)
+    })
+}
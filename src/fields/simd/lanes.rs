@@ -0,0 +1,206 @@
+/*
+ * Magba is licensed under The 3-Clause BSD, see LICENSE.
+ * Copyright 2025 Sira Pornsiriprasert <code@psira.me>
+ */
+
+use core::ops::{Add, BitAnd, BitOr, Div, Mul, Neg, Not, Sub};
+
+use nalgebra::{Matrix3, Vector3};
+
+/// SIMD vector holding one value of `T` per lane.
+///
+/// Comparisons return masks of the same type, with all bits set in the lanes where the
+/// comparison holds. Masks are combined with `&`, `|`, and `!`, and consumed by
+/// [select](Lanes::select).
+///
+/// Implemented for [wide::f64x4] and [wide::f32x8].
+pub trait Lanes<T>:
+    Copy
+    + Send
+    + Sync
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+    + BitAnd<Output = Self>
+    + BitOr<Output = Self>
+    + Not<Output = Self>
+{
+    /// Number of lanes.
+    const LANES: usize;
+
+    /// All lanes set to `value`.
+    fn splat(value: T) -> Self;
+
+    /// All lanes set to `value` converted to `T`.
+    fn constant(value: f64) -> Self;
+
+    /// Lanes set to `f(0)`, ..., `f(LANES - 1)`.
+    fn from_fn(f: impl FnMut(usize) -> T) -> Self;
+
+    /// Value of lane `i`.
+    fn lane(self, i: usize) -> T;
+
+    fn sqrt(self) -> Self;
+    fn abs(self) -> Self;
+    fn ln(self) -> Self;
+    fn atan2(self, x: Self) -> Self;
+
+    fn lt(self, other: Self) -> Self;
+    fn le(self, other: Self) -> Self;
+    fn gt(self, other: Self) -> Self;
+    fn eq(self, other: Self) -> Self;
+
+    /// Lanes of `t` where `mask` is set, otherwise lanes of `f`.
+    fn select(mask: Self, t: Self, f: Self) -> Self;
+
+    /// Whether the mask is set in any lane.
+    fn any(self) -> bool;
+
+    /// Whether the mask is set in all lanes.
+    fn all(self) -> bool;
+
+    #[inline]
+    fn zero() -> Self {
+        Self::constant(0.0)
+    }
+
+    #[inline]
+    fn one() -> Self {
+        Self::constant(1.0)
+    }
+}
+
+macro_rules! impl_lanes {
+    ($lanes:ty, $t:ty, $n:expr) => {
+        impl Lanes<$t> for $lanes {
+            const LANES: usize = $n;
+
+            #[inline]
+            fn splat(value: $t) -> Self {
+                <$lanes>::splat(value)
+            }
+
+            #[inline]
+            fn constant(value: f64) -> Self {
+                <$lanes>::splat(value as $t)
+            }
+
+            #[inline]
+            fn from_fn(f: impl FnMut(usize) -> $t) -> Self {
+                <$lanes>::new(core::array::from_fn(f))
+            }
+
+            #[inline]
+            fn lane(self, i: usize) -> $t {
+                self.to_array()[i]
+            }
+
+            #[inline]
+            fn sqrt(self) -> Self {
+                <$lanes>::sqrt(self)
+            }
+
+            #[inline]
+            fn abs(self) -> Self {
+                <$lanes>::abs(self)
+            }
+
+            #[inline]
+            fn ln(self) -> Self {
+                <$lanes>::ln(self)
+            }
+
+            #[inline]
+            fn atan2(self, x: Self) -> Self {
+                <$lanes>::atan2(self, x)
+            }
+
+            #[inline]
+            fn lt(self, other: Self) -> Self {
+                wide::CmpLt::cmp_lt(self, other)
+            }
+
+            #[inline]
+            fn le(self, other: Self) -> Self {
+                wide::CmpLe::cmp_le(self, other)
+            }
+
+            #[inline]
+            fn gt(self, other: Self) -> Self {
+                wide::CmpGt::cmp_gt(self, other)
+            }
+
+            #[inline]
+            fn eq(self, other: Self) -> Self {
+                wide::CmpEq::cmp_eq(self, other)
+            }
+
+            #[inline]
+            fn select(mask: Self, t: Self, f: Self) -> Self {
+                mask.blend(t, f)
+            }
+
+            #[inline]
+            fn any(self) -> bool {
+                <$lanes>::any(self)
+            }
+
+            #[inline]
+            fn all(self) -> bool {
+                <$lanes>::all(self)
+            }
+        }
+    };
+}
+
+impl_lanes!(wide::f64x4, f64, 4);
+impl_lanes!(wide::f32x8, f32, 8);
+
+/// Vector with each component in lanes, one observer per lane.
+#[derive(Clone, Copy)]
+pub(crate) struct Vector3Lanes<L> {
+    pub x: L,
+    pub y: L,
+    pub z: L,
+}
+
+impl<L> Vector3Lanes<L> {
+    #[inline]
+    pub fn new(x: L, y: L, z: L) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Vector in lane `i`.
+    #[inline]
+    pub fn lane<T: nalgebra::Scalar + Copy>(&self, i: usize) -> Vector3<T>
+    where
+        L: Lanes<T>,
+    {
+        Vector3::new(self.x.lane(i), self.y.lane(i), self.z.lane(i))
+    }
+
+    /// `matrix * v` in each lane.
+    #[inline]
+    pub fn rotate<T: nalgebra::RealField + Copy>(&self, matrix: &Matrix3<T>) -> Self
+    where
+        L: Lanes<T>,
+    {
+        let m = |i, j| L::splat(matrix[(i, j)]);
+        Self::new(
+            m(0, 0) * self.x + m(0, 1) * self.y + m(0, 2) * self.z,
+            m(1, 0) * self.x + m(1, 1) * self.y + m(1, 2) * self.z,
+            m(2, 0) * self.x + m(2, 1) * self.y + m(2, 2) * self.z,
+        )
+    }
+
+    /// `matrixᵀ * v` in each lane.
+    #[inline]
+    pub fn rotate_transpose<T: nalgebra::RealField + Copy>(&self, matrix: &Matrix3<T>) -> Self
+    where
+        L: Lanes<T>,
+    {
+        self.rotate(&matrix.transpose())
+    }
+}
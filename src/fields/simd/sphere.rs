@@ -0,0 +1,70 @@
+/*
+ * Magba is licensed under The 3-Clause BSD, see LICENSE.
+ * Copyright 2025 Sira Pornsiriprasert <code@psira.me>
+ */
+
+//! Vectorized B-field computation for homogeneously magnetized sphere.
+
+use nalgebra::{Point3, UnitQuaternion, Vector3};
+
+use super::{Lanes, SimdFloat, Vector3Lanes, c, impl_simd_batch, splat};
+
+/// Vectorized [local_sphere_B](crate::fields::sphere_B) in the local frame.
+#[inline]
+#[allow(non_snake_case)]
+fn local_sphere_B<T: SimdFloat>(
+    p: Vector3Lanes<T::Lanes>,
+    polarization: Vector3<T>,
+    diameter: T,
+) -> Vector3Lanes<T::Lanes> {
+    let r_sphere = num_traits::Float::abs(diameter) / T::from_f64(2.0).unwrap();
+    let r2 = p.x * p.x + p.y * p.y + p.z * p.z;
+    let r = r2.sqrt();
+    let r5 = r2 * r2 * r;
+
+    let pol_dot_p =
+        (splat(polarization.x) * p.x + splat(polarization.y) * p.y + splat(polarization.z) * p.z)
+            * c::<T>(3.0);
+    let factor = splat(r_sphere * r_sphere * r_sphere) / (c::<T>(3.0) * r5);
+
+    // Dipole field outside, constant field inside
+    let outside = r.gt(splat(r_sphere));
+    let component = |p: T::Lanes, pol: T| {
+        let dipole = (p * pol_dot_p - splat(pol) * r2) * factor;
+        T::Lanes::select(
+            outside,
+            dipole,
+            splat(pol * T::from_f64(2.0 / 3.0).unwrap()),
+        )
+    };
+
+    Vector3Lanes::new(
+        component(p.x, polarization.x),
+        component(p.y, polarization.y),
+        component(p.z, polarization.z),
+    )
+}
+
+/// Vectorized [sphere_B_batch](crate::fields::sphere_B_batch).
+///
+/// # Arguments
+///
+/// - `points`: Observer positions (m)
+/// - `position`: Sphere center (m)
+/// - `orientation`: Sphere orientation in unit quaternion
+/// - `polarization`: Polarization vector (T)
+/// - `diameter`: Sphere diameter (m)
+/// - `out`: Mutable slice to store the B-field vectors at each observer (T)
+#[allow(non_snake_case)]
+pub fn sphere_B_batch<T: SimdFloat>(
+    points: &[Point3<T>],
+    position: Point3<T>,
+    orientation: UnitQuaternion<T>,
+    polarization: Vector3<T>,
+    diameter: T,
+    out: &mut [Vector3<T>],
+) {
+    impl_simd_batch(points, position, orientation, out, 3100, |p| {
+        local_sphere_B(p, polarization, diameter)
+    })
+}
@@ -42,8 +42,12 @@ extern crate alloc;
 pub(crate) mod crate_utils;
 use crate::crate_utils::need_std;
 
+#[cfg(feature = "alloc")]
+pub mod analysis;
 pub mod base;
+pub mod collections;
 pub mod conversion;
+mod error;
 pub mod fields;
 pub mod measurement;
 
@@ -51,8 +55,20 @@ pub mod currents;
 pub mod magnets;
 pub mod sensors;
 
+pub use error::Error;
+
 need_std!(
-    pub mod collections;
+    #[cfg(feature = "export")]
+    pub mod export;
+
+    #[cfg(feature = "scene")]
+    pub mod scene;
+
+    #[cfg(feature = "magpylib")]
+    pub mod magpylib;
+
+    #[cfg(feature = "capi")]
+    pub mod capi;
 
     #[cfg(any(test, feature = "test-utils"))]
     pub mod testing_util;
@@ -69,19 +85,18 @@ pub mod prelude {
 
     #[cfg(feature = "mesh")]
     pub use currents::SheetCurrent;
+    #[cfg(feature = "alloc")]
+    pub use magnets::PrismMagnet;
     pub use magnets::{
         CuboidMagnet, CylinderMagnet, Dipole, Magnet, SphereMagnet, TetrahedronMagnet,
         TriangleMagnet,
     };
     pub use sensors::{Sensor, hall_effect};
 
-    need_std!(
-        pub use collections::{
-            SourceComponent, SourceAssembly, ObserverComponent, ObserverAssembly, SourceArray,
-            ObserverArray,
-        };
-    );
+    pub use collections::{ObserverArray, SourceArray};
+    #[cfg(feature = "alloc")]
+    pub use collections::{ObserverAssembly, ObserverComponent, SourceAssembly, SourceComponent};
 
     #[cfg(feature = "mesh")]
-    pub use magnets::MeshMagnet;
+    pub use magnets::{MeshMagnet, TetMeshMagnet};
 }
@@ -121,4 +121,31 @@ mod tests {
     fn test_with_height_validation() {
         let _: CylinderMagnet<f64> = CylinderMagnet::<f64>::default().with_height(-1.0_f64);
     }
+
+    #[test]
+    fn test_fallible_validation() {
+        use crate::Error;
+
+        let result = CylinderMagnet::<f64>::try_new(
+            [0.0; 3],
+            nalgebra::UnitQuaternion::identity(),
+            [0.0, 0.0, 1.0],
+            1.0,
+            -1.0,
+        );
+        assert!(matches!(
+            result,
+            Err(Error::InvalidGeometry("Height cannot be negative."))
+        ));
+
+        let mut magnet = CylinderMagnet::<f64>::default();
+        assert!(magnet.try_set_diameter(-1.0).is_err());
+        assert_eq!(magnet.diameter(), 1.0);
+        assert!(magnet.try_set_diameter(2.0).is_ok());
+        assert_eq!(magnet.diameter(), 2.0);
+
+        let magnet = magnet.try_with_height(3.0).unwrap();
+        assert_eq!(magnet.height(), 3.0);
+        assert!(magnet.try_with_height(0.0).is_err());
+    }
 }
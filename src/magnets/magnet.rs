@@ -3,9 +3,6 @@
  * Copyright 2025 Sira Pornsiriprasert <code@psira.me>
  */
 
-#[cfg(feature = "std")]
-use derive_more::Display;
-
 use enum_dispatch::enum_dispatch;
 
 use crate::{
@@ -15,8 +12,11 @@ use crate::{
     },
 };
 
+#[cfg(feature = "alloc")]
+use crate::magnets::PrismMagnet;
+
 #[cfg(feature = "mesh")]
-use crate::magnets::MeshMagnet;
+use crate::magnets::{MeshMagnet, TetMeshMagnet};
 
 use nalgebra::{Point3, Vector3};
 
@@ -36,18 +36,37 @@ use nalgebra::{Point3, Vector3};
 /// assert_relative_eq!(b_field, vector![0.03358623061457353, 0.06717246122914705, 0.6376649834015807]);
 /// ```
 #[derive(Clone, Debug, PartialEq, Eq)]
-#[cfg_attr(feature = "std", derive(Display))]
 #[enum_dispatch(Source<T>, Transform<T>,)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type"))]
 pub enum Magnet<T: Float = f64> {
+    #[cfg_attr(feature = "serde", serde(rename = "CylinderMagnet"))]
     Cylinder(CylinderMagnet<T>),
+    #[cfg_attr(feature = "serde", serde(rename = "CuboidMagnet"))]
     Cuboid(CuboidMagnet<T>),
     Dipole(Dipole<T>),
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(feature = "serde", serde(rename = "PrismMagnet"))]
+    Prism(PrismMagnet<T>),
+    #[cfg_attr(feature = "serde", serde(rename = "SphereMagnet"))]
     Sphere(SphereMagnet<T>),
+    #[cfg_attr(feature = "serde", serde(rename = "TetrahedronMagnet"))]
     Tetrahedron(TetrahedronMagnet<T>),
+    #[cfg_attr(feature = "serde", serde(rename = "TriangleMagnet"))]
     Triangle(TriangleMagnet<T>),
 
     #[cfg(feature = "mesh")]
+    #[cfg_attr(feature = "serde", serde(rename = "MeshMagnet"))]
     Mesh(MeshMagnet<T>),
+    #[cfg(feature = "mesh")]
+    #[cfg_attr(feature = "serde", serde(rename = "TetMeshMagnet"))]
+    TetMesh(TetMeshMagnet<T>),
+}
+
+impl<T: Float> core::fmt::Display for Magnet<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        <Self as Source<T>>::format(self, f, "")
+    }
 }
 
 #[cfg(all(test, feature = "std"))]
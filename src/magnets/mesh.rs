@@ -27,8 +27,9 @@ define_source! {
             /// Construct a [MeshMagnet].
             ///
             /// This constructor does not validate the mesh. The field computation guarantees
-            /// correct results only if the mesh is closed, connected, not self-intersecting
-            /// and all faces are oriented outwards. It is recommended to validate the mesh
+            /// correct results only if the mesh is closed and not self-intersecting, body shells
+            /// are oriented outwards and cavity shells are oriented inwards. The mesh may consist
+            /// of several disjoint bodies. It is recommended to validate the mesh
             /// first or use [MeshMagnet::from_vertices_and_faces] constructor that validates the mesh.
             ///
             /// # Examples
@@ -58,7 +59,7 @@ impl<T: Float + core::iter::Sum> MeshMagnet<T> {
     ///
     /// # Returns
     ///
-    /// Returns a [MeshError] if the mesh has holes or zero-faces.
+    /// Returns a [MeshError] if the mesh has holes, zero-faces, or shells with wrong orientation.
     ///
     /// # Notes
     ///
@@ -96,6 +97,46 @@ impl<T: Float + core::iter::Sum> MeshMagnet<T> {
             trimesh,
         ))
     }
+
+    /// Validates and constructs a [`MeshMagnet`] from a Wavefront OBJ reader.
+    ///
+    /// To scale or repair the mesh on import, read it with
+    /// [TriMesh::from_obj_with_options] instead.
+    #[cfg(feature = "io-obj")]
+    #[inline]
+    pub fn from_obj<R: std::io::BufRead>(
+        reader: R,
+        polarization: impl Into<Vector3<T>>,
+    ) -> std::io::Result<Self> {
+        let trimesh = TriMesh::from_obj(reader)?;
+
+        Ok(Self::new(
+            [T::zero(); 3],
+            nalgebra::UnitQuaternion::identity(),
+            polarization,
+            trimesh,
+        ))
+    }
+
+    /// Validates and constructs a [`MeshMagnet`] from a PLY reader.
+    ///
+    /// To scale or repair the mesh on import, read it with
+    /// [TriMesh::from_ply_with_options] instead.
+    #[cfg(feature = "io-ply")]
+    #[inline]
+    pub fn from_ply<R: std::io::BufRead>(
+        reader: R,
+        polarization: impl Into<Vector3<T>>,
+    ) -> std::io::Result<Self> {
+        let trimesh = TriMesh::from_ply(reader)?;
+
+        Ok(Self::new(
+            [T::zero(); 3],
+            nalgebra::UnitQuaternion::identity(),
+            polarization,
+            trimesh,
+        ))
+    }
 }
 
 #[cfg(all(test, feature = "std"))]
@@ -165,3 +206,121 @@ mod stl_tests {
         );
     }
 }
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::{base::Source, magnets::CuboidMagnet, testing_util::cube_mesh};
+    use approx::assert_relative_eq;
+    use nalgebra::{UnitQuaternion, point, vector};
+
+    fn points() -> Vec<nalgebra::Point3<f64>> {
+        vec![
+            point![0.0, 0.0, 0.0],
+            point![0.01, 0.02, -0.03],
+            point![0.2, 0.1, 0.05],
+            point![-0.3, 0.4, 0.5],
+            point![0.25, 0.0, 0.0],
+        ]
+    }
+
+    #[test]
+    fn test_points_aligned_with_edges() {
+        let (vertices, faces) = cube_mesh(Vector3::zeros(), 0.2, false);
+        let pol = vector![0.1, 0.2, 0.3];
+        let magnet = MeshMagnet::from_vertices_and_faces(vertices, faces, pol).unwrap();
+        let cuboid = CuboidMagnet::new([0.0; 3], UnitQuaternion::identity(), pol, [0.2, 0.2, 0.2]);
+
+        for p in [
+            point![0.0, 0.0, 0.0],
+            point![0.05, 0.05, 0.05],
+            point![-0.2, 0.1, 0.1],
+            point![-0.2, 0.0, 0.0],
+        ] {
+            assert_relative_eq!(
+                magnet.compute_B(p),
+                cuboid.compute_B(p),
+                epsilon = 1e-12,
+                max_relative = 1e-9
+            );
+        }
+    }
+
+    #[test]
+    fn test_hollow_cube() {
+        let (mut vertices, mut faces) = cube_mesh(Vector3::zeros(), 0.2, false);
+        let (inner_vertices, inner_faces) = cube_mesh(vector![0.01, 0.0, 0.0], 0.1, true);
+        let offset = vertices.len();
+        vertices.extend(inner_vertices);
+        faces.extend(inner_faces.iter().map(|f| f.map(|i| i + offset)));
+
+        let pol = vector![0.1, 0.2, 0.3];
+        let magnet = MeshMagnet::from_vertices_and_faces(vertices, faces, pol).unwrap();
+        let outer = CuboidMagnet::new([0.0; 3], UnitQuaternion::identity(), pol, [0.2, 0.2, 0.2]);
+        let inner = CuboidMagnet::new(
+            [0.01, 0.0, 0.0],
+            UnitQuaternion::identity(),
+            -pol,
+            [0.1, 0.1, 0.1],
+        );
+
+        for p in points() {
+            let expected = outer.compute_B(p) + inner.compute_B(p);
+            assert_relative_eq!(
+                magnet.compute_B(p),
+                expected,
+                epsilon = 1e-12,
+                max_relative = 1e-9
+            );
+        }
+        assert!((magnet.mesh().volume() - (0.008 - 0.001)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_disjoint_cubes() {
+        let (mut vertices, mut faces) = cube_mesh(vector![-0.1, 0.0, 0.0], 0.1, false);
+        let (other_vertices, other_faces) = cube_mesh(vector![0.1, 0.0, 0.0], 0.1, false);
+        let offset = vertices.len();
+        vertices.extend(other_vertices);
+        faces.extend(other_faces.iter().map(|f| f.map(|i| i + offset)));
+
+        let pol = vector![0.0, 0.0, 1.0];
+        let magnet = MeshMagnet::from_vertices_and_faces(vertices, faces, pol).unwrap();
+        let left = CuboidMagnet::new(
+            [-0.1, 0.0, 0.0],
+            UnitQuaternion::identity(),
+            pol,
+            [0.1, 0.1, 0.1],
+        );
+        let right = CuboidMagnet::new(
+            [0.1, 0.0, 0.0],
+            UnitQuaternion::identity(),
+            pol,
+            [0.1, 0.1, 0.1],
+        );
+
+        for p in points() {
+            let expected = left.compute_B(p) + right.compute_B(p);
+            assert_relative_eq!(
+                magnet.compute_B(p),
+                expected,
+                epsilon = 1e-12,
+                max_relative = 1e-9
+            );
+        }
+    }
+
+    #[test]
+    fn test_cavity_orientation() {
+        let (mut vertices, mut faces) = cube_mesh(Vector3::zeros(), 0.2, false);
+        let (inner_vertices, inner_faces) = cube_mesh(Vector3::zeros(), 0.1, false);
+        let offset = vertices.len();
+        vertices.extend(inner_vertices);
+        faces.extend(inner_faces.iter().map(|f| f.map(|i| i + offset)));
+
+        assert_eq!(
+            MeshMagnet::from_vertices_and_faces(vertices, faces, [0.0, 0.0, 1.0]).unwrap_err(),
+            MeshError::InwardNormals
+        );
+    }
+}
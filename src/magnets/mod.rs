@@ -34,7 +34,7 @@
 //!
 //! ```
 //! # use magba::prelude::*;
-//! # use nalgebra::point;
+//! # use nalgebra::{Vector3, point};
 //! # let magnet = CuboidMagnet::default();
 //! // Compute the B-field at a specific point
 //! let b_field = magnet.compute_B(point![0.0, 0.0, 0.02]);
@@ -42,8 +42,16 @@
 //! // Compute the B-field at multiple points
 //! let points = vec![point![0.0, 0.0, 0.02], point![0.0, 0.0, 0.03]];
 //! let b_fields = magnet.compute_B_batch(&points);
+//!
+//! // Or write into an existing buffer without allocating
+//! let mut out = [Vector3::zeros(); 2];
+//! magnet.compute_B_batch_into(&points, &mut out);
+//! assert_eq!(out.as_slice(), b_fields.as_slice());
 //! ```
 //!
+//! [Source::add_B_batch_into](crate::base::Source::add_B_batch_into) adds the field to the
+//! buffer instead, for summing the fields of several sources.
+//!
 //! With the `rayon` feature (default), `compute_B_batch` automatically parallelizes the magnetic
 //! field computation using [Rayon](https://github.com/rayon-rs/rayon) if the number of input
 //! points is greater than the threshold to overcome the parallelization overhead.
@@ -55,6 +63,8 @@
 mod cuboid;
 mod cylinder;
 mod dipole;
+#[cfg(feature = "alloc")]
+mod prism;
 mod sphere;
 mod tetrahedron;
 mod triangle;
@@ -62,6 +72,8 @@ mod triangle;
 pub use cuboid::CuboidMagnet;
 pub use cylinder::CylinderMagnet;
 pub use dipole::Dipole;
+#[cfg(feature = "alloc")]
+pub use prism::PrismMagnet;
 pub use sphere::SphereMagnet;
 pub use tetrahedron::TetrahedronMagnet;
 pub use triangle::TriangleMagnet;
@@ -74,6 +86,11 @@ mod mesh;
 #[cfg(feature = "mesh")]
 pub use mesh::MeshMagnet;
 
+#[cfg(feature = "mesh")]
+mod tet_mesh;
+#[cfg(feature = "mesh")]
+pub use tet_mesh::TetMeshMagnet;
+
 #[cfg(test)]
 mod stable_field;
 #[cfg(test)]
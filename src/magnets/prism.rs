@@ -0,0 +1,102 @@
+/*
+ * Magba is licensed under The 3-Clause BSD, see LICENSE.
+ * Copyright 2025 Sira Pornsiriprasert <code@psira.me>
+ */
+
+use nalgebra::{Vector2, Vector3};
+
+use crate::base::polygon::Polygon;
+use crate::crate_utils::define_source;
+
+define_source! {
+    /// Prism magnet source, extruding a polygon cross-section along the z-axis.
+    ///
+    /// The polygon may be convex or concave, e.g., laser-cut D-shapes, arcs, and trapezoids.
+    /// The prism spans from `-height/2` to `height/2` along the local z-axis.
+    PrismMagnet
+    field_fn: prism_B
+    args: {
+        polarization: Vector3<T> = Vector3::z(),
+        height: T = T::one();
+            validate height >= T::zero();
+            error "Height must be non-negative.",
+        polygon: @ref Polygon<T> = Polygon::new([
+            Vector2::new(-T::from_f64(0.5).unwrap(), -T::from_f64(0.5).unwrap()),
+            Vector2::new(T::from_f64(0.5).unwrap(), -T::from_f64(0.5).unwrap()),
+            Vector2::new(T::from_f64(0.5).unwrap(), T::from_f64(0.5).unwrap()),
+            Vector2::new(-T::from_f64(0.5).unwrap(), T::from_f64(0.5).unwrap()),
+        ]),
+    }
+    arg_display: "pol={}, h={}, vertices count={}";
+    arg_fmt: [format_vector3, format_float, format_polygon_count]
+    docs: {
+        new: {
+            /// Construct a [PrismMagnet].
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// # use magba::magnets::PrismMagnet;
+            /// # use magba::base::polygon::Polygon;
+            /// # use nalgebra::{UnitQuaternion, vector};
+            /// // Trapezoid profile
+            /// let polygon = Polygon::new(vec![
+            ///     vector![-0.01, -0.005],
+            ///     vector![0.01, -0.005],
+            ///     vector![0.005, 0.005],
+            ///     vector![-0.005, 0.005],
+            /// ]);
+            ///
+            /// let magnet = PrismMagnet::new(
+            ///     [0.0, 0.0, 0.0],              // position: Center of the prism (m)
+            ///     UnitQuaternion::identity(),   // orientation as unit quaternion
+            ///     [0.0, 0.0, 1.0],              // polarization (T)
+            ///     0.003,                        // height (m)
+            ///     polygon,                      // cross-section (m)
+            /// );
+            /// ```
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::{base::Source, magnets::CylinderMagnet};
+    use approx::assert_relative_eq;
+    use nalgebra::{UnitQuaternion, point, vector};
+
+    #[test]
+    #[should_panic]
+    fn test_height_validation() {
+        PrismMagnet::<f64>::default().set_height(-1.0);
+    }
+
+    #[test]
+    fn test_fine_polygon_approximates_cylinder() {
+        let n = 720;
+        let polygon = Polygon::new((0..n).map(|i| {
+            let theta = 2.0 * core::f64::consts::PI * i as f64 / n as f64;
+            vector![0.01 * theta.cos(), 0.01 * theta.sin()]
+        }));
+        let orientation = UnitQuaternion::from_scaled_axis(vector![0.2, 0.1, 0.0]);
+        let prism = PrismMagnet::new(
+            [0.0, 0.01, 0.0],
+            orientation,
+            [0.3, 0.0, 1.0],
+            0.01,
+            polygon,
+        );
+        let cylinder =
+            CylinderMagnet::new([0.0, 0.01, 0.0], orientation, [0.3, 0.0, 1.0], 0.02, 0.01);
+
+        for point in [point![0.02, 0.0, 0.02], point![-0.01, 0.03, -0.02]] {
+            let expected = cylinder.compute_B(point);
+            assert_relative_eq!(
+                prism.compute_B(point),
+                expected,
+                epsilon = 1e-4 * expected.norm()
+            );
+        }
+    }
+}
@@ -30,8 +30,12 @@ impl<T: Float> Source<T> for StableFieldMagnet<T> {
     }
 
     #[allow(non_snake_case)]
-    #[cfg(feature = "alloc")]
-    fn compute_B_batch(&self, points: &[Point3<T>]) -> alloc::vec::Vec<Vector3<T>> {
-        points.iter().map(|_| self.b_field).collect()
+    fn compute_B_batch_into(&self, points: &[Point3<T>], out: &mut [Vector3<T>]) {
+        assert_eq!(
+            out.len(),
+            points.len(),
+            "Output slice length must match input vectors length."
+        );
+        out.fill(self.b_field);
     }
 }
@@ -0,0 +1,270 @@
+/*
+ * Magba is licensed under The 3-Clause BSD, see LICENSE.
+ * Copyright 2025 Sira Pornsiriprasert <code@psira.me>
+ */
+
+use alloc::vec::Vec;
+use nalgebra::Vector3;
+
+use crate::base::Float;
+use crate::base::mesh::TetMesh;
+use crate::crate_utils::define_source;
+
+define_source! {
+    /// Tetrahedral volume mesh with a homogeneous polarization in each element.
+    ///
+    /// Useful for non-uniformly magnetized parts, e.g., imported from a finite element
+    /// magnetization solve.
+    TetMeshMagnet
+    field_fn: tet_mesh_B
+    args: {
+        polarizations: @ref Vec<Vector3<T>> = Vec::new();
+            validate polarizations.len() == mesh.tetrahedra().len();
+            error "Polarization count must match the tetrahedron count.",
+        mesh: @ref TetMesh<T> = TetMesh::new(Vec::new(), Vec::new());
+            validate polarizations.len() == mesh.tetrahedra().len();
+            error "Polarization count must match the tetrahedron count.",
+    }
+    arg_display: "polarizations_count: {}, tetrahedra count: {}";
+    arg_fmt: [format_vertices_count, format_tetmesh_count]
+    docs: {
+        new: {
+            /// Construct a [TetMeshMagnet].
+            ///
+            /// `polarizations` holds one polarization vector (T) per tetrahedron of the mesh.
+            /// To replace the mesh with one of a different size, construct a new magnet.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// # use magba::magnets::TetMeshMagnet;
+            /// # use magba::base::mesh::TetMesh;
+            /// # use nalgebra::{UnitQuaternion, vector};
+            /// let vertices = vec![
+            ///     vector![0.0, 0.0, 0.0],
+            ///     vector![0.01, 0.0, 0.0],
+            ///     vector![0.0, 0.01, 0.0],
+            ///     vector![0.0, 0.0, 0.01],
+            ///     vector![0.01, 0.01, 0.01],
+            /// ];
+            /// let mesh = TetMesh::new(vertices, vec![[0, 1, 2, 3], [1, 2, 3, 4]]);
+            ///
+            /// let magnet = TetMeshMagnet::new(
+            ///     [0.0, 0.0, 0.0],              // position (m)
+            ///     UnitQuaternion::identity(),   // orientation as unit quaternion
+            ///     vec![vector![0.0, 0.0, 1.0], vector![0.0, 0.5, 1.0]], // polarizations (T)
+            ///     mesh,
+            /// );
+            /// ```
+        }
+    }
+}
+
+impl<T: Float> TetMeshMagnet<T> {
+    /// Construct a [TetMeshMagnet] with the polarization of each tetrahedron given by
+    /// `polarization_fn` evaluated at the tetrahedron centroid (m).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use magba::magnets::TetMeshMagnet;
+    /// # use magba::base::mesh::TetMesh;
+    /// # use nalgebra::{vector, Vector3};
+    /// # let mesh = TetMesh::new(
+    /// #     vec![vector![0.0, 0.0, 0.0], vector![0.01, 0.0, 0.0], vector![0.0, 0.01, 0.0], vector![0.0, 0.0, 0.01]],
+    /// #     vec![[0, 1, 2, 3]],
+    /// # );
+    /// // Radially magnetized part
+    /// let magnet = TetMeshMagnet::from_fn(mesh, |p: Vector3<f64>| {
+    ///     vector![p.x, p.y, 0.0].normalize()
+    /// });
+    /// ```
+    pub fn from_fn<F>(mesh: TetMesh<T>, polarization_fn: F) -> Self
+    where
+        F: Fn(Vector3<T>) -> Vector3<T>,
+    {
+        let polarizations = mesh.centroids().into_iter().map(polarization_fn).collect();
+
+        Self::new(
+            [T::zero(); 3],
+            nalgebra::UnitQuaternion::identity(),
+            polarizations,
+            mesh,
+        )
+    }
+
+    /// Constructs a [`TetMeshMagnet`] from a Gmsh `.msh` reader, given the polarization of
+    /// each tetrahedron in file order. See [TetMesh::from_msh].
+    ///
+    /// Returns an [InvalidInput](std::io::ErrorKind::InvalidInput) error if the number of
+    /// polarizations differs from the number of tetrahedra in the file.
+    #[cfg(feature = "io-msh")]
+    #[inline]
+    pub fn from_msh<R: std::io::BufRead>(
+        reader: R,
+        polarizations: Vec<Vector3<T>>,
+    ) -> std::io::Result<Self> {
+        let mesh = TetMesh::from_msh(reader)?;
+
+        Self::try_new(
+            [T::zero(); 3],
+            nalgebra::UnitQuaternion::identity(),
+            polarizations,
+            mesh,
+        )
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e.to_string()))
+    }
+
+    /// Constructs a [`TetMeshMagnet`] from a Gmsh `.msh` reader, with the polarization of
+    /// each tetrahedron given by `polarization_fn` at its centroid. See [TetMeshMagnet::from_fn].
+    ///
+    /// To scale the mesh on import, read it with [TetMesh::from_msh_with_options] instead.
+    #[cfg(feature = "io-msh")]
+    #[inline]
+    pub fn from_msh_fn<R, F>(reader: R, polarization_fn: F) -> std::io::Result<Self>
+    where
+        R: std::io::BufRead,
+        F: Fn(Vector3<T>) -> Vector3<T>,
+    {
+        Ok(Self::from_fn(TetMesh::from_msh(reader)?, polarization_fn))
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::{base::Source, collections::SourceAssembly, magnets::TetrahedronMagnet};
+    use approx::assert_relative_eq;
+    use nalgebra::{UnitQuaternion, point, vector};
+
+    fn cube_tets(side: f64) -> TetMesh<f64> {
+        let vertices = (0..8).map(|i| {
+            vector![
+                (i & 1) as f64 * side,
+                ((i >> 1) & 1) as f64 * side,
+                ((i >> 2) & 1) as f64 * side
+            ]
+        });
+        // Six tetrahedra around the main diagonal
+        let tetrahedra = [
+            [0, 1, 3, 7],
+            [0, 3, 2, 7],
+            [0, 2, 6, 7],
+            [0, 6, 4, 7],
+            [0, 4, 5, 7],
+            [0, 5, 1, 7],
+        ];
+        TetMesh::new(vertices, tetrahedra)
+    }
+
+    #[test]
+    fn test_matches_assembly() {
+        let mesh = cube_tets(0.01);
+        let magnet = TetMeshMagnet::from_fn(mesh.clone(), |c| vector![c.y * 100.0, 0.0, 1.0])
+            .with_position([0.01, -0.02, 0.0])
+            .with_orientation(UnitQuaternion::from_scaled_axis(vector![0.3, 0.2, 0.1]));
+
+        let mut assembly = SourceAssembly::default();
+        for (i, pol) in magnet.polarizations().iter().enumerate() {
+            assembly.push(TetrahedronMagnet::new(
+                [0.0; 3],
+                UnitQuaternion::identity(),
+                *pol,
+                mesh.element(i),
+            ));
+        }
+        let assembly = assembly
+            .with_position(magnet.position())
+            .with_orientation(magnet.orientation());
+
+        let points = [
+            point![0.03, 0.0, 0.01],
+            point![0.015, -0.015, 0.005],
+            point![-0.02, 0.01, -0.01],
+        ];
+        for (b, expected) in magnet
+            .compute_B_batch(&points)
+            .iter()
+            .zip(assembly.compute_B_batch(&points))
+        {
+            assert_relative_eq!(*b, expected, epsilon = 1e-14);
+        }
+    }
+
+    #[test]
+    fn test_uniform_matches_cuboid() {
+        use crate::magnets::CuboidMagnet;
+
+        let mesh = cube_tets(0.01);
+        let magnet = TetMeshMagnet::from_fn(mesh, |_| vector![0.2, 0.0, 1.0]);
+        let cuboid = CuboidMagnet::new(
+            [0.005; 3],
+            UnitQuaternion::identity(),
+            [0.2, 0.0, 1.0],
+            [0.01; 3],
+        );
+
+        for point in [point![0.02, 0.01, 0.03], point![0.004, 0.007, 0.003]] {
+            assert_relative_eq!(
+                magnet.compute_B(point),
+                cuboid.compute_B(point),
+                epsilon = 1e-12
+            );
+        }
+    }
+
+    #[test]
+    fn test_polarization_count() {
+        let mesh = cube_tets(0.01);
+        let polarizations = vec![vector![0.0, 0.0, 1.0]; 6];
+        let result = TetMeshMagnet::try_new(
+            [0.0; 3],
+            UnitQuaternion::identity(),
+            polarizations[..5].to_vec(),
+            mesh.clone(),
+        );
+        assert!(matches!(result, Err(crate::Error::InvalidGeometry(_))));
+
+        let mut magnet =
+            TetMeshMagnet::new([0.0; 3], UnitQuaternion::identity(), polarizations, mesh);
+        assert!(magnet.try_set_polarizations(Vec::new()).is_err());
+        assert!(magnet.try_set_mesh(cube_tets(0.02)).is_ok());
+        assert!(
+            magnet
+                .try_set_mesh(TetMesh::new(Vec::new(), Vec::new()))
+                .is_err()
+        );
+        assert_eq!(magnet.polarizations().len(), 6);
+    }
+
+    #[cfg(feature = "io-msh")]
+    #[test]
+    fn test_from_msh_polarization_count() {
+        let msh = "\
+$MeshFormat
+4.1 0 8
+$EndMeshFormat
+$Nodes
+1 4 1 4
+3 1 0 4
+1
+2
+3
+4
+0 0 0
+1 0 0
+0 1 0
+0 0 1
+$EndNodes
+$Elements
+1 1 1 1
+3 1 4 1
+1 1 2 3 4
+$EndElements
+";
+        let pol = vector![0.0, 0.0, 1.0];
+        assert!(TetMeshMagnet::<f64>::from_msh(msh.as_bytes(), vec![pol]).is_ok());
+        let error = TetMeshMagnet::<f64>::from_msh(msh.as_bytes(), vec![pol; 2]).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+    }
+}
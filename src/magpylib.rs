@@ -0,0 +1,1224 @@
+/*
+ * Magba is licensed under The 3-Clause BSD, see LICENSE.
+ * Copyright 2025 Sira Pornsiriprasert <code@psira.me>
+ */
+
+//! Conversion of source collections to and from [Magpylib](https://magpylib.readthedocs.io)
+//! object descriptions in JSON.
+//!
+//! Magba follows the formulas and conventions of Magpylib, so objects carry over one to
+//! one. A description is a list of objects, each a JSON object with a Magpylib `type` and
+//! its attributes in SI units (m, T, A).
+//!
+//! | Type | Attributes | Magba source |
+//! |------|------------|--------------|
+//! | `Cuboid` | `dimension` (3), `polarization` | [CuboidMagnet] |
+//! | `Cylinder` | `dimension` (diameter, height), `polarization` | [CylinderMagnet] |
+//! | `CylinderSegment` | `dimension` (r1, r2, h, φ1, φ2 in deg), `polarization` | [PrismMagnet] |
+//! | `Sphere` | `diameter`, `polarization` | [SphereMagnet] |
+//! | `Dipole` | `moment` (A·m²) | [Dipole] |
+//! | `Triangle` | `vertices` (3), `polarization` | [TriangleMagnet] |
+//! | `Tetrahedron` | `vertices` (4), `polarization` | [TetrahedronMagnet] |
+//! | `TriangularMesh` | `vertices`, `faces`, `polarization` | [MeshMagnet] |
+//! | `Circle` | `diameter`, `current` | [CircularCurrent] |
+//! | `Polyline` | `vertices`, `current` | [PathCurrent] |
+//! | `Collection` | `children` | [SourceAssembly] |
+//!
+//! Every object takes an optional `position` and an `orientation` as a rotation vector
+//! (rad), or `null`. Magnets take a `magnetization` (A/m) instead of the `polarization`
+//! if preferred. Child positions are global, as in Magpylib.
+//!
+//! A `CylinderSegment` becomes a prism whose arcs are divided into chords of at most 1°.
+//! On export, a [PrismMagnet] is written as a `TriangularMesh` and a [TetMeshMagnet] as a
+//! `Collection` of `Tetrahedron` objects. Sheet and triangle currents have no Magpylib
+//! equivalent.
+//!
+//! # Paths
+//!
+//! The `position` and `orientation` may also be lists of poses, the path of the object.
+//! [read_json_path] returns the sources at each step of the longest path, where shorter
+//! paths stay at their last pose, as in Magpylib. [write_json_path] writes a sequence of
+//! assemblies that differ only in poses as paths.
+//!
+//! # Python
+//!
+//! The description can be produced and consumed in Python with a few lines.
+//!
+//! ```python
+//! import magpylib as magpy
+//! from scipy.spatial.transform import Rotation
+//!
+//! KEYS = ("dimension", "diameter", "polarization", "moment", "current", "vertices", "faces")
+//!
+//! def describe(obj):
+//!     d = {"type": type(obj).__name__, "position": obj.position.tolist(),
+//!          "orientation": obj.orientation.as_rotvec().tolist()}
+//!     for key in KEYS:
+//!         value = getattr(obj, key, None)
+//!         if value is not None:
+//!             d[key] = value.tolist() if hasattr(value, "tolist") else value
+//!     if isinstance(obj, magpy.Collection):
+//!         d["children"] = [describe(child) for child in obj.children]
+//!     return d
+//!
+//! def build(d):
+//!     d = dict(d)
+//!     kind, children = d.pop("type"), d.pop("children", [])
+//!     d["orientation"] = Rotation.from_rotvec(d["orientation"])
+//!     if kind == "Collection":
+//!         return magpy.Collection(*map(build, children), **d)
+//!     for module in (magpy.magnet, magpy.current, magpy.misc):
+//!         if hasattr(module, kind):
+//!             return getattr(module, kind)(**d)
+//! ```
+//!
+//! # Examples
+//!
+//! ```
+//! # use magba::magpylib;
+//! # use magba::prelude::*;
+//! let json = r#"[
+//!     {"type": "Cylinder", "dimension": [0.01, 0.005], "polarization": [0, 0, 1.3]},
+//!     {"type": "Collection", "orientation": [0, 0, 1.5707963267948966], "children": [
+//!         {"type": "Cuboid", "position": [0.02, 0, 0], "dimension": [0.005, 0.005, 0.005],
+//!          "polarization": [1, 0, 0]},
+//!         {"type": "Circle", "position": [0.02, 0, 0.01], "diameter": 0.01, "current": 2}
+//!     ]}
+//! ]"#;
+//! let sources: SourceAssembly = magpylib::read_json(json.as_bytes())?;
+//! assert_eq!(sources.iter().count(), 2);
+//!
+//! let mut out = Vec::new();
+//! magpylib::write_json(&mut out, &sources)?;
+//! let again: SourceAssembly = magpylib::read_json(out.as_slice())?;
+//! assert_eq!(again, sources);
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use core::iter::Sum;
+use std::io::{self, Error, ErrorKind, Read, Write};
+
+use nalgebra::{Point3, UnitQuaternion, Vector2, Vector3};
+use num_traits::ToPrimitive;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    base::{
+        Float, Pose, Transform,
+        mesh::{Tessellate, TriMesh},
+        polygon::Polygon,
+    },
+    collections::{SourceAssembly, SourceComponent},
+    currents::{CircularCurrent, Current, PathCurrent},
+    magnets::{
+        CuboidMagnet, CylinderMagnet, Dipole, Magnet, MeshMagnet, PrismMagnet, SphereMagnet,
+        TetMeshMagnet, TetrahedronMagnet, TriangleMagnet,
+    },
+};
+
+/// Largest angle of a chord approximating the arcs of a `CylinderSegment` (deg).
+const ARC_STEP: f64 = 1.0;
+
+/// Relative tolerance between a given `polarization` and `magnetization`.
+const CONSISTENCY_TOLERANCE: f64 = 1e-6;
+
+// MARK: Read
+
+/// Reads sources from a Magpylib description.
+///
+/// Returns an error of kind [ErrorKind::InvalidData] if the description is invalid or has
+/// paths of more than one pose, see [read_json_path].
+pub fn read_json<T: Float + Sum, R: Read>(reader: R) -> io::Result<SourceAssembly<T>> {
+    let (sources, paths) = read(reader)?;
+    match steps(&paths) {
+        1 => Ok(sources),
+        n => Err(invalid_data(format!(
+            "Description has paths of {n} poses, read it with `read_json_path`."
+        ))),
+    }
+}
+
+/// Reads sources from a Magpylib description, one assembly per step of the longest path.
+///
+/// Objects with shorter paths stay at their last pose. Returns an error of kind
+/// [ErrorKind::InvalidData] if the description is invalid.
+pub fn read_json_path<T: Float + Sum, R: Read>(reader: R) -> io::Result<Vec<SourceAssembly<T>>> {
+    let (sources, paths) = read(reader)?;
+    Ok((0..steps(&paths))
+        .map(|step| {
+            let mut sources = sources.clone();
+            for (i, path) in paths.iter().enumerate() {
+                path.place(&mut sources[i], step);
+            }
+            sources
+        })
+        .collect())
+}
+
+fn read<T: Float + Sum, R: Read>(
+    mut reader: R,
+) -> io::Result<(SourceAssembly<T>, Vec<PoseTree<T>>)> {
+    let mut text = String::new();
+    reader.read_to_string(&mut text)?;
+    // A list of objects, or a single object such as a collection
+    let objects = if text.trim_start().starts_with('[') {
+        serde_json::from_str(&text)
+    } else {
+        serde_json::from_str(&text).map(|object| vec![object])
+    };
+    let objects: Vec<Object> = objects.map_err(invalid_data)?;
+    let (components, paths): (Vec<_>, Vec<_>) = objects
+        .iter()
+        .enumerate()
+        .map(|(i, object)| object.build(&format!("/{i}")))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(invalid_data)?
+        .into_iter()
+        .unzip();
+    let sources = SourceAssembly::new(Point3::origin(), UnitQuaternion::identity(), components);
+    Ok((sources, paths))
+}
+
+/// Number of steps of the longest path.
+fn steps<T: Float>(paths: &[PoseTree<T>]) -> usize {
+    paths.iter().map(PoseTree::steps).max().unwrap_or(1)
+}
+
+/// Path of an object and of its children.
+struct PoseTree<T: Float> {
+    path: Vec<Pose<T>>,
+    children: Vec<PoseTree<T>>,
+}
+
+impl<T: Float> PoseTree<T> {
+    fn steps(&self) -> usize {
+        self.children
+            .iter()
+            .map(Self::steps)
+            .fold(self.path.len(), usize::max)
+    }
+
+    /// Moves `component` to its pose at `step`, then its children to theirs.
+    fn place(&self, component: &mut SourceComponent<T>, step: usize) {
+        let pose = self.path[step.min(self.path.len() - 1)];
+        match component {
+            SourceComponent::Assembly(assembly) => {
+                assembly.set_pose(pose);
+                for (i, child) in self.children.iter().enumerate() {
+                    child.place(&mut assembly[i], step);
+                }
+            }
+            component => component.set_pose(pose),
+        }
+    }
+}
+
+// MARK: Write
+
+/// Writes sources as a Magpylib description.
+///
+/// Returns an error of kind [ErrorKind::InvalidInput] if a source has no Magpylib
+/// equivalent.
+pub fn write_json<T: Float, W: Write>(writer: W, sources: &SourceAssembly<T>) -> io::Result<()> {
+    write_json_path(writer, core::slice::from_ref(sources))
+}
+
+/// Writes a sequence of assemblies as a Magpylib description with paths.
+///
+/// The assemblies must hold the same sources, differing only in their poses. A single
+/// assembly is written without paths. Returns an error of kind [ErrorKind::InvalidInput]
+/// if `steps` is empty, the assemblies differ otherwise, or a source has no Magpylib
+/// equivalent.
+pub fn write_json_path<T: Float, W: Write>(
+    writer: W,
+    steps: &[SourceAssembly<T>],
+) -> io::Result<()> {
+    let Some(first) = steps.first() else {
+        return Err(invalid_input("No path steps given."));
+    };
+    let count = first.iter().count();
+    if steps.iter().any(|sources| sources.iter().count() != count) {
+        return Err(invalid_input("Path steps must have the same sources."));
+    }
+    let objects = (0..count)
+        .map(|i| {
+            let pointer = format!("/{i}");
+            let objects = steps
+                .iter()
+                .map(|sources| Object::describe(&sources[i], &pointer))
+                .collect::<Result<Vec<_>, _>>()?;
+            Object::merge(&objects, &pointer)
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(invalid_input)?;
+    serde_json::to_writer_pretty(writer, &objects).map_err(Error::other)
+}
+
+// MARK: Object
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+enum Kind {
+    Cuboid,
+    Cylinder,
+    CylinderSegment,
+    Sphere,
+    Dipole,
+    Triangle,
+    Tetrahedron,
+    TriangularMesh,
+    Circle,
+    Polyline,
+    Collection,
+}
+
+impl Kind {
+    /// Attributes besides `type`, `position`, and `orientation`.
+    fn fields(self) -> &'static [&'static str] {
+        match self {
+            Self::Cuboid | Self::Cylinder | Self::CylinderSegment => {
+                &["dimension", "polarization", "magnetization"]
+            }
+            Self::Sphere => &["diameter", "polarization", "magnetization"],
+            Self::Dipole => &["moment"],
+            Self::Triangle | Self::Tetrahedron => &["vertices", "polarization", "magnetization"],
+            Self::TriangularMesh => &["vertices", "faces", "polarization", "magnetization"],
+            Self::Circle => &["diameter", "current"],
+            Self::Polyline => &["vertices", "current"],
+            Self::Collection => &["children"],
+        }
+    }
+}
+
+/// A single pose or a path of poses.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+enum Poses {
+    One([f64; 3]),
+    Path(Vec<[f64; 3]>),
+}
+
+impl Poses {
+    fn to_vec(&self) -> Vec<[f64; 3]> {
+        match self {
+            Self::One(value) => vec![*value],
+            Self::Path(values) => values.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Object {
+    #[serde(rename = "type")]
+    kind: Kind,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    position: Option<Poses>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    orientation: Option<Poses>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    dimension: Option<Vec<f64>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    diameter: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    polarization: Option<[f64; 3]>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    magnetization: Option<[f64; 3]>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    moment: Option<[f64; 3]>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    current: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    vertices: Option<Vec<[f64; 3]>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    faces: Option<Vec<[usize; 3]>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    children: Option<Vec<Object>>,
+}
+
+impl Object {
+    fn new(kind: Kind) -> Self {
+        Self {
+            kind,
+            position: None,
+            orientation: None,
+            dimension: None,
+            diameter: None,
+            polarization: None,
+            magnetization: None,
+            moment: None,
+            current: None,
+            vertices: None,
+            faces: None,
+            children: None,
+        }
+    }
+
+    // MARK: Build
+
+    /// Builds the source at the first pose of its path, with errors prefixed by the JSON
+    /// pointer of the object.
+    fn build<T: Float + Sum>(
+        &self,
+        pointer: &str,
+    ) -> Result<(SourceComponent<T>, PoseTree<T>), String> {
+        let path = self
+            .check_fields()
+            .and_then(|_| self.path())
+            .map_err(|e| format!("{pointer}: {e}"))?;
+        let (children, paths): (Vec<_>, Vec<_>) = self
+            .children
+            .iter()
+            .flatten()
+            .enumerate()
+            .map(|(i, child)| child.build(&format!("{pointer}/children/{i}")))
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .unzip();
+        let component = self
+            .component(path[0], children)
+            .map_err(|e| format!("{pointer}: {e}"))?;
+        let tree = PoseTree {
+            path,
+            children: paths,
+        };
+        Ok((component, tree))
+    }
+
+    fn check_fields(&self) -> Result<(), String> {
+        let present = [
+            ("dimension", self.dimension.is_some()),
+            ("diameter", self.diameter.is_some()),
+            ("polarization", self.polarization.is_some()),
+            ("magnetization", self.magnetization.is_some()),
+            ("moment", self.moment.is_some()),
+            ("current", self.current.is_some()),
+            ("vertices", self.vertices.is_some()),
+            ("faces", self.faces.is_some()),
+            ("children", self.children.is_some()),
+        ];
+        let allowed = self.kind.fields();
+        match present
+            .iter()
+            .find(|(name, set)| *set && !allowed.contains(name))
+        {
+            Some((name, _)) => Err(format!("`{name}` does not apply to {:?}.", self.kind)),
+            None => Ok(()),
+        }
+    }
+
+    fn required<V>(&self, name: &str, value: Option<V>) -> Result<V, String> {
+        value.ok_or_else(|| format!("{:?} needs `{name}`.", self.kind))
+    }
+
+    fn path<T: Float>(&self) -> Result<Vec<Pose<T>>, String> {
+        let positions = self.position.as_ref().map_or(vec![[0.0; 3]], Poses::to_vec);
+        let orientations = self
+            .orientation
+            .as_ref()
+            .map_or(vec![[0.0; 3]], Poses::to_vec);
+        let n = positions.len().max(orientations.len());
+        if positions.is_empty() || orientations.is_empty() {
+            return Err("`position` and `orientation` must not be empty.".into());
+        }
+        if [positions.len(), orientations.len()]
+            .iter()
+            .any(|&len| len != 1 && len != n)
+        {
+            return Err("`position` and `orientation` paths must have the same length.".into());
+        }
+        if positions
+            .iter()
+            .chain(&orientations)
+            .flatten()
+            .any(|c| !c.is_finite())
+        {
+            return Err("`position` and `orientation` must be finite.".into());
+        }
+        Ok((0..n)
+            .map(|i| {
+                let position = vector::<T>(positions[i.min(positions.len() - 1)]);
+                let rotvec = vector::<T>(orientations[i.min(orientations.len() - 1)]);
+                Pose::new(position, UnitQuaternion::from_scaled_axis(rotvec))
+            })
+            .collect())
+    }
+
+    fn polarization<T: Float>(&self) -> Result<Vector3<T>, String> {
+        let polarization = match (self.polarization, self.magnetization) {
+            (Some(j), Some(m)) => {
+                let (j, m) = (Vector3::from(j), Vector3::from(m) * f64::mu0());
+                if (j - m).norm() > CONSISTENCY_TOLERANCE * j.norm().max(m.norm()) {
+                    return Err("`polarization` and `magnetization` do not match.".into());
+                }
+                j
+            }
+            (Some(j), None) => Vector3::from(j),
+            (None, Some(m)) => Vector3::from(m) * f64::mu0(),
+            (None, None) => {
+                return Err(format!(
+                    "{:?} needs a `polarization` or a `magnetization`.",
+                    self.kind
+                ));
+            }
+        };
+        Ok(vector(polarization.into()))
+    }
+
+    fn dimension<const N: usize>(&self) -> Result<[f64; N], String> {
+        let dimension = self.required("dimension", self.dimension.as_ref())?;
+        dimension
+            .as_slice()
+            .try_into()
+            .map_err(|_| format!("{:?} needs {N} `dimension` values.", self.kind))
+    }
+
+    fn vertices<T: Float>(&self) -> Result<Vec<Vector3<T>>, String> {
+        let vertices = self.required("vertices", self.vertices.as_ref())?;
+        Ok(vertices.iter().map(|&v| vector(v)).collect())
+    }
+
+    fn exact_vertices<T: Float, const N: usize>(&self) -> Result<[Vector3<T>; N], String> {
+        self.vertices()?
+            .try_into()
+            .map_err(|_| format!("{:?} needs {N} `vertices`.", self.kind))
+    }
+
+    fn component<T: Float + Sum>(
+        &self,
+        pose: Pose<T>,
+        children: Vec<SourceComponent<T>>,
+    ) -> Result<SourceComponent<T>, String> {
+        let (position, orientation) = (pose.position(), pose.orientation());
+        let component: SourceComponent<T> = match self.kind {
+            Kind::Cuboid => {
+                let dimension = self.dimension::<3>()?;
+                if !dimension.iter().all(|&d| d > 0.0 && d.is_finite()) {
+                    return Err("`dimension` must be positive.".into());
+                }
+                CuboidMagnet::new(
+                    position,
+                    orientation,
+                    self.polarization()?,
+                    vector(dimension),
+                )
+                .into()
+            }
+            Kind::Cylinder => {
+                let [d, h] = self
+                    .dimension::<2>()?
+                    .map(|v| positive::<T>("dimension", v));
+                CylinderMagnet::new(position, orientation, self.polarization()?, d?, h?).into()
+            }
+            Kind::CylinderSegment => {
+                cylinder_segment(self.dimension::<5>()?, pose, self.polarization()?)?
+            }
+            Kind::Sphere => SphereMagnet::new(
+                position,
+                orientation,
+                self.polarization()?,
+                positive::<T>("diameter", self.required("diameter", self.diameter)?)?,
+            )
+            .into(),
+            Kind::Dipole => {
+                let moment = self.required("moment", self.moment)?;
+                Dipole::new(position, orientation, vector(moment)).into()
+            }
+            Kind::Triangle => TriangleMagnet::new(
+                position,
+                orientation,
+                self.polarization()?,
+                self.exact_vertices::<T, 3>()?,
+            )
+            .into(),
+            Kind::Tetrahedron => TetrahedronMagnet::new(
+                position,
+                orientation,
+                self.polarization()?,
+                self.exact_vertices::<T, 4>()?,
+            )
+            .into(),
+            Kind::TriangularMesh => {
+                let vertices = self.vertices::<T>()?;
+                let faces = self.required("faces", self.faces.clone())?;
+                if faces.iter().flatten().any(|&i| i >= vertices.len()) {
+                    return Err("Face vertex index out of range.".into());
+                }
+                let mesh = TriMesh::new(vertices, faces)
+                    .map_err(|e| format!("Invalid mesh: {}.", e.to_string().trim_end()))?;
+                MeshMagnet::new(position, orientation, self.polarization()?, mesh).into()
+            }
+            Kind::Circle => CircularCurrent::new(
+                position,
+                orientation,
+                positive::<T>("diameter", self.required("diameter", self.diameter)?)?,
+                cast::<T>(self.required("current", self.current)?),
+            )
+            .into(),
+            Kind::Polyline => {
+                let vertices = self.vertices::<T>()?;
+                if vertices.len() < 2 {
+                    return Err("Polyline needs at least 2 `vertices`.".into());
+                }
+                let current = cast::<T>(self.required("current", self.current)?);
+                PathCurrent::new(position, orientation, current, vertices).into()
+            }
+            Kind::Collection => SourceAssembly::new(position, orientation, children).into(),
+        };
+        Ok(component)
+    }
+
+    // MARK: Describe
+
+    /// Describes `component` at its current pose.
+    fn describe<T: Float>(component: &SourceComponent<T>, pointer: &str) -> Result<Self, String> {
+        let unsupported = |name: &str| format!("{pointer}: {name} has no Magpylib equivalent.");
+        let mut object = match component {
+            SourceComponent::Magnet(magnet) => match magnet {
+                Magnet::Cuboid(m) => Self {
+                    dimension: Some(to_array(m.dimensions()).to_vec()),
+                    polarization: Some(to_array(m.polarization())),
+                    ..Self::new(Kind::Cuboid)
+                },
+                Magnet::Cylinder(m) => Self {
+                    dimension: Some(vec![to_f64(m.diameter()), to_f64(m.height())]),
+                    polarization: Some(to_array(m.polarization())),
+                    ..Self::new(Kind::Cylinder)
+                },
+                Magnet::Sphere(m) => Self {
+                    diameter: Some(to_f64(m.diameter())),
+                    polarization: Some(to_array(m.polarization())),
+                    ..Self::new(Kind::Sphere)
+                },
+                Magnet::Dipole(m) => Self {
+                    moment: Some(to_array(m.moment())),
+                    ..Self::new(Kind::Dipole)
+                },
+                Magnet::Triangle(m) => Self {
+                    vertices: Some(m.vertices().map(to_array).to_vec()),
+                    polarization: Some(to_array(m.polarization())),
+                    ..Self::new(Kind::Triangle)
+                },
+                Magnet::Tetrahedron(m) => Self {
+                    vertices: Some(m.vertices().map(to_array).to_vec()),
+                    polarization: Some(to_array(m.polarization())),
+                    ..Self::new(Kind::Tetrahedron)
+                },
+                Magnet::Prism(m) => {
+                    if m.height() <= T::zero() {
+                        return Err(format!(
+                            "{pointer}: PrismMagnet must have a positive height."
+                        ));
+                    }
+                    Self::mesh(&m.tessellate(m.height()), m.polarization())
+                }
+                Magnet::Mesh(m) => Self::mesh(m.mesh(), m.polarization()),
+                Magnet::TetMesh(m) => Self::tet_mesh(m, component.pose()),
+            },
+            SourceComponent::Current(current) => match current {
+                Current::Circular(c) => Self {
+                    diameter: Some(to_f64(c.diameter())),
+                    current: Some(to_f64(c.current())),
+                    ..Self::new(Kind::Circle)
+                },
+                Current::Path(c) => Self {
+                    vertices: Some(c.vertices().iter().map(|&v| to_array(v)).collect()),
+                    current: Some(to_f64(c.current())),
+                    ..Self::new(Kind::Polyline)
+                },
+                Current::Sheet(_) => return Err(unsupported("SheetCurrent")),
+                Current::Triangle(_) => return Err(unsupported("TriangleCurrent")),
+            },
+            SourceComponent::Assembly(assembly) => Self {
+                children: Some(
+                    assembly
+                        .iter()
+                        .enumerate()
+                        .map(|(i, child)| Self::describe(child, &format!("{pointer}/children/{i}")))
+                        .collect::<Result<_, _>>()?,
+                ),
+                ..Self::new(Kind::Collection)
+            },
+            SourceComponent::Custom(_) => return Err(unsupported("Custom source")),
+        };
+        let pose = component.pose();
+        object.position = Some(Poses::One(to_array(pose.position().coords)));
+        object.orientation = Some(Poses::One(to_array(pose.orientation().scaled_axis())));
+        Ok(object)
+    }
+
+    fn mesh<T: Float>(mesh: &TriMesh<T>, polarization: Vector3<T>) -> Self {
+        Self {
+            vertices: Some(mesh.vertices().iter().map(|&v| to_array(v)).collect()),
+            faces: Some(mesh.faces().to_vec()),
+            polarization: Some(to_array(polarization)),
+            ..Self::new(Kind::TriangularMesh)
+        }
+    }
+
+    /// A collection of tetrahedra at the pose of the mesh.
+    fn tet_mesh<T: Float>(magnet: &TetMeshMagnet<T>, pose: &Pose<T>) -> Self {
+        let mesh = magnet.mesh();
+        let children = mesh
+            .tetrahedra()
+            .iter()
+            .zip(magnet.polarizations())
+            .map(|(tetrahedron, &polarization)| Self {
+                position: Some(Poses::One(to_array(pose.position().coords))),
+                orientation: Some(Poses::One(to_array(pose.orientation().scaled_axis()))),
+                vertices: Some(tetrahedron.map(|i| to_array(mesh.vertices()[i])).to_vec()),
+                polarization: Some(to_array(polarization)),
+                ..Self::new(Kind::Tetrahedron)
+            })
+            .collect();
+        Self {
+            children: Some(children),
+            ..Self::new(Kind::Collection)
+        }
+    }
+
+    /// Copy without poses, for comparing path steps.
+    fn without_poses(&self) -> Self {
+        Self {
+            position: None,
+            orientation: None,
+            children: self
+                .children
+                .as_ref()
+                .map(|children| children.iter().map(Self::without_poses).collect()),
+            ..self.clone()
+        }
+    }
+
+    /// Merges the descriptions of an object at each path step into one with paths.
+    fn merge(steps: &[Self], pointer: &str) -> Result<Self, String> {
+        let [first, rest @ ..] = steps else {
+            unreachable!("at least one path step");
+        };
+        if rest.is_empty() {
+            return Ok(first.clone());
+        }
+        let shape = first.without_poses();
+        if rest.iter().any(|step| step.without_poses() != shape) {
+            return Err(format!(
+                "{pointer}: Path steps must differ only in positions and orientations."
+            ));
+        }
+        let path = |poses: fn(&Self) -> &Option<Poses>| {
+            let values = steps
+                .iter()
+                .flat_map(|step| poses(step).iter().flat_map(Poses::to_vec));
+            Some(Poses::Path(values.collect()))
+        };
+        let children = match &first.children {
+            Some(children) => Some(
+                (0..children.len())
+                    .map(|i| {
+                        let child_steps: Vec<Self> = steps
+                            .iter()
+                            .map(|step| step.children.as_ref().unwrap()[i].clone())
+                            .collect();
+                        Self::merge(&child_steps, &format!("{pointer}/children/{i}"))
+                    })
+                    .collect::<Result<_, _>>()?,
+            ),
+            None => None,
+        };
+        Ok(Self {
+            position: path(|step| &step.position),
+            orientation: path(|step| &step.orientation),
+            children,
+            ..first.clone()
+        })
+    }
+}
+
+/// Prism approximating an annular sector `[r1, r2, h, phi1, phi2]`, split in two halves
+/// for a full ring.
+fn cylinder_segment<T: Float>(
+    dimension: [f64; 5],
+    pose: Pose<T>,
+    polarization: Vector3<T>,
+) -> Result<SourceComponent<T>, String> {
+    let [r1, r2, h, phi1, phi2] = dimension;
+    if !(0.0 <= r1 && r1 < r2 && r2.is_finite()) {
+        return Err("CylinderSegment needs 0 <= r1 < r2.".into());
+    }
+    let h = positive::<T>("dimension", h)?;
+    let span = phi2 - phi1;
+    if !(span > 0.0 && span <= 360.0) {
+        return Err("CylinderSegment needs phi1 < phi2 <= phi1 + 360.".into());
+    }
+
+    let prism = |start: f64, stop: f64, full: bool| -> Result<SourceComponent<T>, String> {
+        let n = ((stop - start) / ARC_STEP).ceil().max(1.0) as usize;
+        let arc = |r: f64| {
+            (0..=n).map(move |i| {
+                let phi = (start + (stop - start) * i as f64 / n as f64).to_radians();
+                Vector2::new(cast(r * phi.cos()), cast(r * phi.sin()))
+            })
+        };
+        let vertices: Vec<Vector2<T>> = if full {
+            arc(r2).take(n).collect()
+        } else if r1 == 0.0 {
+            arc(r2).chain([Vector2::zeros()]).collect()
+        } else {
+            arc(r2)
+                .chain(arc(r1).collect::<Vec<_>>().into_iter().rev())
+                .collect()
+        };
+        let polygon = Polygon::from_vertices(vertices)?;
+        Ok(PrismMagnet::new(
+            pose.position(),
+            pose.orientation(),
+            polarization,
+            h,
+            polygon,
+        )
+        .into())
+    };
+
+    if span < 360.0 {
+        prism(phi1, phi2, false)
+    } else if r1 == 0.0 {
+        prism(phi1, phi2, true)
+    } else {
+        let middle = phi1 + 180.0;
+        let halves = [prism(phi1, middle, false)?, prism(middle, phi2, false)?];
+        Ok(SourceAssembly::new(pose.position(), pose.orientation(), halves).into())
+    }
+}
+
+// MARK: Utilities
+
+fn cast<T: Float>(value: f64) -> T {
+    T::from_f64(value).unwrap()
+}
+
+fn vector<T: Float>(v: [f64; 3]) -> Vector3<T> {
+    Vector3::from(v.map(cast))
+}
+
+fn to_f64<T: Float>(value: T) -> f64 {
+    ToPrimitive::to_f64(&value).unwrap()
+}
+
+fn to_array<T: Float>(v: Vector3<T>) -> [f64; 3] {
+    [to_f64(v.x), to_f64(v.y), to_f64(v.z)]
+}
+
+fn positive<T: Float>(name: &str, value: f64) -> Result<T, String> {
+    if value > 0.0 && value.is_finite() {
+        Ok(cast(value))
+    } else {
+        Err(format!("`{name}` must be positive."))
+    }
+}
+
+fn invalid_data(error: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> Error {
+    Error::new(ErrorKind::InvalidData, error)
+}
+
+fn invalid_input(error: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> Error {
+    Error::new(ErrorKind::InvalidInput, error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        base::{Source, mesh::TetMesh},
+        currents::TriangleCurrent,
+    };
+    use approx::assert_relative_eq;
+    use nalgebra::{point, vector};
+
+    fn read(json: &str) -> io::Result<SourceAssembly> {
+        read_json(json.as_bytes())
+    }
+
+    fn error(json: &str) -> String {
+        read(json).unwrap_err().to_string()
+    }
+
+    fn write(sources: &SourceAssembly) -> String {
+        let mut out = Vec::new();
+        write_json(&mut out, sources).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    const TETRAHEDRON: [[f64; 3]; 4] = [
+        [0.0, 0.0, 0.0],
+        [0.01, 0.0, 0.0],
+        [0.0, 0.01, 0.0],
+        [0.0, 0.0, 0.01],
+    ];
+
+    #[test]
+    fn test_read() {
+        let json = r#"[
+            {"type": "Cuboid", "position": [0.01, 0, 0], "orientation": [0, 0, 0.5],
+             "dimension": [0.01, 0.02, 0.03], "polarization": [0, 0, 1]},
+            {"type": "Cylinder", "orientation": null, "dimension": [0.01, 0.005],
+             "magnetization": [0, 0, 1e6]},
+            {"type": "Sphere", "diameter": 0.01, "polarization": [1, 0, 0]},
+            {"type": "Dipole", "position": [0, 0, 0.1], "moment": [0, 0, 0.5]},
+            {"type": "Triangle", "vertices": [[0, 0, 0], [0.01, 0, 0], [0, 0.01, 0]],
+             "polarization": [0, 0, 1]},
+            {"type": "Tetrahedron", "vertices": [[0, 0, 0], [0.01, 0, 0], [0, 0.01, 0], [0, 0, 0.01]],
+             "polarization": [0, 1, 0]},
+            {"type": "TriangularMesh", "vertices": [[0, 0, 0], [0.01, 0, 0], [0, 0.01, 0], [0, 0, 0.01]],
+             "faces": [[0, 2, 1], [0, 1, 3], [1, 2, 3], [0, 3, 2]], "polarization": [0, 0, 1]},
+            {"type": "Collection", "position": [0, 0, 0.05], "children": [
+                {"type": "Circle", "position": [0, 0, 0.05], "diameter": 0.02, "current": 3},
+                {"type": "Polyline", "vertices": [[0, 0, 0], [0.01, 0, 0], [0.01, 0.01, 0]],
+                 "current": -1}
+            ]}
+        ]"#;
+        let sources = read(json).unwrap();
+
+        let rotation = UnitQuaternion::from_scaled_axis(vector![0.0, 0.0, 0.5]);
+        let identity = UnitQuaternion::identity();
+        let tetrahedron = TETRAHEDRON.map(Vector3::from);
+        let mesh = TriMesh::new(tetrahedron, [[0, 2, 1], [0, 1, 3], [1, 2, 3], [0, 3, 2]]).unwrap();
+        let expected = SourceAssembly::default()
+            .with(CuboidMagnet::new(
+                [0.01, 0.0, 0.0],
+                rotation,
+                [0.0, 0.0, 1.0],
+                [0.01, 0.02, 0.03],
+            ))
+            .with(CylinderMagnet::new(
+                [0.0; 3],
+                identity,
+                [0.0, 0.0, 1e6 * f64::mu0()],
+                0.01,
+                0.005,
+            ))
+            .with(SphereMagnet::new([0.0; 3], identity, [1.0, 0.0, 0.0], 0.01))
+            .with(Dipole::new([0.0, 0.0, 0.1], identity, [0.0, 0.0, 0.5]))
+            .with(TriangleMagnet::new(
+                [0.0; 3],
+                identity,
+                [0.0, 0.0, 1.0],
+                [tetrahedron[0], tetrahedron[1], tetrahedron[2]],
+            ))
+            .with(TetrahedronMagnet::new(
+                [0.0; 3],
+                identity,
+                [0.0, 1.0, 0.0],
+                tetrahedron,
+            ))
+            .with(MeshMagnet::new([0.0; 3], identity, [0.0, 0.0, 1.0], mesh))
+            .with(SourceAssembly::new(
+                point![0.0, 0.0, 0.05],
+                identity,
+                [
+                    SourceComponent::from(CircularCurrent::new(
+                        [0.0, 0.0, 0.05],
+                        identity,
+                        0.02,
+                        3.0,
+                    )),
+                    PathCurrent::new(
+                        [0.0; 3],
+                        identity,
+                        -1.0,
+                        vec![
+                            vector![0.0, 0.0, 0.0],
+                            vector![0.01, 0.0, 0.0],
+                            vector![0.01, 0.01, 0.0],
+                        ],
+                    )
+                    .into(),
+                ],
+            ));
+        assert_eq!(sources, expected);
+
+        // A single object is read as a list of one
+        let single = read(r#"{"type": "Sphere", "diameter": 0.01, "polarization": [1, 0, 0]}"#);
+        assert_eq!(single.unwrap().iter().count(), 1);
+    }
+
+    #[test]
+    fn test_cylinder_segment() {
+        let pose =
+            r#""position": [0, 0.01, 0], "orientation": [0.3, 0, 0], "polarization": [0.2, 0, 1]"#;
+        let orientation = UnitQuaternion::from_scaled_axis(vector![0.3, 0.0, 0.0]);
+        let cylinder =
+            |d: f64| CylinderMagnet::new([0.0, 0.01, 0.0], orientation, [0.2, 0.0, 1.0], d, 0.01);
+        let points = [point![0.03, 0.0, 0.02], point![-0.01, 0.04, -0.02]];
+
+        // Four quarters make a cylinder
+        let quarters = (0..4)
+            .map(|i| {
+                let (start, stop) = (90 * i - 45, 90 * i + 45);
+                format!(r#"{{"type": "CylinderSegment", "dimension": [0, 0.01, 0.01, {start}, {stop}], {pose}}}"#)
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+        let sources = read(&format!("[{quarters}]")).unwrap();
+        for point in points {
+            let expected = cylinder(0.02).compute_B(point);
+            assert_relative_eq!(
+                sources.compute_B(point),
+                expected,
+                epsilon = 1e-4 * expected.norm()
+            );
+        }
+
+        // A full ring is a cylinder without the bore
+        let ring = format!(
+            r#"{{"type": "CylinderSegment", "dimension": [0.004, 0.01, 0.01, 0, 360], {pose}}}"#
+        );
+        let sources = read(&ring).unwrap();
+        assert!(matches!(sources[0], SourceComponent::Assembly(_)));
+        for point in points {
+            let expected = cylinder(0.02).compute_B(point) - cylinder(0.008).compute_B(point);
+            assert_relative_eq!(
+                sources.compute_B(point),
+                expected,
+                epsilon = 1e-4 * expected.norm()
+            );
+        }
+
+        let disk = format!(
+            r#"{{"type": "CylinderSegment", "dimension": [0, 0.01, 0.01, 10, 370], {pose}}}"#
+        );
+        let sources = read(&disk).unwrap();
+        for point in points {
+            let expected = cylinder(0.02).compute_B(point);
+            assert_relative_eq!(
+                sources.compute_B(point),
+                expected,
+                epsilon = 1e-4 * expected.norm()
+            );
+        }
+    }
+
+    #[test]
+    fn test_paths() {
+        let json = r#"[
+            {"type": "Sphere", "diameter": 0.01, "polarization": [0, 0, 1],
+             "position": [[0, 0, 0], [0.01, 0, 0], [0.02, 0, 0]]},
+            {"type": "Collection", "orientation": [[0, 0, 0], [0, 0, 1.5707963267948966]],
+             "children": [
+                {"type": "Dipole", "moment": [1, 0, 0],
+                 "position": [[0.1, 0, 0], [0, 0.1, 0]],
+                 "orientation": [[0, 0, 0], [0, 0, 1.5707963267948966]]}
+            ]}
+        ]"#;
+        let steps: Vec<SourceAssembly> = read_json_path(json.as_bytes()).unwrap();
+        assert_eq!(steps.len(), 3);
+        let positions: Vec<_> = steps.iter().map(|s| s[0].pose().position()).collect();
+        assert_eq!(
+            positions,
+            [
+                point![0.0, 0.0, 0.0],
+                point![0.01, 0.0, 0.0],
+                point![0.02, 0.0, 0.0]
+            ]
+        );
+        let SourceComponent::Assembly(collection) = &steps[2][1] else {
+            panic!("expected a collection");
+        };
+        assert_relative_eq!(
+            collection[0].pose().position(),
+            point![0.0, 0.1, 0.0],
+            epsilon = 1e-15
+        );
+        assert_relative_eq!(
+            collection[0].compute_B(point![0.0, 0.2, 0.0]),
+            Dipole::new(
+                [0.0, 0.1, 0.0],
+                UnitQuaternion::from_axis_angle(&Vector3::z_axis(), core::f64::consts::FRAC_PI_2),
+                [1.0, 0.0, 0.0]
+            )
+            .compute_B(point![0.0, 0.2, 0.0]),
+            epsilon = 1e-12
+        );
+
+        let mut out = Vec::new();
+        write_json_path(&mut out, &steps).unwrap();
+        let again: Vec<SourceAssembly> = read_json_path(out.as_slice()).unwrap();
+        assert_eq!(again.len(), 3);
+        for (a, b) in again.iter().zip(&steps) {
+            let point = point![0.03, 0.02, 0.01];
+            assert_relative_eq!(a.compute_B(point), b.compute_B(point), epsilon = 1e-15);
+        }
+
+        assert_eq!(
+            error(json),
+            "Description has paths of 3 poses, read it with `read_json_path`."
+        );
+
+        let mut changed = steps.clone();
+        changed[1].push(Dipole::default());
+        let result = write_json_path(Vec::new(), &changed);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Path steps must have the same sources."
+        );
+        let mut changed = steps;
+        changed[1][0] =
+            SphereMagnet::new([0.0; 3], UnitQuaternion::identity(), [0.0, 0.0, 1.0], 0.02).into();
+        let result = write_json_path(Vec::new(), &changed);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "/0: Path steps must differ only in positions and orientations."
+        );
+    }
+
+    #[test]
+    fn test_write() {
+        let rotation = UnitQuaternion::from_scaled_axis(vector![0.1, -0.2, 0.3]);
+        let tetrahedron = TETRAHEDRON.map(Vector3::from);
+        let sources = SourceAssembly::default()
+            .with(CuboidMagnet::new(
+                [0.01, 0.0, 0.0],
+                rotation,
+                [0.0, 0.0, 1.0],
+                [0.01, 0.02, 0.03],
+            ))
+            .with(CylinderMagnet::new(
+                [0.0; 3],
+                rotation,
+                [0.0, 1.0, 1.0],
+                0.01,
+                0.005,
+            ))
+            .with(SphereMagnet::new([0.0; 3], rotation, [1.0, 0.0, 0.0], 0.01))
+            .with(Dipole::new([0.0, 0.0, 0.1], rotation, [0.0, 0.0, 0.5]))
+            .with(TetrahedronMagnet::new(
+                [0.0; 3],
+                rotation,
+                [0.0, 1.0, 0.0],
+                tetrahedron,
+            ))
+            .with(SourceAssembly::new(
+                point![0.0, 0.0, 0.05],
+                rotation,
+                [SourceComponent::from(CircularCurrent::new(
+                    [0.0, 0.0, 0.05],
+                    rotation,
+                    0.02,
+                    3.0,
+                ))],
+            ));
+        let json = write(&sources);
+        assert!(json.contains(r#""type": "Cuboid""#));
+        let again = read(&json).unwrap();
+        let point = point![0.02, -0.01, 0.03];
+        assert_relative_eq!(
+            again.compute_B(point),
+            sources.compute_B(point),
+            epsilon = 1e-15
+        );
+        assert_eq!(again.iter().count(), sources.iter().count());
+
+        // Prisms become triangular meshes, and tetrahedral meshes collections of tetrahedra
+        let prism = PrismMagnet::new(
+            [0.0, 0.01, 0.0],
+            rotation,
+            [0.3, 0.0, 1.0],
+            0.01,
+            Polygon::new([vector![0.0, 0.0], vector![0.01, 0.0], vector![0.0, 0.01]]),
+        );
+        let tet_mesh = TetMeshMagnet::new(
+            [0.0, 0.0, 0.02],
+            rotation,
+            vec![vector![0.0, 0.0, 1.0]],
+            TetMesh::new(tetrahedron, [[0, 1, 2, 3]]),
+        );
+        let sources = SourceAssembly::default().with(prism).with(tet_mesh);
+        let json = write(&sources);
+        assert!(json.contains(r#""type": "TriangularMesh""#));
+        assert!(json.contains(r#""type": "Tetrahedron""#));
+        let again = read(&json).unwrap();
+        for point in [point![0.02, -0.01, 0.03], point![-0.01, 0.0, 0.0]] {
+            let expected = sources.compute_B(point);
+            assert_relative_eq!(
+                again.compute_B(point),
+                expected,
+                epsilon = 1e-12 * expected.norm()
+            );
+        }
+
+        let sources = SourceAssembly::default()
+            .with(Dipole::default())
+            .with(SourceAssembly::default().with(TriangleCurrent::<f64>::default()));
+        let mut out = Vec::new();
+        let result = write_json(&mut out, &sources);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "/1/children/0: TriangleCurrent has no Magpylib equivalent."
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        for (json, expected) in [
+            (
+                r#"[{"type": "Cylinder", "polarization": [0, 0, 1]}]"#,
+                "/0: Cylinder needs `dimension`.",
+            ),
+            (
+                r#"[{"type": "Dipole", "moment": [1, 0, 0], "current": 1}]"#,
+                "/0: `current` does not apply to Dipole.",
+            ),
+            (
+                r#"[{"type": "Collection", "children": [{"type": "Sphere", "diameter": -1, "polarization": [0, 0, 1]}]}]"#,
+                "/0/children/0: `diameter` must be positive.",
+            ),
+            (
+                r#"[{"type": "Sphere", "diameter": 1}]"#,
+                "/0: Sphere needs a `polarization` or a `magnetization`.",
+            ),
+            (
+                r#"[{"type": "Sphere", "diameter": 1, "polarization": [0, 0, 1], "magnetization": [0, 0, 1]}]"#,
+                "/0: `polarization` and `magnetization` do not match.",
+            ),
+            (
+                r#"[{"type": "Cylinder", "dimension": [1, 1, 1], "polarization": [0, 0, 1]}]"#,
+                "/0: Cylinder needs 2 `dimension` values.",
+            ),
+            (
+                r#"[{"type": "CylinderSegment", "dimension": [0.02, 0.01, 1, 0, 90], "polarization": [0, 0, 1]}]"#,
+                "/0: CylinderSegment needs 0 <= r1 < r2.",
+            ),
+            (
+                r#"[{"type": "CylinderSegment", "dimension": [0, 0.01, 1, 0, 400], "polarization": [0, 0, 1]}]"#,
+                "/0: CylinderSegment needs phi1 < phi2 <= phi1 + 360.",
+            ),
+            (
+                r#"[{"type": "Triangle", "vertices": [[0, 0, 0]], "polarization": [0, 0, 1]}]"#,
+                "/0: Triangle needs 3 `vertices`.",
+            ),
+            (
+                r#"[{"type": "TriangularMesh", "vertices": [[0, 0, 0]], "faces": [[0, 1, 2]], "polarization": [0, 0, 1]}]"#,
+                "/0: Face vertex index out of range.",
+            ),
+            (
+                r#"[{"type": "TriangularMesh", "vertices": [[0, 0, 0], [1, 0, 0], [0, 1, 0]], "faces": [[0, 1, 2]], "polarization": [0, 0, 1]}]"#,
+                "/0: Invalid mesh: Open edges.",
+            ),
+            (
+                r#"[{"type": "Polyline", "vertices": [[0, 0, 0]], "current": 1}]"#,
+                "/0: Polyline needs at least 2 `vertices`.",
+            ),
+            (
+                r#"[{"type": "Dipole", "moment": [1, 0, 0], "position": [[0, 0, 0], [1, 0, 0]], "orientation": [[0, 0, 0], [0, 0, 1], [0, 1, 0]]}]"#,
+                "/0: `position` and `orientation` paths must have the same length.",
+            ),
+            (
+                r#"[{"type": "Dipole", "moment": [1, 0, 0], "position": []}]"#,
+                "/0: `position` and `orientation` must not be empty.",
+            ),
+        ] {
+            assert_eq!(error(json), expected, "{json}");
+        }
+
+        let message = error("[\n  {\"type\": \"Sensor\"}\n]");
+        assert!(message.starts_with("unknown variant `Sensor`"), "{message}");
+        assert!(message.ends_with("at line 2 column 19"), "{message}");
+    }
+}
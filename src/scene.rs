@@ -0,0 +1,1586 @@
+/*
+ * Magba is licensed under The 3-Clause BSD, see LICENSE.
+ * Copyright 2025 Sira Pornsiriprasert <code@psira.me>
+ */
+
+//! Scene files describing magnets, currents, and sensors in TOML or JSON.
+//!
+//! A [SceneFile] builds a [SourceAssembly] and an [ObserverAssembly] from a declarative
+//! description, so that setups can be defined without writing Rust. Errors point to the
+//! line and column of the offending entry.
+//!
+//! # Format
+//!
+//! The top level has four optional keys.
+//!
+//! | Key | Content |
+//! |-----|---------|
+//! | `units` | Units of the numbers in the file, see [Units]. SI by default. |
+//! | `materials` | Named materials with a `remanence` in field units. |
+//! | `sources` | List of sources, each with a `type`. |
+//! | `sensors` | List of sensors, each with a `type`. |
+//!
+//! Every source and sensor takes an optional `position` and `rotation` relative to its
+//! parent. A rotation is a table with one of
+//! - `quaternion = [x, y, z, w]`, normalized on load,
+//! - `axis = [x, y, z]` and `angle`,
+//! - `euler = [a, b, c]` and an optional `sequence` of three axes, `"xyz"` by default.
+//!   Lowercase axes are extrinsic (fixed frame) and uppercase axes are intrinsic (rotating
+//!   frame), as in SciPy.
+//!
+//! Magnets take either a `polarization` vector or a `material` with a `direction`, which
+//! defaults to the z-axis. The other fields depend on the `type`.
+//!
+//! | Type | Fields |
+//! |------|--------|
+//! | `CuboidMagnet` | `dimensions` |
+//! | `CylinderMagnet` | `diameter`, `height` |
+//! | `SphereMagnet` | `diameter` |
+//! | `PrismMagnet` | `vertices` (2D), `height` |
+//! | `TetrahedronMagnet` | `vertices` (4 points) |
+//! | `TriangleMagnet` | `vertices` (3 points) |
+//! | `MeshMagnet` | `mesh` (STL, OBJ, or PLY file), `mesh_unit`, `repair`, `bvh_theta` |
+//! | `TetMeshMagnet` | `mesh` (Gmsh `.msh` file), `mesh_unit` |
+//! | `Dipole` | `moment` (current × length²) |
+//! | `CircularCurrent` | `diameter`, `current` |
+//! | `PathCurrent` | `vertices`, `current` |
+//! | `TriangleCurrent` | `vertices` (3 points), `current_density` (current / length) |
+//! | `Assembly` | `components`, a nested list of sources |
+//! | `LinearHallSensor` | `axis`, `sensitivity` (V / field unit), `supply_voltage` (V) |
+//! | `HallSwitch` | `axis`, `b_op` |
+//! | `HallLatch` | `axis`, `b_op`, `b_rp` |
+//!
+//! Mesh paths are relative to the scene file, and the mesh coordinates are in the length
+//! unit of the scene unless `mesh_unit` is given. Reading a mesh format needs the matching
+//! `io-stl`, `io-obj`, `io-ply`, or `io-msh` feature. Sensor `axis` defaults to the z-axis.
+//!
+//! # Examples
+//!
+//! ```
+//! # use magba::scene::SceneFile;
+//! # use magba::prelude::*;
+//! let toml = r#"
+//! [units]
+//! length = "mm"
+//! angle = "deg"
+//! field = "mT"
+//!
+//! [materials.N42]
+//! remanence = 1300
+//!
+//! [[sources]]
+//! type = "CylinderMagnet"
+//! material = "N42"
+//! diameter = 10
+//! height = 5
+//!
+//! [[sources]]
+//! type = "Assembly"
+//! position = [30, 0, 0]
+//! rotation = { axis = [0, 0, 1], angle = 90 }
+//!
+//! [[sources.components]]
+//! type = "CuboidMagnet"
+//! position = [5, 0, 0]
+//! polarization = [0, 0, 1000]
+//! dimensions = [4, 4, 4]
+//!
+//! [[sensors]]
+//! type = "LinearHallSensor"
+//! position = [0, 0, 10]
+//! sensitivity = 0.05
+//! supply_voltage = 5
+//! "#;
+//!
+//! let scene = SceneFile::<f64>::from_toml(toml, ".").unwrap();
+//! let SourceComponent::Assembly(assembly) = &scene.sources()[1] else { unreachable!() };
+//! let cuboid = assembly[0].pose().position();
+//! assert!((cuboid.coords - nalgebra::vector![0.03, 0.005, 0.0]).norm() < 1e-12);
+//!
+//! let SensorOutput::Scalar(voltage) = scene.sensors()[0].read(scene.sources()) else {
+//!     unreachable!()
+//! };
+//! assert!(voltage > 2.5);
+//! ```
+//!
+//! The same structure in JSON:
+//!
+//! ```
+//! # use magba::scene::SceneFile;
+//! let json = r#"{
+//!     "units": { "length": "mm" },
+//!     "sources": [
+//!         { "type": "SphereMagnet", "polarization": [0, 0, 1], "diameter": 5 }
+//!     ]
+//! }"#;
+//! let scene = SceneFile::<f64>::from_json(json, ".").unwrap();
+//! assert_eq!(scene.sources().iter().count(), 1);
+//! ```
+
+use core::marker::PhantomData;
+use std::{
+    cell::RefCell,
+    collections::BTreeMap,
+    fs,
+    io::{self, Error, ErrorKind},
+    path::{Path, PathBuf},
+};
+
+use getset::Getters;
+use nalgebra::{Point3, Unit, UnitQuaternion, Vector2, Vector3};
+use serde::{
+    Deserialize,
+    de::{self, DeserializeOwned, IgnoredAny},
+};
+
+#[cfg(any(
+    feature = "io-stl",
+    feature = "io-obj",
+    feature = "io-ply",
+    feature = "io-msh"
+))]
+use crate::{
+    base::mesh::{MeshReadOptions, RepairOptions, TetMesh, TriMesh},
+    magnets::{MeshMagnet, TetMeshMagnet},
+};
+use crate::{
+    base::{Float, Pose, polygon::Polygon},
+    collections::{ObserverAssembly, ObserverComponent, SourceAssembly, SourceComponent},
+    currents::{CircularCurrent, PathCurrent, TriangleCurrent},
+    magnets::{
+        CuboidMagnet, CylinderMagnet, Dipole, PrismMagnet, SphereMagnet, TetrahedronMagnet,
+        TriangleMagnet,
+    },
+    sensors::hall_effect::{HallLatch, HallSwitch, LinearHallSensor},
+};
+
+// MARK: Units
+
+/// Length units of a scene file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub enum LengthUnit {
+    #[default]
+    #[serde(rename = "m")]
+    Meter,
+    #[serde(rename = "cm")]
+    Centimeter,
+    #[serde(rename = "mm")]
+    Millimeter,
+    #[serde(rename = "um")]
+    Micrometer,
+    #[serde(rename = "in")]
+    Inch,
+}
+
+impl LengthUnit {
+    /// Length of one unit in meters.
+    pub fn in_meters(self) -> f64 {
+        match self {
+            Self::Meter => 1.0,
+            Self::Centimeter => 1e-2,
+            Self::Millimeter => 1e-3,
+            Self::Micrometer => 1e-6,
+            Self::Inch => 0.0254,
+        }
+    }
+}
+
+/// Angle units of a scene file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub enum AngleUnit {
+    #[default]
+    #[serde(rename = "rad")]
+    Radian,
+    #[serde(rename = "deg")]
+    Degree,
+}
+
+impl AngleUnit {
+    /// Angle of one unit in radians.
+    pub fn in_radians(self) -> f64 {
+        match self {
+            Self::Radian => 1.0,
+            Self::Degree => core::f64::consts::PI / 180.0,
+        }
+    }
+}
+
+/// Magnetic flux density units of a scene file, used for polarizations, remanences, and
+/// sensor thresholds.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub enum FieldUnit {
+    #[default]
+    #[serde(rename = "T")]
+    Tesla,
+    #[serde(rename = "mT")]
+    Millitesla,
+    #[serde(rename = "uT")]
+    Microtesla,
+    #[serde(rename = "G")]
+    Gauss,
+}
+
+impl FieldUnit {
+    /// Flux density of one unit in teslas.
+    pub fn in_teslas(self) -> f64 {
+        match self {
+            Self::Tesla => 1.0,
+            Self::Millitesla => 1e-3,
+            Self::Microtesla => 1e-6,
+            Self::Gauss => 1e-4,
+        }
+    }
+}
+
+/// Current units of a scene file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub enum CurrentUnit {
+    #[default]
+    #[serde(rename = "A")]
+    Ampere,
+    #[serde(rename = "mA")]
+    Milliampere,
+}
+
+impl CurrentUnit {
+    /// Current of one unit in amperes.
+    pub fn in_amperes(self) -> f64 {
+        match self {
+            Self::Ampere => 1.0,
+            Self::Milliampere => 1e-3,
+        }
+    }
+}
+
+/// Units of the numbers in a scene file, given by the `units` table.
+///
+/// | Key | Values | Default |
+/// |-----|--------|---------|
+/// | `length` | `"m"`, `"cm"`, `"mm"`, `"um"`, `"in"` | `"m"` |
+/// | `angle` | `"rad"`, `"deg"` | `"rad"` |
+/// | `field` | `"T"`, `"mT"`, `"uT"`, `"G"` | `"T"` |
+/// | `current` | `"A"`, `"mA"` | `"A"` |
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Units {
+    pub length: LengthUnit,
+    pub angle: AngleUnit,
+    pub field: FieldUnit,
+    pub current: CurrentUnit,
+}
+
+// MARK: Scene file
+
+/// Sources and sensors loaded from a scene file. See the [module documentation](self) for
+/// the format.
+#[derive(Debug, Clone, Getters)]
+#[getset(get = "pub")]
+pub struct SceneFile<T: Float = f64> {
+    /// Units declared by the file.
+    units: Units,
+    /// Top-level sources, at their global poses.
+    sources: SourceAssembly<T>,
+    /// Sensors, at their global poses.
+    sensors: ObserverAssembly<T>,
+}
+
+impl<T: Float> SceneFile<T> {
+    /// Reads a scene file, choosing the format by the `.toml` or `.json` extension.
+    ///
+    /// Mesh paths are resolved relative to the directory of the file.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)?;
+        let base_dir = path.parent().unwrap_or(Path::new(""));
+        match path.extension().and_then(|e| e.to_str()) {
+            Some(e) if e.eq_ignore_ascii_case("toml") => Self::from_toml(&text, base_dir),
+            Some(e) if e.eq_ignore_ascii_case("json") => Self::from_json(&text, base_dir),
+            _ => Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Scene file must have a `.toml` or `.json` extension.",
+            )),
+        }
+    }
+
+    /// Parses a scene from TOML, resolving mesh paths relative to `base_dir`.
+    pub fn from_toml(text: &str, base_dir: impl AsRef<Path>) -> io::Result<Self> {
+        Self::parse::<Toml>(text, base_dir.as_ref())
+    }
+
+    /// Parses a scene from JSON, resolving mesh paths relative to `base_dir`.
+    pub fn from_json(text: &str, base_dir: impl AsRef<Path>) -> io::Result<Self> {
+        Self::parse::<Json>(text, base_dir.as_ref())
+    }
+
+    /// Splits the scene into its sources and sensors.
+    pub fn into_parts(self) -> (SourceAssembly<T>, ObserverAssembly<T>) {
+        (self.sources, self.sensors)
+    }
+
+    fn parse<F: Format>(text: &str, base_dir: &Path) -> io::Result<Self> {
+        // Units and materials first, as the entries depend on them
+        let Header { units, materials } = F::parse(text)?;
+        let _context = ContextGuard::set(Context {
+            units,
+            materials,
+            base_dir: base_dir.to_path_buf(),
+        });
+        let Document::<T> {
+            sources, sensors, ..
+        } = F::parse(text)?;
+
+        let origin = Point3::origin();
+        let identity = UnitQuaternion::identity();
+        Ok(Self {
+            units,
+            sources: SourceAssembly::new(origin, identity, sources.into_iter().map(|e| e.0)),
+            sensors: ObserverAssembly::new(origin, identity, sensors.into_iter().map(|e| e.0)),
+        })
+    }
+}
+
+fn invalid_data(line: usize, column: usize, msg: impl core::fmt::Display) -> Error {
+    Error::new(
+        ErrorKind::InvalidData,
+        format!("line {line}, column {column}: {msg}"),
+    )
+}
+
+trait Format {
+    fn parse<D: DeserializeOwned>(text: &str) -> io::Result<D>;
+}
+
+struct Toml;
+
+impl Format for Toml {
+    fn parse<D: DeserializeOwned>(text: &str) -> io::Result<D> {
+        toml::from_str(text).map_err(|e| match e.span() {
+            Some(span) => {
+                let before = &text[..span.start];
+                let line = before.matches('\n').count() + 1;
+                let column = before.rsplit('\n').next().unwrap_or("").chars().count() + 1;
+                invalid_data(line, column, e.message())
+            }
+            None => Error::new(ErrorKind::InvalidData, e.message()),
+        })
+    }
+}
+
+struct Json;
+
+impl Format for Json {
+    fn parse<D: DeserializeOwned>(text: &str) -> io::Result<D> {
+        serde_json::from_str(text).map_err(|e| {
+            let msg = e.to_string();
+            let location = format!(" at line {} column {}", e.line(), e.column());
+            match msg.strip_suffix(&location) {
+                Some(msg) if e.line() > 0 => invalid_data(e.line(), e.column(), msg),
+                _ => Error::new(ErrorKind::InvalidData, msg),
+            }
+        })
+    }
+}
+
+// MARK: Context
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Material {
+    /// Remanence in field units.
+    remanence: f64,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct Header {
+    units: Units,
+    materials: BTreeMap<String, Material>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields, bound = "T: Float")]
+struct Document<T: Float> {
+    #[serde(default)]
+    #[allow(dead_code)]
+    units: IgnoredAny,
+    #[serde(default)]
+    #[allow(dead_code)]
+    materials: IgnoredAny,
+    #[serde(default)]
+    sources: Vec<SourceEntry<T>>,
+    #[serde(default)]
+    sensors: Vec<SensorEntry<T>>,
+}
+
+/// Units, materials, and mesh directory of the scene being parsed.
+struct Context {
+    units: Units,
+    materials: BTreeMap<String, Material>,
+    #[cfg_attr(
+        not(any(
+            feature = "io-stl",
+            feature = "io-obj",
+            feature = "io-ply",
+            feature = "io-msh"
+        )),
+        allow(dead_code)
+    )]
+    base_dir: PathBuf,
+}
+
+thread_local! {
+    static CONTEXT: RefCell<Option<Context>> = const { RefCell::new(None) };
+}
+
+/// Sets the [Context] for the entries until dropped.
+struct ContextGuard(Option<Context>);
+
+impl ContextGuard {
+    fn set(context: Context) -> Self {
+        Self(CONTEXT.replace(Some(context)))
+    }
+}
+
+impl Drop for ContextGuard {
+    fn drop(&mut self) {
+        CONTEXT.set(self.0.take());
+    }
+}
+
+fn with_context<R>(f: impl FnOnce(&Context) -> R) -> R {
+    CONTEXT.with_borrow(|context| f(context.as_ref().expect("Scene context is not set.")))
+}
+
+/// Deserializes a table into `S` and builds the entry from it.
+///
+/// The build runs within `deserializer`, so that the format reports the location of this
+/// entry instead of its parent.
+fn build_entry<'de, D, S, V>(
+    deserializer: D,
+    build: impl FnOnce(S) -> Result<V, String>,
+) -> Result<V, D::Error>
+where
+    D: de::Deserializer<'de>,
+    S: Deserialize<'de>,
+{
+    struct EntryVisitor<S, F>(F, PhantomData<S>);
+
+    impl<'de, S, V, F> de::Visitor<'de> for EntryVisitor<S, F>
+    where
+        S: Deserialize<'de>,
+        F: FnOnce(S) -> Result<V, String>,
+    {
+        type Value = V;
+
+        fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+            formatter.write_str("a table")
+        }
+
+        fn visit_map<A: de::MapAccess<'de>>(self, map: A) -> Result<V, A::Error> {
+            let spec = S::deserialize(de::value::MapAccessDeserializer::new(map))?;
+            (self.0)(spec).map_err(de::Error::custom)
+        }
+    }
+
+    deserializer.deserialize_map(EntryVisitor(build, PhantomData))
+}
+
+fn cast<T: Float>(value: f64) -> T {
+    T::from_f64(value).unwrap()
+}
+
+fn vector<T: Float>(v: [f64; 3], scale: f64) -> Vector3<T> {
+    Vector3::from(v.map(|c| cast(c * scale)))
+}
+
+fn positive(name: &str, value: f64) -> Result<f64, String> {
+    if value > 0.0 && value.is_finite() {
+        Ok(value)
+    } else {
+        Err(format!("`{name}` must be positive."))
+    }
+}
+
+fn direction(name: &str, v: [f64; 3]) -> Result<Vector3<f64>, String> {
+    Unit::try_new(Vector3::from(v), 0.0)
+        .filter(|axis| axis.iter().all(|c| c.is_finite()))
+        .map(Unit::into_inner)
+        .ok_or_else(|| format!("`{name}` must be a non-zero vector."))
+}
+
+impl Context {
+    fn length<T: Float>(&self, value: f64) -> T {
+        cast(value * self.units.length.in_meters())
+    }
+
+    fn point<T: Float>(&self, v: Option<[f64; 3]>) -> Point3<T> {
+        vector(v.unwrap_or_default(), self.units.length.in_meters()).into()
+    }
+
+    fn field(&self) -> f64 {
+        self.units.field.in_teslas()
+    }
+
+    fn current(&self) -> f64 {
+        self.units.current.in_amperes()
+    }
+}
+
+// MARK: Rotation
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RotationSpec {
+    quaternion: Option<[f64; 4]>,
+    axis: Option<[f64; 3]>,
+    angle: Option<f64>,
+    euler: Option<[f64; 3]>,
+    sequence: Option<String>,
+}
+
+impl RotationSpec {
+    fn build<T: Float>(&self, context: &Context) -> Result<UnitQuaternion<T>, String> {
+        let scale = context.units.angle.in_radians();
+        let rotation = match *self {
+            Self {
+                quaternion: Some([x, y, z, w]),
+                axis: None,
+                angle: None,
+                euler: None,
+                sequence: None,
+            } => {
+                let q = nalgebra::Quaternion::new(w, x, y, z);
+                Unit::try_new(q, 0.0)
+                    .filter(|q| q.coords.iter().all(|c| c.is_finite()))
+                    .ok_or("`quaternion` must be non-zero.")?
+            }
+            Self {
+                quaternion: None,
+                axis: Some(axis),
+                angle: Some(angle),
+                euler: None,
+                sequence: None,
+            } => {
+                let axis = Unit::new_unchecked(direction("axis", axis)?);
+                UnitQuaternion::from_axis_angle(&axis, angle * scale)
+            }
+            Self {
+                quaternion: None,
+                axis: None,
+                angle: None,
+                euler: Some(angles),
+                ref sequence,
+            } => euler(
+                angles.map(|a| a * scale),
+                sequence.as_deref().unwrap_or("xyz"),
+            )?,
+            _ => {
+                return Err(
+                    "Rotation needs exactly one of `quaternion`, `axis` with `angle`, or `euler`."
+                        .into(),
+                );
+            }
+        };
+        Ok(nalgebra::convert(rotation))
+    }
+}
+
+/// Rotation from Euler angles about three axes, extrinsic for lowercase and intrinsic for
+/// uppercase axis names.
+fn euler(angles: [f64; 3], sequence: &str) -> Result<UnitQuaternion<f64>, String> {
+    let error = || format!("Invalid Euler sequence `{sequence}`.");
+    let axes: Vec<char> = sequence.chars().collect();
+    let intrinsic = axes.iter().all(|c| c.is_ascii_uppercase());
+    if axes.len() != 3 || !(intrinsic || axes.iter().all(|c| c.is_ascii_lowercase())) {
+        return Err(error());
+    }
+
+    let mut rotation = UnitQuaternion::identity();
+    for (axis, angle) in axes.into_iter().zip(angles) {
+        let axis = match axis.to_ascii_lowercase() {
+            'x' => Vector3::x_axis(),
+            'y' => Vector3::y_axis(),
+            'z' => Vector3::z_axis(),
+            _ => return Err(error()),
+        };
+        let step = UnitQuaternion::from_axis_angle(&axis, angle);
+        rotation = if intrinsic {
+            rotation * step
+        } else {
+            step * rotation
+        };
+    }
+    Ok(rotation)
+}
+
+// MARK: Sources
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+enum SourceKind {
+    CuboidMagnet,
+    CylinderMagnet,
+    SphereMagnet,
+    PrismMagnet,
+    TetrahedronMagnet,
+    TriangleMagnet,
+    MeshMagnet,
+    TetMeshMagnet,
+    Dipole,
+    CircularCurrent,
+    PathCurrent,
+    TriangleCurrent,
+    Assembly,
+}
+
+impl SourceKind {
+    /// Fields besides `type`, `position`, and `rotation`.
+    fn fields(self) -> &'static [&'static str] {
+        match self {
+            Self::CuboidMagnet => &["polarization", "material", "direction", "dimensions"],
+            Self::CylinderMagnet => &[
+                "polarization",
+                "material",
+                "direction",
+                "diameter",
+                "height",
+            ],
+            Self::SphereMagnet => &["polarization", "material", "direction", "diameter"],
+            Self::PrismMagnet => &[
+                "polarization",
+                "material",
+                "direction",
+                "vertices",
+                "height",
+            ],
+            Self::TetrahedronMagnet | Self::TriangleMagnet => {
+                &["polarization", "material", "direction", "vertices"]
+            }
+            Self::MeshMagnet => &[
+                "polarization",
+                "material",
+                "direction",
+                "mesh",
+                "mesh_unit",
+                "repair",
+                "bvh_theta",
+            ],
+            Self::TetMeshMagnet => &["polarization", "material", "direction", "mesh", "mesh_unit"],
+            Self::Dipole => &["moment"],
+            Self::CircularCurrent => &["diameter", "current"],
+            Self::PathCurrent => &["vertices", "current"],
+            Self::TriangleCurrent => &["vertices", "current_density"],
+            Self::Assembly => &["components"],
+        }
+    }
+}
+
+/// Rejects the `present` fields that are not `allowed` for `kind`.
+fn check_fields(
+    kind: impl core::fmt::Debug,
+    allowed: &[&str],
+    present: &[(&str, bool)],
+) -> Result<(), String> {
+    match present
+        .iter()
+        .find(|(name, set)| *set && !allowed.contains(name))
+    {
+        Some((name, _)) => Err(format!("`{name}` does not apply to {kind:?}.")),
+        None => Ok(()),
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields, bound = "T: Float")]
+struct SourceSpec<T: Float> {
+    #[serde(rename = "type")]
+    kind: SourceKind,
+    position: Option<[f64; 3]>,
+    rotation: Option<RotationSpec>,
+    polarization: Option<[f64; 3]>,
+    material: Option<String>,
+    direction: Option<[f64; 3]>,
+    dimensions: Option<[f64; 3]>,
+    diameter: Option<f64>,
+    height: Option<f64>,
+    vertices: Option<Vec<Vec<f64>>>,
+    mesh: Option<PathBuf>,
+    mesh_unit: Option<LengthUnit>,
+    repair: Option<bool>,
+    bvh_theta: Option<f64>,
+    moment: Option<[f64; 3]>,
+    current: Option<f64>,
+    current_density: Option<[f64; 3]>,
+    components: Option<Vec<SourceEntry<T>>>,
+}
+
+/// Source built while deserializing, so that errors carry the location of the entry.
+struct SourceEntry<T: Float>(SourceComponent<T>);
+
+impl<'de, T: Float> Deserialize<'de> for SourceEntry<T> {
+    fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        build_entry(deserializer, |spec: SourceSpec<T>| {
+            with_context(|context| spec.build(context)).map(Self)
+        })
+    }
+}
+
+impl<T: Float> SourceSpec<T> {
+    fn check_fields(&self) -> Result<(), String> {
+        let present = [
+            ("polarization", self.polarization.is_some()),
+            ("material", self.material.is_some()),
+            ("direction", self.direction.is_some()),
+            ("dimensions", self.dimensions.is_some()),
+            ("diameter", self.diameter.is_some()),
+            ("height", self.height.is_some()),
+            ("vertices", self.vertices.is_some()),
+            ("mesh", self.mesh.is_some()),
+            ("mesh_unit", self.mesh_unit.is_some()),
+            ("repair", self.repair.is_some()),
+            ("bvh_theta", self.bvh_theta.is_some()),
+            ("moment", self.moment.is_some()),
+            ("current", self.current.is_some()),
+            ("current_density", self.current_density.is_some()),
+            ("components", self.components.is_some()),
+        ];
+        check_fields(self.kind, self.kind.fields(), &present)
+    }
+
+    fn required<V>(&self, name: &str, value: Option<V>) -> Result<V, String> {
+        value.ok_or_else(|| format!("{:?} needs `{name}`.", self.kind))
+    }
+
+    /// Required positive length.
+    fn size(&self, context: &Context, name: &str, value: Option<f64>) -> Result<T, String> {
+        Ok(context.length(positive(name, self.required(name, value)?)?))
+    }
+
+    fn polarization(&self, context: &Context) -> Result<Vector3<T>, String> {
+        match (self.polarization, &self.material) {
+            (Some(_), Some(_)) => Err("Give either `polarization` or `material`, not both.".into()),
+            (Some(_), None) if self.direction.is_some() => {
+                Err("`direction` needs a `material`.".into())
+            }
+            (Some(polarization), None) => Ok(vector(polarization, context.field())),
+            (None, Some(name)) => {
+                let material = context
+                    .materials
+                    .get(name)
+                    .ok_or_else(|| format!("Unknown material `{name}`."))?;
+                let direction = direction("direction", self.direction.unwrap_or([0.0, 0.0, 1.0]))?;
+                let polarization = direction * material.remanence * context.field();
+                Ok(vector(polarization.into(), 1.0))
+            }
+            (None, None) => Err(format!(
+                "{:?} needs a `polarization` or a `material`.",
+                self.kind
+            )),
+        }
+    }
+
+    fn points<const N: usize>(&self, context: &Context) -> Result<Vec<[T; N]>, String> {
+        let vertices = self.required("vertices", self.vertices.as_ref())?;
+        vertices
+            .iter()
+            .map(|v| {
+                let v: [f64; N] = v
+                    .as_slice()
+                    .try_into()
+                    .map_err(|_| format!("Each vertex must have {N} coordinates."))?;
+                Ok(v.map(|c| context.length(c)))
+            })
+            .collect()
+    }
+
+    fn vertices<const N: usize>(&self, context: &Context) -> Result<[Vector3<T>; N], String> {
+        let vertices: Vec<Vector3<T>> = self
+            .points::<3>(context)?
+            .into_iter()
+            .map(Vector3::from)
+            .collect();
+        vertices
+            .try_into()
+            .map_err(|_| format!("{:?} needs {N} `vertices`.", self.kind))
+    }
+
+    #[cfg(any(
+        feature = "io-stl",
+        feature = "io-obj",
+        feature = "io-ply",
+        feature = "io-msh"
+    ))]
+    fn mesh_options<F: Float>(&self, context: &Context) -> MeshReadOptions<F> {
+        let unit = self.mesh_unit.unwrap_or(context.units.length);
+        let options = MeshReadOptions::default().with_scale(cast(unit.in_meters()));
+        match self.repair {
+            Some(true) => options.with_repair(RepairOptions::default()),
+            _ => options,
+        }
+    }
+
+    /// Resolves the mesh path relative to the scene file.
+    #[cfg(any(
+        feature = "io-stl",
+        feature = "io-obj",
+        feature = "io-ply",
+        feature = "io-msh"
+    ))]
+    fn mesh_path(&self, context: &Context) -> Result<PathBuf, String> {
+        let mesh = self.required("mesh", self.mesh.as_ref())?;
+        Ok(context.base_dir.join(mesh))
+    }
+
+    #[cfg(any(
+        feature = "io-stl",
+        feature = "io-obj",
+        feature = "io-ply",
+        feature = "io-msh"
+    ))]
+    fn mesh_error(&self, error: io::Error) -> String {
+        let mesh = self.mesh.as_deref().unwrap_or(Path::new(""));
+        format!("Cannot read mesh `{}`: {error}", mesh.display())
+    }
+
+    #[cfg(any(
+        feature = "io-stl",
+        feature = "io-obj",
+        feature = "io-ply",
+        feature = "io-msh"
+    ))]
+    fn mesh_magnet(&self, context: &Context, pose: Pose<T>) -> Result<MeshMagnet<T>, String> {
+        let polarization = self.polarization(context)?;
+        let path = self.mesh_path(context)?;
+        let mut mesh =
+            read_tri_mesh(&path, &self.mesh_options(context)).map_err(|e| self.mesh_error(e))?;
+        if let Some(theta) = self.bvh_theta {
+            mesh.build_bvh(cast(positive("bvh_theta", theta)?));
+        }
+        Ok(MeshMagnet::new(
+            pose.position(),
+            pose.orientation(),
+            polarization,
+            mesh,
+        ))
+    }
+
+    #[cfg(any(
+        feature = "io-stl",
+        feature = "io-obj",
+        feature = "io-ply",
+        feature = "io-msh"
+    ))]
+    fn tet_mesh_magnet(
+        &self,
+        context: &Context,
+        pose: Pose<T>,
+    ) -> Result<TetMeshMagnet<T>, String> {
+        let polarization = self.polarization(context)?;
+        let path = self.mesh_path(context)?;
+        let mesh =
+            read_tet_mesh(&path, &self.mesh_options(context)).map_err(|e| self.mesh_error(e))?;
+        let polarizations = vec![polarization; mesh.tetrahedra().len()];
+        Ok(TetMeshMagnet::new(
+            pose.position(),
+            pose.orientation(),
+            polarizations,
+            mesh,
+        ))
+    }
+
+    fn build(self, context: &Context) -> Result<SourceComponent<T>, String> {
+        self.check_fields()?;
+        let position = context.point(self.position);
+        let orientation = match &self.rotation {
+            Some(rotation) => rotation.build(context)?,
+            None => UnitQuaternion::identity(),
+        };
+        let length = context.units.length.in_meters();
+
+        let component: SourceComponent<T> = match self.kind {
+            SourceKind::CuboidMagnet => {
+                let dimensions = self.required("dimensions", self.dimensions)?;
+                if !dimensions.iter().all(|d| (0.0..f64::INFINITY).contains(d)) {
+                    return Err("`dimensions` must be non-negative.".into());
+                }
+                CuboidMagnet::new(
+                    position,
+                    orientation,
+                    self.polarization(context)?,
+                    vector(dimensions, length),
+                )
+                .into()
+            }
+            SourceKind::CylinderMagnet => CylinderMagnet::new(
+                position,
+                orientation,
+                self.polarization(context)?,
+                self.size(context, "diameter", self.diameter)?,
+                self.size(context, "height", self.height)?,
+            )
+            .into(),
+            SourceKind::SphereMagnet => SphereMagnet::new(
+                position,
+                orientation,
+                self.polarization(context)?,
+                self.size(context, "diameter", self.diameter)?,
+            )
+            .into(),
+            SourceKind::PrismMagnet => {
+                let vertices = self.points::<2>(context)?.into_iter().map(Vector2::from);
+                let polygon = Polygon::from_vertices(vertices.collect())?;
+                PrismMagnet::new(
+                    position,
+                    orientation,
+                    self.polarization(context)?,
+                    self.size(context, "height", self.height)?,
+                    polygon,
+                )
+                .into()
+            }
+            SourceKind::TetrahedronMagnet => TetrahedronMagnet::new(
+                position,
+                orientation,
+                self.polarization(context)?,
+                self.vertices::<4>(context)?,
+            )
+            .into(),
+            SourceKind::TriangleMagnet => TriangleMagnet::new(
+                position,
+                orientation,
+                self.polarization(context)?,
+                self.vertices::<3>(context)?,
+            )
+            .into(),
+            #[cfg(any(
+                feature = "io-stl",
+                feature = "io-obj",
+                feature = "io-ply",
+                feature = "io-msh"
+            ))]
+            SourceKind::MeshMagnet => self
+                .mesh_magnet(context, Pose::new(position, orientation))?
+                .into(),
+            #[cfg(any(
+                feature = "io-stl",
+                feature = "io-obj",
+                feature = "io-ply",
+                feature = "io-msh"
+            ))]
+            SourceKind::TetMeshMagnet => self
+                .tet_mesh_magnet(context, Pose::new(position, orientation))?
+                .into(),
+            #[cfg(not(any(
+                feature = "io-stl",
+                feature = "io-obj",
+                feature = "io-ply",
+                feature = "io-msh"
+            )))]
+            SourceKind::MeshMagnet | SourceKind::TetMeshMagnet => {
+                return Err(format!(
+                    "{:?} needs the `io-stl`, `io-obj`, `io-ply`, or `io-msh` feature.",
+                    self.kind
+                ));
+            }
+            SourceKind::Dipole => {
+                let moment = self.required("moment", self.moment)?;
+                Dipole::new(
+                    position,
+                    orientation,
+                    vector(moment, context.current() * length * length),
+                )
+                .into()
+            }
+            SourceKind::CircularCurrent => CircularCurrent::new(
+                position,
+                orientation,
+                self.size(context, "diameter", self.diameter)?,
+                cast::<T>(self.required("current", self.current)? * context.current()),
+            )
+            .into(),
+            SourceKind::PathCurrent => {
+                let vertices: Vec<Vector3<T>> = self
+                    .points::<3>(context)?
+                    .into_iter()
+                    .map(Vector3::from)
+                    .collect();
+                if vertices.len() < 2 {
+                    return Err("PathCurrent needs at least 2 `vertices`.".into());
+                }
+                PathCurrent::new(
+                    position,
+                    orientation,
+                    cast::<T>(self.required("current", self.current)? * context.current()),
+                    vertices,
+                )
+                .into()
+            }
+            SourceKind::TriangleCurrent => {
+                let density = self.required("current_density", self.current_density)?;
+                TriangleCurrent::new(
+                    position,
+                    orientation,
+                    vector(density, context.current() / length),
+                    self.vertices::<3>(context)?,
+                )
+                .into()
+            }
+            SourceKind::Assembly => {
+                // Components are placed relative to the assembly
+                let components = self.components.into_iter().flatten().map(|e| e.0);
+                SourceAssembly::new(Point3::origin(), UnitQuaternion::identity(), components)
+                    .with_pose(Pose::new(position, orientation))
+                    .into()
+            }
+        };
+        Ok(component)
+    }
+}
+
+/// Reads a surface mesh, choosing the format by the file extension.
+///
+/// The mesh is read and validated in `f64`, so that scenes do not need `T: Sum`.
+#[cfg(any(
+    feature = "io-stl",
+    feature = "io-obj",
+    feature = "io-ply",
+    feature = "io-msh"
+))]
+#[allow(unused_variables)]
+fn read_tri_mesh<T: Float>(path: &Path, options: &MeshReadOptions<f64>) -> io::Result<TriMesh<T>> {
+    let open = || fs::File::open(path).map(io::BufReader::new);
+    let mesh: TriMesh<f64> = match extension(path).as_str() {
+        #[cfg(feature = "io-stl")]
+        "stl" => TriMesh::from_stl_with_options(&mut open()?, options),
+        #[cfg(feature = "io-obj")]
+        "obj" => TriMesh::from_obj_with_options(open()?, options),
+        #[cfg(feature = "io-ply")]
+        "ply" => TriMesh::from_ply_with_options(open()?, options),
+        extension => Err(unsupported_mesh(extension)),
+    }?;
+    let vertices = mesh.vertices().iter().map(|v| v.cast());
+    Ok(TriMesh::new_unchecked(
+        vertices,
+        mesh.faces().iter().copied(),
+    ))
+}
+
+/// Reads a tetrahedral mesh, choosing the format by the file extension.
+#[cfg(any(
+    feature = "io-stl",
+    feature = "io-obj",
+    feature = "io-ply",
+    feature = "io-msh"
+))]
+#[allow(unused_variables)]
+fn read_tet_mesh<T: Float>(path: &Path, options: &MeshReadOptions<T>) -> io::Result<TetMesh<T>> {
+    match extension(path).as_str() {
+        #[cfg(feature = "io-msh")]
+        "msh" => TetMesh::from_msh_with_options(io::BufReader::new(fs::File::open(path)?), options),
+        extension => Err(unsupported_mesh(extension)),
+    }
+}
+
+#[cfg(any(
+    feature = "io-stl",
+    feature = "io-obj",
+    feature = "io-ply",
+    feature = "io-msh"
+))]
+fn extension(path: &Path) -> String {
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default();
+    extension.to_ascii_lowercase()
+}
+
+#[cfg(any(
+    feature = "io-stl",
+    feature = "io-obj",
+    feature = "io-ply",
+    feature = "io-msh"
+))]
+fn unsupported_mesh(extension: &str) -> Error {
+    Error::new(
+        ErrorKind::Unsupported,
+        format!(
+            "Unsupported mesh format `.{extension}`. STL, OBJ, PLY, and MSH files need the \
+             `io-stl`, `io-obj`, `io-ply`, and `io-msh` features."
+        ),
+    )
+}
+
+// MARK: Sensors
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+enum SensorKind {
+    LinearHallSensor,
+    HallSwitch,
+    HallLatch,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SensorSpec {
+    #[serde(rename = "type")]
+    kind: SensorKind,
+    position: Option<[f64; 3]>,
+    rotation: Option<RotationSpec>,
+    axis: Option<[f64; 3]>,
+    sensitivity: Option<f64>,
+    supply_voltage: Option<f64>,
+    b_op: Option<f64>,
+    b_rp: Option<f64>,
+}
+
+/// Sensor built while deserializing, so that errors carry the location of the entry.
+struct SensorEntry<T: Float>(ObserverComponent<T>);
+
+impl<'de, T: Float> Deserialize<'de> for SensorEntry<T> {
+    fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        build_entry(deserializer, |spec: SensorSpec| {
+            with_context(|context| spec.build(context)).map(Self)
+        })
+    }
+}
+
+impl SensorSpec {
+    fn required(&self, name: &str, value: Option<f64>) -> Result<f64, String> {
+        value.ok_or_else(|| format!("{:?} needs `{name}`.", self.kind))
+    }
+
+    fn build<T: Float>(self, context: &Context) -> Result<ObserverComponent<T>, String> {
+        let allowed: &[&str] = match self.kind {
+            SensorKind::LinearHallSensor => &["sensitivity", "supply_voltage"],
+            SensorKind::HallSwitch => &["b_op"],
+            SensorKind::HallLatch => &["b_op", "b_rp"],
+        };
+        let present = [
+            ("sensitivity", self.sensitivity.is_some()),
+            ("supply_voltage", self.supply_voltage.is_some()),
+            ("b_op", self.b_op.is_some()),
+            ("b_rp", self.b_rp.is_some()),
+        ];
+        check_fields(self.kind, allowed, &present)?;
+
+        let position: Point3<T> = context.point(self.position);
+        let orientation = match &self.rotation {
+            Some(rotation) => rotation.build(context)?,
+            None => UnitQuaternion::identity(),
+        };
+        let axis: Vector3<T> = vector(
+            direction("axis", self.axis.unwrap_or([0.0, 0.0, 1.0]))?.into(),
+            1.0,
+        );
+        let field = context.field();
+
+        let sensor: ObserverComponent<T> = match self.kind {
+            SensorKind::LinearHallSensor => {
+                let sensitivity = self.required("sensitivity", self.sensitivity)?;
+                let supply = positive(
+                    "supply_voltage",
+                    self.required("supply_voltage", self.supply_voltage)?,
+                )?;
+                LinearHallSensor::new(
+                    position,
+                    orientation,
+                    axis,
+                    cast(sensitivity / field),
+                    cast(supply),
+                )
+                .into()
+            }
+            SensorKind::HallSwitch => {
+                let b_op = self.required("b_op", self.b_op)?;
+                if !(0.0..f64::INFINITY).contains(&b_op) {
+                    return Err("`b_op` must be non-negative.".into());
+                }
+                HallSwitch::new(position, orientation, axis, cast(b_op * field)).into()
+            }
+            SensorKind::HallLatch => {
+                let b_op = self.required("b_op", self.b_op)?;
+                let b_rp = self.required("b_rp", self.b_rp)?;
+                if b_op <= b_rp || !b_op.is_finite() || !b_rp.is_finite() {
+                    return Err("`b_op` must be greater than `b_rp`.".into());
+                }
+                HallLatch::new(
+                    position,
+                    orientation,
+                    axis,
+                    cast(b_op * field),
+                    cast(b_rp * field),
+                )
+                .into()
+            }
+        };
+        Ok(sensor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::f64::consts::PI;
+
+    use super::*;
+    use crate::{
+        base::{Source, Transform},
+        sensors::Sensor,
+    };
+    use approx::assert_relative_eq;
+    use nalgebra::point;
+
+    const SCENE: &str = r#"
+[units]
+length = "mm"
+angle = "deg"
+field = "mT"
+current = "mA"
+
+[materials.N42]
+remanence = 1300
+
+[[sources]]
+type = "CylinderMagnet"
+position = [0, 0, -5]
+material = "N42"
+direction = [2, 0, 0]
+diameter = 10
+height = 5
+
+[[sources]]
+type = "Assembly"
+position = [20, 0, 0]
+rotation = { euler = [0, 0, 90], sequence = "XYZ" }
+
+[[sources.components]]
+type = "CuboidMagnet"
+position = [5, 0, 0]
+rotation = { axis = [1, 0, 0], angle = 45 }
+polarization = [0, 0, 1000]
+dimensions = [2, 4, 6]
+
+[[sources.components]]
+type = "CircularCurrent"
+diameter = 8
+current = 500
+
+[[sources]]
+type = "Dipole"
+position = [0, 20, 0]
+moment = [0, 0, 1000]
+
+[[sensors]]
+type = "HallSwitch"
+position = [0, 0, 5]
+b_op = 10
+
+[[sensors]]
+type = "HallLatch"
+position = [0, 0, 8]
+rotation = { quaternion = [1, 0, 0, 0] }
+b_op = 5
+b_rp = -5
+"#;
+
+    fn expected_sources() -> SourceAssembly {
+        let identity = UnitQuaternion::identity();
+        let cylinder =
+            CylinderMagnet::new([0.0, 0.0, -0.005], identity, [1.3, 0.0, 0.0], 0.01, 0.005);
+        let cuboid = CuboidMagnet::new(
+            [0.005, 0.0, 0.0],
+            UnitQuaternion::from_axis_angle(&Vector3::x_axis(), PI / 4.0),
+            [0.0, 0.0, 1.0],
+            [0.002, 0.004, 0.006],
+        );
+        let coil = CircularCurrent::new([0.0; 3], identity, 0.008, 0.5);
+        let assembly = SourceAssembly::new(
+            Point3::origin(),
+            identity,
+            [SourceComponent::from(cuboid), coil.into()],
+        )
+        .with_pose(Pose::new(
+            point![0.02, 0.0, 0.0],
+            UnitQuaternion::from_axis_angle(&Vector3::z_axis(), PI / 2.0),
+        ));
+        // 1000 mA mm²
+        let dipole = Dipole::new([0.0, 0.02, 0.0], identity, [0.0, 0.0, 1e-6]);
+        SourceAssembly::default()
+            .with(cylinder)
+            .with(assembly)
+            .with(dipole)
+    }
+
+    #[test]
+    fn test_toml_scene() {
+        let scene = SceneFile::<f64>::from_toml(SCENE, ".").unwrap();
+        assert_eq!(scene.units().length, LengthUnit::Millimeter);
+
+        let expected = expected_sources();
+        for point in [
+            point![0.0, 0.0, 0.01],
+            point![0.03, 0.01, 0.002],
+            point![-0.01, 0.02, 0.005],
+        ] {
+            assert_relative_eq!(
+                scene.sources().compute_B(point),
+                expected.compute_B(point),
+                max_relative = 1e-12
+            );
+        }
+        let SourceComponent::Assembly(assembly) = &scene.sources()[1] else {
+            panic!("Expected an assembly.");
+        };
+        assert_relative_eq!(
+            assembly[0].pose().position(),
+            point![0.02, 0.005, 0.0],
+            epsilon = 1e-15
+        );
+
+        let ObserverComponent::Sensor(Sensor::HallSwitch(switch)) = &scene.sensors()[0] else {
+            panic!("Expected a Hall switch.");
+        };
+        assert_relative_eq!(*switch.b_op(), 0.01, max_relative = 1e-12);
+        let ObserverComponent::Sensor(Sensor::HallLatch(latch)) = &scene.sensors()[1] else {
+            panic!("Expected a Hall latch.");
+        };
+        assert_relative_eq!(*latch.b_rp(), -0.005, max_relative = 1e-12);
+        assert_relative_eq!(
+            latch.pose().orientation(),
+            UnitQuaternion::from_axis_angle(&Vector3::x_axis(), PI),
+            epsilon = 1e-15
+        );
+    }
+
+    #[test]
+    fn test_json_scene() {
+        let json = r#"{
+            "units": { "length": "mm", "angle": "deg", "field": "mT", "current": "mA" },
+            "materials": { "N42": { "remanence": 1300 } },
+            "sources": [
+                {
+                    "type": "CylinderMagnet",
+                    "position": [0, 0, -5],
+                    "material": "N42",
+                    "direction": [2, 0, 0],
+                    "diameter": 10,
+                    "height": 5
+                },
+                {
+                    "type": "Assembly",
+                    "position": [20, 0, 0],
+                    "rotation": { "euler": [0, 0, 90], "sequence": "XYZ" },
+                    "components": [
+                        {
+                            "type": "CuboidMagnet",
+                            "position": [5, 0, 0],
+                            "rotation": { "axis": [1, 0, 0], "angle": 45 },
+                            "polarization": [0, 0, 1000],
+                            "dimensions": [2, 4, 6]
+                        },
+                        { "type": "CircularCurrent", "diameter": 8, "current": 500 }
+                    ]
+                },
+                { "type": "Dipole", "position": [0, 20, 0], "moment": [0, 0, 1000] }
+            ],
+            "sensors": [
+                { "type": "HallSwitch", "position": [0, 0, 5], "b_op": 10 },
+                {
+                    "type": "HallLatch",
+                    "position": [0, 0, 8],
+                    "rotation": { "quaternion": [1, 0, 0, 0] },
+                    "b_op": 5,
+                    "b_rp": -5
+                }
+            ]
+        }"#;
+        let from_json = SceneFile::<f64>::from_json(json, ".").unwrap();
+        let from_toml = SceneFile::<f64>::from_toml(SCENE, ".").unwrap();
+        assert_eq!(from_json.sources(), from_toml.sources());
+        assert_eq!(from_json.sensors(), from_toml.sensors());
+    }
+
+    #[test]
+    fn test_rotations() {
+        let rotation = |rotation: &str| {
+            let toml = format!(
+                "units = {{ angle = \"deg\" }}\n\
+                 [[sources]]\n\
+                 type = \"Dipole\"\n\
+                 rotation = {rotation}\n\
+                 moment = [0, 0, 1]\n"
+            );
+            let scene = SceneFile::<f64>::from_toml(&toml, ".").unwrap();
+            scene.sources()[0].pose().orientation()
+        };
+        let rx = UnitQuaternion::from_axis_angle(&Vector3::x_axis(), PI / 2.0);
+        let rz = UnitQuaternion::from_axis_angle(&Vector3::z_axis(), PI / 2.0);
+
+        // Extrinsic rotations apply about the fixed axes, intrinsic about the rotated ones
+        assert_relative_eq!(
+            rotation("{ euler = [90, 0, 90] }"),
+            rz * rx,
+            epsilon = 1e-15
+        );
+        assert_relative_eq!(
+            rotation("{ euler = [90, 0, 90], sequence = \"XYZ\" }"),
+            rx * rz,
+            epsilon = 1e-15
+        );
+        assert_relative_eq!(
+            rotation("{ euler = [90, 90, 0], sequence = \"zxy\" }"),
+            rx * rz,
+            epsilon = 1e-15
+        );
+        assert_relative_eq!(
+            rotation("{ axis = [0, 0, 2], angle = 90 }"),
+            rz,
+            epsilon = 1e-15
+        );
+        let half = (0.5f64).sqrt();
+        assert_relative_eq!(
+            rotation(&format!("{{ quaternion = [{half}, 0, 0, {half}] }}")),
+            rx,
+            epsilon = 1e-15
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = |toml: &str| {
+            SceneFile::<f64>::from_toml(toml, ".")
+                .unwrap_err()
+                .to_string()
+        };
+
+        // Nested entries report their own line
+        let nested = "\
+[materials.N42]
+remanence = 1.3
+
+[[sources]]
+type = \"Assembly\"
+
+[[sources.components]]
+type = \"SphereMagnet\"
+material = \"N42\"
+diameter = 1
+
+[[sources.components]]
+type = \"CylinderMagnet\"
+material = \"N52\"
+diameter = 1
+height = 1
+";
+        assert_eq!(error(nested), "line 12, column 1: Unknown material `N52`.");
+
+        let cases = [
+            (
+                "[[sources]]\ntype = \"SphereMagnet\"\npolarization = [0, 0, 1]\ndiamter = 1\n",
+                "line 4, column 1: unknown field `diamter`",
+            ),
+            (
+                "[[sources]]\ntype = \"Sphere\"\n",
+                "line 2, column 8: unknown variant `Sphere`",
+            ),
+            (
+                "[units]\nlength = \"km\"\n",
+                "line 2, column 10: unknown variant `km`",
+            ),
+            (
+                "[[sources]]\ntype = \"SphereMagnet\"\npolarization = [0, 0, 1]\ndiameter = -1\n",
+                "line 1, column 1: `diameter` must be positive.",
+            ),
+            (
+                "[[sources]]\ntype = \"SphereMagnet\"\npolarization = [0, 0, 1]\n",
+                "line 1, column 1: SphereMagnet needs `diameter`.",
+            ),
+            (
+                "[[sources]]\ntype = \"SphereMagnet\"\ndiameter = 1\n",
+                "line 1, column 1: SphereMagnet needs a `polarization` or a `material`.",
+            ),
+            (
+                "[[sources]]\ntype = \"Dipole\"\nmoment = [0, 0, 1]\nheight = 1\n",
+                "line 1, column 1: `height` does not apply to Dipole.",
+            ),
+            (
+                "[[sources]]\ntype = \"Dipole\"\nmoment = [0, 0, 1]\nrotation = { axis = [0, 0, 1] }\n",
+                "line 1, column 1: Rotation needs exactly one of",
+            ),
+            (
+                "[[sources]]\ntype = \"TriangleMagnet\"\npolarization = [0, 0, 1]\nvertices = [[0, 0, 0], [1, 0, 0]]\n",
+                "line 1, column 1: TriangleMagnet needs 3 `vertices`.",
+            ),
+            (
+                "[[sources]]\ntype = \"PrismMagnet\"\npolarization = [0, 0, 1]\nheight = 1\nvertices = [[0, 0], [1, 0], [2, 0]]\n",
+                "line 1, column 1: Polygon must have non-zero area.",
+            ),
+            (
+                "[[sensors]]\ntype = \"HallLatch\"\nb_op = 1\nb_rp = 2\n",
+                "line 1, column 1: `b_op` must be greater than `b_rp`.",
+            ),
+            (
+                "[[sensors]]\ntype = \"HallSwitch\"\nb_op = 1\nsensitivity = 2\n",
+                "line 1, column 1: `sensitivity` does not apply to HallSwitch.",
+            ),
+        ];
+        for (toml, expected) in cases {
+            let message = error(toml);
+            assert!(message.starts_with(expected), "{message}");
+        }
+
+        let json = "{\n  \"sources\": [\n    {\n      \"type\": \"SphereMagnet\",\n      \"diameter\": 1\n    }\n  ]\n}";
+        let message = SceneFile::<f64>::from_json(json, ".")
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            message,
+            "line 6, column 5: SphereMagnet needs a `polarization` or a `material`."
+        );
+    }
+
+    #[cfg(feature = "io-obj")]
+    #[test]
+    fn test_mesh_reference() {
+        use nalgebra::vector;
+
+        let dir = std::env::temp_dir().join(format!("magba-scene-{}", std::process::id()));
+        fs::create_dir_all(dir.join("parts")).unwrap();
+        let obj = "v -5 -5 -5\nv 5 -5 -5\nv 0 5 -5\nv 0 0 5\nf 1 3 2\nf 1 2 4\nf 2 3 4\nf 1 4 3\n";
+        fs::write(dir.join("parts/tet.obj"), obj).unwrap();
+        let scene = "\
+[[sources]]
+type = \"MeshMagnet\"
+position = [0, 0, 1]
+polarization = [0, 0, 1]
+mesh = \"parts/tet.obj\"
+mesh_unit = \"mm\"
+bvh_theta = 0.3
+
+[[sources]]
+type = \"MeshMagnet\"
+polarization = [0, 0, 1]
+mesh = \"parts/missing.obj\"
+";
+        fs::write(dir.join("scene.toml"), scene).unwrap();
+        let message = SceneFile::<f64>::load(dir.join("scene.toml"))
+            .unwrap_err()
+            .to_string();
+        assert!(
+            message.starts_with("line 9, column 1: Cannot read mesh `parts/missing.obj`"),
+            "{message}"
+        );
+
+        let scene = scene.split("\n\n").next().unwrap();
+        fs::write(dir.join("scene.toml"), scene).unwrap();
+        let scene = SceneFile::<f32>::load(dir.join("scene.toml")).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        let SourceComponent::Magnet(crate::magnets::Magnet::Mesh(magnet)) = &scene.sources()[0]
+        else {
+            panic!("Expected a mesh magnet.");
+        };
+        assert_eq!(magnet.position(), point![0.0, 0.0, 1.0]);
+        assert_eq!(magnet.mesh().vertices()[3], vector![0.0, 0.0, 0.005]);
+        assert!(magnet.mesh().bvh().is_some());
+    }
+}
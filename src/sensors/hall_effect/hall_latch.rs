@@ -10,6 +10,7 @@ use getset::Getters;
 use nalgebra::{Point3, UnitQuaternion, Vector3};
 
 use crate::{
+    Error,
     base::{
         Float, Observer, Pose, SensorOutput, Source, pose::impl_pose_methods,
         transform::impl_transform,
@@ -22,12 +23,14 @@ use crate::{
 /// Outputs a digital reading based on the magnetic operate point (B_OP)
 /// and release point (B_RP) thresholds. Provides hysteresis by maintaining its internal state.
 #[derive(Debug, Getters)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[getset(get = "pub")]
 pub struct HallLatch<T: Float = f64> {
     pose: Pose<T>,
     sensitive_axis: Vector3<T>,
     b_op: T,
     b_rp: T,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_state"))]
     state: AtomicBool,
 }
 
@@ -59,6 +62,12 @@ impl<T: Float> Eq for HallLatch<T> {}
 
 impl<T: Float> HallLatch<T> {
     // MARK: New
+
+    /// Construct a [HallLatch].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `b_op` is not greater than `b_rp`, see [try_new](Self::try_new).
     pub fn new(
         position: impl Into<Point3<T>>,
         orientation: UnitQuaternion<T>,
@@ -66,16 +75,27 @@ impl<T: Float> HallLatch<T> {
         b_op: T,
         b_rp: T,
     ) -> Self {
-        if b_op <= b_rp {
-            panic!("B_OP must be greater than B_RP.");
-        }
-        Self {
+        Self::try_new(position, orientation, sensitive_axis, b_op, b_rp)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Construct a [HallLatch], or return [Error::InvalidSensor] if `b_op` is not greater
+    /// than `b_rp`.
+    pub fn try_new(
+        position: impl Into<Point3<T>>,
+        orientation: UnitQuaternion<T>,
+        sensitive_axis: impl Into<Vector3<T>>,
+        b_op: T,
+        b_rp: T,
+    ) -> Result<Self, Error> {
+        check_thresholds(b_op, b_rp)?;
+        Ok(Self {
             pose: Pose::new(position.into(), orientation),
             sensitive_axis: sensitive_axis.into().normalize(),
             b_op,
             b_rp,
             state: AtomicBool::new(false),
-        }
+        })
     }
 
     impl_pose_methods!();
@@ -124,20 +144,34 @@ impl<T: Float> HallLatch<T> {
         self.sensitive_axis = sensitive_axis.into().normalize();
     }
 
+    /// Set `b_op`, or return [Error::InvalidSensor] if it is not greater than `b_rp`.
+    #[inline]
+    pub fn try_set_b_op(&mut self, b_op: T) -> Result<(), Error> {
+        check_thresholds(b_op, self.b_rp)?;
+        self.b_op = b_op;
+        Ok(())
+    }
+
     #[inline]
     pub fn set_b_op(&mut self, b_op: T) {
-        if b_op <= self.b_rp {
-            panic!("B_OP must be greater than B_RP.");
+        if let Err(error) = self.try_set_b_op(b_op) {
+            panic!("{error}");
         }
-        self.b_op = b_op;
+    }
+
+    /// Set `b_rp`, or return [Error::InvalidSensor] if it is not less than `b_op`.
+    #[inline]
+    pub fn try_set_b_rp(&mut self, b_rp: T) -> Result<(), Error> {
+        check_thresholds(self.b_op, b_rp)?;
+        self.b_rp = b_rp;
+        Ok(())
     }
 
     #[inline]
     pub fn set_b_rp(&mut self, b_rp: T) {
-        if self.b_op <= b_rp {
-            panic!("B_OP must be greater than B_RP.");
+        if let Err(error) = self.try_set_b_rp(b_rp) {
+            panic!("{error}");
         }
-        self.b_rp = b_rp;
     }
 
     // MARK: With setters
@@ -159,6 +193,25 @@ impl<T: Float> HallLatch<T> {
         self.set_b_rp(b_rp);
         self
     }
+
+    #[inline]
+    pub fn try_with_b_op(mut self, b_op: T) -> Result<Self, Error> {
+        self.try_set_b_op(b_op)?;
+        Ok(self)
+    }
+
+    #[inline]
+    pub fn try_with_b_rp(mut self, b_rp: T) -> Result<Self, Error> {
+        self.try_set_b_rp(b_rp)?;
+        Ok(self)
+    }
+}
+
+fn check_thresholds<T: Float>(b_op: T, b_rp: T) -> Result<(), Error> {
+    if b_op <= b_rp {
+        return Err(Error::InvalidSensor("B_OP must be greater than B_RP."));
+    }
+    Ok(())
 }
 
 impl<T: Float> Default for HallLatch<T> {
@@ -173,6 +226,63 @@ impl<T: Float> Default for HallLatch<T> {
     }
 }
 
+// MARK: Serde
+
+/// Omitted fields take their defaults, and the fields are validated as in `new`.
+#[cfg(feature = "serde")]
+fn serialize_state<S: serde::Serializer>(
+    state: &AtomicBool,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_bool(state.load(Ordering::SeqCst))
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: Float + serde::Deserialize<'de>> serde::Deserialize<'de> for HallLatch<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(serde::Deserialize)]
+        #[serde(rename = "HallLatch", default, deny_unknown_fields)]
+        struct Repr<T: Float> {
+            pose: Pose<T>,
+            sensitive_axis: Vector3<T>,
+            b_op: T,
+            b_rp: T,
+            state: bool,
+        }
+
+        impl<T: Float> Default for Repr<T> {
+            fn default() -> Self {
+                let latch = HallLatch::default();
+                Self {
+                    pose: latch.pose,
+                    sensitive_axis: latch.sensitive_axis,
+                    b_op: latch.b_op,
+                    b_rp: latch.b_rp,
+                    state: latch.state.into_inner(),
+                }
+            }
+        }
+
+        let Repr {
+            pose,
+            sensitive_axis,
+            b_op,
+            b_rp,
+            state,
+        } = Repr::deserialize(deserializer)?;
+        if b_op <= b_rp {
+            return Err(serde::de::Error::custom("B_OP must be greater than B_RP."));
+        }
+        Ok(Self {
+            pose,
+            sensitive_axis: sensitive_axis.normalize(),
+            b_op,
+            b_rp,
+            state: AtomicBool::new(state),
+        })
+    }
+}
+
 impl<T: Float> Observer<T> for HallLatch<T> {
     /// Alias of [HallLatch::read_state], returning `SensorOutput::Digital`.
     fn read(&self, source: &dyn Source<T>) -> SensorOutput<T> {
@@ -275,4 +385,22 @@ mod tests {
         let mut sensor = HallLatch::default().with_b_op(0.010).with_b_rp(-0.010);
         sensor.set_b_rp(0.020);
     }
+
+    #[test]
+    fn test_fallible_validation() {
+        let result = HallLatch::try_new(
+            [0.0; 3],
+            UnitQuaternion::identity(),
+            [0.0, 0.0, 1.0],
+            0.01,
+            0.02,
+        );
+        assert!(matches!(result, Err(Error::InvalidSensor(_))));
+
+        let mut sensor = HallLatch::default().with_b_op(0.010).with_b_rp(-0.010);
+        assert!(sensor.try_set_b_rp(0.020).is_err());
+        assert_eq!(*sensor.b_rp(), -0.010);
+        let sensor = sensor.try_with_b_op(0.015).unwrap();
+        assert_eq!(*sensor.b_op(), 0.015);
+    }
 }
@@ -9,6 +9,7 @@ use getset::Getters;
 use nalgebra::{Point3, UnitQuaternion, Vector3};
 
 use crate::{
+    Error,
     base::{
         Float, Observer, Pose, SensorOutput, Source, pose::impl_pose_methods,
         transform::impl_transform,
@@ -21,6 +22,7 @@ use crate::{
 /// Outputs a digital reading solely based on the magnetic operate point (B_OP) threshold.
 /// It is completely stateless because it does not model hysteresis natively.
 #[derive(Clone, Debug, PartialEq, Eq, Getters)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[getset(get = "pub")]
 pub struct HallSwitch<T: Float = f64> {
     pose: Pose<T>,
@@ -32,20 +34,36 @@ impl_transform!(HallSwitch<T> where T: Float);
 
 impl<T: Float> HallSwitch<T> {
     // MARK: New
+
+    /// Construct a [HallSwitch].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `b_op` is negative, see [try_new](Self::try_new).
     pub fn new(
         position: impl Into<Point3<T>>,
         orientation: UnitQuaternion<T>,
         sensitive_axis: impl Into<Vector3<T>>,
         b_op: T,
     ) -> Self {
-        if b_op < T::zero() {
-            panic!("B_OP must be non-negative.");
-        }
-        Self {
+        Self::try_new(position, orientation, sensitive_axis, b_op)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Construct a [HallSwitch], or return [Error::InvalidSensor] if `b_op` is negative.
+    pub fn try_new(
+        position: impl Into<Point3<T>>,
+        orientation: UnitQuaternion<T>,
+        sensitive_axis: impl Into<Vector3<T>>,
+        b_op: T,
+    ) -> Result<Self, Error> {
+        let mut sensor = Self {
             pose: Pose::new(position.into(), orientation),
             sensitive_axis: sensitive_axis.into().normalize(),
-            b_op,
-        }
+            b_op: T::zero(),
+        };
+        sensor.try_set_b_op(b_op)?;
+        Ok(sensor)
     }
 
     impl_pose_methods!();
@@ -76,12 +94,21 @@ impl<T: Float> HallSwitch<T> {
         self.sensitive_axis = sensitive_axis.into().normalize();
     }
 
+    /// Set `b_op`, or return [Error::InvalidSensor] if it is negative.
     #[inline]
-    pub fn set_b_op(&mut self, b_op: T) {
+    pub fn try_set_b_op(&mut self, b_op: T) -> Result<(), Error> {
         if b_op < T::zero() {
-            panic!("B_OP must be non-negative.");
+            return Err(Error::InvalidSensor("B_OP must be non-negative."));
         }
         self.b_op = b_op;
+        Ok(())
+    }
+
+    #[inline]
+    pub fn set_b_op(&mut self, b_op: T) {
+        if let Err(error) = self.try_set_b_op(b_op) {
+            panic!("{error}");
+        }
     }
 
     // MARK: With setters
@@ -97,6 +124,12 @@ impl<T: Float> HallSwitch<T> {
         self.set_b_op(b_op);
         self
     }
+
+    #[inline]
+    pub fn try_with_b_op(mut self, b_op: T) -> Result<Self, Error> {
+        self.try_set_b_op(b_op)?;
+        Ok(self)
+    }
 }
 
 impl<T: Float> Default for HallSwitch<T> {
@@ -109,6 +142,51 @@ impl<T: Float> Default for HallSwitch<T> {
     }
 }
 
+// MARK: Serde
+
+/// Omitted fields take their defaults, and the fields are validated as in `new`.
+#[cfg(feature = "serde")]
+impl<'de, T: Float + serde::Deserialize<'de>> serde::Deserialize<'de> for HallSwitch<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(serde::Deserialize)]
+        #[serde(rename = "HallSwitch", default, deny_unknown_fields)]
+        struct Repr<T: Float> {
+            pose: Pose<T>,
+            sensitive_axis: Vector3<T>,
+            b_op: T,
+        }
+
+        impl<T: Float> Default for Repr<T> {
+            fn default() -> Self {
+                let HallSwitch {
+                    pose,
+                    sensitive_axis,
+                    b_op,
+                } = HallSwitch::default();
+                Self {
+                    pose,
+                    sensitive_axis,
+                    b_op,
+                }
+            }
+        }
+
+        let Repr {
+            pose,
+            sensitive_axis,
+            b_op,
+        } = Repr::deserialize(deserializer)?;
+        if b_op < T::zero() {
+            return Err(serde::de::Error::custom("B_OP must be non-negative."));
+        }
+        Ok(Self {
+            pose,
+            sensitive_axis: sensitive_axis.normalize(),
+            b_op,
+        })
+    }
+}
+
 impl<T: Float> Observer<T> for HallSwitch<T> {
     /// Alias of [HallSwitch::read_state], returning `SensorOutput::Digital`.
     fn read(&self, source: &dyn Source<T>) -> SensorOutput<T> {
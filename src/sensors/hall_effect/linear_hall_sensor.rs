@@ -9,6 +9,7 @@ use getset::Getters;
 use nalgebra::{Point3, UnitQuaternion, Vector3};
 
 use crate::{
+    Error,
     base::{
         Float, Observer, Pose, SensorOutput, Source, pose::impl_pose_methods,
         transform::impl_transform,
@@ -18,6 +19,7 @@ use crate::{
 
 /// A physical representation of a linear Hall effect sensor.
 #[derive(Clone, Debug, PartialEq, Eq, Getters)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[getset(get = "pub")]
 pub struct LinearHallSensor<T: Float = f64> {
     pose: Pose<T>,
@@ -47,6 +49,10 @@ impl<T: Float> LinearHallSensor<T> {
     ///     5.0,                          // supply voltage (V)
     /// );
     /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `supply_voltage` is not positive, see [try_new](Self::try_new).
     pub fn new(
         position: impl Into<Point3<T>>,
         orientation: UnitQuaternion<T>,
@@ -54,20 +60,34 @@ impl<T: Float> LinearHallSensor<T> {
         sensitivity: T,
         supply_voltage: T,
     ) -> Self {
-        if supply_voltage <= T::zero() {
-            panic!("Supply voltage must be positive.");
-        }
-
-        let two = T::from_f64(2.0).unwrap();
-        let sensitivity_vector = sensitive_axis.into().normalize() * sensitivity;
+        Self::try_new(
+            position,
+            orientation,
+            sensitive_axis,
+            sensitivity,
+            supply_voltage,
+        )
+        .unwrap_or_else(|error| panic!("{error}"))
+    }
 
-        Self {
+    /// Construct a [LinearHallSensor], or return [Error::InvalidSensor] if
+    /// `supply_voltage` is not positive.
+    pub fn try_new(
+        position: impl Into<Point3<T>>,
+        orientation: UnitQuaternion<T>,
+        sensitive_axis: impl Into<Vector3<T>>,
+        sensitivity: T,
+        supply_voltage: T,
+    ) -> Result<Self, Error> {
+        let mut sensor = Self {
             pose: Pose::new(position.into(), orientation),
-            sensitivity_vector,
-            quiescent_voltage: supply_voltage / two,
+            sensitivity_vector: sensitive_axis.into().normalize() * sensitivity,
+            quiescent_voltage: T::zero(),
             min_voltage: T::zero(),
-            max_voltage: supply_voltage,
-        }
+            max_voltage: T::zero(),
+        };
+        sensor.try_set_supply_voltage(supply_voltage)?;
+        Ok(sensor)
     }
 
     impl_pose_methods!();
@@ -140,14 +160,23 @@ impl<T: Float> LinearHallSensor<T> {
         self.sensitivity_vector = self.sensitive_axis() * sensitivity;
     }
 
+    /// Set `supply_voltage`, or return [Error::InvalidSensor] if it is not positive.
     #[inline]
-    pub fn set_supply_voltage(&mut self, supply_voltage: T) {
+    pub fn try_set_supply_voltage(&mut self, supply_voltage: T) -> Result<(), Error> {
         if supply_voltage <= T::zero() {
-            panic!("Supply voltage must be positive.");
+            return Err(Error::InvalidSensor("Supply voltage must be positive."));
         }
         let two = T::from_f64(2.0).unwrap();
         self.max_voltage = supply_voltage;
         self.quiescent_voltage = supply_voltage / two;
+        Ok(())
+    }
+
+    #[inline]
+    pub fn set_supply_voltage(&mut self, supply_voltage: T) {
+        if let Err(error) = self.try_set_supply_voltage(supply_voltage) {
+            panic!("{error}");
+        }
     }
 
     // MARK: With setters
@@ -163,6 +192,12 @@ impl<T: Float> LinearHallSensor<T> {
         self.set_supply_voltage(supply_voltage);
         self
     }
+
+    #[inline]
+    pub fn try_with_supply_voltage(mut self, supply_voltage: T) -> Result<Self, Error> {
+        self.try_set_supply_voltage(supply_voltage)?;
+        Ok(self)
+    }
 }
 
 impl<T: Float> Default for LinearHallSensor<T> {
@@ -177,6 +212,63 @@ impl<T: Float> Default for LinearHallSensor<T> {
     }
 }
 
+// MARK: Serde
+
+/// Omitted fields take their defaults, and the output range must not be empty.
+#[cfg(feature = "serde")]
+impl<'de, T: Float + serde::Deserialize<'de>> serde::Deserialize<'de> for LinearHallSensor<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(serde::Deserialize)]
+        #[serde(rename = "LinearHallSensor", default, deny_unknown_fields)]
+        struct Repr<T: Float> {
+            pose: Pose<T>,
+            sensitivity_vector: Vector3<T>,
+            quiescent_voltage: T,
+            min_voltage: T,
+            max_voltage: T,
+        }
+
+        impl<T: Float> Default for Repr<T> {
+            fn default() -> Self {
+                let LinearHallSensor {
+                    pose,
+                    sensitivity_vector,
+                    quiescent_voltage,
+                    min_voltage,
+                    max_voltage,
+                } = LinearHallSensor::default();
+                Self {
+                    pose,
+                    sensitivity_vector,
+                    quiescent_voltage,
+                    min_voltage,
+                    max_voltage,
+                }
+            }
+        }
+
+        let Repr {
+            pose,
+            sensitivity_vector,
+            quiescent_voltage,
+            min_voltage,
+            max_voltage,
+        } = Repr::deserialize(deserializer)?;
+        if max_voltage <= min_voltage {
+            return Err(serde::de::Error::custom(
+                "Maximum voltage must be greater than the minimum voltage.",
+            ));
+        }
+        Ok(Self {
+            pose,
+            sensitivity_vector,
+            quiescent_voltage,
+            min_voltage,
+            max_voltage,
+        })
+    }
+}
+
 impl<T: Float> Observer<T> for LinearHallSensor<T> {
     /// Alias of [LinearHallSensor::read_voltage].
     fn read(&self, source: &dyn Source<T>) -> SensorOutput<T> {
@@ -3,8 +3,6 @@
  * Copyright 2025 Sira Pornsiriprasert <code@psira.me>
  */
 
-#[cfg(feature = "std")]
-use derive_more::Display;
 use enum_dispatch::enum_dispatch;
 
 use crate::{
@@ -14,14 +12,24 @@ use crate::{
 
 /// Sensor variants
 #[derive(Clone, Debug, PartialEq, Eq)]
-#[cfg_attr(feature = "std", derive(Display))]
 #[enum_dispatch(Observer<T>, Transform<T>)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type"))]
 pub enum Sensor<T: Float = f64> {
+    #[cfg_attr(feature = "serde", serde(rename = "LinearHallSensor"))]
     LinearHall(LinearHallSensor<T>),
+    #[cfg_attr(feature = "serde", serde(rename = "HallSwitch"))]
     HallSwitch(HallSwitch<T>),
+    #[cfg_attr(feature = "serde", serde(rename = "HallLatch"))]
     HallLatch(HallLatch<T>),
 }
 
+impl<T: Float> core::fmt::Display for Sensor<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        <Self as Observer<T>>::format(self, f, "")
+    }
+}
+
 #[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
@@ -375,6 +375,40 @@ impl<T: Float> ScaleParam<T> for TriMesh<T> {
     }
 }
 
+#[cfg(feature = "mesh")]
+/// Axis-aligned cube, oriented outwards unless `inverted`.
+pub fn cube_mesh(
+    center: Vector3<f64>,
+    side: f64,
+    inverted: bool,
+) -> (Vec<Vector3<f64>>, Vec<[usize; 3]>) {
+    let h = side / 2.0;
+    let vertices = (0..8)
+        .map(|i| {
+            let sign = |bit: usize| if i & bit == 0 { -h } else { h };
+            center + vector![sign(1), sign(2), sign(4)]
+        })
+        .collect();
+    let faces = [
+        [0, 2, 1],
+        [1, 2, 3],
+        [4, 5, 6],
+        [5, 7, 6],
+        [0, 1, 4],
+        [1, 5, 4],
+        [2, 6, 3],
+        [3, 6, 7],
+        [0, 4, 2],
+        [2, 4, 6],
+        [1, 3, 5],
+        [3, 7, 5],
+    ]
+    .into_iter()
+    .map(|[a, b, c]| if inverted { [a, c, b] } else { [a, b, c] })
+    .collect();
+    (vertices, faces)
+}
+
 /// Generate basic tests for magnetic sources.
 /// Tests compute_B, compute_B for small magnets, translate, and rotate.
 ///
@@ -0,0 +1,97 @@
+/*
+ * Magba is licensed under The 3-Clause BSD, see LICENSE.
+ * Copyright 2025 Sira Pornsiriprasert <code@psira.me>
+ */
+
+//! Checks `include/magba.h` against `src/capi.rs` and runs `tests/capi/test_capi.c`.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+const ROOT: &str = env!("CARGO_MANIFEST_DIR");
+
+fn header() -> String {
+    let config = cbindgen::Config::from_file(Path::new(ROOT).join("cbindgen.toml")).unwrap();
+    let mut header = Vec::new();
+    cbindgen::Builder::new()
+        .with_config(config)
+        .with_src(Path::new(ROOT).join("src/capi.rs"))
+        .generate()
+        .unwrap()
+        .write(&mut header);
+    String::from_utf8(header).unwrap()
+}
+
+#[test]
+fn test_header() {
+    let path = Path::new(ROOT).join("include/magba.h");
+    let expected = header();
+    if env::var_os("MAGBA_UPDATE_HEADER").is_some() {
+        fs::write(&path, expected).unwrap();
+        return;
+    }
+    let actual = fs::read_to_string(&path).unwrap_or_default();
+    assert!(
+        actual == expected,
+        "include/magba.h is out of date, run `MAGBA_UPDATE_HEADER=1 cargo test --features capi --test capi`."
+    );
+}
+
+/// Builds the library as a `cdylib` in a separate target directory, since the one of this
+/// test is locked by Cargo.
+fn build_library() -> PathBuf {
+    let target = Path::new(ROOT).join("target/capi");
+    let status = Command::new(env!("CARGO"))
+        .current_dir(ROOT)
+        .args([
+            "rustc",
+            "--lib",
+            "--features",
+            "capi",
+            "--crate-type",
+            "cdylib",
+        ])
+        .arg("--target-dir")
+        .arg(&target)
+        .status()
+        .unwrap();
+    assert!(status.success(), "Building the library failed.");
+    target.join("debug")
+}
+
+#[test]
+#[cfg(unix)]
+fn test_c() {
+    let compiler = env::var("CC").unwrap_or_else(|_| "cc".to_string());
+    if Command::new(&compiler).arg("--version").output().is_err() {
+        eprintln!("Skipping the C test, `{compiler}` is not available.");
+        return;
+    }
+    let lib = build_library();
+    let exe = lib.join("test_capi");
+    let status = Command::new(&compiler)
+        .arg(Path::new(ROOT).join("tests/capi/test_capi.c"))
+        .arg("-std=c99")
+        .args(["-Wall", "-Wextra", "-Werror"])
+        .arg("-I")
+        .arg(Path::new(ROOT).join("include"))
+        .arg("-L")
+        .arg(&lib)
+        .arg(format!("-Wl,-rpath,{}", lib.display()))
+        .args(["-lmagba", "-lm", "-o"])
+        .arg(&exe)
+        .status()
+        .unwrap();
+    assert!(status.success(), "Compiling the C test failed.");
+
+    let output = Command::new(&exe).output().unwrap();
+    assert!(
+        output.status.success(),
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
@@ -0,0 +1,137 @@
+/*
+ * Magba is licensed under The 3-Clause BSD, see LICENSE.
+ * Copyright 2025 Sira Pornsiriprasert <code@psira.me>
+ */
+
+/* Exercises the C interface through include/magba.h, run by tests/capi.rs. */
+
+#include <math.h>
+#include <stdio.h>
+#include <string.h>
+
+#include "magba.h"
+
+static int failures = 0;
+
+#define CHECK(condition)                                                      \
+    do {                                                                      \
+        if (!(condition)) {                                                   \
+            fprintf(stderr, "%s:%d: check failed: %s (last error: %s)\n",     \
+                    __FILE__, __LINE__, #condition, magba_last_error());      \
+            failures++;                                                       \
+        }                                                                     \
+    } while (0)
+
+static int close_to(double a, double b) {
+    return fabs(a - b) <= 1e-12 * fmax(1.0, fmax(fabs(a), fabs(b)));
+}
+
+/* On the axis between two opposite magnets, the field is along x by symmetry. */
+static void test_sources(void) {
+    const double up[3] = {0.0, 0.0, 1.0};
+    const double down[3] = {0.0, 0.0, -1.0};
+    const double left[3] = {-0.01, 0.0, 0.0};
+    const double right[3] = {0.01, 0.0, 0.0};
+    const double dimensions[3] = {0.005, 0.005, 0.005};
+
+    MagbaSource *assembly = NULL;
+    MagbaSource *a = NULL;
+    MagbaSource *b = NULL;
+    CHECK(magba_source_assembly_new(NULL, NULL, &assembly) == MAGBA_STATUS_OK);
+    CHECK(magba_cuboid_magnet_new(left, NULL, up, dimensions, &a) == MAGBA_STATUS_OK);
+    CHECK(magba_cuboid_magnet_new(right, NULL, down, dimensions, &b) == MAGBA_STATUS_OK);
+    CHECK(magba_source_assembly_add(assembly, a) == MAGBA_STATUS_OK);
+    CHECK(magba_source_assembly_add(assembly, b) == MAGBA_STATUS_OK);
+
+    const double points[6] = {0.0, 0.0, 0.002, 0.0, 0.0, 0.004};
+    double field[6];
+    CHECK(magba_source_compute_B(assembly, points, 2, field) == MAGBA_STATUS_OK);
+    for (int i = 0; i < 6; i++) {
+        if (i % 3 != 0) {
+            CHECK(close_to(field[i], 0.0));
+        }
+    }
+    CHECK(field[0] > 0.0 && field[3] > 0.0);
+
+    /* Turning the assembly half a turn about z swaps the magnets. */
+    const double half_turn[4] = {0.0, 0.0, 1.0, 0.0};
+    double turned[6];
+    CHECK(magba_source_set_pose(assembly, NULL, half_turn) == MAGBA_STATUS_OK);
+    CHECK(magba_source_compute_B(assembly, points, 2, turned) == MAGBA_STATUS_OK);
+    CHECK(close_to(turned[0], -field[0]));
+
+    double position[3];
+    double orientation[4];
+    CHECK(magba_source_get_pose(assembly, position, orientation) == MAGBA_STATUS_OK);
+    CHECK(close_to(orientation[2], 1.0) && close_to(orientation[3], 0.0));
+
+    magba_source_free(assembly);
+}
+
+static void test_sensors(void) {
+    const double axis[3] = {0.0, 0.0, 1.0};
+    const double above[3] = {0.0, 0.0, 0.02};
+    const double moment[3] = {0.0, 0.0, 1.0};
+
+    MagbaSource *dipole = NULL;
+    MagbaSensorAssembly *sensors = NULL;
+    MagbaSensor *hall = NULL;
+    MagbaSensor *latch = NULL;
+    CHECK(magba_dipole_new(NULL, NULL, moment, &dipole) == MAGBA_STATUS_OK);
+    CHECK(magba_sensor_assembly_new(NULL, NULL, &sensors) == MAGBA_STATUS_OK);
+    CHECK(magba_linear_hall_sensor_new(above, NULL, axis, 20.0, 5.0, &hall) == MAGBA_STATUS_OK);
+    CHECK(magba_hall_latch_new(above, NULL, axis, 0.01, -0.01, &latch) == MAGBA_STATUS_OK);
+    CHECK(magba_sensor_assembly_add(sensors, hall) == MAGBA_STATUS_OK);
+    CHECK(magba_sensor_assembly_add(sensors, latch) == MAGBA_STATUS_OK);
+
+    size_t len = 0;
+    CHECK(magba_sensor_assembly_len(sensors, &len) == MAGBA_STATUS_OK);
+    CHECK(len == 2);
+
+    /* B_z = μ0 m / (2π r³) on the axis of the dipole, with μ0 close to 4π × 1e-7. */
+    double values[2];
+    double b = 2e-7 / pow(0.02, 3.0);
+    CHECK(magba_sensor_assembly_read(sensors, dipole, values, 2) == MAGBA_STATUS_OK);
+    CHECK(fabs(values[0] - (2.5 + 20.0 * b)) < 1e-6);
+    CHECK(values[1] == 1.0);
+
+    /* Far away, the field falls between the thresholds and the latch holds. */
+    const double far[3] = {0.0, 0.0, 1.0};
+    CHECK(magba_sensor_assembly_set_pose(sensors, far, NULL) == MAGBA_STATUS_OK);
+    CHECK(magba_sensor_assembly_read(sensors, dipole, values, 2) == MAGBA_STATUS_OK);
+    CHECK(values[1] == 1.0);
+
+    magba_sensor_assembly_free(sensors);
+    magba_source_free(dipole);
+}
+
+static void test_errors(void) {
+    const double polarization[3] = {0.0, 0.0, 1.0};
+    MagbaSource *magnet = NULL;
+
+    CHECK(magba_cylinder_magnet_new(NULL, NULL, NULL, 1.0, 1.0, &magnet) == MAGBA_STATUS_NULL_POINTER);
+    CHECK(strcmp(magba_last_error(), "`polarization` is null.") == 0);
+    CHECK(magba_cylinder_magnet_new(NULL, NULL, polarization, -1.0, 1.0, &magnet) == MAGBA_STATUS_INVALID_ARGUMENT);
+    CHECK(strcmp(magba_last_error(), "Diameter cannot be negative.") == 0);
+    CHECK(magnet == NULL);
+
+    const double vertices[9] = {0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0};
+    const size_t faces[3] = {0, 1, 3};
+    CHECK(magba_mesh_magnet_new(NULL, NULL, polarization, vertices, 3, faces, 1, &magnet) ==
+          MAGBA_STATUS_INVALID_ARGUMENT);
+    CHECK(magba_source_compute_B(NULL, NULL, 0, NULL) == MAGBA_STATUS_NULL_POINTER);
+
+    magba_source_free(NULL);
+}
+
+int main(void) {
+    test_sources();
+    test_sensors();
+    test_errors();
+    if (failures > 0) {
+        fprintf(stderr, "%d checks failed.\n", failures);
+        return 1;
+    }
+    printf("All checks passed.\n");
+    return 0;
+}